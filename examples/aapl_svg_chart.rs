@@ -1,35 +1,24 @@
 use chrono::{TimeZone, Utc};
 use rust_d3::axis::{AxisRenderable, axis_bottom, axis_left};
+use rust_d3::dsv::csv_parse_with;
 use rust_d3::scale::ScaleLinear;
 use rust_d3::selection::{Arena, Selection};
 use rust_d3::shape::Area;
 use rust_d3::time::format::time_parse;
 use std::cell::RefCell;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::rc::Rc;
 
 fn main() {
     // Read CSV data from a file (aapl.csv)
-    let file = File::open("examples/aapl.csv").expect("Cannot open aapl.csv");
-    let reader = BufReader::new(file);
-    let mut dates = Vec::new();
-    let mut closes = Vec::new();
-    for (i, line) in reader.lines().enumerate() {
-        let line = line.expect("Failed to read line");
-        if i == 0 {
-            continue;
-        } // skip header
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() < 2 {
-            continue;
-        }
-        // println!("Line {}: {:?}", i, parts);
-        let parsed = time_parse("%Y-%m-%d", parts[0].to_string().as_str());
-        // println!("Parsed date: {:?}", parts[0].to_string().as_str());
-        dates.push(Utc.from_utc_datetime(&parsed.unwrap()));
-        closes.push(parts[1].parse::<f32>().unwrap_or(0.0));
-    }
+    let text = std::fs::read_to_string("examples/aapl.csv").expect("Cannot open aapl.csv");
+    let data = csv_parse_with(&text, |d, _, _| {
+        let date = time_parse("%Y-%m-%d", &d["date"])?;
+        Some((
+            Utc.from_utc_datetime(&date),
+            d["close"].parse::<f32>().unwrap_or(0.0),
+        ))
+    });
+    let (dates, closes): (Vec<_>, Vec<_>) = data.rows.into_iter().unzip();
     println!("Dates: {:?}", dates);
     println!("Closes: {:?}", closes);
     // Create dimensions and margins for the chart
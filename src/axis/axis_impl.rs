@@ -1,6 +1,7 @@
 // Axis methods implementation
 // Implements Axis<S> methods for D3-like axes
 
+use super::axis_renderable::AxisScale;
 use super::axis_structs::{Axis, AxisLayout};
 use super::orientation::AxisOrientation;
 use super::ticks::{Tick, TickFormat};
@@ -146,26 +147,14 @@ impl<S> Axis<S> {
 // D3-style ticks method for different scale types
 // This matches the D3 API where axis.ticks() returns the generated ticks
 
-impl<T: Clone + PartialEq + ToString> Axis<crate::scale::ScaleBand<T>> {
+impl<S: AxisScale> Axis<S> {
     /// Returns the tick values that would be generated for this axis
     pub fn ticks(&self) -> Vec<Tick> {
-        self.generate_ticks()
-    }
-}
-
-impl<T: Clone + PartialEq + ToString> Axis<crate::scale::ScalePoint<T>> {
-    /// Returns the tick values that would be generated for this axis
-    pub fn ticks(&self) -> Vec<Tick> {
-        self.generate_ticks()
+        S::axis_ticks(self)
     }
 }
 
 impl Axis<crate::scale::ScaleLinear> {
-    /// Returns the tick values that would be generated for this axis
-    pub fn ticks(&self) -> Vec<Tick> {
-        self.generate_ticks()
-    }
-    
     /// Returns the tick values using the specified values instead of the scale's automatic tick generator
     pub fn ticks_with(&self, tick_values: Option<&[f64]>) -> Vec<Tick> {
         self.generate_ticks_with(tick_values)
@@ -173,11 +162,6 @@ impl Axis<crate::scale::ScaleLinear> {
 }
 
 impl Axis<crate::scale::ScaleLog> {
-    /// Returns the tick values that would be generated for this axis
-    pub fn ticks(&self) -> Vec<Tick> {
-        self.generate_ticks()
-    }
-    
     /// Returns the tick values using the specified values instead of the scale's automatic tick generator
     pub fn ticks_with(&self, tick_values: Option<Vec<f64>>) -> Vec<Tick> {
         self.generate_ticks_with(tick_values)
@@ -185,11 +169,6 @@ impl Axis<crate::scale::ScaleLog> {
 }
 
 impl Axis<crate::scale::ScaleTime> {
-    /// Returns the tick values that would be generated for this axis
-    pub fn ticks(&self) -> Vec<Tick> {
-        self.generate_ticks()
-    }
-    
     /// Returns the tick values using the specified values instead of the scale's automatic tick generator
     pub fn ticks_with(&self, tick_values: Option<Vec<chrono::NaiveDateTime>>) -> Vec<Tick> {
         self.generate_ticks_with(tick_values)
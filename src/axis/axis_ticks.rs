@@ -2,37 +2,63 @@
 // Responsible for generating visual ticks across different axis types like linear, log, and time.
 // Includes context-aware format patterns for time-based scales to ensure accurate display.
 
+use super::axis_renderable::AxisScale;
 use super::axis_structs::Axis;
 use super::ticks::Tick;
+use crate::scale::{BandLikeScale, ContinuousScale, Scale};
 
-impl<T: Clone + PartialEq + ToString> Axis<crate::scale::ScaleBand<T>> {
+impl<S> Axis<S>
+where
+    S: BandLikeScale,
+    S::Domain: ToString,
+{
+    /// One tick per domain value, centred on its band (D3 places point-scale
+    /// ticks on the point itself, which is the same thing with zero bandwidth)
     pub fn generate_ticks(&self) -> Vec<Tick> {
-        let domain = &self.scale.domain;
-        domain
+        let offset = self.scale.bandwidth() / 2.0;
+        self.scale
+            .domain()
             .iter()
             .filter_map(|v| {
-                self.scale.scale(v).map(|pos| {
-                    let label = v.to_string();
-                    Tick::new(0.0, label, pos)
-                })
+                self.scale
+                    .scale(v)
+                    .map(|pos| Tick::new(0.0, v.to_string(), pos + offset))
             })
             .collect()
     }
 }
 
-impl<T: Clone + PartialEq + ToString> Axis<crate::scale::ScalePoint<T>> {
-    pub fn generate_ticks(&self) -> Vec<Tick> {
-        let domain = &self.scale.domain;
-        domain
-            .iter()
-            .filter_map(|v| {
-                self.scale.scale(v).map(|pos| {
-                    let label = v.to_string();
-                    Tick::new(0.0, label, pos)
-                })
-            })
-            .collect()
-    }
+// Ticks for numeric continuous scales without a dedicated generator, labelled
+// with the scale's own tick format unless the axis overrides it
+fn continuous_ticks<S>(axis: &Axis<S>) -> Vec<Tick>
+where
+    S: ContinuousScale<Domain = f64, Output = f64>,
+{
+    let count = axis
+        .tick_arguments
+        .as_ref()
+        .and_then(|args| args.first())
+        .map(|&c| c as usize)
+        .unwrap_or(axis.tick_count);
+    let values = axis
+        .tick_values
+        .clone()
+        .unwrap_or_else(|| axis.scale.ticks(count));
+    let format = axis.scale.tick_format(count, None);
+    values
+        .into_iter()
+        .map(|value| {
+            let label = if let Some(fmt) = axis.tick_format {
+                fmt(value)
+            } else if let Some(ref locale) = axis.locale {
+                crate::format::format_locale(value, locale, true)
+            } else {
+                format(&value)
+            };
+            let position = axis.scale.scale(&value).unwrap_or(f64::NAN);
+            Tick::new(value, label, position)
+        })
+        .collect()
 }
 
 impl Axis<crate::scale::ScaleLinear> {
@@ -67,7 +93,7 @@ impl Axis<crate::scale::ScaleLinear> {
             // Get domain bounds
             let domain = self.scale.domain();
             let domain_min = domain[0];
-            let domain_max = domain[1];
+            let domain_max = domain[domain.len() - 1];
             let tolerance = 1e-10;
             
             // Check if first tick is outside tolerance of domain minimum
@@ -139,7 +165,7 @@ impl Axis<crate::scale::ScaleTime> {
         
         // Get the domain span to determine if we need special handling
         let start = self.scale.domain[0];
-        let stop = self.scale.domain[1];
+        let stop = self.scale.domain[self.scale.domain.len() - 1];
         let duration = (stop - start).abs();
         
         match tick_interval {
@@ -236,6 +262,73 @@ impl Axis<crate::scale::ScaleTime> {
 }
 
 
+impl AxisScale for crate::scale::ScaleLinear {
+    fn axis_ticks(axis: &Axis<Self>) -> Vec<Tick> {
+        axis.generate_ticks()
+    }
+    fn minor_tick_position(&self, value: f64) -> f64 {
+        crate::scale::ScaleLinear::scale(self, value)
+    }
+}
+
+impl AxisScale for crate::scale::ScaleLog {
+    fn axis_ticks(axis: &Axis<Self>) -> Vec<Tick> {
+        axis.generate_ticks()
+    }
+    fn minor_tick_position(&self, value: f64) -> f64 {
+        crate::scale::ScaleLog::scale(self, value)
+    }
+}
+
+impl AxisScale for crate::scale::ScaleTime {
+    fn axis_ticks(axis: &Axis<Self>) -> Vec<Tick> {
+        axis.generate_ticks()
+    }
+    /// Minor tick values are epoch milliseconds, like `Tick::value`
+    fn minor_tick_position(&self, value: f64) -> f64 {
+        match chrono::DateTime::from_timestamp_millis(value as i64) {
+            Some(datetime) => crate::scale::ScaleTime::scale(self, datetime.naive_utc()),
+            None => f64::NAN,
+        }
+    }
+}
+
+macro_rules! continuous_axis_scale {
+    ($scale:ty) => {
+        impl AxisScale for $scale {
+            fn axis_ticks(axis: &Axis<Self>) -> Vec<Tick> {
+                continuous_ticks(axis)
+            }
+            fn minor_tick_position(&self, value: f64) -> f64 {
+                Scale::scale(self, &value).unwrap_or(f64::NAN)
+            }
+        }
+    };
+}
+
+continuous_axis_scale!(crate::scale::ScalePow);
+continuous_axis_scale!(crate::scale::ScaleSymlog);
+continuous_axis_scale!(crate::scale::ScaleIdentity);
+
+// Minor tick values on band and point axes are already range positions
+impl<T: Clone + PartialEq + ToString> AxisScale for crate::scale::ScaleBand<T> {
+    fn axis_ticks(axis: &Axis<Self>) -> Vec<Tick> {
+        axis.generate_ticks()
+    }
+    fn minor_tick_position(&self, value: f64) -> f64 {
+        value
+    }
+}
+
+impl<T: Clone + PartialEq + ToString> AxisScale for crate::scale::ScalePoint<T> {
+    fn axis_ticks(axis: &Axis<Self>) -> Vec<Tick> {
+        axis.generate_ticks()
+    }
+    fn minor_tick_position(&self, value: f64) -> f64 {
+        value
+    }
+}
+
 // D3-format compatible formatter matching formatDefaultLocale(".6g")
 // 
 // This function replicates D3's default number formatting behavior:
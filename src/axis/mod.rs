@@ -16,20 +16,16 @@
 pub mod axis_constructors;
 pub mod axis_impl;
 pub mod axis_renderable;
-pub mod axis_renderable_band;
-pub mod axis_renderable_linear;
-pub mod axis_renderable_log;
-pub mod axis_renderable_point;
-pub mod axis_renderable_time;
 pub mod axis_structs;
 pub mod axis_ticks;
 pub mod orientation;
 pub mod ticks;
 
 pub use axis_constructors::{axis_bottom, axis_left, axis_right, axis_top};
-pub use axis_renderable::AxisRenderable;
+pub use axis_renderable::{AxisRenderable, AxisScale};
 pub use axis_structs::{Axis, AxisLayout, GridStyle, TitleStyle, TickLabelStyle, AxisLineStyle};
 pub use orientation::AxisOrientation;
 pub use ticks::{Tick, TickFormat};
-// All AxisRenderable implementations are now in their own files for each scale type.
-// This ensures 100% D3 parity and modularity for the axis module.
+// `AxisRenderable` is implemented once for every `Axis<S: AxisScale>` in
+// axis_renderable.rs; the `AxisScale` impls for each scale type are in
+// axis_ticks.rs.
@@ -1,47 +1,55 @@
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use eframe::egui;
 use eframe::egui::{ColorImage, TextureHandle};
-use resvg::render;
 use rust_d3::axis::AxisRenderable;
 use rust_d3::axis::{axis_bottom, axis_left};
+use rust_d3::dsv::csv_parse_with;
 use rust_d3::scale::ScaleLinear;
 use rust_d3::scale::ScaleTime;
-use rust_d3::selection::{Arena, Selection};
+use rust_d3::selection::{Arena, RasterOptions, Selection};
 use rust_d3::shape::Area;
 use rust_d3::time::format::time_parse;
+use rust_d3::zoom::{WheelDeltaMode, Zoom, ZoomEventType, ZoomInput, ZoomTransform};
 use slotmap::SlotMap;
 use std::cell::RefCell;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::rc::Rc;
-use tiny_skia::Pixmap;
-use usvg::fontdb;
-use usvg::{Options, Tree};
 
-fn generate_svg_chart() -> String {
-    let file = File::open("examples/aapl.csv").expect("Cannot open aapl.csv");
-    let reader = BufReader::new(file);
-    let mut dates = Vec::new();
-    let mut closes = Vec::new();
-    for (i, line) in reader.lines().enumerate() {
-        let line = line.expect("Failed to read line");
-        if i == 0 {
-            continue;
-        }
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() < 2 {
-            continue;
-        }
-        let parsed = time_parse("%Y-%m-%d", parts[0].to_string().as_str());
-        dates.push(Utc.from_utc_datetime(&parsed.unwrap()));
-        closes.push(parts[1].parse::<f32>().unwrap_or(0.0));
-    }
-    let width: usize = 928;
-    let height: usize = 500;
-    let _margin_top: i32 = 20;
-    let margin_right: i32 = 30;
-    let margin_bottom: i32 = 30;
-    let margin_left: i32 = 40;
+const WIDTH: usize = 928;
+const HEIGHT: usize = 500;
+const MARGIN_RIGHT: i32 = 30;
+const MARGIN_BOTTOM: i32 = 30;
+const MARGIN_LEFT: i32 = 40;
+
+fn load_data() -> (Vec<DateTime<Utc>>, Vec<f32>) {
+    let text = std::fs::read_to_string("examples/aapl.csv").expect("Cannot open aapl.csv");
+    let data = csv_parse_with(&text, |d, _, _| {
+        let date = time_parse("%Y-%m-%d", &d["date"])?;
+        Some((
+            Utc.from_utc_datetime(&date),
+            d["close"].parse::<f32>().unwrap_or(0.0),
+        ))
+    });
+    data.rows.into_iter().unzip()
+}
+
+// Only the x axis zooms: pan and zoom stay within the plot area horizontally
+fn chart_zoom() -> Zoom {
+    let plot = [
+        [MARGIN_LEFT as f64, 0.0],
+        [(WIDTH as i32 - MARGIN_RIGHT) as f64, HEIGHT as f64],
+    ];
+    Zoom::new(plot).scale_extent([1.0, 32.0]).translate_extent([
+        [MARGIN_LEFT as f64, f64::NEG_INFINITY],
+        [(WIDTH as i32 - MARGIN_RIGHT) as f64, f64::INFINITY],
+    ])
+}
+
+fn generate_svg_chart(dates: &[DateTime<Utc>], closes: &[f32], transform: ZoomTransform) -> Selection {
+    let width = WIDTH;
+    let height = HEIGHT;
+    let margin_right = MARGIN_RIGHT;
+    let margin_bottom = MARGIN_BOTTOM;
+    let margin_left = MARGIN_LEFT;
 
     // Add padding to prevent text clipping
     let _n = closes.len();
@@ -56,13 +64,13 @@ fn generate_svg_chart() -> String {
         .attr("viewBox", &format!("{} {} {} {}", 0, 0, width, height))
         .attr("xmlns", "http://www.w3.org/2000/svg")
         .attr("style", "max-width: 100%; height: auto;");
-    let x = ScaleTime::new(
+    let x = transform.rescale_x(&ScaleTime::new(
         [
             dates.first().unwrap().naive_utc(),
             dates.last().unwrap().naive_utc(),
         ],
         [(margin_left) as f64, (width as i32 - margin_right) as f64],
-    );
+    ));
     let mut y = ScaleLinear::new(
         [min_close as f64, max_close as f64],
         [(height as i32 - margin_bottom) as f64, 20.0],
@@ -76,16 +84,25 @@ fn generate_svg_chart() -> String {
         .x(|_d: &f32, i: usize| x.scale(dates[i].naive_utc()))
         .y0(|_d: &f32, _| y.scale(min_close as f64))
         .y1(|d: &f32, _i| y.scale(*d as f64));
+    // Clip the zoomed area to the plot
+    svg.append("clipPath")
+        .attr("id", "plot-clip")
+        .append("rect")
+        .attr("x", &margin_left.to_string())
+        .attr("y", "0")
+        .attr(
+            "width",
+            &(width as i32 - margin_left - margin_right).to_string(),
+        )
+        .attr("height", &height.to_string());
     svg.append("path")
+        .attr("clip-path", "url(#plot-clip)")
         .attr("fill", "steelblue")
-        .attr("d", &area.generate(&closes));
-
-    let root_key = svg.iter().next().copied().unwrap();
+        .attr("d", &area.generate(closes));
 
     // // Append x-axis
     let mut x_axis_group = svg.append("g");
     let x_transform = format!("translate(0,{})", height as i32 - margin_bottom);
-    println!("[DEBUG] Setting x-axis transform: {}", x_transform);
     x_axis_group.attr("transform", &x_transform);
     x_axis_group.call(|sel| {
         axis_bottom(x.clone())
@@ -96,7 +113,6 @@ fn generate_svg_chart() -> String {
 
     // Append y-axis
     let y_transform = format!("translate({},{})", margin_left, 0);
-    println!("[DEBUG] Setting y-axis transform: {}", y_transform);
     svg.append("g")
         .attr("transform", &y_transform)
         .call(|sel| {
@@ -123,52 +139,124 @@ fn generate_svg_chart() -> String {
             // .attr("stroke", "#888");
         });
 
-    Selection::render_node(&arena, root_key)
+    svg
 }
 
 struct ChartApp {
-    svg: String,
+    dates: Vec<DateTime<Utc>>,
+    closes: Vec<f32>,
+    zoom: Zoom,
+    raster: RasterOptions,
+    svg: Selection,
     texture: Option<TextureHandle>,
 }
 
 impl ChartApp {
     fn rasterize_svg(&self, ctx: &eframe::egui::Context) -> Option<TextureHandle> {
-        let mut opt = Options::default();
-        let mut fontdb = fontdb::Database::new();
-        fontdb.load_system_fonts(); // Load system fonts for SVG text rendering
-        opt.fontdb = std::sync::Arc::new(fontdb); // Fix: wrap fontdb in Arc
-        let tree = Tree::from_str(&self.svg, &opt).ok()?;
-        let size = tree.size();
-        let mut pixmap = Pixmap::new(size.width() as u32, size.height() as u32)?;
-        render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+        let pixmap = self
+            .svg
+            .render_pixmap_with(WIDTH as u32, HEIGHT as u32, 1.0, &self.raster)
+            .ok()?;
         let image = ColorImage::from_rgba_unmultiplied(
-            [size.width() as usize, size.height() as usize],
+            [pixmap.width() as usize, pixmap.height() as usize],
             pixmap.data(),
         );
         Some(ctx.load_texture("chart_svg", image, eframe::egui::TextureOptions::default()))
     }
+
+    // Translate egui input over the chart image into zoom inputs, in chart
+    // (SVG) coordinates
+    fn zoom_inputs(&self, ctx: &egui::Context, response: &egui::Response) -> Vec<ZoomInput> {
+        let rect = response.rect;
+        let to_chart = |pos: egui::Pos2| {
+            [
+                ((pos.x - rect.min.x) * WIDTH as f32 / rect.width()) as f64,
+                ((pos.y - rect.min.y) * HEIGHT as f32 / rect.height()) as f64,
+            ]
+        };
+        let mut inputs = Vec::new();
+        if let Some(pos) = response.interact_pointer_pos() {
+            let point = to_chart(pos);
+            if response.drag_started() {
+                inputs.push(ZoomInput::PointerDown { id: 0, point });
+            } else if response.dragged() {
+                inputs.push(ZoomInput::PointerMove { id: 0, point });
+            }
+            if response.double_clicked() {
+                let shift = ctx.input(|i| i.modifiers.shift);
+                inputs.push(ZoomInput::DoubleClick { point, shift });
+            }
+        }
+        if response.drag_stopped() {
+            inputs.push(ZoomInput::PointerUp { id: 0 });
+        }
+        if let Some(pos) = response.hover_pos() {
+            ctx.input(|i| {
+                for event in &i.events {
+                    if let egui::Event::MouseWheel {
+                        unit,
+                        delta,
+                        modifiers,
+                    } = event
+                    {
+                        // egui deltas move the content, so scrolling down is negative
+                        let mode = match unit {
+                            egui::MouseWheelUnit::Point => WheelDeltaMode::Pixel,
+                            egui::MouseWheelUnit::Line => WheelDeltaMode::Line,
+                            egui::MouseWheelUnit::Page => WheelDeltaMode::Page,
+                        };
+                        inputs.push(ZoomInput::Wheel {
+                            point: to_chart(pos),
+                            delta_y: -delta.y as f64,
+                            mode,
+                            ctrl: modifiers.ctrl || modifiers.command,
+                        });
+                    }
+                }
+            });
+        }
+        inputs
+    }
 }
 
 impl eframe::App for ChartApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Always reload the texture for dynamic updates
-        self.texture = self.rasterize_svg(ctx);
+        if self.texture.is_none() {
+            self.texture = self.rasterize_svg(ctx);
+        }
+        let now = ctx.input(|i| i.time) * 1000.0;
+        let mut events = Vec::new();
         egui::CentralPanel::default()
             .frame(egui::Frame::default().fill(egui::Color32::WHITE))
             .show(ctx, |ui| {
                 ui.heading("Apple Stock Chart");
                 if let Some(tex) = &self.texture {
-                    ui.image(tex);
+                    let response =
+                        ui.add(egui::Image::new(tex).sense(egui::Sense::click_and_drag()));
+                    for input in self.zoom_inputs(ctx, &response) {
+                        events.extend(self.zoom.handle(input, now));
+                    }
                 } else {
                     ui.label("Failed to render SVG");
                 }
             });
+        events.extend(self.zoom.tick(now));
+        if self.zoom.needs_tick() {
+            ctx.request_repaint();
+        }
+        if events.iter().any(|e| e.event_type == ZoomEventType::Zoom) {
+            self.svg = generate_svg_chart(&self.dates, &self.closes, self.zoom.get_transform());
+            self.texture = self.rasterize_svg(ctx);
+        }
     }
 }
 
 fn main() -> eframe::Result<()> {
-    let svg_str = generate_svg_chart();
-    println!("\n--- SVG OUTPUT ---\n{}\n--- END SVG ---\n", svg_str);
+    let (dates, closes) = load_data();
+    let svg = generate_svg_chart(&dates, &closes, ZoomTransform::IDENTITY);
+    println!("\n--- SVG OUTPUT ---\n{}\n--- END SVG ---\n", svg.render());
+    // Load system fonts for SVG text rendering
+    let raster = RasterOptions::new().system_fonts();
     let options = eframe::NativeOptions::default(); // No window size field available in this version
     // To set window size, use ctx.request_repaint() or egui window API after startup if needed
     eframe::run_native(
@@ -176,7 +264,11 @@ fn main() -> eframe::Result<()> {
         options,
         Box::new(|_cc| {
             Ok(Box::new(ChartApp {
-                svg: svg_str,
+                dates,
+                closes,
+                zoom: chart_zoom(),
+                raster,
+                svg,
                 texture: None,
             }))
         }),
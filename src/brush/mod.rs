@@ -0,0 +1,601 @@
+//! d3-brush: selecting a one- or two-dimensional region (Rust port)
+//!
+//! A [`Brush`] is a state machine fed abstract pointer inputs in the
+//! coordinates of the brushed area. Pressing on empty space starts a new
+//! selection, dragging the selection moves it and dragging a handle resizes
+//! it, always within the brush extent. Each input returns the start, brush
+//! and end events it caused; [`Brush::on`] listeners registered through
+//! [`Dispatch`](crate::dispatch::Dispatch) receive them from
+//! [`Brush::handle_async`] and [`Brush::dispatch`]. [`Brush::render`] draws
+//! the overlay, selection and handle rectangles like D3.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::brush::{BrushEventType, BrushInput, brush_x};
+//! use rust_d3::scale::ScaleLinear;
+//!
+//! let x = ScaleLinear::new([0.0, 100.0], [0.0, 500.0]);
+//! let mut brush = brush_x([[0.0, 0.0], [500.0, 80.0]]);
+//!
+//! brush.handle(BrushInput::PointerDown { id: 0, point: [100.0, 40.0] });
+//! brush.handle(BrushInput::PointerMove { id: 0, point: [250.0, 60.0] });
+//! let events = brush.handle(BrushInput::PointerUp { id: 0 });
+//! assert_eq!(events[0].event_type, BrushEventType::End);
+//! assert_eq!(brush.get_selection_x(), Some([100.0, 250.0]));
+//!
+//! // The brushed data range, for filtering a linked view
+//! assert_eq!(brush.invert_x(|px| x.invert(px)), Some([20.0, 50.0]));
+//! ```
+
+use crate::dispatch::{Dispatch, Event, HandlerHandle};
+use crate::selection::Selection;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+/// A rectangle `[[x0, y0], [x1, y1]]`
+pub type Extent = [[f64; 2]; 2];
+
+/// Which dimensions a brush selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrushDimension {
+    X,
+    Y,
+    XY,
+}
+
+/// A pointer input, in the coordinates of the brushed area
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrushInput {
+    PointerDown { id: u64, point: [f64; 2] },
+    PointerMove { id: u64, point: [f64; 2] },
+    PointerUp { id: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrushEventType {
+    Start,
+    Brush,
+    End,
+}
+
+impl BrushEventType {
+    /// The listener type name: `start`, `brush` or `end`
+    pub fn name(&self) -> &'static str {
+        match self {
+            BrushEventType::Start => "start",
+            BrushEventType::Brush => "brush",
+            BrushEventType::End => "end",
+        }
+    }
+}
+
+/// What a gesture is doing to the selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrushMode {
+    /// Moving the selection
+    Drag,
+    /// Resizing the selection by a handle, or drawing a new one
+    Handle,
+}
+
+/// Reported by [`Brush`] as the selection changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrushEvent {
+    pub event_type: BrushEventType,
+    /// The selection rectangle; one-dimensional brushes span the extent in
+    /// the other dimension
+    pub selection: Option<Extent>,
+    /// The gesture's mode; `None` for [`Brush::move_to`]
+    pub mode: Option<BrushMode>,
+}
+
+/// The resize handles, in drawing order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Handle {
+    N,
+    W,
+    E,
+    S,
+    NW,
+    NE,
+    SW,
+    SE,
+}
+
+impl Handle {
+    fn name(&self) -> &'static str {
+        match self {
+            Handle::N => "n",
+            Handle::W => "w",
+            Handle::E => "e",
+            Handle::S => "s",
+            Handle::NW => "nw",
+            Handle::NE => "ne",
+            Handle::SW => "sw",
+            Handle::SE => "se",
+        }
+    }
+
+    fn cursor(&self) -> &'static str {
+        match self {
+            Handle::N | Handle::S => "ns-resize",
+            Handle::W | Handle::E => "ew-resize",
+            Handle::NW | Handle::SE => "nwse-resize",
+            Handle::NE | Handle::SW => "nesw-resize",
+        }
+    }
+
+    // The edges moved: -1 for west/north, 1 for east/south
+    fn signs(&self) -> (f64, f64) {
+        match self {
+            Handle::N => (0.0, -1.0),
+            Handle::W => (-1.0, 0.0),
+            Handle::E => (1.0, 0.0),
+            Handle::S => (0.0, 1.0),
+            Handle::NW => (-1.0, -1.0),
+            Handle::NE => (1.0, -1.0),
+            Handle::SW => (-1.0, 1.0),
+            Handle::SE => (1.0, 1.0),
+        }
+    }
+}
+
+// An in-progress gesture: the selection edges when it started (w0 etc.) and
+// now (w1 etc.), as in d3-brush
+#[derive(Debug, Clone, Copy)]
+struct Gesture {
+    id: u64,
+    mode: BrushMode,
+    sign_x: f64,
+    sign_y: f64,
+    point0: [f64; 2],
+    start: [f64; 4],
+    current: [f64; 4],
+}
+
+/// A brush over `extent`; see [`brush_x`], [`brush_y`] and [`brush`]
+pub struct Brush {
+    dimension: BrushDimension,
+    extent: Extent,
+    handle_size: f64,
+    selection: Option<Extent>,
+    gesture: Option<Gesture>,
+    listeners: Dispatch,
+}
+
+/// A brush along the x dimension
+pub fn brush_x(extent: Extent) -> Brush {
+    Brush::new(BrushDimension::X, extent)
+}
+
+/// A brush along the y dimension
+pub fn brush_y(extent: Extent) -> Brush {
+    Brush::new(BrushDimension::Y, extent)
+}
+
+/// A two-dimensional brush
+pub fn brush(extent: Extent) -> Brush {
+    Brush::new(BrushDimension::XY, extent)
+}
+
+impl Brush {
+    pub fn new(dimension: BrushDimension, extent: Extent) -> Self {
+        Self {
+            dimension,
+            extent,
+            handle_size: 6.0,
+            selection: None,
+            gesture: None,
+            listeners: Dispatch::new(),
+        }
+    }
+
+    /// The brushable area; selections stay within it
+    pub fn extent(mut self, extent: Extent) -> Self {
+        self.extent = extent;
+        self
+    }
+
+    /// Width of the resize handles, 6 by default
+    pub fn handle_size(mut self, handle_size: f64) -> Self {
+        self.handle_size = handle_size;
+        self
+    }
+
+    pub fn get_dimension(&self) -> BrushDimension {
+        self.dimension
+    }
+
+    pub fn get_extent(&self) -> Extent {
+        self.extent
+    }
+
+    pub fn get_handle_size(&self) -> f64 {
+        self.handle_size
+    }
+
+    /// The selection rectangle, if any
+    pub fn get_selection(&self) -> Option<Extent> {
+        self.selection
+    }
+
+    /// The selected `[x0, x1]`, if any
+    pub fn get_selection_x(&self) -> Option<[f64; 2]> {
+        self.selection.map(|[[x0, _], [x1, _]]| [x0, x1])
+    }
+
+    /// The selected `[y0, y1]`, if any
+    pub fn get_selection_y(&self) -> Option<[f64; 2]> {
+        self.selection.map(|[[_, y0], [_, y1]]| [y0, y1])
+    }
+
+    /// The selected x range mapped through `invert`, e.g. a scale's `invert`
+    pub fn invert_x<T>(&self, invert: impl Fn(f64) -> T) -> Option<[T; 2]> {
+        self.get_selection_x()
+            .map(|[x0, x1]| [invert(x0), invert(x1)])
+    }
+
+    /// The selected y range mapped through `invert`
+    pub fn invert_y<T>(&self, invert: impl Fn(f64) -> T) -> Option<[T; 2]> {
+        self.get_selection_y()
+            .map(|[y0, y1]| [invert(y0), invert(y1)])
+    }
+
+    /// Whether a gesture is in progress
+    pub fn is_active(&self) -> bool {
+        self.gesture.is_some()
+    }
+
+    /// Feed a pointer input
+    pub fn handle(&mut self, input: BrushInput) -> Vec<BrushEvent> {
+        match input {
+            BrushInput::PointerDown { id, point } => self.pointer_down(id, point),
+            BrushInput::PointerMove { id, point } => self.pointer_move(id, point),
+            BrushInput::PointerUp { id } => self.pointer_up(id),
+        }
+    }
+
+    /// Set the selection, as d3's `brush.move`; one-dimensional brushes take
+    /// the other dimension from the extent. An empty selection clears it
+    pub fn move_to(&mut self, selection: Option<Extent>) -> Vec<BrushEvent> {
+        let mut events = Vec::new();
+        // A programmatic move interrupts the gesture in progress
+        if let Some(gesture) = self.gesture.take() {
+            events.push(self.event(BrushEventType::End, Some(gesture.mode)));
+        }
+        self.selection = selection
+            .map(|[[x0, y0], [x1, y1]]| {
+                let [[ex0, ey0], [ex1, ey1]] = self.extent;
+                match self.dimension {
+                    BrushDimension::X => [[x0, ey0], [x1, ey1]],
+                    BrushDimension::Y => [[ex0, y0], [ex1, y1]],
+                    BrushDimension::XY => [[x0, y0], [x1, y1]],
+                }
+            })
+            .filter(|s| !is_empty(s));
+        for event_type in [
+            BrushEventType::Start,
+            BrushEventType::Brush,
+            BrushEventType::End,
+        ] {
+            events.push(self.event(event_type, None));
+        }
+        events
+    }
+
+    /// [`move_to`](Self::move_to) for an x range
+    pub fn move_x(&mut self, selection: Option<[f64; 2]>) -> Vec<BrushEvent> {
+        let [[_, y0], [_, y1]] = self.extent;
+        self.move_to(selection.map(|[x0, x1]| [[x0, y0], [x1, y1]]))
+    }
+
+    /// [`move_to`](Self::move_to) for a y range
+    pub fn move_y(&mut self, selection: Option<[f64; 2]>) -> Vec<BrushEvent> {
+        let [[x0, _], [x1, _]] = self.extent;
+        self.move_to(selection.map(|[y0, y1]| [[x0, y0], [x1, y1]]))
+    }
+
+    /// Clear the selection
+    pub fn clear(&mut self) -> Vec<BrushEvent> {
+        self.move_to(None)
+    }
+
+    /// Register a listener for `typename`: `start`, `brush` or `end`,
+    /// optionally with a `.name` suffix (e.g. `brush.linked`)
+    pub async fn on<F>(&self, typename: &str, listener: F) -> HandlerHandle
+    where
+        F: Fn(&BrushEvent) + Send + Sync + 'static,
+    {
+        self.listeners
+            .on_with_handle(typename, move |event: &Event| {
+                if let Some(brush_event) = event
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.downcast_ref::<BrushEvent>())
+                {
+                    listener(brush_event);
+                }
+            })
+            .await
+    }
+
+    /// Remove a listener registered with [`on`](Self::on)
+    pub async fn off(&self, typename: &str, handle: &HandlerHandle) {
+        self.listeners.off_handle(typename, handle).await;
+    }
+
+    /// Send `events` to the registered listeners
+    pub async fn dispatch(&self, events: &[BrushEvent]) {
+        let typenames = self.listeners.events().await;
+        for brush_event in events {
+            let name = brush_event.event_type.name();
+            for typename in typenames
+                .iter()
+                .filter(|t| t.split('.').next() == Some(name))
+            {
+                let event = Arc::new(Event {
+                    event_type: Cow::Borrowed(name),
+                    data: Some(Arc::new(*brush_event)),
+                    timestamp: std::time::Instant::now(),
+                    source: Some(Cow::Borrowed("brush")),
+                    propagation_stopped: Arc::new(AtomicBool::new(false)),
+                    default_prevented: Arc::new(AtomicBool::new(false)),
+                });
+                self.listeners.call_event(typename, event).await;
+            }
+        }
+    }
+
+    /// [`handle`](Self::handle), then dispatch the events to listeners
+    pub async fn handle_async(&mut self, input: BrushInput) -> Vec<BrushEvent> {
+        let events = self.handle(input);
+        self.dispatch(&events).await;
+        events
+    }
+
+    /// Draw the brush into `g`: an overlay over the extent, the selection and
+    /// its resize handles, hidden when there is no selection
+    ///
+    /// The first call appends the rectangles; later calls update them.
+    pub fn render(&self, g: &mut Selection) {
+        if g.find_all(".overlay").is_empty() {
+            g.append("rect")
+                .attr("class", "overlay")
+                .attr("pointer-events", "all")
+                .attr("cursor", "crosshair");
+            g.append("rect")
+                .attr("class", "selection")
+                .attr("cursor", "move")
+                .attr("fill", "#777")
+                .attr("fill-opacity", "0.3")
+                .attr("stroke", "#fff")
+                .attr("shape-rendering", "crispEdges");
+            for handle in self.handles() {
+                g.append("rect")
+                    .attr("class", &format!("handle handle--{}", handle.name()))
+                    .attr("cursor", handle.cursor());
+            }
+        }
+        set_rect(&mut g.find_all(".overlay"), self.extent);
+
+        let mut selection = g.find_all(".selection");
+        match self.selection {
+            Some(rect) => {
+                set_rect(&mut selection, rect);
+                selection.attr("display", "");
+            }
+            None => {
+                selection.attr("display", "none");
+            }
+        }
+        for &handle in self.handles() {
+            let mut node = g.find_all(&format!(".handle--{}", handle.name()));
+            match self.selection.map(|s| self.handle_rect(handle, s)) {
+                Some(rect) => {
+                    set_rect(&mut node, rect);
+                    node.attr("display", "");
+                }
+                None => {
+                    node.attr("display", "none");
+                }
+            }
+        }
+    }
+
+    fn handles(&self) -> &'static [Handle] {
+        match self.dimension {
+            BrushDimension::X => &[Handle::W, Handle::E],
+            BrushDimension::Y => &[Handle::N, Handle::S],
+            BrushDimension::XY => &[
+                Handle::N,
+                Handle::W,
+                Handle::E,
+                Handle::S,
+                Handle::NW,
+                Handle::NE,
+                Handle::SW,
+                Handle::SE,
+            ],
+        }
+    }
+
+    // The handle's rectangle: a strip along its edge, or a square at its corner
+    fn handle_rect(&self, handle: Handle, selection: Extent) -> Extent {
+        let [[w, n], [e, s]] = selection;
+        let half = self.handle_size / 2.0;
+        let name = handle.name();
+        let x = if name.ends_with('e') { e } else { w } - half;
+        let y = if name.starts_with('s') { s } else { n } - half;
+        let width = match handle {
+            Handle::N | Handle::S => e - w + self.handle_size,
+            _ => self.handle_size,
+        };
+        let height = match handle {
+            Handle::E | Handle::W => s - n + self.handle_size,
+            _ => self.handle_size,
+        };
+        [[x, y], [x + width, y + height]]
+    }
+
+    fn pointer_down(&mut self, id: u64, point: [f64; 2]) -> Vec<BrushEvent> {
+        if self.gesture.is_some() {
+            return Vec::new();
+        }
+        let [[ex0, ey0], [ex1, ey1]] = self.extent;
+        let [px, py] = point;
+        // The topmost target under the pointer: handles, the selection, then
+        // the overlay
+        let handle = self.selection.and_then(|s| {
+            self.handles()
+                .iter()
+                .rev()
+                .find(|&&h| contains(self.handle_rect(h, s), point))
+        });
+        let (mode, (sign_x, sign_y), start) = match (handle, self.selection) {
+            (Some(handle), Some([[w, n], [e, s]])) => {
+                (BrushMode::Handle, handle.signs(), [w, n, e, s])
+            }
+            (None, Some(selection @ [[w, n], [e, s]])) if contains(selection, point) => {
+                (BrushMode::Drag, (1.0, 1.0), [w, n, e, s])
+            }
+            _ if contains(self.extent, point) => {
+                // A new, empty selection at the pointer
+                let (w, e) = match self.dimension {
+                    BrushDimension::Y => (ex0, ex1),
+                    _ => (px, px),
+                };
+                let (n, s) = match self.dimension {
+                    BrushDimension::X => (ey0, ey1),
+                    _ => (py, py),
+                };
+                self.selection = Some([[w, n], [e, s]]);
+                (BrushMode::Handle, (1.0, 1.0), [w, n, e, s])
+            }
+            _ => return Vec::new(),
+        };
+        // One-dimensional brushes never move the other dimension
+        let sign_x = if self.dimension == BrushDimension::Y {
+            0.0
+        } else {
+            sign_x
+        };
+        let sign_y = if self.dimension == BrushDimension::X {
+            0.0
+        } else {
+            sign_y
+        };
+        self.gesture = Some(Gesture {
+            id,
+            mode,
+            sign_x,
+            sign_y,
+            point0: point,
+            start,
+            current: start,
+        });
+        vec![self.event(BrushEventType::Start, Some(mode))]
+    }
+
+    fn pointer_move(&mut self, id: u64, point: [f64; 2]) -> Vec<BrushEvent> {
+        let Some(mut g) = self.gesture.filter(|g| g.id == id) else {
+            return Vec::new();
+        };
+        let [[ex0, ey0], [ex1, ey1]] = self.extent;
+        let [w0, n0, e0, s0] = g.start;
+        let [mut w1, mut n1, mut e1, mut s1] = g.current;
+        let dx = point[0] - g.point0[0];
+        let dy = point[1] - g.point0[1];
+        match g.mode {
+            BrushMode::Drag => {
+                if g.sign_x != 0.0 {
+                    let dx = dx.min(ex1 - e0).max(ex0 - w0);
+                    w1 = w0 + dx;
+                    e1 = e0 + dx;
+                }
+                if g.sign_y != 0.0 {
+                    let dy = dy.min(ey1 - s0).max(ey0 - n0);
+                    n1 = n0 + dy;
+                    s1 = s0 + dy;
+                }
+            }
+            BrushMode::Handle => {
+                if g.sign_x < 0.0 {
+                    w1 = w0 + dx.min(ex1 - w0).max(ex0 - w0);
+                    e1 = e0;
+                } else if g.sign_x > 0.0 {
+                    w1 = w0;
+                    e1 = e0 + dx.min(ex1 - e0).max(ex0 - e0);
+                }
+                if g.sign_y < 0.0 {
+                    n1 = n0 + dy.min(ey1 - n0).max(ey0 - n0);
+                    s1 = s0;
+                } else if g.sign_y > 0.0 {
+                    n1 = n0;
+                    s1 = s0 + dy.min(ey1 - s0).max(ey0 - s0);
+                }
+            }
+        }
+        // Dragging an edge past the opposite one flips the selection
+        if e1 < w1 {
+            g.sign_x = -g.sign_x;
+            g.start[0] = e0;
+            g.start[2] = w0;
+            std::mem::swap(&mut w1, &mut e1);
+        }
+        if s1 < n1 {
+            g.sign_y = -g.sign_y;
+            g.start[1] = s0;
+            g.start[3] = n0;
+            std::mem::swap(&mut n1, &mut s1);
+        }
+        g.current = [w1, n1, e1, s1];
+        self.gesture = Some(g);
+        let selection = [[w1, n1], [e1, s1]];
+        if self.selection == Some(selection) {
+            return Vec::new();
+        }
+        self.selection = Some(selection);
+        vec![self.event(BrushEventType::Brush, Some(g.mode))]
+    }
+
+    fn pointer_up(&mut self, id: u64) -> Vec<BrushEvent> {
+        let Some(gesture) = self.gesture.filter(|g| g.id == id) else {
+            return Vec::new();
+        };
+        self.gesture = None;
+        if self.selection.is_some_and(|s| is_empty(&s)) {
+            self.selection = None;
+        }
+        vec![self.event(BrushEventType::End, Some(gesture.mode))]
+    }
+
+    fn event(&self, event_type: BrushEventType, mode: Option<BrushMode>) -> BrushEvent {
+        BrushEvent {
+            event_type,
+            selection: self.selection,
+            mode,
+        }
+    }
+}
+
+fn is_empty(selection: &Extent) -> bool {
+    selection[0][0] == selection[1][0] || selection[0][1] == selection[1][1]
+}
+
+fn contains(rect: Extent, point: [f64; 2]) -> bool {
+    let [[x0, y0], [x1, y1]] = rect;
+    point[0] >= x0 && point[0] <= x1 && point[1] >= y0 && point[1] <= y1
+}
+
+fn set_rect(selection: &mut Selection, rect: Extent) {
+    let [[x0, y0], [x1, y1]] = rect;
+    selection
+        .attr("x", &x0.to_string())
+        .attr("y", &y0.to_string())
+        .attr("width", &(x1 - x0).to_string())
+        .attr("height", &(y1 - y0).to_string());
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,191 @@
+//! Tests for d3-brush (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::brush::*;
+    use crate::selection::{Arena, Selection};
+    use slotmap::SlotMap;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Mutex;
+
+    fn down(point: [f64; 2]) -> BrushInput {
+        BrushInput::PointerDown { id: 1, point }
+    }
+
+    fn drag(point: [f64; 2]) -> BrushInput {
+        BrushInput::PointerMove { id: 1, point }
+    }
+
+    const UP: BrushInput = BrushInput::PointerUp { id: 1 };
+
+    fn types(events: &[BrushEvent]) -> Vec<BrushEventType> {
+        events.iter().map(|e| e.event_type).collect()
+    }
+
+    #[test]
+    fn test_brush_new_selection() {
+        let mut b = brush([[0.0, 0.0], [100.0, 100.0]]);
+        let events = b.handle(down([10.0, 20.0]));
+        assert_eq!(types(&events), [BrushEventType::Start]);
+        assert_eq!(events[0].mode, Some(BrushMode::Handle));
+        assert_eq!(events[0].selection, Some([[10.0, 20.0], [10.0, 20.0]]));
+        let events = b.handle(drag([40.0, 60.0]));
+        assert_eq!(types(&events), [BrushEventType::Brush]);
+        assert_eq!(b.get_selection(), Some([[10.0, 20.0], [40.0, 60.0]]));
+        // Unchanged selections are not reported
+        assert!(b.handle(drag([40.0, 60.0])).is_empty());
+        // Drawing back past the start flips the selection; it stays in the extent
+        b.handle(drag([-50.0, 5.0]));
+        assert_eq!(b.get_selection(), Some([[0.0, 5.0], [10.0, 20.0]]));
+        let events = b.handle(UP);
+        assert_eq!(types(&events), [BrushEventType::End]);
+        assert!(!b.is_active());
+        assert_eq!(b.get_selection_x(), Some([0.0, 10.0]));
+        assert_eq!(b.get_selection_y(), Some([5.0, 20.0]));
+    }
+
+    #[test]
+    fn test_click_clears() {
+        let mut b = brush_x([[0.0, 0.0], [100.0, 10.0]]);
+        b.move_x(Some([20.0, 40.0]));
+        b.handle(down([80.0, 5.0]));
+        let events = b.handle(UP);
+        assert_eq!(events[0].selection, None);
+        assert_eq!(b.get_selection(), None);
+        // Pointers outside the extent are ignored
+        assert!(b.handle(down([150.0, 5.0])).is_empty());
+    }
+
+    #[test]
+    fn test_drag_selection() {
+        let mut b = brush_x([[0.0, 0.0], [100.0, 10.0]]);
+        b.move_x(Some([20.0, 40.0]));
+        let events = b.handle(down([30.0, 5.0]));
+        assert_eq!(events[0].mode, Some(BrushMode::Drag));
+        b.handle(drag([45.0, 9.0]));
+        // The y extent is fixed for an x brush
+        assert_eq!(b.get_selection(), Some([[35.0, 0.0], [55.0, 10.0]]));
+        // Clamped to the extent without changing size
+        b.handle(drag([200.0, 5.0]));
+        assert_eq!(b.get_selection_x(), Some([80.0, 100.0]));
+        b.handle(UP);
+        assert_eq!(b.get_selection_x(), Some([80.0, 100.0]));
+    }
+
+    #[test]
+    fn test_resize_handles() {
+        let mut b = brush([[0.0, 0.0], [100.0, 100.0]]);
+        b.move_to(Some([[20.0, 20.0], [60.0, 60.0]]));
+        // East edge
+        b.handle(down([61.0, 40.0]));
+        b.handle(drag([81.0, 90.0]));
+        b.handle(UP);
+        assert_eq!(b.get_selection(), Some([[20.0, 20.0], [80.0, 60.0]]));
+        // North-west corner, past the opposite corner
+        b.handle(down([20.0, 20.0]));
+        b.handle(drag([90.0, 70.0]));
+        assert_eq!(b.get_selection(), Some([[80.0, 60.0], [90.0, 70.0]]));
+        b.handle(UP);
+
+        let mut y = brush_y([[0.0, 0.0], [10.0, 100.0]]).handle_size(10.0);
+        assert_eq!(y.get_handle_size(), 10.0);
+        y.move_y(Some([30.0, 50.0]));
+        assert_eq!(y.get_selection(), Some([[0.0, 30.0], [10.0, 50.0]]));
+        y.handle(down([5.0, 54.0]));
+        y.handle(drag([5.0, 64.0]));
+        assert_eq!(y.get_selection_y(), Some([30.0, 60.0]));
+    }
+
+    #[test]
+    fn test_move_to_and_invert() {
+        let mut b = brush_x([[0.0, 0.0], [200.0, 50.0]]);
+        let events = b.move_to(Some([[50.0, 10.0], [150.0, 20.0]]));
+        assert_eq!(
+            types(&events),
+            [
+                BrushEventType::Start,
+                BrushEventType::Brush,
+                BrushEventType::End
+            ]
+        );
+        assert!(events.iter().all(|e| e.mode.is_none()));
+        assert_eq!(b.get_selection(), Some([[50.0, 0.0], [150.0, 50.0]]));
+        let x = crate::scale::ScaleLinear::new([0.0, 10.0], [0.0, 200.0]);
+        assert_eq!(b.invert_x(|px| x.invert(px)), Some([2.5, 7.5]));
+
+        // Empty selections clear
+        b.move_x(Some([70.0, 70.0]));
+        assert_eq!(b.get_selection(), None);
+        assert_eq!(b.invert_x(|px| px), None);
+
+        // A move during a gesture ends it first
+        b.handle(down([10.0, 10.0]));
+        let events = b.clear();
+        assert_eq!(
+            types(&events)[..2],
+            [BrushEventType::End, BrushEventType::Start]
+        );
+        assert!(!b.is_active());
+    }
+
+    #[test]
+    fn test_render() {
+        let arena = Rc::new(RefCell::new(Arena {
+            nodes: SlotMap::with_key(),
+        }));
+        let mut g = Selection::root(arena, "g");
+        let mut b = brush_x([[0.0, 0.0], [100.0, 20.0]]);
+        b.render(&mut g);
+        assert_eq!(g.children().size(), 4);
+        assert_eq!(
+            g.find_all(".overlay").get_attr("width").as_deref(),
+            Some("100")
+        );
+        assert_eq!(
+            g.find_all(".selection").get_attr("display").as_deref(),
+            Some("none")
+        );
+
+        b.move_x(Some([10.0, 30.0]));
+        b.render(&mut g);
+        assert_eq!(g.children().size(), 4);
+        let selection = g.find_all(".selection");
+        assert_eq!(selection.get_attr("display"), None);
+        assert_eq!(selection.get_attr("x").as_deref(), Some("10"));
+        assert_eq!(selection.get_attr("width").as_deref(), Some("20"));
+        let east = g.find_all(".handle--e");
+        assert_eq!(east.get_attr("x").as_deref(), Some("27"));
+        assert_eq!(east.get_attr("height").as_deref(), Some("26"));
+        assert_eq!(east.get_attr("cursor").as_deref(), Some("ew-resize"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_listeners() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut b = brush_x([[0.0, 0.0], [100.0, 10.0]]);
+        let log = Arc::clone(&seen);
+        b.on("brush", move |e| {
+            log.lock().unwrap().push(("brush", e.selection))
+        })
+        .await;
+        let log = Arc::clone(&seen);
+        let handle = b
+            .on("end.linked", move |e| {
+                log.lock().unwrap().push(("end", e.selection))
+            })
+            .await;
+
+        b.handle_async(down([10.0, 5.0])).await;
+        b.handle_async(drag([30.0, 5.0])).await;
+        b.handle_async(UP).await;
+        let rect = Some([[10.0, 0.0], [30.0, 10.0]]);
+        assert_eq!(*seen.lock().unwrap(), [("brush", rect), ("end", rect)]);
+
+        b.off("end.linked", &handle).await;
+        let events = b.clear();
+        b.dispatch(&events).await;
+        assert_eq!(seen.lock().unwrap().len(), 3);
+        assert_eq!(seen.lock().unwrap()[2], ("brush", None));
+    }
+}
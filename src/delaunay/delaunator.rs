@@ -0,0 +1,415 @@
+//! Sweep-hull Delaunay triangulation (port of mapbox/delaunator, which
+//! d3-delaunay builds on)
+
+use super::EMPTY;
+
+const EPSILON: f64 = f64::EPSILON * 2.0;
+const EDGE_STACK_SIZE: usize = 512;
+
+/// Raw triangulation of a flat `[x0, y0, x1, y1, ...]` coordinate array
+pub(crate) struct Triangulation {
+    pub triangles: Vec<usize>,
+    pub halfedges: Vec<usize>,
+    pub hull: Vec<usize>,
+}
+
+struct Builder<'a> {
+    coords: &'a [f64],
+    triangles: Vec<usize>,
+    halfedges: Vec<usize>,
+    hull_prev: Vec<usize>,
+    hull_next: Vec<usize>,
+    hull_tri: Vec<usize>,
+    hull_hash: Vec<usize>,
+    hull_start: usize,
+    cx: f64,
+    cy: f64,
+    edge_stack: Vec<usize>,
+}
+
+impl Triangulation {
+    pub fn new(coords: &[f64]) -> Self {
+        let n = coords.len() / 2;
+        if n == 0 {
+            return Self {
+                triangles: Vec::new(),
+                halfedges: Vec::new(),
+                hull: Vec::new(),
+            };
+        }
+        let x = |i: usize| coords[2 * i];
+        let y = |i: usize| coords[2 * i + 1];
+
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for i in 0..n {
+            min_x = min_x.min(x(i));
+            min_y = min_y.min(y(i));
+            max_x = max_x.max(x(i));
+            max_y = max_y.max(y(i));
+        }
+        let (cx, cy) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+
+        // Seed triangle: the point closest to the centre, its nearest
+        // neighbour, and the point forming the smallest circumcircle with them
+        let mut i0 = 0;
+        let mut min_dist = f64::INFINITY;
+        for i in 0..n {
+            let d = dist(cx, cy, x(i), y(i));
+            if d < min_dist {
+                i0 = i;
+                min_dist = d;
+            }
+        }
+        let mut i1 = 0;
+        min_dist = f64::INFINITY;
+        for i in 0..n {
+            if i == i0 {
+                continue;
+            }
+            let d = dist(x(i0), y(i0), x(i), y(i));
+            if d < min_dist && d > 0.0 {
+                i1 = i;
+                min_dist = d;
+            }
+        }
+        let mut i2 = 0;
+        let mut min_radius = f64::INFINITY;
+        for i in 0..n {
+            if i == i0 || i == i1 {
+                continue;
+            }
+            let r = circumradius(x(i0), y(i0), x(i1), y(i1), x(i), y(i));
+            if r < min_radius {
+                i2 = i;
+                min_radius = r;
+            }
+        }
+
+        if min_radius == f64::INFINITY {
+            // All points are collinear: the hull is the points sorted along
+            // the line, without duplicates, and there are no triangles
+            let along = |i: usize| {
+                let dx = x(i) - coords[0];
+                if dx != 0.0 { dx } else { y(i) - coords[1] }
+            };
+            let mut ids: Vec<usize> = (0..n).collect();
+            ids.sort_by(|&a, &b| along(a).total_cmp(&along(b)));
+            let mut hull = Vec::new();
+            let mut d0 = f64::NEG_INFINITY;
+            for id in ids {
+                if along(id) > d0 {
+                    hull.push(id);
+                    d0 = along(id);
+                }
+            }
+            return Self {
+                triangles: Vec::new(),
+                halfedges: Vec::new(),
+                hull,
+            };
+        }
+
+        if orient2d(x(i0), y(i0), x(i1), y(i1), x(i2), y(i2)) < 0.0 {
+            std::mem::swap(&mut i1, &mut i2);
+        }
+        let (cx, cy) = circumcenter(x(i0), y(i0), x(i1), y(i1), x(i2), y(i2));
+
+        let dists: Vec<f64> = (0..n).map(|i| dist(x(i), y(i), cx, cy)).collect();
+        let mut ids: Vec<usize> = (0..n).collect();
+        ids.sort_by(|&a, &b| dists[a].total_cmp(&dists[b]));
+
+        let hash_size = (n as f64).sqrt().ceil() as usize;
+        let max_triangles = (2 * n).saturating_sub(5).max(1);
+        let mut b = Builder {
+            coords,
+            triangles: Vec::with_capacity(max_triangles * 3),
+            halfedges: Vec::with_capacity(max_triangles * 3),
+            hull_prev: vec![0; n],
+            hull_next: vec![0; n],
+            hull_tri: vec![0; n],
+            hull_hash: vec![EMPTY; hash_size],
+            hull_start: i0,
+            cx,
+            cy,
+            edge_stack: Vec::with_capacity(EDGE_STACK_SIZE),
+        };
+
+        let mut hull_size = 3;
+        b.hull_next[i0] = i1;
+        b.hull_prev[i2] = i1;
+        b.hull_next[i1] = i2;
+        b.hull_prev[i0] = i2;
+        b.hull_next[i2] = i0;
+        b.hull_prev[i1] = i0;
+        b.hull_tri[i0] = 0;
+        b.hull_tri[i1] = 1;
+        b.hull_tri[i2] = 2;
+        for i in [i0, i1, i2] {
+            let key = b.hash_key(x(i), y(i));
+            b.hull_hash[key] = i;
+        }
+        b.add_triangle(i0, i1, i2, EMPTY, EMPTY, EMPTY);
+
+        let (mut xp, mut yp) = (0.0, 0.0);
+        for (k, &i) in ids.iter().enumerate() {
+            let (px, py) = (x(i), y(i));
+
+            // Skip near-duplicate points
+            if k > 0 && (px - xp).abs() <= EPSILON && (py - yp).abs() <= EPSILON {
+                continue;
+            }
+            xp = px;
+            yp = py;
+            if i == i0 || i == i1 || i == i2 {
+                continue;
+            }
+
+            // Find a visible edge on the convex hull using the edge hash
+            let mut start = 0;
+            let key = b.hash_key(px, py);
+            for j in 0..hash_size {
+                start = b.hull_hash[(key + j) % hash_size];
+                if start != EMPTY && start != b.hull_next[start] {
+                    break;
+                }
+            }
+            start = b.hull_prev[start];
+            let mut e = start;
+            let mut q = b.hull_next[e];
+            let mut visible = true;
+            while orient2d(px, py, x(e), y(e), x(q), y(q)) >= 0.0 {
+                e = q;
+                if e == start {
+                    visible = false;
+                    break;
+                }
+                q = b.hull_next[e];
+            }
+            if !visible {
+                // Likely a near-duplicate point
+                continue;
+            }
+
+            // Add the first triangle from the point
+            let t = b.add_triangle(e, i, b.hull_next[e], EMPTY, EMPTY, b.hull_tri[e]);
+            b.hull_tri[i] = b.legalize(t + 2);
+            b.hull_tri[e] = t;
+            hull_size += 1;
+
+            // Walk forward through the hull, adding more triangles and flipping
+            let mut nx = b.hull_next[e];
+            q = b.hull_next[nx];
+            while orient2d(px, py, x(nx), y(nx), x(q), y(q)) < 0.0 {
+                let t = b.add_triangle(nx, i, q, b.hull_tri[i], EMPTY, b.hull_tri[nx]);
+                b.hull_tri[i] = b.legalize(t + 2);
+                b.hull_next[nx] = nx;
+                hull_size -= 1;
+                nx = q;
+                q = b.hull_next[nx];
+            }
+
+            // Walk backward from the other side
+            if e == start {
+                q = b.hull_prev[e];
+                while orient2d(px, py, x(q), y(q), x(e), y(e)) < 0.0 {
+                    let t = b.add_triangle(q, i, e, EMPTY, b.hull_tri[e], b.hull_tri[q]);
+                    b.legalize(t + 2);
+                    b.hull_tri[q] = t;
+                    b.hull_next[e] = e;
+                    hull_size -= 1;
+                    e = q;
+                    q = b.hull_prev[e];
+                }
+            }
+
+            // Update the hull indices
+            b.hull_start = e;
+            b.hull_prev[i] = e;
+            b.hull_next[e] = i;
+            b.hull_prev[nx] = i;
+            b.hull_next[i] = nx;
+
+            let key = b.hash_key(px, py);
+            b.hull_hash[key] = i;
+            let key = b.hash_key(x(e), y(e));
+            b.hull_hash[key] = e;
+        }
+
+        let mut hull = Vec::with_capacity(hull_size);
+        let mut e = b.hull_start;
+        for _ in 0..hull_size {
+            hull.push(e);
+            e = b.hull_next[e];
+        }
+
+        Self {
+            triangles: b.triangles,
+            halfedges: b.halfedges,
+            hull,
+        }
+    }
+}
+
+impl Builder<'_> {
+    fn hash_key(&self, x: f64, y: f64) -> usize {
+        let size = self.hull_hash.len();
+        let key = (pseudo_angle(x - self.cx, y - self.cy) * size as f64).floor();
+        (key as usize) % size
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.halfedges[a] = b;
+        if b != EMPTY {
+            self.halfedges[b] = a;
+        }
+    }
+
+    fn add_triangle(
+        &mut self,
+        i0: usize,
+        i1: usize,
+        i2: usize,
+        a: usize,
+        b: usize,
+        c: usize,
+    ) -> usize {
+        let t = self.triangles.len();
+        self.triangles.extend([i0, i1, i2]);
+        self.halfedges.extend([EMPTY; 3]);
+        self.link(t, a);
+        self.link(t + 1, b);
+        self.link(t + 2, c);
+        t
+    }
+
+    // Flip edges until every triangle around `a` satisfies the Delaunay
+    // condition; returns the last outgoing edge for the hull bookkeeping
+    fn legalize(&mut self, mut a: usize) -> usize {
+        let coords = self.coords;
+        let mut ar;
+        loop {
+            let b = self.halfedges[a];
+            let a0 = a - a % 3;
+            ar = a0 + (a + 2) % 3;
+
+            if b == EMPTY {
+                match self.edge_stack.pop() {
+                    Some(next) => {
+                        a = next;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            let b0 = b - b % 3;
+            let al = a0 + (a + 1) % 3;
+            let bl = b0 + (b + 2) % 3;
+
+            let p0 = self.triangles[ar];
+            let pr = self.triangles[a];
+            let pl = self.triangles[al];
+            let p1 = self.triangles[bl];
+
+            let illegal = in_circle(
+                coords[2 * p0],
+                coords[2 * p0 + 1],
+                coords[2 * pr],
+                coords[2 * pr + 1],
+                coords[2 * pl],
+                coords[2 * pl + 1],
+                coords[2 * p1],
+                coords[2 * p1 + 1],
+            );
+
+            if illegal {
+                self.triangles[a] = p1;
+                self.triangles[b] = p0;
+
+                let hbl = self.halfedges[bl];
+
+                // The flipped edge was on the hull; point the hull at the new triangle
+                if hbl == EMPTY {
+                    let mut e = self.hull_start;
+                    loop {
+                        if self.hull_tri[e] == bl {
+                            self.hull_tri[e] = a;
+                            break;
+                        }
+                        e = self.hull_prev[e];
+                        if e == self.hull_start {
+                            break;
+                        }
+                    }
+                }
+                self.link(a, hbl);
+                self.link(b, self.halfedges[ar]);
+                self.link(ar, bl);
+
+                let br = b0 + (b + 1) % 3;
+                if self.edge_stack.len() < EDGE_STACK_SIZE {
+                    self.edge_stack.push(br);
+                }
+            } else {
+                match self.edge_stack.pop() {
+                    Some(next) => a = next,
+                    None => break,
+                }
+            }
+        }
+        ar
+    }
+}
+
+// Monotonically increasing with the real angle, but cheaper to compute
+fn pseudo_angle(dx: f64, dy: f64) -> f64 {
+    let p = dx / (dx.abs() + dy.abs());
+    (if dy > 0.0 { 3.0 - p } else { 1.0 + p }) / 4.0
+}
+
+fn dist(ax: f64, ay: f64, bx: f64, by: f64) -> f64 {
+    let dx = ax - bx;
+    let dy = ay - by;
+    dx * dx + dy * dy
+}
+
+/// Positive when a, b, c turn clockwise in y-up coordinates
+pub(crate) fn orient2d(ax: f64, ay: f64, bx: f64, by: f64, cx: f64, cy: f64) -> f64 {
+    (ay - cy) * (bx - cx) - (ax - cx) * (by - cy)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn in_circle(ax: f64, ay: f64, bx: f64, by: f64, cx: f64, cy: f64, px: f64, py: f64) -> bool {
+    let dx = ax - px;
+    let dy = ay - py;
+    let ex = bx - px;
+    let ey = by - py;
+    let fx = cx - px;
+    let fy = cy - py;
+
+    let ap = dx * dx + dy * dy;
+    let bp = ex * ex + ey * ey;
+    let cp = fx * fx + fy * fy;
+
+    dx * (ey * cp - bp * fy) - dy * (ex * cp - bp * fx) + ap * (ex * fy - ey * fx) < 0.0
+}
+
+fn circumradius(ax: f64, ay: f64, bx: f64, by: f64, cx: f64, cy: f64) -> f64 {
+    let (x, y) = circumcenter(ax, ay, bx, by, cx, cy);
+    let (x, y) = (x - ax, y - ay);
+    x * x + y * y
+}
+
+fn circumcenter(ax: f64, ay: f64, bx: f64, by: f64, cx: f64, cy: f64) -> (f64, f64) {
+    let dx = bx - ax;
+    let dy = by - ay;
+    let ex = cx - ax;
+    let ey = cy - ay;
+
+    let bl = dx * dx + dy * dy;
+    let cl = ex * ex + ey * ey;
+    let d = 0.5 / (dx * ey - dy * ex);
+
+    (ax + (ey * bl - dy * cl) * d, ay + (dx * cl - ex * bl) * d)
+}
@@ -0,0 +1,337 @@
+//! d3-delaunay: Delaunay triangulation and Voronoi diagrams (Rust port)
+//!
+//! `Delaunay` triangulates a set of points with the sweep-hull algorithm from
+//! Delaunator; [`Voronoi`] derives the dual cells, clipped to a bounding box.
+//!
+//! ```
+//! use rust_d3::delaunay::Delaunay;
+//!
+//! let delaunay = Delaunay::from(&[(0.0, 0.0), (10.0, 0.0), (0.0, 10.0), (10.0, 10.0)]);
+//! assert_eq!(delaunay.triangles.len() / 3, 2);
+//! assert_eq!(delaunay.find(9.0, 8.0, 0), Some(3));
+//!
+//! let voronoi = delaunay.voronoi([0.0, 0.0, 10.0, 10.0]);
+//! let cell = voronoi.cell_polygon(0).unwrap();
+//! assert_eq!(rust_d3::polygon_area(&cell).abs(), 25.0);
+//! ```
+
+mod delaunator;
+mod voronoi;
+
+pub use voronoi::Voronoi;
+
+use crate::path::Path;
+use delaunator::Triangulation;
+
+/// Marks a missing index: a hull halfedge with no twin, or a coincident
+/// point with no incoming edge (D3 uses `-1`)
+pub const EMPTY: usize = usize::MAX;
+
+/// Delaunay triangulation of a set of points
+#[derive(Debug, Clone)]
+pub struct Delaunay {
+    /// Flat coordinates `[x0, y0, x1, y1, ...]`; jittered slightly when all
+    /// points are collinear
+    pub points: Vec<f64>,
+    /// Point indices, three per triangle
+    pub triangles: Vec<usize>,
+    /// For each halfedge, the opposite halfedge in the adjacent triangle, or
+    /// [`EMPTY`] on the hull
+    pub halfedges: Vec<usize>,
+    /// Point indices of the convex hull, in order around it
+    pub hull: Vec<usize>,
+    /// For each point, an incoming halfedge (preferring hull edges), or [`EMPTY`]
+    pub inedges: Vec<usize>,
+    /// Points sorted along the line when every point is collinear
+    pub collinear: Option<Vec<usize>>,
+    hull_index: Vec<usize>,
+}
+
+impl Delaunay {
+    /// Triangulate flat coordinates `[x0, y0, x1, y1, ...]`
+    pub fn new(points: Vec<f64>) -> Self {
+        let triangulation = Triangulation::new(&points);
+        let n = points.len() / 2;
+        let mut delaunay = Self {
+            points,
+            triangles: Vec::new(),
+            halfedges: Vec::new(),
+            hull: Vec::new(),
+            inedges: vec![EMPTY; n],
+            collinear: None,
+            hull_index: vec![EMPTY; n],
+        };
+        delaunay.init(triangulation);
+        delaunay
+    }
+
+    /// Triangulate `(x, y)` points (D3 `Delaunay.from`)
+    pub fn from(points: &[(f64, f64)]) -> Self {
+        Self::new(points.iter().flat_map(|&(x, y)| [x, y]).collect())
+    }
+
+    fn init(&mut self, mut triangulation: Triangulation) {
+        let points = &mut self.points;
+        let n = points.len() / 2;
+
+        // Collinear points have no triangles; triangulate a slightly jittered
+        // copy so Voronoi cells still exist, and remember the exact order
+        if triangulation.hull.len() > 2 && triangulation.triangles.is_empty() {
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|&i, &j| {
+                points[2 * i]
+                    .total_cmp(&points[2 * j])
+                    .then(points[2 * i + 1].total_cmp(&points[2 * j + 1]))
+            });
+            let (e, f) = (order[0], order[n - 1]);
+            let r =
+                1e-8 * (points[2 * f + 1] - points[2 * e + 1]).hypot(points[2 * f] - points[2 * e]);
+            for i in 0..n {
+                let (x, y) = (points[2 * i], points[2 * i + 1]);
+                points[2 * i] = x + (x + y).sin() * r;
+                points[2 * i + 1] = y + (x - y).cos() * r;
+            }
+            triangulation = Triangulation::new(points);
+            self.collinear = Some(order);
+        } else {
+            self.collinear = None;
+        }
+
+        self.triangles = triangulation.triangles;
+        self.halfedges = triangulation.halfedges;
+        self.hull = triangulation.hull;
+        self.inedges.fill(EMPTY);
+        self.hull_index.fill(EMPTY);
+
+        // An incoming halfedge per point gives its first neighbour; on the
+        // hull, exterior halfedges take priority
+        for e in 0..self.halfedges.len() {
+            let p = self.triangles[next_halfedge(e)];
+            if self.halfedges[e] == EMPTY || self.inedges[p] == EMPTY {
+                self.inedges[p] = e;
+            }
+        }
+        for (i, &h) in self.hull.iter().enumerate() {
+            self.hull_index[h] = i;
+        }
+
+        // One or two distinct points: a single degenerate triangle
+        if !self.hull.is_empty() && self.hull.len() <= 2 {
+            self.triangles = vec![self.hull[0], EMPTY, EMPTY];
+            self.halfedges = vec![EMPTY; 3];
+            self.inedges[self.hull[0]] = 1;
+            if let [_, h1] = self.hull[..] {
+                self.inedges[h1] = 0;
+                self.triangles[1] = h1;
+                self.triangles[2] = h1;
+            }
+        }
+    }
+
+    fn point(&self, i: usize) -> (f64, f64) {
+        (self.points[2 * i], self.points[2 * i + 1])
+    }
+
+    /// Indices of the points sharing a Delaunay edge with point `i`
+    pub fn neighbors(&self, i: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        if let Some(collinear) = &self.collinear {
+            if let Some(l) = collinear.iter().position(|&c| c == i) {
+                if l > 0 {
+                    result.push(collinear[l - 1]);
+                }
+                if l + 1 < collinear.len() {
+                    result.push(collinear[l + 1]);
+                }
+            }
+            return result;
+        }
+
+        let e0 = self.inedges[i];
+        if e0 == EMPTY {
+            // Coincident point
+            return result;
+        }
+        let mut e = e0;
+        loop {
+            let p0 = self.triangles[e];
+            if p0 == EMPTY {
+                // The lone point of a one-point triangulation
+                break;
+            }
+            result.push(p0);
+            e = next_halfedge(e);
+            if self.triangles[e] != i {
+                // Bad triangulation
+                break;
+            }
+            e = self.halfedges[e];
+            if e == EMPTY {
+                let p = self.hull[(self.hull_index[i] + 1) % self.hull.len()];
+                if p != p0 {
+                    result.push(p);
+                }
+                break;
+            }
+            if e == e0 {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Index of the point closest to `(x, y)`, walking the triangulation from
+    /// point `start`; `None` for NaN input or no points
+    ///
+    /// Passing the previous result as `start` makes repeated lookups for a
+    /// moving pointer close to constant time.
+    pub fn find(&self, x: f64, y: f64, start: usize) -> Option<usize> {
+        let n = self.points.len() / 2;
+        if x.is_nan() || y.is_nan() || n == 0 {
+            return None;
+        }
+        let i0 = start.min(n - 1);
+        let mut i = i0;
+        loop {
+            let c = self.step(i, x, y);
+            if c == i || c == i0 {
+                return Some(c);
+            }
+            i = c;
+        }
+    }
+
+    // One greedy step of `find`: the neighbour of `i` closest to (x, y)
+    pub(crate) fn step(&self, i: usize, x: f64, y: f64) -> usize {
+        let n = self.points.len() / 2;
+        if self.inedges[i] == EMPTY || n == 0 {
+            return (i + 1) % n.max(1);
+        }
+        let dist = |p: usize| {
+            let (px, py) = self.point(p);
+            (x - px).powi(2) + (y - py).powi(2)
+        };
+        let mut c = i;
+        let mut dc = dist(i);
+        let e0 = self.inedges[i];
+        let mut e = e0;
+        loop {
+            let t = self.triangles[e];
+            if t == EMPTY {
+                break;
+            }
+            let dt = dist(t);
+            if dt < dc {
+                dc = dt;
+                c = t;
+            }
+            e = next_halfedge(e);
+            if self.triangles[e] != i {
+                break;
+            }
+            e = self.halfedges[e];
+            if e == EMPTY {
+                let h = self.hull[(self.hull_index[i] + 1) % self.hull.len()];
+                if h != t && dist(h) < dc {
+                    return h;
+                }
+                break;
+            }
+            if e == e0 {
+                break;
+            }
+        }
+        c
+    }
+
+    /// Draw every Delaunay edge into `path`
+    pub fn render(&self, path: &mut Path) {
+        for (i, &j) in self.halfedges.iter().enumerate() {
+            // Each interior edge once; hull edges come from render_hull
+            if j == EMPTY || j < i {
+                continue;
+            }
+            let (x0, y0) = self.point(self.triangles[i]);
+            let (x1, y1) = self.point(self.triangles[next_halfedge(i)]);
+            path.move_to(x0, y0);
+            path.line_to(x1, y1);
+        }
+        self.render_hull(path);
+    }
+
+    /// Draw the convex hull as a closed ring
+    pub fn render_hull(&self, path: &mut Path) {
+        let Some((&first, rest)) = self.hull.split_first() else {
+            return;
+        };
+        let (x, y) = self.point(first);
+        path.move_to(x, y);
+        for &h in rest {
+            let (x, y) = self.point(h);
+            path.line_to(x, y);
+        }
+        path.close_path();
+    }
+
+    /// Draw triangle `t` (the points at `triangles[3t..3t + 3]`)
+    pub fn render_triangle(&self, t: usize, path: &mut Path) {
+        let corners = &self.triangles[3 * t..3 * t + 3];
+        if corners.contains(&EMPTY) {
+            return;
+        }
+        let (x, y) = self.point(corners[0]);
+        path.move_to(x, y);
+        for &c in &corners[1..] {
+            let (x, y) = self.point(c);
+            path.line_to(x, y);
+        }
+        path.close_path();
+    }
+
+    /// Draw a circle of radius `r` at every point
+    pub fn render_points(&self, path: &mut Path, r: f64) {
+        for i in 0..self.points.len() / 2 {
+            let (x, y) = self.point(i);
+            path.move_to(x + r, y);
+            path.arc(r, r, 0.0, false, true, x - r, y);
+            path.arc(r, r, 0.0, false, true, x + r, y);
+        }
+    }
+
+    /// Triangle `t` as a closed ring of its three corners; empty for the
+    /// placeholder triangle of one or two points
+    pub fn triangle_polygon(&self, t: usize) -> Vec<(f64, f64)> {
+        let corners = &self.triangles[3 * t..3 * t + 3];
+        if corners.contains(&EMPTY) {
+            return Vec::new();
+        }
+        let mut ring: Vec<(f64, f64)> = corners.iter().map(|&c| self.point(c)).collect();
+        ring.push(ring[0]);
+        ring
+    }
+
+    /// The convex hull as a closed ring
+    pub fn hull_polygon(&self) -> Vec<(f64, f64)> {
+        let mut ring: Vec<(f64, f64)> = self.hull.iter().map(|&h| self.point(h)).collect();
+        if let Some(&first) = ring.first() {
+            ring.push(first);
+        }
+        ring
+    }
+
+    /// Voronoi diagram clipped to `[xmin, ymin, xmax, ymax]`
+    ///
+    /// # Panics
+    ///
+    /// If the bounds are inverted or NaN.
+    pub fn voronoi(&self, bounds: [f64; 4]) -> Voronoi<'_> {
+        Voronoi::new(self, bounds)
+    }
+}
+
+fn next_halfedge(e: usize) -> usize {
+    if e % 3 == 2 { e - 2 } else { e + 1 }
+}
+
+#[cfg(test)]
+mod tests;
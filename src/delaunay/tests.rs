@@ -0,0 +1,175 @@
+//! Tests for d3-delaunay (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::delaunay::{Delaunay, EMPTY};
+    use crate::path::Path;
+    use crate::{polygon_area, polygon_centroid, polygon_contains};
+
+    fn square() -> Delaunay {
+        Delaunay::from(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)])
+    }
+
+    #[test]
+    fn test_delaunay_square() {
+        let d = square();
+        assert_eq!(d.triangles.len(), 6);
+        assert_eq!(d.halfedges.iter().filter(|&&h| h != EMPTY).count(), 2);
+        let mut hull = d.hull.clone();
+        hull.sort();
+        assert_eq!(hull, vec![0, 1, 2, 3]);
+        // Halfedges are symmetric
+        for (e, &h) in d.halfedges.iter().enumerate() {
+            if h != EMPTY {
+                assert_eq!(d.halfedges[h], e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_delaunay_is_delaunay() {
+        // A small grid with jitter; no point may fall inside any circumcircle
+        let points: Vec<(f64, f64)> = (0..30)
+            .map(|i| {
+                let (x, y) = ((i % 6) as f64, (i / 6) as f64);
+                (
+                    x * 10.0 + (i as f64 * 1.3).sin(),
+                    y * 10.0 + (i as f64 * 2.1).cos(),
+                )
+            })
+            .collect();
+        let d = Delaunay::from(&points);
+        // Euler: 2n - 2 - h triangles
+        assert_eq!(d.triangles.len() / 3, 2 * points.len() - 2 - d.hull.len());
+        for t in d.triangles.chunks(3) {
+            let [a, b, c] = [points[t[0]], points[t[1]], points[t[2]]];
+            let d2 = 2.0 * (a.0 * (b.1 - c.1) + b.0 * (c.1 - a.1) + c.0 * (a.1 - b.1));
+            let sq = |p: (f64, f64)| p.0 * p.0 + p.1 * p.1;
+            let ux = (sq(a) * (b.1 - c.1) + sq(b) * (c.1 - a.1) + sq(c) * (a.1 - b.1)) / d2;
+            let uy = (sq(a) * (c.0 - b.0) + sq(b) * (a.0 - c.0) + sq(c) * (b.0 - a.0)) / d2;
+            let r2 = (a.0 - ux).powi(2) + (a.1 - uy).powi(2);
+            for (i, p) in points.iter().enumerate() {
+                if !t.contains(&i) {
+                    assert!((p.0 - ux).powi(2) + (p.1 - uy).powi(2) >= r2 - 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_delaunay_neighbors_and_find() {
+        let d = Delaunay::from(&[
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (5.0, 5.0),
+            (0.0, 10.0),
+            (10.0, 10.0),
+        ]);
+        let mut n = d.neighbors(2);
+        n.sort();
+        assert_eq!(n, vec![0, 1, 3, 4]);
+        assert_eq!(d.find(9.0, 1.0, 0), Some(1));
+        assert_eq!(d.find(4.0, 6.0, 4), Some(2));
+        assert_eq!(d.find(f64::NAN, 0.0, 0), None);
+        assert_eq!(Delaunay::from(&[]).find(0.0, 0.0, 0), None);
+    }
+
+    #[test]
+    fn test_delaunay_collinear_and_degenerate() {
+        let d = Delaunay::from(&[(0.0, 0.0), (2.0, 0.0), (1.0, 0.0), (3.0, 0.0)]);
+        assert_eq!(d.collinear, Some(vec![0, 2, 1, 3]));
+        assert_eq!(d.neighbors(2), vec![0, 1]);
+        assert_eq!(d.find(2.2, 5.0, 0), Some(1));
+
+        let one = Delaunay::from(&[(1.0, 1.0)]);
+        assert_eq!(one.neighbors(0), Vec::<usize>::new());
+        assert_eq!(one.find(5.0, 5.0, 0), Some(0));
+        let two = Delaunay::from(&[(0.0, 0.0), (1.0, 0.0)]);
+        assert_eq!(two.neighbors(0), vec![1]);
+        assert_eq!(two.neighbors(1), vec![0]);
+    }
+
+    #[test]
+    fn test_delaunay_render() {
+        let d = Delaunay::from(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]);
+        let mut path = Path::new();
+        d.render(&mut path);
+        assert_eq!(path.to_string().matches('M').count(), 1);
+        assert!(path.to_string().ends_with('Z'));
+        let mut path = Path::new();
+        d.render_triangle(0, &mut path);
+        assert_eq!(path.to_string().matches('L').count(), 2);
+        assert_eq!(polygon_area(&d.triangle_polygon(0)).abs(), 0.5);
+        assert_eq!(d.hull_polygon().len(), 4);
+    }
+
+    #[test]
+    fn test_voronoi_cells_tile_bounds() {
+        let points: Vec<(f64, f64)> = (0..20)
+            .map(|i| {
+                let i = i as f64;
+                (
+                    50.0 + 40.0 * (i * 2.4).cos() * (i / 20.0).sqrt(),
+                    50.0 + 40.0 * (i * 2.4).sin() * (i / 20.0).sqrt(),
+                )
+            })
+            .collect();
+        let d = Delaunay::from(&points);
+        let v = d.voronoi([0.0, 0.0, 100.0, 100.0]);
+        let mut total = 0.0;
+        for (i, cell) in v.cell_polygons() {
+            assert_eq!(cell.first(), cell.last());
+            total += polygon_area(&cell).abs();
+            // Each site lies in its own cell
+            assert!(polygon_contains(&cell, points[i].0, points[i].1));
+            assert!(v.contains(i, points[i].0, points[i].1));
+            let (cx, cy) = polygon_centroid(&cell);
+            assert!((0.0..=100.0).contains(&cx) && (0.0..=100.0).contains(&cy));
+        }
+        assert!((total - 10_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_voronoi_square_and_render() {
+        let d = square();
+        let v = d.voronoi([0.0, 0.0, 1.0, 1.0]);
+        let cell = v.cell_polygon(3).unwrap();
+        assert_eq!(polygon_area(&cell).abs(), 0.25);
+        assert_eq!(polygon_centroid(&cell), (0.75, 0.75));
+        assert!(v.contains(3, 0.9, 0.6));
+        assert!(!v.contains(3, 0.1, 0.6));
+
+        let mut path = Path::new();
+        v.render_cell(0, &mut path);
+        assert!(path.to_string().starts_with('M') && path.to_string().ends_with('Z'));
+        let mut path = Path::new();
+        v.render(&mut path);
+        assert!(!path.to_string().is_empty());
+        let mut path = Path::new();
+        v.render_bounds(&mut path);
+        assert_eq!(path.to_string(), "M0 0 L1 0 L1 1 L0 1Z");
+    }
+
+    #[test]
+    fn test_voronoi_degenerate() {
+        let one = Delaunay::from(&[(5.0, 5.0)]);
+        let cell = one.voronoi([0.0, 0.0, 10.0, 10.0]).cell_polygon(0).unwrap();
+        assert_eq!(polygon_area(&cell).abs(), 100.0);
+
+        let two = Delaunay::from(&[(2.0, 5.0), (8.0, 5.0)]);
+        let v = two.voronoi([0.0, 0.0, 10.0, 10.0]);
+        assert_eq!(polygon_area(&v.cell_polygon(0).unwrap()).abs(), 50.0);
+        assert_eq!(polygon_area(&v.cell_polygon(1).unwrap()).abs(), 50.0);
+
+        let line = Delaunay::from(&[(1.0, 5.0), (5.0, 5.0), (9.0, 5.0)]);
+        let v = line.voronoi([0.0, 0.0, 10.0, 10.0]);
+        let area = polygon_area(&v.cell_polygon(1).unwrap()).abs();
+        assert!((area - 40.0).abs() < 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid bounds")]
+    fn test_voronoi_invalid_bounds() {
+        square().voronoi([1.0, 0.0, 0.0, 1.0]);
+    }
+}
@@ -0,0 +1,592 @@
+//! d3-delaunay: Voronoi diagram derived from a Delaunay triangulation
+
+use super::{Delaunay, EMPTY, next_halfedge};
+use crate::path::Path;
+
+// Cohen–Sutherland style codes: which side(s) of the bounds a point is on
+const LEFT: u8 = 0b0001;
+const RIGHT: u8 = 0b0010;
+const TOP: u8 = 0b0100;
+const BOTTOM: u8 = 0b1000;
+
+/// Voronoi diagram of a [`Delaunay`] triangulation, clipped to a rectangle
+#[derive(Debug, Clone)]
+pub struct Voronoi<'a> {
+    pub delaunay: &'a Delaunay,
+    /// Flat circumcenters `[x0, y0, x1, y1, ...]`, one per triangle
+    pub circumcenters: Vec<f64>,
+    /// For each hull point, the directions `[vx0, vy0, vx1, vy1]` of the rays
+    /// bounding its infinite cell; zero for interior points
+    pub vectors: Vec<f64>,
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+impl<'a> Voronoi<'a> {
+    /// Voronoi diagram clipped to `[xmin, ymin, xmax, ymax]`
+    ///
+    /// # Panics
+    ///
+    /// If the bounds are inverted or NaN.
+    pub fn new(delaunay: &'a Delaunay, bounds: [f64; 4]) -> Self {
+        let [xmin, ymin, xmax, ymax] = bounds;
+        // Written so that NaN bounds fail too
+        let valid = xmin <= xmax && ymin <= ymax;
+        if !valid {
+            panic!("invalid bounds: {:?}", bounds);
+        }
+        let mut voronoi = Self {
+            delaunay,
+            circumcenters: Vec::new(),
+            vectors: vec![0.0; delaunay.points.len() * 2],
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+        };
+        voronoi.init();
+        voronoi
+    }
+
+    fn init(&mut self) {
+        let Delaunay {
+            points,
+            hull,
+            triangles,
+            ..
+        } = self.delaunay;
+        // Hull barycenter, only needed for degenerate triangles
+        let mut barycenter = None;
+
+        self.circumcenters = Vec::with_capacity(triangles.len() / 3 * 2);
+        for t in triangles.chunks_exact(3) {
+            if t.contains(&EMPTY) {
+                self.circumcenters.extend([f64::NAN, f64::NAN]);
+                continue;
+            }
+            let (x1, y1) = (points[2 * t[0]], points[2 * t[0] + 1]);
+            let (x2, y2) = (points[2 * t[1]], points[2 * t[1] + 1]);
+            let (x3, y3) = (points[2 * t[2]], points[2 * t[2] + 1]);
+
+            let dx = x2 - x1;
+            let dy = y2 - y1;
+            let ex = x3 - x1;
+            let ey = y3 - y1;
+            let ab = (dx * ey - dy * ex) * 2.0;
+
+            let (x, y) = if ab.abs() < 1e-9 {
+                // The circumcenter of a degenerate triangle is at infinity,
+                // orthogonal to the edge and away from the hull's barycenter
+                let (bx, by) = *barycenter.get_or_insert_with(|| {
+                    let (sx, sy) = hull.iter().fold((0.0, 0.0), |(sx, sy), &i| {
+                        (sx + points[2 * i], sy + points[2 * i + 1])
+                    });
+                    (sx / hull.len() as f64, sy / hull.len() as f64)
+                });
+                let a = 1e9 * sign((bx - x1) * ey - (by - y1) * ex);
+                ((x1 + x3) / 2.0 - a * ey, (y1 + y3) / 2.0 + a * ex)
+            } else {
+                let d = 1.0 / ab;
+                let bl = dx * dx + dy * dy;
+                let cl = ex * ex + ey * ey;
+                (x1 + (ey * bl - dy * cl) * d, y1 + (dx * cl - ex * bl) * d)
+            };
+            self.circumcenters.extend([x, y]);
+        }
+
+        // Rays of the exterior cells, perpendicular to each hull edge
+        let Some(&last) = hull.last() else {
+            return;
+        };
+        let mut p1 = last * 4;
+        let (mut x1, mut y1) = (points[2 * last], points[2 * last + 1]);
+        for &h in hull {
+            let (p0, x0, y0) = (p1, x1, y1);
+            p1 = h * 4;
+            x1 = points[2 * h];
+            y1 = points[2 * h + 1];
+            self.vectors[p0 + 2] = y0 - y1;
+            self.vectors[p1] = y0 - y1;
+            self.vectors[p0 + 3] = x1 - x0;
+            self.vectors[p1 + 1] = x1 - x0;
+        }
+    }
+
+    /// Draw every cell edge into `path`, clipped to the bounds
+    pub fn render(&self, path: &mut Path) {
+        let Delaunay {
+            halfedges,
+            inedges,
+            hull,
+            ..
+        } = self.delaunay;
+        if hull.len() <= 1 {
+            return;
+        }
+        let cc = &self.circumcenters;
+        for (i, &j) in halfedges.iter().enumerate() {
+            if j == EMPTY || j < i {
+                continue;
+            }
+            let (ti, tj) = (i / 3 * 2, j / 3 * 2);
+            self.render_segment(cc[ti], cc[ti + 1], cc[tj], cc[tj + 1], path);
+        }
+        let mut h1 = hull[hull.len() - 1];
+        for &h in hull {
+            let h0 = h1;
+            h1 = h;
+            let t = inedges[h1] / 3 * 2;
+            let (x, y) = (cc[t], cc[t + 1]);
+            let v = h0 * 4;
+            if let Some((px, py)) = self.project(x, y, self.vectors[v + 2], self.vectors[v + 3]) {
+                self.render_segment(x, y, px, py, path);
+            }
+        }
+    }
+
+    /// Draw the clipping rectangle
+    pub fn render_bounds(&self, path: &mut Path) {
+        path.move_to(self.xmin, self.ymin);
+        path.line_to(self.xmax, self.ymin);
+        path.line_to(self.xmax, self.ymax);
+        path.line_to(self.xmin, self.ymax);
+        path.close_path();
+    }
+
+    /// Draw the cell of point `i` as a closed ring; nothing for coincident
+    /// points or cells entirely outside the bounds
+    pub fn render_cell(&self, i: usize, path: &mut Path) {
+        let Some(ring) = self.cell_ring(i) else {
+            return;
+        };
+        path.move_to(ring[0].0, ring[0].1);
+        for &(x, y) in &ring[1..] {
+            path.line_to(x, y);
+        }
+        path.close_path();
+    }
+
+    /// The cell of point `i` as a closed ring (first point repeated), ready
+    /// for [`polygon_area`](crate::polygon_area) and friends
+    pub fn cell_polygon(&self, i: usize) -> Option<Vec<(f64, f64)>> {
+        let mut ring = self.cell_ring(i)?;
+        ring.push(ring[0]);
+        Some(ring)
+    }
+
+    /// `(index, polygon)` for every point with a non-empty cell
+    pub fn cell_polygons(&self) -> impl Iterator<Item = (usize, Vec<(f64, f64)>)> + '_ {
+        (0..self.delaunay.points.len() / 2)
+            .filter_map(|i| self.cell_polygon(i).map(|polygon| (i, polygon)))
+    }
+
+    /// Whether `(x, y)` lies in the cell of point `i`
+    pub fn contains(&self, i: usize, x: f64, y: f64) -> bool {
+        if x.is_nan() || y.is_nan() {
+            return false;
+        }
+        self.delaunay.step(i, x, y) == i
+    }
+
+    // Clipped cell vertices with consecutive duplicates dropped
+    fn cell_ring(&self, i: usize) -> Option<Vec<(f64, f64)>> {
+        let points = self.clip(i)?;
+        if points.is_empty() {
+            return None;
+        }
+        let mut n = points.len();
+        while n > 2 && points[0] == points[n - 2] && points[1] == points[n - 1] {
+            n -= 2;
+        }
+        let mut ring = vec![(points[0], points[1])];
+        for k in (2..n).step_by(2) {
+            if points[k] != points[k - 2] || points[k + 1] != points[k - 1] {
+                ring.push((points[k], points[k + 1]));
+            }
+        }
+        Some(ring)
+    }
+
+    fn render_segment(&self, x0: f64, y0: f64, x1: f64, y1: f64, path: &mut Path) {
+        let c0 = self.regioncode(x0, y0);
+        let c1 = self.regioncode(x1, y1);
+        if c0 == 0 && c1 == 0 {
+            path.move_to(x0, y0);
+            path.line_to(x1, y1);
+        } else if let Some([sx0, sy0, sx1, sy1]) = self.clip_segment(x0, y0, x1, y1, c0, c1) {
+            path.move_to(sx0, sy0);
+            path.line_to(sx1, sy1);
+        }
+    }
+
+    // The unclipped cell of point `i`: circumcenters of its triangles
+    fn cell(&self, i: usize) -> Option<Vec<f64>> {
+        let Delaunay {
+            inedges,
+            halfedges,
+            triangles,
+            ..
+        } = self.delaunay;
+        let e0 = inedges[i];
+        if e0 == EMPTY {
+            // Coincident point
+            return None;
+        }
+        let mut points = Vec::new();
+        let mut e = e0;
+        loop {
+            let t = e / 3;
+            points.extend([self.circumcenters[t * 2], self.circumcenters[t * 2 + 1]]);
+            e = next_halfedge(e);
+            if triangles[e] != i {
+                // Bad triangulation
+                break;
+            }
+            e = halfedges[e];
+            if e == e0 || e == EMPTY {
+                break;
+            }
+        }
+        Some(points)
+    }
+
+    fn clip(&self, i: usize) -> Option<Vec<f64>> {
+        // A single point owns the whole box
+        if i == 0 && self.delaunay.hull.len() == 1 {
+            return Some(self.box_ring());
+        }
+        let points = self.cell(i)?;
+        let v = &self.vectors[i * 4..i * 4 + 4];
+        let clipped = if v[0] != 0.0 || v[1] != 0.0 {
+            self.clip_infinite(i, points, v[0], v[1], v[2], v[3])
+        } else {
+            self.clip_finite(i, &points)
+        };
+        simplify(clipped)
+    }
+
+    fn box_ring(&self) -> Vec<f64> {
+        vec![
+            self.xmax, self.ymin, self.xmax, self.ymax, self.xmin, self.ymax, self.xmin, self.ymin,
+        ]
+    }
+
+    fn clip_finite(&self, i: usize, points: &[f64]) -> Option<Vec<f64>> {
+        let n = points.len();
+        let mut p: Vec<f64> = Vec::new();
+        let (mut x1, mut y1) = (points[n - 2], points[n - 1]);
+        let mut c1 = self.regioncode(x1, y1);
+        let mut e1 = 0;
+        for j in (0..n).step_by(2) {
+            let (x0, y0) = (x1, y1);
+            x1 = points[j];
+            y1 = points[j + 1];
+            let c0 = c1;
+            c1 = self.regioncode(x1, y1);
+            if c0 == 0 && c1 == 0 {
+                e1 = 0;
+                p.extend([x1, y1]);
+            } else {
+                let (sx1, sy1) = if c0 == 0 {
+                    let Some([_, _, sx1, sy1]) = self.clip_segment(x0, y0, x1, y1, c0, c1) else {
+                        continue;
+                    };
+                    (sx1, sy1)
+                } else {
+                    let Some([sx1, sy1, sx0, sy0]) = self.clip_segment(x1, y1, x0, y0, c1, c0)
+                    else {
+                        continue;
+                    };
+                    let e0 = e1;
+                    e1 = self.edgecode(sx0, sy0);
+                    if e0 != 0 && e1 != 0 {
+                        let len = p.len();
+                        self.edge(i, e0, e1, &mut p, len);
+                    }
+                    p.extend([sx0, sy0]);
+                    (sx1, sy1)
+                };
+                let e0 = e1;
+                e1 = self.edgecode(sx1, sy1);
+                if e0 != 0 && e1 != 0 {
+                    let len = p.len();
+                    self.edge(i, e0, e1, &mut p, len);
+                }
+                p.extend([sx1, sy1]);
+            }
+        }
+        if !p.is_empty() {
+            let e0 = e1;
+            e1 = self.edgecode(p[0], p[1]);
+            if e0 != 0 && e1 != 0 {
+                let len = p.len();
+                self.edge(i, e0, e1, &mut p, len);
+            }
+            Some(p)
+        } else if self.contains(
+            i,
+            (self.xmin + self.xmax) / 2.0,
+            (self.ymin + self.ymax) / 2.0,
+        ) {
+            Some(self.box_ring())
+        } else {
+            None
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn clip_segment(
+        &self,
+        mut x0: f64,
+        mut y0: f64,
+        mut x1: f64,
+        mut y1: f64,
+        mut c0: u8,
+        mut c1: u8,
+    ) -> Option<[f64; 4]> {
+        // For robustness, always consider the segment in the same order
+        let flip = c0 < c1;
+        if flip {
+            (x0, y0, x1, y1, c0, c1) = (x1, y1, x0, y0, c1, c0);
+        }
+        loop {
+            if c0 == 0 && c1 == 0 {
+                return Some(if flip {
+                    [x1, y1, x0, y0]
+                } else {
+                    [x0, y0, x1, y1]
+                });
+            }
+            if c0 & c1 != 0 {
+                return None;
+            }
+            let c = if c0 != 0 { c0 } else { c1 };
+            let (x, y) = if c & BOTTOM != 0 {
+                (x0 + (x1 - x0) * (self.ymax - y0) / (y1 - y0), self.ymax)
+            } else if c & TOP != 0 {
+                (x0 + (x1 - x0) * (self.ymin - y0) / (y1 - y0), self.ymin)
+            } else if c & RIGHT != 0 {
+                (self.xmax, y0 + (y1 - y0) * (self.xmax - x0) / (x1 - x0))
+            } else {
+                (self.xmin, y0 + (y1 - y0) * (self.xmin - x0) / (x1 - x0))
+            };
+            if c0 != 0 {
+                (x0, y0) = (x, y);
+                c0 = self.regioncode(x0, y0);
+            } else {
+                (x1, y1) = (x, y);
+                c1 = self.regioncode(x1, y1);
+            }
+        }
+    }
+
+    fn clip_infinite(
+        &self,
+        i: usize,
+        mut points: Vec<f64>,
+        vx0: f64,
+        vy0: f64,
+        vxn: f64,
+        vyn: f64,
+    ) -> Option<Vec<f64>> {
+        if let Some((x, y)) = self.project(points[0], points[1], vx0, vy0) {
+            points.splice(0..0, [x, y]);
+        }
+        let n = points.len();
+        if let Some((x, y)) = self.project(points[n - 2], points[n - 1], vxn, vyn) {
+            points.extend([x, y]);
+        }
+        if let Some(mut p) = self.clip_finite(i, &points) {
+            let mut n = p.len();
+            let mut c1 = self.edgecode(p[n - 2], p[n - 1]);
+            let mut j = 0;
+            while j < n {
+                let c0 = c1;
+                c1 = self.edgecode(p[j], p[j + 1]);
+                if c0 != 0 && c1 != 0 {
+                    j = self.edge(i, c0, c1, &mut p, j);
+                    n = p.len();
+                }
+                j += 2;
+            }
+            Some(p)
+        } else if self.contains(
+            i,
+            (self.xmin + self.xmax) / 2.0,
+            (self.ymin + self.ymax) / 2.0,
+        ) {
+            Some(vec![
+                self.xmin, self.ymin, self.xmax, self.ymin, self.xmax, self.ymax, self.xmin,
+                self.ymax,
+            ])
+        } else {
+            None
+        }
+    }
+
+    // Walk clockwise along the bounds from edge code `e0` to `e1`, inserting
+    // the corners that belong to cell `i` at position `j`
+    fn edge(&self, i: usize, mut e0: u8, e1: u8, p: &mut Vec<f64>, mut j: usize) -> usize {
+        while e0 != e1 {
+            let (x, y);
+            match e0 {
+                0b0101 => {
+                    e0 = TOP;
+                    continue;
+                }
+                TOP => {
+                    e0 = 0b0110;
+                    (x, y) = (self.xmax, self.ymin);
+                }
+                0b0110 => {
+                    e0 = RIGHT;
+                    continue;
+                }
+                RIGHT => {
+                    e0 = 0b1010;
+                    (x, y) = (self.xmax, self.ymax);
+                }
+                0b1010 => {
+                    e0 = BOTTOM;
+                    continue;
+                }
+                BOTTOM => {
+                    e0 = 0b1001;
+                    (x, y) = (self.xmin, self.ymax);
+                }
+                0b1001 => {
+                    e0 = LEFT;
+                    continue;
+                }
+                LEFT => {
+                    e0 = 0b0101;
+                    (x, y) = (self.xmin, self.ymin);
+                }
+                _ => return j,
+            }
+            if (p.get(j) != Some(&x) || p.get(j + 1) != Some(&y)) && self.contains(i, x, y) {
+                p.splice(j..j, [x, y]);
+                j += 2;
+            }
+        }
+        j
+    }
+
+    // Where the ray from (x0, y0) along (vx, vy) leaves the bounds
+    fn project(&self, x0: f64, y0: f64, vx: f64, vy: f64) -> Option<(f64, f64)> {
+        let mut t = f64::INFINITY;
+        let (mut x, mut y) = (f64::NAN, f64::NAN);
+        if vy < 0.0 {
+            if y0 <= self.ymin {
+                return None;
+            }
+            let c = (self.ymin - y0) / vy;
+            if c < t {
+                t = c;
+                (x, y) = (x0 + t * vx, self.ymin);
+            }
+        } else if vy > 0.0 {
+            if y0 >= self.ymax {
+                return None;
+            }
+            let c = (self.ymax - y0) / vy;
+            if c < t {
+                t = c;
+                (x, y) = (x0 + t * vx, self.ymax);
+            }
+        }
+        if vx > 0.0 {
+            if x0 >= self.xmax {
+                return None;
+            }
+            let c = (self.xmax - x0) / vx;
+            if c < t {
+                t = c;
+                (x, y) = (self.xmax, y0 + t * vy);
+            }
+        } else if vx < 0.0 {
+            if x0 <= self.xmin {
+                return None;
+            }
+            let c = (self.xmin - x0) / vx;
+            if c < t {
+                t = c;
+                (x, y) = (self.xmin, y0 + t * vy);
+            }
+        }
+        if t.is_infinite() { None } else { Some((x, y)) }
+    }
+
+    // Which bounds edge(s) a point lies exactly on
+    fn edgecode(&self, x: f64, y: f64) -> u8 {
+        let cx = if x == self.xmin {
+            LEFT
+        } else if x == self.xmax {
+            RIGHT
+        } else {
+            0
+        };
+        let cy = if y == self.ymin {
+            TOP
+        } else if y == self.ymax {
+            BOTTOM
+        } else {
+            0
+        };
+        cx | cy
+    }
+
+    // Which side(s) of the bounds a point lies outside of
+    fn regioncode(&self, x: f64, y: f64) -> u8 {
+        let cx = if x < self.xmin {
+            LEFT
+        } else if x > self.xmax {
+            RIGHT
+        } else {
+            0
+        };
+        let cy = if y < self.ymin {
+            TOP
+        } else if y > self.ymax {
+            BOTTOM
+        } else {
+            0
+        };
+        cx | cy
+    }
+}
+
+// Drop the middle of any three consecutive vertices on the same vertical or
+// horizontal line
+fn simplify(p: Option<Vec<f64>>) -> Option<Vec<f64>> {
+    let mut p = p?;
+    if p.len() > 4 {
+        let mut i = 0;
+        while i < p.len() {
+            let len = p.len();
+            let j = (i + 2) % len;
+            let k = (i + 4) % len;
+            if (p[i] == p[j] && p[j] == p[k]) || (p[i + 1] == p[j + 1] && p[j + 1] == p[k + 1]) {
+                p.drain(j..j + 2);
+            } else {
+                i += 2;
+            }
+        }
+        if p.is_empty() {
+            return None;
+        }
+    }
+    Some(p)
+}
+
+fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,335 @@
+//! d3-drag: dragging subjects with a pointer (Rust port)
+//!
+//! A [`Drag`] is a state machine fed abstract pointer inputs. Pressing a
+//! pointer resolves a *subject* (a node, a handle, ...) under it; moving the
+//! pointer reports the subject's new position, keeping the offset between the
+//! pointer and the subject, until the pointer is released. Each pointer drives
+//! its own gesture, so several subjects can be dragged at once.
+//!
+//! Inputs are in source coordinates (e.g. window pixels); the
+//! [`container`](Drag::container) maps them into the coordinate space of the
+//! subjects, such as a zoomed graph. Each input returns the start, drag and
+//! end events it caused; [`Drag::on`] listeners registered through
+//! [`Dispatch`](crate::dispatch::Dispatch) receive them from
+//! [`Drag::handle_async`] and [`Drag::dispatch`].
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::drag::{DragEventType, DragInput, drag};
+//!
+//! let mut positions = vec![[10.0, 10.0], [50.0, 50.0]];
+//! let snapshot = positions.clone();
+//! let mut drag = drag().subject(move |[px, py]| {
+//!     // The node within 5px of the pointer, and where it is
+//!     snapshot.iter().enumerate().find_map(|(i, &[x, y])| {
+//!         ((px - x).hypot(py - y) < 5.0).then_some((i, [x, y]))
+//!     })
+//! });
+//!
+//! drag.handle(DragInput::PointerDown { id: 0, point: [52.0, 51.0] });
+//! for event in drag.handle(DragInput::PointerMove { id: 0, point: [62.0, 71.0] }) {
+//!     if event.event_type == DragEventType::Drag {
+//!         positions[event.subject] = [event.x, event.y];
+//!     }
+//! }
+//! assert_eq!(positions[1], [60.0, 70.0]);
+//! ```
+
+use crate::dispatch::{Dispatch, Event, HandlerHandle};
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+/// Maps a source point into container coordinates
+pub type Container = Box<dyn Fn([f64; 2]) -> [f64; 2] + Send + Sync>;
+
+/// Resolves the subject under a container point, with the subject's position
+pub type Subject<S> = Box<dyn Fn([f64; 2]) -> Option<(S, [f64; 2])> + Send + Sync>;
+
+/// A pointer input, in source coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragInput {
+    PointerDown { id: u64, point: [f64; 2] },
+    PointerMove { id: u64, point: [f64; 2] },
+    PointerUp { id: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragEventType {
+    Start,
+    Drag,
+    End,
+}
+
+impl DragEventType {
+    /// The listener type name: `start`, `drag` or `end`
+    pub fn name(&self) -> &'static str {
+        match self {
+            DragEventType::Start => "start",
+            DragEventType::Drag => "drag",
+            DragEventType::End => "end",
+        }
+    }
+}
+
+/// Reported by [`Drag`] for each gesture
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragEvent<S> {
+    pub event_type: DragEventType,
+    /// The subject resolved when the gesture started
+    pub subject: S,
+    /// The subject's new position, in container coordinates
+    pub x: f64,
+    pub y: f64,
+    /// Movement since the gesture's previous event
+    pub dx: f64,
+    pub dy: f64,
+    /// The pointer driving the gesture
+    pub identifier: u64,
+    /// Number of other gestures in progress
+    pub active: usize,
+    /// On end: whether the pointer stayed within the click distance, so the
+    /// gesture should also count as a click
+    pub click: bool,
+}
+
+// An in-progress gesture: the offset from the pointer to the subject, the
+// last container point, and where the pointer went down in source coordinates
+#[derive(Debug, Clone)]
+struct Gesture<S> {
+    id: u64,
+    subject: S,
+    offset: [f64; 2],
+    point: [f64; 2],
+    down: [f64; 2],
+    moved: bool,
+}
+
+/// A drag behaviour; see [`drag`]
+pub struct Drag<S> {
+    container: Container,
+    subject: Subject<S>,
+    click_distance: f64,
+    gestures: Vec<Gesture<S>>,
+    listeners: Dispatch,
+}
+
+/// A drag behaviour whose subject is the pointer position itself
+pub fn drag() -> Drag<[f64; 2]> {
+    Drag {
+        container: Box::new(|point| point),
+        subject: Box::new(|point| Some((point, point))),
+        click_distance: 0.0,
+        gestures: Vec::new(),
+        listeners: Dispatch::new(),
+    }
+}
+
+impl<S: Clone> Drag<S> {
+    /// Map source points into container coordinates, e.g. through the
+    /// inverse of a zoom transform; the identity by default
+    pub fn container<F>(mut self, container: F) -> Self
+    where
+        F: Fn([f64; 2]) -> [f64; 2] + Send + Sync + 'static,
+    {
+        self.container = Box::new(container);
+        self
+    }
+
+    /// Resolve the subject under a container point along with its position;
+    /// pointers with no subject are ignored
+    ///
+    /// Gestures in progress are dropped.
+    pub fn subject<T, F>(self, subject: F) -> Drag<T>
+    where
+        F: Fn([f64; 2]) -> Option<(T, [f64; 2])> + Send + Sync + 'static,
+    {
+        Drag {
+            container: self.container,
+            subject: Box::new(subject),
+            click_distance: self.click_distance,
+            gestures: Vec::new(),
+            listeners: self.listeners,
+        }
+    }
+
+    /// How far, in source coordinates, a pointer may move and still count
+    /// as a click; 0 by default
+    pub fn click_distance(mut self, click_distance: f64) -> Self {
+        self.click_distance = click_distance;
+        self
+    }
+
+    pub fn get_click_distance(&self) -> f64 {
+        self.click_distance
+    }
+
+    /// Number of gestures in progress
+    pub fn active(&self) -> usize {
+        self.gestures.len()
+    }
+
+    /// Whether a gesture is in progress
+    pub fn is_active(&self) -> bool {
+        !self.gestures.is_empty()
+    }
+
+    /// Feed a pointer input, resolving subjects with
+    /// [`subject`](Self::subject)
+    pub fn handle(&mut self, input: DragInput) -> Vec<DragEvent<S>> {
+        match input {
+            DragInput::PointerDown { id, point } => {
+                let subject = (self.subject)((self.container)(point));
+                self.pointer_down(id, point, subject)
+            }
+            _ => self.handle_with(input, |_| None),
+        }
+    }
+
+    /// Feed a pointer input, resolving the subject of a new gesture with
+    /// `subject` instead, for subjects that borrow from the caller (such as
+    /// the nodes of a running simulation)
+    pub fn handle_with<F>(&mut self, input: DragInput, subject: F) -> Vec<DragEvent<S>>
+    where
+        F: FnOnce([f64; 2]) -> Option<(S, [f64; 2])>,
+    {
+        match input {
+            DragInput::PointerDown { id, point } => {
+                let subject = subject((self.container)(point));
+                self.pointer_down(id, point, subject)
+            }
+            DragInput::PointerMove { id, point } => self.pointer_move(id, point),
+            DragInput::PointerUp { id } => self.pointer_up(id),
+        }
+    }
+
+    fn pointer_down(
+        &mut self,
+        id: u64,
+        source: [f64; 2],
+        subject: Option<(S, [f64; 2])>,
+    ) -> Vec<DragEvent<S>> {
+        if self.gestures.iter().any(|g| g.id == id) {
+            return Vec::new();
+        }
+        let Some((subject, [sx, sy])) = subject else {
+            return Vec::new();
+        };
+        let point = (self.container)(source);
+        let gesture = Gesture {
+            id,
+            subject,
+            offset: [sx - point[0], sy - point[1]],
+            point,
+            down: source,
+            moved: false,
+        };
+        let event = self.event(DragEventType::Start, &gesture, [0.0, 0.0]);
+        self.gestures.push(gesture);
+        vec![event]
+    }
+
+    fn pointer_move(&mut self, id: u64, source: [f64; 2]) -> Vec<DragEvent<S>> {
+        let Some(i) = self.gestures.iter().position(|g| g.id == id) else {
+            return Vec::new();
+        };
+        let point = (self.container)(source);
+        let click_distance2 = self.click_distance * self.click_distance;
+        let gesture = &mut self.gestures[i];
+        let delta = [point[0] - gesture.point[0], point[1] - gesture.point[1]];
+        gesture.point = point;
+        if !gesture.moved {
+            let (mx, my) = (source[0] - gesture.down[0], source[1] - gesture.down[1]);
+            gesture.moved = mx * mx + my * my > click_distance2;
+        }
+        let gesture = self.gestures[i].clone();
+        vec![self.event(DragEventType::Drag, &gesture, delta)]
+    }
+
+    fn pointer_up(&mut self, id: u64) -> Vec<DragEvent<S>> {
+        let Some(i) = self.gestures.iter().position(|g| g.id == id) else {
+            return Vec::new();
+        };
+        let gesture = self.gestures.remove(i);
+        let mut event = self.event(DragEventType::End, &gesture, [0.0, 0.0]);
+        event.click = !gesture.moved;
+        vec![event]
+    }
+
+    fn event(
+        &self,
+        event_type: DragEventType,
+        gesture: &Gesture<S>,
+        delta: [f64; 2],
+    ) -> DragEvent<S> {
+        DragEvent {
+            event_type,
+            subject: gesture.subject.clone(),
+            x: gesture.point[0] + gesture.offset[0],
+            y: gesture.point[1] + gesture.offset[1],
+            dx: delta[0],
+            dy: delta[1],
+            identifier: gesture.id,
+            active: self.gestures.iter().filter(|g| g.id != gesture.id).count(),
+            click: false,
+        }
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static> Drag<S> {
+    /// Register a listener for `typename`: `start`, `drag` or `end`,
+    /// optionally with a `.name` suffix (e.g. `drag.graph`)
+    pub async fn on<F>(&self, typename: &str, listener: F) -> HandlerHandle
+    where
+        F: Fn(&DragEvent<S>) + Send + Sync + 'static,
+    {
+        self.listeners
+            .on_with_handle(typename, move |event: &Event| {
+                if let Some(drag_event) = event
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.downcast_ref::<DragEvent<S>>())
+                {
+                    listener(drag_event);
+                }
+            })
+            .await
+    }
+
+    /// Remove a listener registered with [`on`](Self::on)
+    pub async fn off(&self, typename: &str, handle: &HandlerHandle) {
+        self.listeners.off_handle(typename, handle).await;
+    }
+
+    /// Send `events` to the registered listeners
+    pub async fn dispatch(&self, events: &[DragEvent<S>]) {
+        let typenames = self.listeners.events().await;
+        for drag_event in events {
+            let name = drag_event.event_type.name();
+            for typename in typenames
+                .iter()
+                .filter(|t| t.split('.').next() == Some(name))
+            {
+                let event = Arc::new(Event {
+                    event_type: Cow::Borrowed(name),
+                    data: Some(Arc::new(drag_event.clone())),
+                    timestamp: std::time::Instant::now(),
+                    source: Some(Cow::Borrowed("drag")),
+                    propagation_stopped: Arc::new(AtomicBool::new(false)),
+                    default_prevented: Arc::new(AtomicBool::new(false)),
+                });
+                self.listeners.call_event(typename, event).await;
+            }
+        }
+    }
+
+    /// [`handle`](Self::handle), then dispatch the events to listeners
+    pub async fn handle_async(&mut self, input: DragInput) -> Vec<DragEvent<S>> {
+        let events = self.handle(input);
+        self.dispatch(&events).await;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,182 @@
+//! Tests for d3-drag (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::drag::*;
+    use crate::force::{Simulation, SimulationNode};
+    use crate::zoom::ZoomTransform;
+    use std::sync::Mutex;
+
+    fn down(id: u64, point: [f64; 2]) -> DragInput {
+        DragInput::PointerDown { id, point }
+    }
+
+    fn moved(id: u64, point: [f64; 2]) -> DragInput {
+        DragInput::PointerMove { id, point }
+    }
+
+    fn types<S>(events: &[DragEvent<S>]) -> Vec<DragEventType> {
+        events.iter().map(|e| e.event_type).collect()
+    }
+
+    #[test]
+    fn test_default_subject() {
+        let mut d = drag();
+        let events = d.handle(down(1, [10.0, 20.0]));
+        assert_eq!(types(&events), [DragEventType::Start]);
+        assert_eq!(events[0].subject, [10.0, 20.0]);
+        assert_eq!((events[0].x, events[0].y), (10.0, 20.0));
+        assert!(d.is_active());
+        let events = d.handle(moved(1, [15.0, 18.0]));
+        assert_eq!(types(&events), [DragEventType::Drag]);
+        assert_eq!((events[0].x, events[0].y), (15.0, 18.0));
+        assert_eq!((events[0].dx, events[0].dy), (5.0, -2.0));
+        let events = d.handle(moved(1, [16.0, 18.0]));
+        assert_eq!((events[0].dx, events[0].dy), (1.0, 0.0));
+        let events = d.handle(DragInput::PointerUp { id: 1 });
+        assert_eq!(types(&events), [DragEventType::End]);
+        assert_eq!((events[0].x, events[0].y), (16.0, 18.0));
+        assert!(!events[0].click);
+        assert!(!d.is_active());
+        // Inputs from pointers without a gesture are ignored
+        assert!(d.handle(moved(1, [0.0, 0.0])).is_empty());
+        assert!(d.handle(DragInput::PointerUp { id: 1 }).is_empty());
+    }
+
+    #[test]
+    fn test_subject_offset() {
+        let mut d = drag().subject(|[x, y]| {
+            let handle = [100.0, 100.0];
+            ((x - handle[0]).abs() < 4.0 && (y - handle[1]).abs() < 4.0)
+                .then_some(("handle", handle))
+        });
+        // Missing the handle starts nothing
+        assert!(d.handle(down(1, [50.0, 50.0])).is_empty());
+        assert!(!d.is_active());
+        let events = d.handle(down(1, [102.0, 97.0]));
+        assert_eq!(events[0].subject, "handle");
+        // The subject keeps its offset from the pointer
+        assert_eq!((events[0].x, events[0].y), (100.0, 100.0));
+        let events = d.handle(moved(1, [112.0, 107.0]));
+        assert_eq!((events[0].x, events[0].y), (110.0, 110.0));
+    }
+
+    #[test]
+    fn test_container() {
+        // Pointer in screen space, subjects in a graph zoomed 2x and panned
+        let t = ZoomTransform::new(2.0, 50.0, 0.0);
+        let mut d = drag().container(move |p| t.invert(p));
+        let events = d.handle(down(0, [70.0, 40.0]));
+        assert_eq!((events[0].x, events[0].y), (10.0, 20.0));
+        let events = d.handle(moved(0, [90.0, 40.0]));
+        assert_eq!((events[0].x, events[0].dx), (20.0, 10.0));
+    }
+
+    #[test]
+    fn test_click_distance() {
+        let mut d = drag().click_distance(3.0);
+        assert_eq!(d.get_click_distance(), 3.0);
+        d.handle(down(0, [0.0, 0.0]));
+        d.handle(moved(0, [2.0, 2.0]));
+        let events = d.handle(DragInput::PointerUp { id: 0 });
+        assert!(events[0].click);
+
+        d.handle(down(0, [0.0, 0.0]));
+        d.handle(moved(0, [5.0, 0.0]));
+        // Returning within the distance is still a drag
+        d.handle(moved(0, [0.0, 0.0]));
+        assert!(!d.handle(DragInput::PointerUp { id: 0 })[0].click);
+
+        let mut d = drag();
+        d.handle(down(0, [0.0, 0.0]));
+        assert!(d.handle(DragInput::PointerUp { id: 0 })[0].click);
+    }
+
+    #[test]
+    fn test_multiple_pointers() {
+        let mut d = drag();
+        assert_eq!(d.handle(down(1, [0.0, 0.0]))[0].active, 0);
+        assert_eq!(d.handle(down(2, [10.0, 10.0]))[0].active, 1);
+        // The same pointer cannot start a second gesture
+        assert!(d.handle(down(2, [20.0, 20.0])).is_empty());
+        assert_eq!(d.active(), 2);
+        let events = d.handle(moved(2, [12.0, 10.0]));
+        assert_eq!(events[0].identifier, 2);
+        assert_eq!(events[0].subject, [10.0, 10.0]);
+        let events = d.handle(DragInput::PointerUp { id: 1 });
+        assert_eq!((events[0].identifier, events[0].active), (1, 1));
+        let events = d.handle(DragInput::PointerUp { id: 2 });
+        assert_eq!(events[0].active, 0);
+    }
+
+    #[test]
+    fn test_drag_simulation_node() {
+        let mut simulation = Simulation::new(vec![
+            SimulationNode::at(0.0, 0.0),
+            SimulationNode::at(50.0, 50.0),
+        ]);
+        let mut d = drag().subject(|_| None::<(usize, [f64; 2])>);
+        let apply = |simulation: &mut Simulation, events: Vec<DragEvent<usize>>| {
+            for event in events {
+                let node = &mut simulation.nodes_mut()[event.subject];
+                match event.event_type {
+                    DragEventType::Start | DragEventType::Drag => {
+                        node.fx = Some(event.x);
+                        node.fy = Some(event.y);
+                    }
+                    DragEventType::End => {
+                        node.fx = None;
+                        node.fy = None;
+                    }
+                }
+            }
+        };
+        let events = d.handle_with(down(0, [48.0, 51.0]), |[x, y]| {
+            simulation
+                .find(x, y, Some(10.0))
+                .map(|i| (i, [simulation.nodes()[i].x, simulation.nodes()[i].y]))
+        });
+        apply(&mut simulation, events);
+        let events = d.handle(moved(0, [58.0, 61.0]));
+        apply(&mut simulation, events);
+        let node = &simulation.nodes()[1];
+        assert_eq!((node.fx, node.fy), (Some(60.0), Some(60.0)));
+        let events = d.handle(DragInput::PointerUp { id: 0 });
+        apply(&mut simulation, events);
+        assert_eq!(simulation.nodes()[1].fx, None);
+        // The stored subject finds nothing
+        assert!(d.handle(down(1, [0.0, 0.0])).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_listeners() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut d = drag();
+        let log = Arc::clone(&seen);
+        d.on("drag", move |e| {
+            log.lock().unwrap().push((e.event_type, e.dx))
+        })
+        .await;
+        let log = Arc::clone(&seen);
+        let handle = d
+            .on("end.graph", move |e| {
+                log.lock().unwrap().push((e.event_type, e.dx))
+            })
+            .await;
+
+        d.handle_async(down(0, [0.0, 0.0])).await;
+        d.handle_async(moved(0, [4.0, 0.0])).await;
+        d.handle_async(DragInput::PointerUp { id: 0 }).await;
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [(DragEventType::Drag, 4.0), (DragEventType::End, 0.0)]
+        );
+
+        d.off("end.graph", &handle).await;
+        let events = d.handle(down(0, [0.0, 0.0]));
+        d.dispatch(&events).await;
+        let events = d.handle(DragInput::PointerUp { id: 0 });
+        d.dispatch(&events).await;
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+}
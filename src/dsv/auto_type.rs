@@ -0,0 +1,155 @@
+//! d3-dsv: autoType, inferring values from their text
+
+use super::DsvRow;
+use crate::time::format::time_parse;
+use chrono::{Duration, NaiveDateTime, Timelike};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A field value inferred by [`auto_type`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DsvValue {
+    /// An empty (or whitespace-only) field
+    Null,
+    Bool(bool),
+    Number(f64),
+    /// An ISO 8601 date, in UTC when the field has a time zone
+    Date(NaiveDateTime),
+    String(String),
+}
+
+impl DsvValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, DsvValue::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            DsvValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DsvValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_date(&self) -> Option<NaiveDateTime> {
+        match self {
+            DsvValue::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DsvValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// The text d3-dsv writes for the value: empty for null, and dates in the
+/// shortest ISO 8601 form
+impl fmt::Display for DsvValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DsvValue::Null => Ok(()),
+            DsvValue::Bool(b) => write!(f, "{}", b),
+            DsvValue::Number(n) if n.is_infinite() => {
+                write!(f, "{}Infinity", if *n < 0.0 { "-" } else { "" })
+            }
+            DsvValue::Number(n) => write!(f, "{}", n),
+            DsvValue::Date(d) => write!(f, "{}", format_date(d)),
+            DsvValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+lazy_static! {
+    static ref NUMBER: Regex =
+        Regex::new(r"^[-+]?(\d+\.?\d*|\.\d+)([eE][-+]?\d+)?$|^[-+]?Infinity$").unwrap();
+    static ref DATE: Regex = Regex::new(
+        r"^\d{4}(-\d{2}(-\d{2})?)?(T\d{2}:\d{2}(:\d{2}(\.\d{3})?)?(Z|[-+]\d{2}:\d{2})?)?$"
+    )
+    .unwrap();
+}
+
+/// Infer the type of every field of `row`, e.g. as a row conversion function
+/// for [`Dsv::parse_with`](super::Dsv::parse_with)
+pub fn auto_type(row: DsvRow) -> HashMap<String, DsvValue> {
+    row.into_iter()
+        .map(|(key, value)| (key, auto_type_value(&value)))
+        .collect()
+}
+
+/// Infer the type of a single field: empty fields are null, `true` and
+/// `false` booleans, then numbers, ISO 8601 dates, and anything else a string
+pub fn auto_type_value(text: &str) -> DsvValue {
+    let value = text.trim();
+    if value.is_empty() {
+        return DsvValue::Null;
+    }
+    match value {
+        "true" => return DsvValue::Bool(true),
+        "false" => return DsvValue::Bool(false),
+        "NaN" => return DsvValue::Number(f64::NAN),
+        _ => {}
+    }
+    if NUMBER.is_match(value) {
+        let number = value.replace("Infinity", "inf").parse::<f64>();
+        if let Ok(number) = number {
+            return DsvValue::Number(number);
+        }
+    }
+    if let Some(date) = parse_date(value) {
+        return DsvValue::Date(date);
+    }
+    DsvValue::String(text.to_string())
+}
+
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let m = DATE.captures(value)?;
+    // Build the specifier for exactly the components present
+    let mut spec = String::from("%Y");
+    for (group, part) in [
+        (1, "-%m"),
+        (2, "-%d"),
+        (3, "T%H:%M"),
+        (4, ":%S"),
+        (5, ".%L"),
+    ] {
+        if m.get(group).is_some() {
+            spec.push_str(part);
+        }
+    }
+    let date = time_parse(&spec, value)?;
+    match m.get(6).map(|zone| zone.as_str()) {
+        None | Some("Z") => Some(date),
+        Some(offset) => {
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let hours: i64 = offset[1..3].parse().ok()?;
+            let minutes: i64 = offset[4..6].parse().ok()?;
+            date.checked_sub_signed(Duration::try_minutes(sign * (hours * 60 + minutes))?)
+        }
+    }
+}
+
+fn format_date(date: &NaiveDateTime) -> String {
+    let day = date.format("%Y-%m-%d");
+    let millis = date.nanosecond() / 1_000_000;
+    if millis != 0 {
+        format!("{}{}.{:03}Z", day, date.format("T%H:%M:%S"), millis)
+    } else if date.second() != 0 {
+        format!("{}{}Z", day, date.format("T%H:%M:%S"))
+    } else if date.minute() != 0 || date.hour() != 0 {
+        format!("{}{}Z", day, date.format("T%H:%M"))
+    } else {
+        day.to_string()
+    }
+}
@@ -0,0 +1,269 @@
+//! d3-dsv: parsing and formatting delimiter-separated values (Rust port)
+//!
+//! [`dsv_format`] builds a [`Dsv`] parser and formatter for any single-byte
+//! delimiter; [`CSV`] and [`TSV`] are ready-made, with `csv_*` and `tsv_*`
+//! shorthands. Fields may be quoted (`"a, ""quoted"" b"`) and may then
+//! contain delimiters and line breaks. [`auto_type`] infers numbers, dates
+//! and booleans from the parsed text.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::dsv::{DsvValue, auto_type, csv_format, csv_parse, csv_parse_with};
+//!
+//! let text = "date,close,label\n2007-04-23,93.24,\"Apple, Inc.\"\n2007-04-24,95.35,\n";
+//! let data = csv_parse(text);
+//! assert_eq!(data.columns, ["date", "close", "label"]);
+//! assert_eq!(data.rows[0]["label"], "Apple, Inc.");
+//!
+//! // Row conversion: return None to skip a row
+//! let closes = csv_parse_with(text, |d, _, _| d["close"].parse::<f64>().ok());
+//! assert_eq!(closes.rows, [93.24, 95.35]);
+//!
+//! let typed = csv_parse_with(text, |d, _, _| Some(auto_type(d)));
+//! assert_eq!(typed.rows[1]["close"], DsvValue::Number(95.35));
+//! assert_eq!(typed.rows[1]["label"], DsvValue::Null);
+//! assert_eq!(csv_format(&typed.rows, &typed.columns), text.trim_end());
+//! ```
+
+mod auto_type;
+mod parse;
+mod reader;
+
+pub use auto_type::{DsvValue, auto_type, auto_type_value};
+pub use reader::{DsvReader, DsvRecords};
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, BufRead};
+
+/// A parsed row, keyed by column name
+pub type DsvRow = HashMap<String, String>;
+
+/// Rows parsed with a header, and the header's column names in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct DsvParsed<T> {
+    pub columns: Vec<String>,
+    pub rows: Vec<T>,
+}
+
+/// Parser and formatter for one delimiter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dsv {
+    delimiter: u8,
+}
+
+/// Comma-separated values
+pub const CSV: Dsv = Dsv { delimiter: b',' };
+
+/// Tab-separated values
+pub const TSV: Dsv = Dsv { delimiter: b'\t' };
+
+/// A parser and formatter for `delimiter`
+///
+/// Panics if `delimiter` is not a single-byte (ASCII) character, or is a
+/// quote or line break.
+pub fn dsv_format(delimiter: char) -> Dsv {
+    assert!(
+        delimiter.is_ascii() && !matches!(delimiter, '"' | '\n' | '\r'),
+        "invalid delimiter: {:?}",
+        delimiter
+    );
+    Dsv {
+        delimiter: delimiter as u8,
+    }
+}
+
+impl Dsv {
+    pub fn get_delimiter(&self) -> char {
+        self.delimiter as char
+    }
+
+    /// Parse `text` with a header row into rows keyed by column name;
+    /// missing fields are empty and extra fields are dropped
+    pub fn parse(&self, text: &str) -> DsvParsed<DsvRow> {
+        self.parse_with(text, |d, _, _| Some(d))
+    }
+
+    /// Like [`parse`](Self::parse), converting each row with
+    /// `row(d, index, columns)`; rows converted to `None` are skipped
+    pub fn parse_with<T>(
+        &self,
+        text: &str,
+        mut row: impl FnMut(DsvRow, usize, &[String]) -> Option<T>,
+    ) -> DsvParsed<T> {
+        let mut columns: Option<Vec<String>> = None;
+        let rows = parse::parse_rows(text, self.delimiter, |fields, i| match &columns {
+            None => {
+                columns = Some(fields);
+                None
+            }
+            Some(columns) => row(row_object(columns, fields), i - 1, columns),
+        });
+        DsvParsed {
+            columns: columns.unwrap_or_default(),
+            rows,
+        }
+    }
+
+    /// Parse `text` without a header into rows of fields
+    pub fn parse_rows(&self, text: &str) -> Vec<Vec<String>> {
+        self.parse_rows_with(text, |fields, _| Some(fields))
+    }
+
+    /// Like [`parse_rows`](Self::parse_rows), converting each row with
+    /// `row(fields, index)`; rows converted to `None` are skipped
+    pub fn parse_rows_with<T>(
+        &self,
+        text: &str,
+        row: impl FnMut(Vec<String>, usize) -> Option<T>,
+    ) -> Vec<T> {
+        parse::parse_rows(text, self.delimiter, row)
+    }
+
+    /// Stream rows of fields from `reader`, without a header
+    pub fn read_rows<R: BufRead>(&self, reader: R) -> DsvReader<R> {
+        DsvReader::new(reader, self.delimiter)
+    }
+
+    /// Stream rows keyed by the header, which is read immediately
+    pub fn read<R: BufRead>(&self, reader: R) -> io::Result<DsvRecords<R>> {
+        DsvRecords::new(self.read_rows(reader))
+    }
+
+    /// Format rows with a header of `columns`; fields missing from a row are
+    /// empty
+    pub fn format<V: Display>(
+        &self,
+        rows: &[HashMap<String, V>],
+        columns: &[impl AsRef<str>],
+    ) -> String {
+        let header = self.format_row(columns.iter().map(AsRef::as_ref));
+        if rows.is_empty() {
+            return header;
+        }
+        header + "\n" + &self.format_body(rows, columns)
+    }
+
+    /// Like [`format`](Self::format), without the header
+    pub fn format_body<V: Display>(
+        &self,
+        rows: &[HashMap<String, V>],
+        columns: &[impl AsRef<str>],
+    ) -> String {
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                self.format_row(columns.iter().map(|column| match row.get(column.as_ref()) {
+                    Some(value) => value.to_string(),
+                    None => String::new(),
+                }))
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Format rows of fields, one line per row
+    pub fn format_rows<I>(&self, rows: impl IntoIterator<Item = I>) -> String
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        let lines: Vec<String> = rows.into_iter().map(|row| self.format_row(row)).collect();
+        lines.join("\n")
+    }
+
+    /// Format one row of fields, quoting where needed
+    pub fn format_row<V: Display>(&self, row: impl IntoIterator<Item = V>) -> String {
+        let fields: Vec<String> = row.into_iter().map(|v| self.format_value(v)).collect();
+        fields.join(&(self.delimiter as char).to_string())
+    }
+
+    /// A single field, quoted (with quotes doubled) if it contains a quote,
+    /// the delimiter or a line break
+    pub fn format_value<V: Display>(&self, value: V) -> String {
+        let value = value.to_string();
+        if value
+            .bytes()
+            .any(|c| matches!(c, b'"' | b'\n' | b'\r') || c == self.delimiter)
+        {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value
+        }
+    }
+}
+
+fn row_object(columns: &[String], fields: Vec<String>) -> DsvRow {
+    let mut fields = fields.into_iter();
+    columns
+        .iter()
+        .map(|column| (column.clone(), fields.next().unwrap_or_default()))
+        .collect()
+}
+
+/// [`Dsv::parse`] for CSV
+pub fn csv_parse(text: &str) -> DsvParsed<DsvRow> {
+    CSV.parse(text)
+}
+
+/// [`Dsv::parse_with`] for CSV
+pub fn csv_parse_with<T>(
+    text: &str,
+    row: impl FnMut(DsvRow, usize, &[String]) -> Option<T>,
+) -> DsvParsed<T> {
+    CSV.parse_with(text, row)
+}
+
+/// [`Dsv::parse_rows`] for CSV
+pub fn csv_parse_rows(text: &str) -> Vec<Vec<String>> {
+    CSV.parse_rows(text)
+}
+
+/// [`Dsv::format`] for CSV
+pub fn csv_format<V: Display>(rows: &[HashMap<String, V>], columns: &[impl AsRef<str>]) -> String {
+    CSV.format(rows, columns)
+}
+
+/// [`Dsv::format_rows`] for CSV
+pub fn csv_format_rows<I>(rows: impl IntoIterator<Item = I>) -> String
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    CSV.format_rows(rows)
+}
+
+/// [`Dsv::parse`] for TSV
+pub fn tsv_parse(text: &str) -> DsvParsed<DsvRow> {
+    TSV.parse(text)
+}
+
+/// [`Dsv::parse_with`] for TSV
+pub fn tsv_parse_with<T>(
+    text: &str,
+    row: impl FnMut(DsvRow, usize, &[String]) -> Option<T>,
+) -> DsvParsed<T> {
+    TSV.parse_with(text, row)
+}
+
+/// [`Dsv::parse_rows`] for TSV
+pub fn tsv_parse_rows(text: &str) -> Vec<Vec<String>> {
+    TSV.parse_rows(text)
+}
+
+/// [`Dsv::format`] for TSV
+pub fn tsv_format<V: Display>(rows: &[HashMap<String, V>], columns: &[impl AsRef<str>]) -> String {
+    TSV.format(rows, columns)
+}
+
+/// [`Dsv::format_rows`] for TSV
+pub fn tsv_format_rows<I>(rows: impl IntoIterator<Item = I>) -> String
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    TSV.format_rows(rows)
+}
+
+#[cfg(test)]
+mod tests;
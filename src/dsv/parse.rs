@@ -0,0 +1,168 @@
+//! d3-dsv: tokenizing delimiter-separated text
+
+const QUOTE: u8 = b'"';
+const NEWLINE: u8 = b'\n';
+const RETURN: u8 = b'\r';
+
+enum Token {
+    Field(String),
+    EndOfLine,
+    EndOfFile,
+}
+
+struct Tokenizer<'a> {
+    text: &'a str,
+    delimiter: u8,
+    /// End of the text, not counting one trailing line break
+    n: usize,
+    /// Start of the next token
+    i: usize,
+    eof: bool,
+    eol: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(text: &'a str, delimiter: u8) -> Self {
+        let bytes = text.as_bytes();
+        let mut n = bytes.len();
+        if n > 0 && bytes[n - 1] == NEWLINE {
+            n -= 1;
+        }
+        if n > 0 && bytes[n - 1] == RETURN {
+            n -= 1;
+        }
+        Self {
+            text,
+            delimiter,
+            n,
+            i: 0,
+            eof: bytes.is_empty(),
+            eol: false,
+        }
+    }
+
+    fn byte(&self, i: usize) -> Option<u8> {
+        self.text.as_bytes().get(i).copied()
+    }
+
+    // A field between `start` and `end`, clamped for malformed quoting
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        let end = end.min(self.text.len());
+        &self.text[start.min(end)..end]
+    }
+
+    fn token(&mut self) -> Token {
+        if self.eof {
+            return Token::EndOfFile;
+        }
+        if self.eol {
+            self.eol = false;
+            return Token::EndOfLine;
+        }
+        let j = self.i;
+
+        // Quoted field: find the closing quote, skipping escaped "" pairs
+        if self.byte(j) == Some(QUOTE) {
+            loop {
+                let before = self.i;
+                self.i += 1;
+                if before < self.n && self.byte(self.i) != Some(QUOTE) {
+                    continue;
+                }
+                self.i += 1;
+                if self.byte(self.i) != Some(QUOTE) {
+                    break;
+                }
+            }
+            let i = self.i;
+            if i >= self.n {
+                self.eof = true;
+            } else {
+                let c = self.byte(self.i);
+                self.i += 1;
+                if c == Some(NEWLINE) {
+                    self.eol = true;
+                } else if c == Some(RETURN) {
+                    self.eol = true;
+                    if self.byte(self.i) == Some(NEWLINE) {
+                        self.i += 1;
+                    }
+                }
+            }
+            return Token::Field(self.slice(j + 1, i - 1).replace("\"\"", "\""));
+        }
+
+        // Unquoted field: up to the next delimiter or line break
+        while self.i < self.n {
+            let i = self.i;
+            let c = self.text.as_bytes()[i];
+            self.i += 1;
+            if c == NEWLINE {
+                self.eol = true;
+            } else if c == RETURN {
+                self.eol = true;
+                if self.byte(self.i) == Some(NEWLINE) {
+                    self.i += 1;
+                }
+            } else if c != self.delimiter {
+                continue;
+            }
+            return Token::Field(self.slice(j, i).to_string());
+        }
+        self.eof = true;
+        Token::Field(self.slice(j, self.n).to_string())
+    }
+}
+
+/// Split `text` into rows of fields, passing each row and its index to `f`;
+/// rows for which `f` returns `None` are skipped
+pub(super) fn parse_rows<T>(
+    text: &str,
+    delimiter: u8,
+    mut f: impl FnMut(Vec<String>, usize) -> Option<T>,
+) -> Vec<T> {
+    let mut tokenizer = Tokenizer::new(text, delimiter);
+    let mut rows = Vec::new();
+    let mut n = 0;
+    loop {
+        let mut t = tokenizer.token();
+        if matches!(t, Token::EndOfFile) {
+            break;
+        }
+        let mut row = Vec::new();
+        while let Token::Field(field) = t {
+            row.push(field);
+            t = tokenizer.token();
+        }
+        if let Some(row) = f(row, n) {
+            rows.push(row);
+        }
+        n += 1;
+    }
+    rows
+}
+
+/// Whether `record` ends inside a quoted field, i.e. the record continues on
+/// the next line
+pub(super) fn in_quoted_field(record: &str, delimiter: u8) -> bool {
+    let mut in_quote = false;
+    let mut field_start = true;
+    let mut bytes = record.bytes().peekable();
+    while let Some(c) = bytes.next() {
+        if in_quote {
+            if c == QUOTE {
+                if bytes.peek() == Some(&QUOTE) {
+                    bytes.next();
+                } else {
+                    in_quote = false;
+                }
+            }
+        } else if field_start && c == QUOTE {
+            in_quote = true;
+            field_start = false;
+        } else {
+            field_start = c == delimiter || c == NEWLINE || c == RETURN;
+        }
+    }
+    in_quote
+}
@@ -0,0 +1,75 @@
+//! d3-dsv: streaming parse, one record at a time
+
+use super::parse::{in_quoted_field, parse_rows};
+use super::{DsvRow, row_object};
+use std::io::{self, BufRead};
+
+/// Iterator over the rows of a reader, reading one record (which may span
+/// several lines when a quoted field contains line breaks) at a time
+pub struct DsvReader<R: BufRead> {
+    reader: R,
+    delimiter: u8,
+    record: String,
+}
+
+impl<R: BufRead> DsvReader<R> {
+    pub(super) fn new(reader: R, delimiter: u8) -> Self {
+        Self {
+            reader,
+            delimiter,
+            record: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for DsvReader<R> {
+    type Item = io::Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.record.clear();
+        loop {
+            match self.reader.read_line(&mut self.record) {
+                Ok(0) => break,
+                Ok(_) if in_quoted_field(&self.record, self.delimiter) => continue,
+                Ok(_) => break,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if self.record.is_empty() {
+            return None;
+        }
+        // An empty line is a row with a single empty field
+        let row = parse_rows(&self.record, self.delimiter, |row, _| Some(row))
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| vec![String::new()]);
+        Some(Ok(row))
+    }
+}
+
+/// Iterator over the records of a reader, keyed by the header row
+pub struct DsvRecords<R: BufRead> {
+    rows: DsvReader<R>,
+    columns: Vec<String>,
+}
+
+impl<R: BufRead> DsvRecords<R> {
+    pub(super) fn new(mut rows: DsvReader<R>) -> io::Result<Self> {
+        let columns = rows.next().transpose()?.unwrap_or_default();
+        Ok(Self { rows, columns })
+    }
+
+    /// The header row
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+}
+
+impl<R: BufRead> Iterator for DsvRecords<R> {
+    type Item = io::Result<DsvRow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        Some(row.map(|row| row_object(&self.columns, row)))
+    }
+}
@@ -0,0 +1,204 @@
+//! Tests for d3-dsv (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::dsv::*;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    fn row(pairs: &[(&str, &str)]) -> DsvRow {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_parse() {
+        let parsed = csv_parse("a,b,c\n1,2,3\n4,5\n6,7,8,9\n");
+        assert_eq!(parsed.columns, ["a", "b", "c"]);
+        assert_eq!(
+            parsed.rows,
+            [
+                row(&[("a", "1"), ("b", "2"), ("c", "3")]),
+                row(&[("a", "4"), ("b", "5"), ("c", "")]),
+                row(&[("a", "6"), ("b", "7"), ("c", "8")]),
+            ]
+        );
+        assert_eq!(csv_parse("").columns, Vec::<String>::new());
+        assert!(csv_parse("a,b").rows.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rows_quoting_and_line_breaks() {
+        assert_eq!(
+            csv_parse_rows("\"a,b\",\"say \"\"hi\"\"\",\"multi\nline\"\r\nx,,\"\"\n"),
+            [vec!["a,b", "say \"hi\"", "multi\nline"], vec!["x", "", ""],]
+        );
+        // CR, LF and CRLF line endings, and empty lines
+        assert_eq!(
+            csv_parse_rows("a\rb\nc\r\n\nd"),
+            [vec!["a"], vec!["b"], vec!["c"], vec![""], vec!["d"]]
+        );
+        assert_eq!(tsv_parse_rows("a\tb,c\n"), [vec!["a", "b,c"]]);
+        assert_eq!(
+            dsv_format('|').parse_rows("a|\"b|c\"|d"),
+            [vec!["a", "b|c", "d"]]
+        );
+        assert!(csv_parse_rows("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_with_row_conversion() {
+        let text = "name,value\nx,1\ny,oops\nz,3";
+        let parsed = csv_parse_with(text, |d, i, columns| {
+            assert_eq!(columns, ["name", "value"]);
+            Some((i, d["name"].clone(), d["value"].parse::<i32>().ok()?))
+        });
+        assert_eq!(
+            parsed.rows,
+            [(0, "x".to_string(), 1), (2, "z".to_string(), 3)]
+        );
+        let lengths = CSV.parse_rows_with(text, |fields, i| (i > 0).then(|| fields.len()));
+        assert_eq!(lengths, [2, 2, 2]);
+    }
+
+    #[test]
+    fn test_format() {
+        let rows = vec![
+            row(&[("name", "Alice, Jr."), ("quote", "she said \"hi\"")]),
+            row(&[("name", "Bob")]),
+        ];
+        assert_eq!(
+            csv_format(&rows, &["name", "quote"]),
+            "name,quote\n\"Alice, Jr.\",\"she said \"\"hi\"\"\"\nBob,"
+        );
+        assert_eq!(CSV.format_body(&rows, &["name"]), "\"Alice, Jr.\"\nBob");
+        assert_eq!(csv_format(&Vec::<DsvRow>::new(), &["a", "b"]), "a,b");
+        assert_eq!(
+            tsv_format_rows([vec!["a\tb", "c"], vec!["line\nbreak", "d"]]),
+            "\"a\tb\"\tc\n\"line\nbreak\"\td"
+        );
+        assert_eq!(csv_format_rows([[1.5, 2.0]]), "1.5,2");
+        assert_eq!(CSV.format_value("plain"), "plain");
+        assert_eq!(CSV.format_value("a\rb"), "\"a\rb\"");
+    }
+
+    #[test]
+    fn test_format_parse_round_trip() {
+        let rows = vec![vec!["a", "b,c", "\"d\""], vec!["", "multi\nline", "e"]];
+        let text = csv_format_rows(&rows);
+        assert_eq!(csv_parse_rows(&text), rows);
+    }
+
+    #[test]
+    fn test_auto_type() {
+        assert_eq!(auto_type_value(""), DsvValue::Null);
+        assert_eq!(auto_type_value("  "), DsvValue::Null);
+        assert_eq!(auto_type_value("true"), DsvValue::Bool(true));
+        assert_eq!(auto_type_value("false"), DsvValue::Bool(false));
+        assert_eq!(auto_type_value("42"), DsvValue::Number(42.0));
+        assert_eq!(auto_type_value(" -1.5e3 "), DsvValue::Number(-1500.0));
+        assert_eq!(auto_type_value(".5"), DsvValue::Number(0.5));
+        assert_eq!(
+            auto_type_value("-Infinity"),
+            DsvValue::Number(f64::NEG_INFINITY)
+        );
+        assert!(auto_type_value("NaN").as_f64().unwrap().is_nan());
+        assert_eq!(auto_type_value("inf"), DsvValue::String("inf".to_string()));
+        assert_eq!(
+            auto_type_value("True"),
+            DsvValue::String("True".to_string())
+        );
+
+        let date = |y, m, d, h, min, s, ms| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_milli_opt(h, min, s, ms)
+                .unwrap()
+        };
+        // Like D3, a bare year is a number
+        assert_eq!(auto_type_value("2007"), DsvValue::Number(2007.0));
+        assert_eq!(
+            auto_type_value("2007-04"),
+            DsvValue::Date(date(2007, 4, 1, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            auto_type_value("2007-04-23"),
+            DsvValue::Date(date(2007, 4, 23, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            auto_type_value("2007-04-23T12:30:15.250Z"),
+            DsvValue::Date(date(2007, 4, 23, 12, 30, 15, 250))
+        );
+        // Offsets are normalized to UTC
+        assert_eq!(
+            auto_type_value("2007-04-23T01:00+02:00"),
+            DsvValue::Date(date(2007, 4, 22, 23, 0, 0, 0))
+        );
+        assert_eq!(
+            auto_type_value("2007-13-45"),
+            DsvValue::String("2007-13-45".to_string())
+        );
+    }
+
+    #[test]
+    fn test_auto_type_format() {
+        let parsed = csv_parse_with(
+            "date,time,flag,n\n2007-04-23,2007-04-23T12:30Z,true,1\n",
+            |d, _, _| Some(auto_type(d)),
+        );
+        let typed: &HashMap<String, DsvValue> = &parsed.rows[0];
+        assert_eq!(typed["flag"].as_bool(), Some(true));
+        assert_eq!(typed["n"].as_f64(), Some(1.0));
+        assert!(typed["date"].as_date().is_some());
+        assert_eq!(
+            csv_format(&parsed.rows, &parsed.columns),
+            "date,time,flag,n\n2007-04-23,2007-04-23T12:30Z,true,1"
+        );
+        assert_eq!(DsvValue::Null.to_string(), "");
+        assert_eq!(DsvValue::Number(f64::INFINITY).to_string(), "Infinity");
+    }
+
+    #[test]
+    fn test_streaming() {
+        let text = "a,b\n1,\"x\ny\"\n\n2,z\r\n";
+        let rows: Vec<Vec<String>> = CSV
+            .read_rows(Cursor::new(text))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows, CSV.parse_rows(text));
+
+        let mut records = CSV.read(Cursor::new(text)).unwrap();
+        assert_eq!(records.columns(), ["a", "b"]);
+        assert_eq!(
+            records.next().unwrap().unwrap(),
+            row(&[("a", "1"), ("b", "x\ny")])
+        );
+        assert_eq!(
+            records.next().unwrap().unwrap(),
+            row(&[("a", ""), ("b", "")])
+        );
+        assert_eq!(
+            records.next().unwrap().unwrap(),
+            row(&[("a", "2"), ("b", "z")])
+        );
+        assert!(records.next().is_none());
+
+        assert!(CSV.read(Cursor::new("")).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_dsv_format_delimiter() {
+        assert_eq!(dsv_format(';').get_delimiter(), ';');
+        assert_eq!(CSV, dsv_format(','));
+        assert_eq!(TSV.get_delimiter(), '\t');
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid delimiter")]
+    fn test_dsv_format_invalid_delimiter() {
+        dsv_format('"');
+    }
+}
@@ -0,0 +1,235 @@
+//! d3-fetch: loading data from files and HTTP (Rust port)
+//!
+//! Every function takes an input that is an `http://` or `https://` URL, a
+//! `file://` URL or a filesystem path, so the same code reads from a service
+//! or a local cache. Each has an `_async` variant that loads on tokio's
+//! blocking thread pool.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::fetch;
+//!
+//! let data = fetch::csv("examples/aapl.csv").unwrap();
+//! assert_eq!(data.columns, ["date", "close"]);
+//!
+//! let topology: serde_json::Value = fetch::json("tests/fixtures/topojson/squares.json").unwrap();
+//! assert_eq!(topology["type"], "Topology");
+//!
+//! match fetch::text("missing.txt") {
+//!     Err(fetch::FetchError::Io { path, .. }) => assert_eq!(path.to_str(), Some("missing.txt")),
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//! ```
+
+mod source;
+
+use crate::dsv::{CSV, Dsv, DsvParsed, DsvRow, TSV};
+use crate::selection::{ParseError, Selection};
+use serde::de::DeserializeOwned;
+use source::Source;
+use std::fmt;
+use std::path::PathBuf;
+use tiny_skia::Pixmap;
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The input has a scheme other than `http`, `https` or `file`, or is a
+    /// malformed `file://` URL
+    InvalidUrl(String),
+    /// A file could not be read
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// The request failed before a response was received, or its body could
+    /// not be read
+    Http {
+        url: String,
+        message: String,
+    },
+    /// The server responded with a non-2xx status
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The body is not valid UTF-8
+    Utf8,
+    Json(serde_json::Error),
+    /// The body is not a PNG image
+    Image(String),
+    /// The body is not well-formed SVG, XML or HTML
+    Parse(ParseError),
+    /// The blocking task of an async load panicked or was cancelled
+    Task(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::InvalidUrl(url) => write!(f, "unsupported URL '{}'", url),
+            FetchError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            FetchError::Http { url, message } => write!(f, "{}: {}", url, message),
+            FetchError::Status { url, status } => write!(f, "{}: status {}", url, status),
+            FetchError::Utf8 => write!(f, "response is not valid UTF-8"),
+            FetchError::Json(e) => write!(f, "invalid JSON: {}", e),
+            FetchError::Image(e) => write!(f, "invalid image: {}", e),
+            FetchError::Parse(e) => write!(f, "invalid markup: {}", e),
+            FetchError::Task(e) => write!(f, "fetch task failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Io { error, .. } => Some(error),
+            FetchError::Json(e) => Some(e),
+            FetchError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for FetchError {
+    fn from(e: serde_json::Error) -> Self {
+        FetchError::Json(e)
+    }
+}
+
+impl From<ParseError> for FetchError {
+    fn from(e: ParseError) -> Self {
+        FetchError::Parse(e)
+    }
+}
+
+/// The raw bytes of `input`
+pub fn blob(input: &str) -> Result<Vec<u8>, FetchError> {
+    Source::resolve(input)?.load()
+}
+
+/// `input` as UTF-8 text
+pub fn text(input: &str) -> Result<String, FetchError> {
+    String::from_utf8(blob(input)?).map_err(|_| FetchError::Utf8)
+}
+
+/// `input` parsed as JSON, into a `serde_json::Value` or any deserializable type
+pub fn json<T: DeserializeOwned>(input: &str) -> Result<T, FetchError> {
+    Ok(serde_json::from_slice(&blob(input)?)?)
+}
+
+/// `input` parsed as DSV with `delimiter`, with a header row
+pub fn dsv(delimiter: Dsv, input: &str) -> Result<DsvParsed<DsvRow>, FetchError> {
+    Ok(delimiter.parse(&text(input)?))
+}
+
+/// `input` parsed as CSV, with a header row
+pub fn csv(input: &str) -> Result<DsvParsed<DsvRow>, FetchError> {
+    dsv(CSV, input)
+}
+
+/// Like [`csv`], converting rows as [`Dsv::parse_with`]
+pub fn csv_with<T>(
+    input: &str,
+    row: impl FnMut(DsvRow, usize, &[String]) -> Option<T>,
+) -> Result<DsvParsed<T>, FetchError> {
+    Ok(CSV.parse_with(&text(input)?, row))
+}
+
+/// `input` parsed as TSV, with a header row
+pub fn tsv(input: &str) -> Result<DsvParsed<DsvRow>, FetchError> {
+    dsv(TSV, input)
+}
+
+/// Like [`tsv`], converting rows as [`Dsv::parse_with`]
+pub fn tsv_with<T>(
+    input: &str,
+    row: impl FnMut(DsvRow, usize, &[String]) -> Option<T>,
+) -> Result<DsvParsed<T>, FetchError> {
+    Ok(TSV.parse_with(&text(input)?, row))
+}
+
+/// `input` decoded as a PNG image
+pub fn image(input: &str) -> Result<Pixmap, FetchError> {
+    Pixmap::decode_png(&blob(input)?).map_err(|e| FetchError::Image(e.to_string()))
+}
+
+/// `input` parsed as an SVG document into a new selection arena
+pub fn svg(input: &str) -> Result<Selection, FetchError> {
+    Ok(Selection::parse_svg(&text(input)?)?)
+}
+
+/// `input` parsed as XML into a new selection arena
+pub fn xml(input: &str) -> Result<Selection, FetchError> {
+    svg(input)
+}
+
+/// `input` parsed as an HTML fragment into a new selection arena
+pub fn html(input: &str) -> Result<Selection, FetchError> {
+    Ok(Selection::parse_html(&text(input)?)?)
+}
+
+// Run a load on tokio's blocking pool
+async fn spawn<T, F>(load: F) -> Result<T, FetchError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, FetchError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(load)
+        .await
+        .map_err(|e| FetchError::Task(e.to_string()))?
+}
+
+/// Async [`blob`]
+pub async fn blob_async(input: &str) -> Result<Vec<u8>, FetchError> {
+    let source = Source::resolve(input)?;
+    spawn(move || source.load()).await
+}
+
+/// Async [`text`]
+pub async fn text_async(input: &str) -> Result<String, FetchError> {
+    String::from_utf8(blob_async(input).await?).map_err(|_| FetchError::Utf8)
+}
+
+/// Async [`json`]
+pub async fn json_async<T: DeserializeOwned>(input: &str) -> Result<T, FetchError> {
+    Ok(serde_json::from_slice(&blob_async(input).await?)?)
+}
+
+/// Async [`dsv`]
+pub async fn dsv_async(delimiter: Dsv, input: &str) -> Result<DsvParsed<DsvRow>, FetchError> {
+    Ok(delimiter.parse(&text_async(input).await?))
+}
+
+/// Async [`csv`]
+pub async fn csv_async(input: &str) -> Result<DsvParsed<DsvRow>, FetchError> {
+    dsv_async(CSV, input).await
+}
+
+/// Async [`tsv`]
+pub async fn tsv_async(input: &str) -> Result<DsvParsed<DsvRow>, FetchError> {
+    dsv_async(TSV, input).await
+}
+
+/// Async [`image`]
+pub async fn image_async(input: &str) -> Result<Pixmap, FetchError> {
+    Pixmap::decode_png(&blob_async(input).await?).map_err(|e| FetchError::Image(e.to_string()))
+}
+
+/// Async [`svg`]; the markup is parsed on the calling task, since selections
+/// are not `Send`
+pub async fn svg_async(input: &str) -> Result<Selection, FetchError> {
+    Ok(Selection::parse_svg(&text_async(input).await?)?)
+}
+
+/// Async [`xml`]
+pub async fn xml_async(input: &str) -> Result<Selection, FetchError> {
+    svg_async(input).await
+}
+
+/// Async [`html`]
+pub async fn html_async(input: &str) -> Result<Selection, FetchError> {
+    Ok(Selection::parse_html(&text_async(input).await?)?)
+}
+
+#[cfg(test)]
+mod tests;
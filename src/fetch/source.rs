@@ -0,0 +1,92 @@
+//! d3-fetch: resolving inputs to HTTP requests or files
+
+use super::FetchError;
+use std::path::PathBuf;
+
+/// Where an input is loaded from
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Source {
+    Http(String),
+    File(PathBuf),
+}
+
+impl Source {
+    /// `http://` and `https://` URLs are requested, `file://` URLs and plain
+    /// paths read from the filesystem
+    pub fn resolve(input: &str) -> Result<Self, FetchError> {
+        let lower = input.to_ascii_lowercase();
+        if lower.starts_with("http://") || lower.starts_with("https://") {
+            return Ok(Source::Http(input.to_string()));
+        }
+        if lower.starts_with("file://") {
+            let path = &input["file://".len()..];
+            // file://localhost/path and file:///path are the same file
+            let path = match path.get(.."localhost".len()) {
+                Some(host) if host.eq_ignore_ascii_case("localhost") => &path[host.len()..],
+                _ => path,
+            };
+            if !path.starts_with('/') {
+                return Err(FetchError::InvalidUrl(input.to_string()));
+            }
+            return Ok(Source::File(PathBuf::from(percent_decode(path)?)));
+        }
+        if let Some((scheme, _)) = input.split_once("://")
+            && !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return Err(FetchError::InvalidUrl(input.to_string()));
+        }
+        Ok(Source::File(PathBuf::from(input)))
+    }
+
+    pub fn load(&self) -> Result<Vec<u8>, FetchError> {
+        match self {
+            Source::File(path) => std::fs::read(path).map_err(|error| FetchError::Io {
+                path: path.clone(),
+                error,
+            }),
+            Source::Http(url) => {
+                let response = ureq::get(url).call().map_err(|e| match e {
+                    ureq::Error::StatusCode(status) => FetchError::Status {
+                        url: url.clone(),
+                        status,
+                    },
+                    e => FetchError::Http {
+                        url: url.clone(),
+                        message: e.to_string(),
+                    },
+                })?;
+                response
+                    .into_body()
+                    .into_with_config()
+                    .limit(u64::MAX)
+                    .read_to_vec()
+                    .map_err(|e| FetchError::Http {
+                        url: url.clone(),
+                        message: e.to_string(),
+                    })
+            }
+        }
+    }
+}
+
+// Decode %XX escapes in a file URL path
+fn percent_decode(path: &str) -> Result<String, FetchError> {
+    let invalid = || FetchError::InvalidUrl(format!("file://{}", path));
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = path.get(i + 1..i + 3).ok_or_else(invalid)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| invalid())
+}
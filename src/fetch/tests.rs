@@ -0,0 +1,235 @@
+//! Tests for d3-fetch (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::fetch::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use tiny_skia::Pixmap;
+
+    type Route = (&'static str, &'static str, Vec<u8>);
+
+    // A minimal HTTP/1.1 stand-in serving `routes` (path, content type, body)
+    // on a local port, one connection at a time; returns its base URL
+    fn serve(routes: Vec<Route>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                // Skip the headers up to the blank line
+                let mut header = String::new();
+                while reader.read_line(&mut header).is_ok_and(|n| n > 0) && header.trim() != "" {
+                    header.clear();
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or("");
+                let (status, content_type, body) =
+                    match routes.iter().find(|(route, _, _)| *route == path) {
+                        Some((_, content_type, body)) => ("200 OK", *content_type, body.as_slice()),
+                        None => ("404 Not Found", "text/plain", b"not found".as_slice()),
+                    };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    content_type,
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(body);
+            }
+        });
+        base
+    }
+
+    fn png() -> Vec<u8> {
+        let mut pixmap = Pixmap::new(2, 3).unwrap();
+        pixmap.fill(tiny_skia::Color::from_rgba8(255, 0, 0, 255));
+        pixmap.encode_png().unwrap()
+    }
+
+    fn fixtures() -> String {
+        serve(vec![
+            ("/hello.txt", "text/plain", b"hello, world".to_vec()),
+            (
+                "/data.json",
+                "application/json",
+                br#"{"name":"d3","values":[1,2,3]}"#.to_vec(),
+            ),
+            ("/data.csv", "text/csv", b"a,b\n1,2\n3,4\n".to_vec()),
+            (
+                "/data.tsv",
+                "text/tab-separated-values",
+                b"a\tb\n1\t2\n".to_vec(),
+            ),
+            ("/image.png", "image/png", png()),
+            (
+                "/drawing.svg",
+                "image/svg+xml",
+                br#"<svg xmlns="http://www.w3.org/2000/svg"><circle r="5"/><circle r="6"/></svg>"#
+                    .to_vec(),
+            ),
+            ("/page.html", "text/html", b"<p>one<br>two</p>".to_vec()),
+            ("/binary", "application/octet-stream", vec![0xff, 0xfe]),
+        ])
+    }
+
+    #[test]
+    fn test_http_text_and_json() {
+        let base = fixtures();
+        assert_eq!(
+            text(&format!("{}/hello.txt", base)).unwrap(),
+            "hello, world"
+        );
+        let value: serde_json::Value = json(&format!("{}/data.json", base)).unwrap();
+        assert_eq!(value["values"], serde_json::json!([1, 2, 3]));
+
+        #[derive(serde::Deserialize)]
+        struct Data {
+            name: String,
+            values: Vec<u32>,
+        }
+        let data: Data = json(&format!("{}/data.json", base)).unwrap();
+        assert_eq!(data.name, "d3");
+        assert_eq!(data.values, [1, 2, 3]);
+
+        assert!(matches!(
+            json::<Data>(&format!("{}/hello.txt", base)),
+            Err(FetchError::Json(_))
+        ));
+        assert!(matches!(
+            text(&format!("{}/binary", base)),
+            Err(FetchError::Utf8)
+        ));
+        assert_eq!(blob(&format!("{}/binary", base)).unwrap(), [0xff, 0xfe]);
+    }
+
+    #[test]
+    fn test_http_dsv() {
+        let base = fixtures();
+        let data = csv(&format!("{}/data.csv", base)).unwrap();
+        assert_eq!(data.columns, ["a", "b"]);
+        assert_eq!(data.rows[1]["b"], "4");
+        let sums = csv_with(&format!("{}/data.csv", base), |d, _, _| {
+            Some(d["a"].parse::<i32>().ok()? + d["b"].parse::<i32>().ok()?)
+        })
+        .unwrap();
+        assert_eq!(sums.rows, [3, 7]);
+        let data = tsv(&format!("{}/data.tsv", base)).unwrap();
+        assert_eq!(data.rows[0]["a"], "1");
+        let data = dsv(crate::dsv::dsv_format(','), &format!("{}/data.csv", base)).unwrap();
+        assert_eq!(data.rows.len(), 2);
+    }
+
+    #[test]
+    fn test_http_image_and_markup() {
+        let base = fixtures();
+        let pixmap = image(&format!("{}/image.png", base)).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (2, 3));
+        assert_eq!(pixmap.pixel(1, 2).unwrap().red(), 255);
+        assert!(matches!(
+            image(&format!("{}/hello.txt", base)),
+            Err(FetchError::Image(_))
+        ));
+
+        let mut svg = svg(&format!("{}/drawing.svg", base)).unwrap();
+        assert_eq!(svg.select_all(Some("circle")).size(), 2);
+        let mut doc = xml(&format!("{}/drawing.svg", base)).unwrap();
+        assert_eq!(doc.select("circle").get_attr("r").as_deref(), Some("5"));
+        let mut page = html(&format!("{}/page.html", base)).unwrap();
+        assert_eq!(page.select_all(Some("br")).size(), 1);
+        assert!(matches!(
+            crate::fetch::svg(&format!("{}/page.html", base)),
+            Err(FetchError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_http_errors() {
+        let base = fixtures();
+        let url = format!("{}/missing.json", base);
+        match text(&url) {
+            Err(FetchError::Status { url: u, status }) => {
+                assert_eq!(u, url);
+                assert_eq!(status, 404);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        // Connection refused: nothing listens on the local discard port
+        assert!(matches!(
+            text("http://127.0.0.1:9/"),
+            Err(FetchError::Http { .. })
+        ));
+        assert!(matches!(
+            text("ftp://example.com/data.csv"),
+            Err(FetchError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            text("file://relative/path"),
+            Err(FetchError::InvalidUrl(_))
+        ));
+        assert_eq!(
+            FetchError::Status {
+                url: "http://x/".to_string(),
+                status: 500
+            }
+            .to_string(),
+            "http://x/: status 500"
+        );
+    }
+
+    #[test]
+    fn test_files() {
+        let data = csv("examples/aapl.csv").unwrap();
+        assert_eq!(data.columns, ["date", "close"]);
+        assert_eq!(data.rows[0]["date"], "2007-04-23");
+
+        let path = format!(
+            "{}/tests/fixtures/topojson/squares.json",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let topology: serde_json::Value = json(&format!("file://{}", path)).unwrap();
+        assert_eq!(topology["type"], "Topology");
+        let localhost: serde_json::Value = json(&format!("file://localhost{}", path)).unwrap();
+        assert_eq!(topology, localhost);
+        let escaped = format!("file://{}", path.replace("squares", "%73quares"));
+        assert_eq!(text(&escaped).unwrap(), text(&path).unwrap());
+
+        match blob("no/such/file.csv") {
+            Err(FetchError::Io { path, error }) => {
+                assert_eq!(path.to_str(), Some("no/such/file.csv"));
+                assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async() {
+        let base = fixtures();
+        assert_eq!(
+            text_async(&format!("{}/hello.txt", base)).await.unwrap(),
+            "hello, world"
+        );
+        let value: serde_json::Value = json_async(&format!("{}/data.json", base)).await.unwrap();
+        assert_eq!(value["name"], "d3");
+        let data = csv_async(&format!("{}/data.csv", base)).await.unwrap();
+        assert_eq!(data.rows.len(), 2);
+        let data = tsv_async(&format!("{}/data.tsv", base)).await.unwrap();
+        assert_eq!(data.columns, ["a", "b"]);
+        let pixmap = image_async(&format!("{}/image.png", base)).await.unwrap();
+        assert_eq!(pixmap.width(), 2);
+        let mut svg = svg_async(&format!("{}/drawing.svg", base)).await.unwrap();
+        assert_eq!(svg.select_all(Some("circle")).size(), 2);
+        assert!(matches!(
+            blob_async(&format!("{}/missing", base)).await,
+            Err(FetchError::Status { status: 404, .. })
+        ));
+        assert!(csv_async("examples/aapl.csv").await.is_ok());
+    }
+}
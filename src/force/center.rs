@@ -0,0 +1,57 @@
+//! d3-force: keep the nodes' centre of mass in place
+
+use super::{Force, SimulationNode};
+
+/// Translates all nodes so their mean position moves to `(x, y)`
+///
+/// Unlike the other forces this moves positions directly, without changing
+/// velocities.
+pub struct ForceCenter {
+    x: f64,
+    y: f64,
+    strength: f64,
+}
+
+/// Centering force towards `(x, y)` with strength 1
+pub fn force_center(x: f64, y: f64) -> ForceCenter {
+    ForceCenter::new(x, y)
+}
+
+impl ForceCenter {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {
+            x,
+            y,
+            strength: 1.0,
+        }
+    }
+    /// Fraction of the offset corrected per tick; lower values soften the
+    /// motion of interactive layouts (default 1)
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+impl Force for ForceCenter {
+    fn force(
+        &mut self,
+        nodes: &mut [SimulationNode],
+        _alpha: f64,
+        _random: &mut dyn FnMut() -> f64,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+        let n = nodes.len() as f64;
+        let (sx, sy) = nodes
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), node| (sx + node.x, sy + node.y));
+        let sx = (sx / n - self.x) * self.strength;
+        let sy = (sy / n - self.y) * self.strength;
+        for node in nodes {
+            node.x -= sx;
+            node.y -= sy;
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! d3-force: keep circular nodes from overlapping
+
+use super::tree::Cell;
+use super::{Force, NodeAccessor, SimulationNode, constant, jiggle};
+
+/// Treats nodes as circles and pushes overlapping pairs apart
+pub struct ForceCollide {
+    radius: NodeAccessor,
+    strength: f64,
+    iterations: usize,
+    radii: Vec<f64>,
+}
+
+/// Collision force for circles of `radius`
+pub fn force_collide(radius: f64) -> ForceCollide {
+    ForceCollide::new(radius)
+}
+
+impl ForceCollide {
+    pub fn new(radius: f64) -> Self {
+        Self {
+            radius: constant(radius),
+            strength: 1.0,
+            iterations: 1,
+            radii: Vec::new(),
+        }
+    }
+    /// Per-node radius, e.g. from a sqrt scale of the node's value
+    pub fn radius_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.radius = Box::new(f);
+        self
+    }
+    /// How much of each overlap is resolved per iteration, in [0, 1]
+    /// (default 1)
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+        self
+    }
+    /// Passes per tick; more iterations give a more rigid result (default 1)
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    // Largest radius within each cell, for pruning
+    fn prepare(&self, cell: &mut Cell) {
+        let mut r: f64 = 0.0;
+        for &(_, _, i) in &cell.points {
+            r = r.max(self.radii[i]);
+        }
+        for child in &mut cell.children {
+            self.prepare(child);
+            r = r.max(child.value);
+        }
+        cell.value = r;
+    }
+
+    fn apply(
+        &self,
+        cell: &Cell,
+        i: usize,
+        xi: f64,
+        yi: f64,
+        nodes: &mut [SimulationNode],
+        random: &mut dyn FnMut() -> f64,
+    ) {
+        let ri = self.radii[i];
+        let r = ri + cell.value;
+        let (x0, y0, x1, y1) = cell.bounds;
+        if x0 > xi + r || x1 < xi - r || y0 > yi + r || y1 < yi - r {
+            return;
+        }
+        for child in &cell.children {
+            self.apply(child, i, xi, yi, nodes, random);
+        }
+        for &(_, _, j) in &cell.points {
+            // Each pair once
+            if j <= i {
+                continue;
+            }
+            let rj = self.radii[j];
+            let r = ri + rj;
+            let mut x = xi - nodes[j].x - nodes[j].vx;
+            let mut y = yi - nodes[j].y - nodes[j].vy;
+            let mut l = x * x + y * y;
+            if l >= r * r {
+                continue;
+            }
+            if x == 0.0 {
+                x = jiggle(random);
+                l += x * x;
+            }
+            if y == 0.0 {
+                y = jiggle(random);
+                l += y * y;
+            }
+            let l = l.sqrt();
+            let k = (r - l) / l * self.strength;
+            x *= k;
+            y *= k;
+            // The smaller circle moves more
+            let share = rj * rj / (ri * ri + rj * rj);
+            nodes[i].vx += x * share;
+            nodes[i].vy += y * share;
+            nodes[j].vx -= x * (1.0 - share);
+            nodes[j].vy -= y * (1.0 - share);
+        }
+    }
+}
+
+impl Force for ForceCollide {
+    fn initialize(&mut self, nodes: &[SimulationNode]) {
+        self.radii = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (self.radius)(n, i))
+            .collect();
+    }
+
+    fn force(
+        &mut self,
+        nodes: &mut [SimulationNode],
+        _alpha: f64,
+        random: &mut dyn FnMut() -> f64,
+    ) {
+        for _ in 0..self.iterations {
+            // Indexed by anticipated position
+            let Some(mut tree) = Cell::build(nodes.iter().map(|n| (n.x + n.vx, n.y + n.vy))) else {
+                return;
+            };
+            self.prepare(&mut tree);
+            for i in 0..nodes.len() {
+                let xi = nodes[i].x + nodes[i].vx;
+                let yi = nodes[i].y + nodes[i].vy;
+                self.apply(&tree, i, xi, yi, nodes, random);
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! d3-force: spring force between linked nodes
+
+use super::{Force, SimulationNode, jiggle};
+
+/// A link between two nodes, by index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Link {
+    /// Position in the link array
+    pub index: usize,
+    pub source: usize,
+    pub target: usize,
+}
+
+type LinkAccessor = Box<dyn Fn(&Link, usize) -> f64 + Send>;
+
+/// Pushes linked nodes together or apart towards a desired distance
+pub struct ForceLink {
+    links: Vec<Link>,
+    distance: LinkAccessor,
+    strength: Option<LinkAccessor>,
+    iterations: usize,
+    distances: Vec<f64>,
+    strengths: Vec<f64>,
+    bias: Vec<f64>,
+}
+
+/// Link force over `(source, target)` node index pairs
+pub fn force_link(links: Vec<(usize, usize)>) -> ForceLink {
+    ForceLink::new(links)
+}
+
+impl ForceLink {
+    /// Link force with distance 30, and strength `1 / min(degree(source),
+    /// degree(target))` so that hubs are not pulled around by every link
+    ///
+    /// # Panics
+    ///
+    /// When added to a simulation that has no node at a link's source or
+    /// target index.
+    pub fn new(links: Vec<(usize, usize)>) -> Self {
+        Self {
+            links: links
+                .into_iter()
+                .enumerate()
+                .map(|(index, (source, target))| Link {
+                    index,
+                    source,
+                    target,
+                })
+                .collect(),
+            distance: Box::new(|_, _| 30.0),
+            strength: None,
+            iterations: 1,
+            distances: Vec::new(),
+            strengths: Vec::new(),
+            bias: Vec::new(),
+        }
+    }
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+    pub fn distance(mut self, distance: f64) -> Self {
+        self.distance = Box::new(move |_, _| distance);
+        self
+    }
+    pub fn distance_fn<F: Fn(&Link, usize) -> f64 + Send + 'static>(mut self, f: F) -> Self {
+        self.distance = Box::new(f);
+        self
+    }
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = Some(Box::new(move |_, _| strength));
+        self
+    }
+    pub fn strength_fn<F: Fn(&Link, usize) -> f64 + Send + 'static>(mut self, f: F) -> Self {
+        self.strength = Some(Box::new(f));
+        self
+    }
+    /// Passes per tick; more iterations give stiffer links (default 1)
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+}
+
+impl Force for ForceLink {
+    fn initialize(&mut self, nodes: &[SimulationNode]) {
+        let mut count = vec![0usize; nodes.len()];
+        for link in &self.links {
+            for i in [link.source, link.target] {
+                match count.get_mut(i) {
+                    Some(c) => *c += 1,
+                    None => panic!("missing node: {}", i),
+                }
+            }
+        }
+        self.bias = self
+            .links
+            .iter()
+            .map(|l| count[l.source] as f64 / (count[l.source] + count[l.target]) as f64)
+            .collect();
+        self.strengths = self
+            .links
+            .iter()
+            .enumerate()
+            .map(|(i, l)| match &self.strength {
+                Some(f) => f(l, i),
+                None => 1.0 / count[l.source].min(count[l.target]) as f64,
+            })
+            .collect();
+        self.distances = self
+            .links
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (self.distance)(l, i))
+            .collect();
+    }
+
+    fn force(&mut self, nodes: &mut [SimulationNode], alpha: f64, random: &mut dyn FnMut() -> f64) {
+        for _ in 0..self.iterations {
+            for (i, link) in self.links.iter().enumerate() {
+                let (source, target) = (&nodes[link.source], &nodes[link.target]);
+                let mut x = target.x + target.vx - source.x - source.vx;
+                let mut y = target.y + target.vy - source.y - source.vy;
+                if x == 0.0 || x.is_nan() {
+                    x = jiggle(random);
+                }
+                if y == 0.0 || y.is_nan() {
+                    y = jiggle(random);
+                }
+                let mut l = (x * x + y * y).sqrt();
+                l = (l - self.distances[i]) / l * alpha * self.strengths[i];
+                x *= l;
+                y *= l;
+                let b = self.bias[i];
+                let target = &mut nodes[link.target];
+                target.vx -= x * b;
+                target.vy -= y * b;
+                let source = &mut nodes[link.source];
+                source.vx += x * (1.0 - b);
+                source.vy += y * (1.0 - b);
+            }
+        }
+    }
+}
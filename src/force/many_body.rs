@@ -0,0 +1,187 @@
+//! d3-force: n-body attraction or repulsion, approximated with Barnes–Hut
+
+use super::tree::Cell;
+use super::{Force, NodeAccessor, SimulationNode, constant, jiggle};
+
+/// Charge between every pair of nodes: negative strengths repel, positive
+/// strengths attract
+pub struct ForceManyBody {
+    strength: NodeAccessor,
+    strengths: Vec<f64>,
+    distance_min2: f64,
+    distance_max2: f64,
+    theta2: f64,
+}
+
+/// Many-body force with strength -30
+pub fn force_many_body() -> ForceManyBody {
+    ForceManyBody::new()
+}
+
+impl ForceManyBody {
+    pub fn new() -> Self {
+        Self {
+            strength: constant(-30.0),
+            strengths: Vec::new(),
+            distance_min2: 1.0,
+            distance_max2: f64::INFINITY,
+            theta2: 0.81,
+        }
+    }
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = constant(strength);
+        self
+    }
+    pub fn strength_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.strength = Box::new(f);
+        self
+    }
+    /// Barnes–Hut accuracy: cells whose width over distance is below `theta`
+    /// are treated as a single body (default 0.9)
+    pub fn theta(mut self, theta: f64) -> Self {
+        self.theta2 = theta * theta;
+        self
+    }
+    /// Lower bound on distance, avoiding huge forces between close nodes
+    /// (default 1)
+    pub fn distance_min(mut self, distance: f64) -> Self {
+        self.distance_min2 = distance * distance;
+        self
+    }
+    /// Upper bound on distance, to keep the force local (default infinity)
+    pub fn distance_max(mut self, distance: f64) -> Self {
+        self.distance_max2 = distance * distance;
+        self
+    }
+
+    // Total charge of each cell, located at the charge-weighted centre
+    fn accumulate(&self, cell: &mut Cell) {
+        let (mut strength, mut weight, mut x, mut y) = (0.0, 0.0, 0.0, 0.0);
+        if cell.children.is_empty() {
+            for &(px, py, i) in &cell.points {
+                let s = self.strengths[i];
+                strength += s;
+                weight += s.abs();
+                x += s.abs() * px;
+                y += s.abs() * py;
+            }
+            if weight == 0.0 {
+                (x, y, weight) = (cell.points[0].0, cell.points[0].1, 1.0);
+            }
+        } else {
+            for child in &mut cell.children {
+                self.accumulate(child);
+                let c = child.value.abs();
+                if c > 0.0 {
+                    strength += child.value;
+                    weight += c;
+                    x += c * child.x;
+                    y += c * child.y;
+                }
+            }
+        }
+        cell.x = x / weight;
+        cell.y = y / weight;
+        cell.value = strength;
+    }
+
+    fn apply(
+        &self,
+        cell: &Cell,
+        i: usize,
+        node: &mut SimulationNode,
+        alpha: f64,
+        random: &mut dyn FnMut() -> f64,
+    ) {
+        if cell.value == 0.0 {
+            return;
+        }
+        let mut x = cell.x - node.x;
+        let mut y = cell.y - node.y;
+        let w = cell.width();
+        let mut l = x * x + y * y;
+
+        // Far enough away to treat the cell as a single body
+        if w * w / self.theta2 < l {
+            if l < self.distance_max2 {
+                if x == 0.0 {
+                    x = jiggle(random);
+                    l += x * x;
+                }
+                if y == 0.0 {
+                    y = jiggle(random);
+                    l += y * y;
+                }
+                if l < self.distance_min2 {
+                    l = (self.distance_min2 * l).sqrt();
+                }
+                node.vx += x * cell.value * alpha / l;
+                node.vy += y * cell.value * alpha / l;
+            }
+            return;
+        }
+
+        if !cell.children.is_empty() {
+            for child in &cell.children {
+                self.apply(child, i, node, alpha, random);
+            }
+            return;
+        }
+
+        // Too close: apply each point in the leaf individually
+        for &(px, py, j) in &cell.points {
+            if j == i {
+                continue;
+            }
+            let mut x = px - node.x;
+            let mut y = py - node.y;
+            let mut l = x * x + y * y;
+            if l >= self.distance_max2 {
+                continue;
+            }
+            if x == 0.0 {
+                x = jiggle(random);
+                l += x * x;
+            }
+            if y == 0.0 {
+                y = jiggle(random);
+                l += y * y;
+            }
+            if l < self.distance_min2 {
+                l = (self.distance_min2 * l).sqrt();
+            }
+            let w = self.strengths[j] * alpha / l;
+            node.vx += x * w;
+            node.vy += y * w;
+        }
+    }
+}
+
+impl Default for ForceManyBody {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Force for ForceManyBody {
+    fn initialize(&mut self, nodes: &[SimulationNode]) {
+        self.strengths = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (self.strength)(n, i))
+            .collect();
+    }
+
+    fn force(&mut self, nodes: &mut [SimulationNode], alpha: f64, random: &mut dyn FnMut() -> f64) {
+        let Some(mut tree) = Cell::build(nodes.iter().map(|n| (n.x, n.y))) else {
+            return;
+        };
+        self.accumulate(&mut tree);
+        for (i, node) in nodes.iter_mut().enumerate() {
+            self.apply(&tree, i, node, alpha, random);
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! d3-force: force-directed graph layout (Rust port)
+//!
+//! A [`Simulation`] integrates node positions under a set of named forces
+//! using velocity Verlet, cooling `alpha` towards zero as it goes. It can be
+//! stepped synchronously, or driven by a [`Timer`] with [`simulation_timer`].
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::force::{
+//!     Simulation, SimulationNode, force_center, force_link, force_many_body,
+//! };
+//!
+//! let nodes = vec![SimulationNode::new(); 4];
+//! let mut simulation = Simulation::new(nodes);
+//! simulation
+//!     .force("link", force_link(vec![(0, 1), (1, 2), (2, 3)]).distance(50.0))
+//!     .force("charge", force_many_body())
+//!     .force("center", force_center(400.0, 300.0));
+//!
+//! while simulation.step() {}
+//! for node in simulation.nodes() {
+//!     println!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"5\"/>", node.x, node.y);
+//! }
+//! ```
+
+mod center;
+mod collide;
+mod link;
+mod many_body;
+mod position;
+mod radial;
+mod tree;
+
+pub use center::{ForceCenter, force_center};
+pub use collide::{ForceCollide, force_collide};
+pub use link::{ForceLink, Link, force_link};
+pub use many_body::{ForceManyBody, force_many_body};
+pub use position::{ForceX, ForceY, force_x, force_y};
+pub use radial::{ForceRadial, force_radial};
+
+use crate::random::random_lcg;
+use crate::timer::Timer;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+const INITIAL_RADIUS: f64 = 10.0;
+
+/// Per-node value computed from a node and its index, e.g. a link distance
+/// or collision radius
+pub type NodeAccessor = Box<dyn Fn(&SimulationNode, usize) -> f64 + Send>;
+
+/// Source of uniform random numbers in [0, 1)
+pub type RandomSource = Box<dyn FnMut() -> f64 + Send>;
+
+type SimulationHandler = Box<dyn FnMut(&[SimulationNode]) + Send>;
+
+/// A node's position and velocity; `fx`/`fy` pin it in place
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationNode {
+    /// Position in the node array, set by the simulation
+    pub index: usize,
+    pub x: f64,
+    pub y: f64,
+    pub vx: f64,
+    pub vy: f64,
+    pub fx: Option<f64>,
+    pub fy: Option<f64>,
+}
+
+impl SimulationNode {
+    /// A node without a position; the simulation places it on a phyllotaxis
+    /// spiral around the origin
+    pub fn new() -> Self {
+        Self::at(f64::NAN, f64::NAN)
+    }
+    /// A node starting at `(x, y)`
+    pub fn at(x: f64, y: f64) -> Self {
+        Self {
+            index: 0,
+            x,
+            y,
+            vx: 0.0,
+            vy: 0.0,
+            fx: None,
+            fy: None,
+        }
+    }
+    /// Pin the node at `(x, y)`
+    pub fn fixed(x: f64, y: f64) -> Self {
+        Self {
+            fx: Some(x),
+            fy: Some(y),
+            ..Self::at(x, y)
+        }
+    }
+}
+
+impl Default for SimulationNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A force applied to the nodes on every tick
+pub trait Force: Send {
+    /// Called when the force is added to a simulation and whenever its nodes
+    /// are replaced; precompute per-node values here
+    fn initialize(&mut self, _nodes: &[SimulationNode]) {}
+    /// Adjust node velocities (or positions) for the current `alpha`
+    ///
+    /// `random` breaks ties between coincident nodes.
+    fn force(&mut self, nodes: &mut [SimulationNode], alpha: f64, random: &mut dyn FnMut() -> f64);
+}
+
+/// Force-directed simulation over a set of nodes
+pub struct Simulation {
+    nodes: Vec<SimulationNode>,
+    forces: Vec<(String, Box<dyn Force>)>,
+    alpha: f64,
+    alpha_min: f64,
+    alpha_decay: f64,
+    alpha_target: f64,
+    velocity_decay: f64,
+    random: RandomSource,
+    handlers: HashMap<String, Vec<SimulationHandler>>,
+    running: bool,
+}
+
+impl Simulation {
+    /// A running simulation with no forces, seeded with a fixed random source
+    /// so layouts are reproducible
+    pub fn new(nodes: Vec<SimulationNode>) -> Self {
+        let alpha_min = 0.001;
+        let mut simulation = Self {
+            nodes,
+            forces: Vec::new(),
+            alpha: 1.0,
+            alpha_min,
+            alpha_decay: 1.0 - alpha_min.powf(1.0 / 300.0),
+            alpha_target: 0.0,
+            velocity_decay: 0.4,
+            random: Box::new(random_lcg(0.0)),
+            handlers: HashMap::new(),
+            running: true,
+        };
+        simulation.initialize_nodes();
+        simulation
+    }
+
+    fn initialize_nodes(&mut self) {
+        let initial_angle = PI * (3.0 - 5f64.sqrt());
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.index = i;
+            if let Some(fx) = node.fx {
+                node.x = fx;
+            }
+            if let Some(fy) = node.fy {
+                node.y = fy;
+            }
+            if node.x.is_nan() || node.y.is_nan() {
+                let radius = INITIAL_RADIUS * (0.5 + i as f64).sqrt();
+                let angle = i as f64 * initial_angle;
+                node.x = radius * angle.cos();
+                node.y = radius * angle.sin();
+            }
+            if node.vx.is_nan() || node.vy.is_nan() {
+                node.vx = 0.0;
+                node.vy = 0.0;
+            }
+        }
+    }
+
+    pub fn nodes(&self) -> &[SimulationNode] {
+        &self.nodes
+    }
+    /// Mutable access for dragging or pinning nodes between ticks
+    pub fn nodes_mut(&mut self) -> &mut [SimulationNode] {
+        &mut self.nodes
+    }
+    /// Replace the nodes and re-initialize every force
+    pub fn set_nodes(&mut self, nodes: Vec<SimulationNode>) -> &mut Self {
+        self.nodes = nodes;
+        self.initialize_nodes();
+        for (_, force) in &mut self.forces {
+            force.initialize(&self.nodes);
+        }
+        self
+    }
+
+    /// Add a force, replacing any existing force with the same name
+    pub fn force<F: Force + 'static>(&mut self, name: &str, mut force: F) -> &mut Self {
+        force.initialize(&self.nodes);
+        let force: Box<dyn Force> = Box::new(force);
+        match self.forces.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = force,
+            None => self.forces.push((name.to_string(), force)),
+        }
+        self
+    }
+    pub fn remove_force(&mut self, name: &str) -> &mut Self {
+        self.forces.retain(|(n, _)| n != name);
+        self
+    }
+
+    pub fn alpha(&mut self, alpha: f64) -> &mut Self {
+        self.alpha = alpha;
+        self
+    }
+    pub fn get_alpha(&self) -> f64 {
+        self.alpha
+    }
+    /// The simulation stops once alpha falls below this (default 0.001)
+    pub fn alpha_min(&mut self, alpha_min: f64) -> &mut Self {
+        self.alpha_min = alpha_min;
+        self
+    }
+    pub fn get_alpha_min(&self) -> f64 {
+        self.alpha_min
+    }
+    /// Fraction of the distance to `alpha_target` covered per tick; the default
+    /// reaches `alpha_min` in 300 ticks
+    pub fn alpha_decay(&mut self, alpha_decay: f64) -> &mut Self {
+        self.alpha_decay = alpha_decay;
+        self
+    }
+    pub fn get_alpha_decay(&self) -> f64 {
+        self.alpha_decay
+    }
+    /// Value alpha cools (or heats) towards; raise it while dragging
+    pub fn alpha_target(&mut self, alpha_target: f64) -> &mut Self {
+        self.alpha_target = alpha_target;
+        self
+    }
+    pub fn get_alpha_target(&self) -> f64 {
+        self.alpha_target
+    }
+    /// Fraction of velocity lost per tick, like friction (default 0.4)
+    pub fn velocity_decay(&mut self, velocity_decay: f64) -> &mut Self {
+        self.velocity_decay = velocity_decay;
+        self
+    }
+    pub fn get_velocity_decay(&self) -> f64 {
+        self.velocity_decay
+    }
+    /// Replace the random source, e.g. `random_lcg(seed)` for another layout
+    pub fn random_source<R: FnMut() -> f64 + Send + 'static>(&mut self, random: R) -> &mut Self {
+        self.random = Box::new(random);
+        self
+    }
+
+    /// Register a handler for "tick" (after each [`step`](Self::step)) or
+    /// "end" (once alpha falls below `alpha_min`)
+    pub fn on<F>(&mut self, event: &str, handler: F) -> &mut Self
+    where
+        F: FnMut(&[SimulationNode]) + Send + 'static,
+    {
+        self.handlers
+            .entry(event.to_string())
+            .or_default()
+            .push(Box::new(handler));
+        self
+    }
+
+    fn emit(&mut self, event: &str) {
+        if let Some(handlers) = self.handlers.get_mut(event) {
+            for handler in handlers {
+                handler(&self.nodes);
+            }
+        }
+    }
+
+    /// Advance `iterations` ticks without dispatching events or checking
+    /// `alpha_min`
+    pub fn tick(&mut self, iterations: usize) -> &mut Self {
+        for _ in 0..iterations {
+            self.alpha += (self.alpha_target - self.alpha) * self.alpha_decay;
+            for (_, force) in &mut self.forces {
+                force.force(&mut self.nodes, self.alpha, &mut *self.random);
+            }
+            let retain = 1.0 - self.velocity_decay;
+            for node in &mut self.nodes {
+                match node.fx {
+                    Some(fx) => {
+                        node.x = fx;
+                        node.vx = 0.0;
+                    }
+                    None => {
+                        node.vx *= retain;
+                        node.x += node.vx;
+                    }
+                }
+                match node.fy {
+                    Some(fy) => {
+                        node.y = fy;
+                        node.vy = 0.0;
+                    }
+                    None => {
+                        node.vy *= retain;
+                        node.y += node.vy;
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    /// One timer step: tick, dispatch "tick", and stop with "end" once cool
+    ///
+    /// Returns whether the simulation is still running, so `while
+    /// simulation.step() {}` runs a layout to completion.
+    pub fn step(&mut self) -> bool {
+        if !self.running {
+            return false;
+        }
+        self.tick(1);
+        self.emit("tick");
+        if self.alpha < self.alpha_min {
+            self.running = false;
+            self.emit("end");
+        }
+        self.running
+    }
+
+    /// Stop stepping; [`tick`](Self::tick) still works
+    pub fn stop(&mut self) -> &mut Self {
+        self.running = false;
+        self
+    }
+    /// Resume stepping, typically after raising alpha
+    pub fn restart(&mut self) -> &mut Self {
+        self.running = true;
+        self
+    }
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Index of the node closest to `(x, y)`, optionally within `radius`
+    pub fn find(&self, x: f64, y: f64, radius: Option<f64>) -> Option<usize> {
+        let mut best = radius.map_or(f64::INFINITY, |r| r * r);
+        let mut found = None;
+        for (i, node) in self.nodes.iter().enumerate() {
+            let d2 = (x - node.x).powi(2) + (y - node.y).powi(2);
+            if d2 < best {
+                best = d2;
+                found = Some(i);
+            }
+        }
+        found
+    }
+}
+
+/// Start a [`Timer`] that calls [`Simulation::step`] every `delay_ms`
+/// milliseconds, stopping itself when the simulation ends
+///
+/// # Example
+/// ```rust
+/// use rust_d3::force::{Simulation, SimulationNode, force_many_body, simulation_timer};
+/// use std::sync::{Arc, Mutex};
+///
+/// let mut simulation = Simulation::new(vec![SimulationNode::new(); 3]);
+/// simulation.force("charge", force_many_body()).alpha_min(0.5);
+/// let simulation = Arc::new(Mutex::new(simulation));
+/// let mut timer = simulation_timer(&simulation, 1);
+/// while timer.is_running() {
+///     std::thread::sleep(std::time::Duration::from_millis(5));
+/// }
+/// timer.stop();
+/// assert!(!simulation.lock().unwrap().is_running());
+/// ```
+pub fn simulation_timer(simulation: &Arc<Mutex<Simulation>>, delay_ms: u64) -> Timer {
+    let running: Arc<OnceLock<Arc<AtomicBool>>> = Arc::new(OnceLock::new());
+    let timer_running = Arc::clone(&running);
+    let simulation = Arc::clone(simulation);
+    let mut timer = Timer::new(
+        move || {
+            if !simulation.lock().unwrap().step()
+                && let Some(flag) = timer_running.get()
+            {
+                flag.store(false, Ordering::SeqCst);
+            }
+        },
+        delay_ms,
+    );
+    let _ = running.set(Arc::clone(&timer.running));
+    timer.start();
+    timer
+}
+
+/// Tiny random offset used to separate coincident nodes
+pub(crate) fn jiggle(random: &mut dyn FnMut() -> f64) -> f64 {
+    (random() - 0.5) * 1e-6
+}
+
+/// Constant accessor, the default for most force parameters
+pub(crate) fn constant(value: f64) -> NodeAccessor {
+    Box::new(move |_, _| value)
+}
+
+#[cfg(test)]
+mod tests;
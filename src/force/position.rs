@@ -0,0 +1,142 @@
+//! d3-force: pull nodes towards a target x or y coordinate
+
+use super::{Force, NodeAccessor, SimulationNode, constant};
+
+/// Pulls each node towards a target x coordinate
+pub struct ForceX {
+    x: NodeAccessor,
+    strength: NodeAccessor,
+    xz: Vec<f64>,
+    strengths: Vec<f64>,
+}
+
+/// Pulls each node towards a target y coordinate
+pub struct ForceY {
+    y: NodeAccessor,
+    strength: NodeAccessor,
+    yz: Vec<f64>,
+    strengths: Vec<f64>,
+}
+
+/// Force towards `x` with strength 0.1
+pub fn force_x(x: f64) -> ForceX {
+    ForceX::new(x)
+}
+
+/// Force towards `y` with strength 0.1
+pub fn force_y(y: f64) -> ForceY {
+    ForceY::new(y)
+}
+
+impl ForceX {
+    pub fn new(x: f64) -> Self {
+        Self {
+            x: constant(x),
+            strength: constant(0.1),
+            xz: Vec::new(),
+            strengths: Vec::new(),
+        }
+    }
+    /// Per-node target, e.g. a band scale position for grouped layouts
+    pub fn x_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(mut self, f: F) -> Self {
+        self.x = Box::new(f);
+        self
+    }
+    /// Fraction of the distance covered per tick, in [0, 1]
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = constant(strength);
+        self
+    }
+    pub fn strength_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.strength = Box::new(f);
+        self
+    }
+}
+
+impl ForceY {
+    pub fn new(y: f64) -> Self {
+        Self {
+            y: constant(y),
+            strength: constant(0.1),
+            yz: Vec::new(),
+            strengths: Vec::new(),
+        }
+    }
+    /// Per-node target, e.g. a band scale position for grouped layouts
+    pub fn y_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(mut self, f: F) -> Self {
+        self.y = Box::new(f);
+        self
+    }
+    /// Fraction of the distance covered per tick, in [0, 1]
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = constant(strength);
+        self
+    }
+    pub fn strength_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.strength = Box::new(f);
+        self
+    }
+}
+
+fn evaluate(accessor: &NodeAccessor, nodes: &[SimulationNode]) -> Vec<f64> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| accessor(n, i))
+        .collect()
+}
+
+impl Force for ForceX {
+    fn initialize(&mut self, nodes: &[SimulationNode]) {
+        self.xz = evaluate(&self.x, nodes);
+        // A NaN target leaves the node alone
+        self.strengths = evaluate(&self.strength, nodes)
+            .into_iter()
+            .zip(&self.xz)
+            .map(|(s, x)| if x.is_nan() { 0.0 } else { s })
+            .collect();
+    }
+
+    fn force(
+        &mut self,
+        nodes: &mut [SimulationNode],
+        alpha: f64,
+        _random: &mut dyn FnMut() -> f64,
+    ) {
+        for (i, node) in nodes.iter_mut().enumerate() {
+            if self.strengths[i] != 0.0 {
+                node.vx += (self.xz[i] - node.x) * self.strengths[i] * alpha;
+            }
+        }
+    }
+}
+
+impl Force for ForceY {
+    fn initialize(&mut self, nodes: &[SimulationNode]) {
+        self.yz = evaluate(&self.y, nodes);
+        self.strengths = evaluate(&self.strength, nodes)
+            .into_iter()
+            .zip(&self.yz)
+            .map(|(s, y)| if y.is_nan() { 0.0 } else { s })
+            .collect();
+    }
+
+    fn force(
+        &mut self,
+        nodes: &mut [SimulationNode],
+        alpha: f64,
+        _random: &mut dyn FnMut() -> f64,
+    ) {
+        for (i, node) in nodes.iter_mut().enumerate() {
+            if self.strengths[i] != 0.0 {
+                node.vy += (self.yz[i] - node.y) * self.strengths[i] * alpha;
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! d3-force: pull nodes towards a circle
+
+use super::{Force, NodeAccessor, SimulationNode, constant};
+
+/// Pulls each node towards the circle of a given radius around `(x, y)`
+pub struct ForceRadial {
+    radius: NodeAccessor,
+    strength: NodeAccessor,
+    x: f64,
+    y: f64,
+    radiuses: Vec<f64>,
+    strengths: Vec<f64>,
+}
+
+/// Radial force towards a circle of `radius` around `(x, y)`, strength 0.1
+pub fn force_radial(radius: f64, x: f64, y: f64) -> ForceRadial {
+    ForceRadial::new(radius, x, y)
+}
+
+impl ForceRadial {
+    pub fn new(radius: f64, x: f64, y: f64) -> Self {
+        Self {
+            radius: constant(radius),
+            strength: constant(0.1),
+            x,
+            y,
+            radiuses: Vec::new(),
+            strengths: Vec::new(),
+        }
+    }
+    /// Per-node radius, e.g. for concentric rings by category
+    pub fn radius_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.radius = Box::new(f);
+        self
+    }
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = constant(strength);
+        self
+    }
+    pub fn strength_fn<F: Fn(&SimulationNode, usize) -> f64 + Send + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.strength = Box::new(f);
+        self
+    }
+}
+
+impl Force for ForceRadial {
+    fn initialize(&mut self, nodes: &[SimulationNode]) {
+        self.radiuses = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (self.radius)(n, i))
+            .collect();
+        self.strengths = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                if self.radiuses[i].is_nan() {
+                    0.0
+                } else {
+                    (self.strength)(n, i)
+                }
+            })
+            .collect();
+    }
+
+    fn force(
+        &mut self,
+        nodes: &mut [SimulationNode],
+        alpha: f64,
+        _random: &mut dyn FnMut() -> f64,
+    ) {
+        for (i, node) in nodes.iter_mut().enumerate() {
+            // Zero for NaN radii, which would otherwise poison the velocity
+            if self.strengths[i] == 0.0 {
+                continue;
+            }
+            let mut dx = node.x - self.x;
+            if dx == 0.0 {
+                dx = 1e-6;
+            }
+            let mut dy = node.y - self.y;
+            if dy == 0.0 {
+                dy = 1e-6;
+            }
+            let r = (dx * dx + dy * dy).sqrt();
+            let k = (self.radiuses[i] - r) * self.strengths[i] * alpha / r;
+            node.vx += dx * k;
+            node.vy += dy * k;
+        }
+    }
+}
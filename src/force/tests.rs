@@ -0,0 +1,223 @@
+//! Tests for d3-force (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::force::{
+        Force, Simulation, SimulationNode, force_center, force_collide, force_link,
+        force_many_body, force_radial, force_x, force_y,
+    };
+    use crate::random::random_lcg;
+
+    fn distance(a: &SimulationNode, b: &SimulationNode) -> f64 {
+        (a.x - b.x).hypot(a.y - b.y)
+    }
+
+    #[test]
+    fn test_simulation_initial_positions() {
+        let mut nodes = vec![SimulationNode::new(); 3];
+        nodes[2] = SimulationNode::fixed(5.0, 6.0);
+        let simulation = Simulation::new(nodes);
+        let nodes = simulation.nodes();
+        // Phyllotaxis: radius 10 * sqrt(0.5 + i), angle i * PI * (3 - sqrt 5)
+        assert!((nodes[0].x - 7.0710678118654755).abs() < 1e-12);
+        assert_eq!(nodes[0].y, 0.0);
+        assert!((nodes[1].x - -9.03088751750192).abs() < 1e-12);
+        assert!((nodes[1].y - 8.273032735715967).abs() < 1e-12);
+        assert_eq!((nodes[2].x, nodes[2].y), (5.0, 6.0));
+        assert_eq!(nodes[1].index, 1);
+        assert_eq!((nodes[0].vx, nodes[0].vy), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_simulation_alpha_cooling() {
+        let mut simulation = Simulation::new(vec![SimulationNode::new(); 2]);
+        let mut ticks = 0;
+        while simulation.step() {
+            ticks += 1;
+        }
+        assert_eq!(ticks + 1, 300);
+        assert!(simulation.get_alpha() < simulation.get_alpha_min());
+        assert!(!simulation.step());
+        simulation.alpha(1.0).restart();
+        assert!(simulation.step());
+    }
+
+    #[test]
+    fn test_simulation_events() {
+        use std::sync::{Arc, Mutex};
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (tick_log, end_log) = (Arc::clone(&log), Arc::clone(&log));
+        let mut simulation = Simulation::new(vec![SimulationNode::new()]);
+        simulation
+            .alpha_decay(0.5)
+            .alpha_min(0.2)
+            .on("tick", move |nodes| {
+                tick_log
+                    .lock()
+                    .unwrap()
+                    .push(format!("tick {}", nodes.len()))
+            })
+            .on("end", move |_| {
+                end_log.lock().unwrap().push("end".to_string())
+            });
+        while simulation.step() {}
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["tick 1", "tick 1", "tick 1", "end"]
+        );
+    }
+
+    #[test]
+    fn test_force_link_reaches_distance() {
+        let mut simulation = Simulation::new(vec![SimulationNode::new(); 3]);
+        simulation.force("link", force_link(vec![(0, 1), (1, 2)]).distance(40.0));
+        while simulation.step() {}
+        let n = simulation.nodes();
+        assert!((distance(&n[0], &n[1]) - 40.0).abs() < 1.0);
+        assert!((distance(&n[1], &n[2]) - 40.0).abs() < 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing node")]
+    fn test_force_link_missing_node() {
+        Simulation::new(vec![SimulationNode::new(); 2]).force("link", force_link(vec![(0, 5)]));
+    }
+
+    #[test]
+    fn test_force_many_body_repels() {
+        let nodes = vec![SimulationNode::at(-1.0, 0.0), SimulationNode::at(1.0, 0.0)];
+        let mut simulation = Simulation::new(nodes);
+        simulation.force("charge", force_many_body());
+        simulation.tick(10);
+        let n = simulation.nodes();
+        assert!(n[0].x < -1.0 && n[1].x > 1.0);
+        assert!((n[0].x + n[1].x).abs() < 1e-9);
+
+        let nodes = vec![
+            SimulationNode::at(-10.0, 0.0),
+            SimulationNode::at(10.0, 0.0),
+        ];
+        let mut simulation = Simulation::new(nodes);
+        simulation.force("charge", force_many_body().strength(30.0));
+        simulation.tick(1);
+        assert!(simulation.nodes()[0].x > -10.0);
+    }
+
+    #[test]
+    fn test_force_many_body_barnes_hut_matches_exact() {
+        let mut random = random_lcg(0.42);
+        let nodes: Vec<SimulationNode> = (0..200)
+            .map(|_| SimulationNode::at(random() * 500.0, random() * 500.0))
+            .collect();
+        let run = |theta: f64| {
+            let mut nodes = nodes.clone();
+            let mut force = force_many_body().theta(theta);
+            force.initialize(&nodes);
+            force.force(&mut nodes, 1.0, &mut random_lcg(0.0));
+            nodes
+        };
+        let exact = run(0.0);
+        let approx = run(0.9);
+        // Individual nodes can see large relative errors where forces nearly
+        // cancel, so compare in aggregate
+        let (mut error, mut magnitude) = (0.0, 0.0);
+        for (e, a) in exact.iter().zip(&approx) {
+            error += (e.vx - a.vx).hypot(e.vy - a.vy);
+            magnitude += e.vx.hypot(e.vy);
+        }
+        assert!(error / magnitude < 0.05);
+    }
+
+    #[test]
+    fn test_force_many_body_coincident_nodes() {
+        let nodes = vec![SimulationNode::at(0.0, 0.0); 6];
+        let mut simulation = Simulation::new(nodes);
+        simulation.force("charge", force_many_body());
+        simulation.tick(5);
+        let n = simulation.nodes();
+        assert!(n.iter().all(|n| n.x.is_finite() && n.y.is_finite()));
+        assert!(distance(&n[0], &n[1]) > 0.0);
+    }
+
+    #[test]
+    fn test_force_center() {
+        let nodes = vec![SimulationNode::at(0.0, 0.0), SimulationNode::at(10.0, 20.0)];
+        let mut simulation = Simulation::new(nodes);
+        simulation.force("center", force_center(100.0, 100.0));
+        simulation.tick(1);
+        let n = simulation.nodes();
+        assert_eq!((n[0].x, n[0].y), (95.0, 90.0));
+        assert_eq!((n[1].x, n[1].y), (105.0, 110.0));
+    }
+
+    #[test]
+    fn test_force_collide_separates() {
+        let mut random = random_lcg(0.1);
+        let nodes: Vec<SimulationNode> = (0..30)
+            .map(|_| SimulationNode::at(random() * 20.0, random() * 20.0))
+            .collect();
+        let mut simulation = Simulation::new(nodes);
+        simulation.force("collide", force_collide(5.0).iterations(4));
+        simulation.tick(300);
+        let n = simulation.nodes();
+        for i in 0..n.len() {
+            for j in i + 1..n.len() {
+                assert!(distance(&n[i], &n[j]) > 9.5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_force_x_y_and_radial() {
+        let mut simulation = Simulation::new(vec![SimulationNode::new(); 5]);
+        simulation
+            .force(
+                "x",
+                force_x(0.0).x_fn(|_, i| i as f64 * 100.0).strength(0.5),
+            )
+            .force("y", force_y(50.0).strength(0.5));
+        simulation.tick(300);
+        for (i, node) in simulation.nodes().iter().enumerate() {
+            assert!((node.x - i as f64 * 100.0).abs() < 1e-3);
+            assert!((node.y - 50.0).abs() < 1e-3);
+        }
+
+        let mut simulation = Simulation::new(vec![SimulationNode::new(); 5]);
+        simulation.force("radial", force_radial(100.0, 10.0, 10.0).strength(0.5));
+        simulation.tick(300);
+        for node in simulation.nodes() {
+            assert!(((node.x - 10.0).hypot(node.y - 10.0) - 100.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_simulation_fixed_nodes_and_find() {
+        let nodes = vec![
+            SimulationNode::fixed(0.0, 0.0),
+            SimulationNode::at(1.0, 1.0),
+        ];
+        let mut simulation = Simulation::new(nodes);
+        simulation.force("charge", force_many_body());
+        simulation.tick(20);
+        assert_eq!(
+            (simulation.nodes()[0].x, simulation.nodes()[0].y),
+            (0.0, 0.0)
+        );
+        assert_eq!(simulation.find(0.5, 0.0, None), Some(0));
+        assert_eq!(simulation.find(500.0, 0.0, Some(1.0)), None);
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible() {
+        let layout = |seed: f64| {
+            let mut simulation = Simulation::new(vec![SimulationNode::at(0.0, 0.0); 4]);
+            simulation
+                .random_source(random_lcg(seed))
+                .force("charge", force_many_body());
+            simulation.tick(50);
+            simulation.nodes().to_vec()
+        };
+        assert_eq!(layout(0.3), layout(0.3));
+        assert_ne!(layout(0.3), layout(0.7));
+    }
+}
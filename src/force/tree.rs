@@ -0,0 +1,71 @@
+//! Quadtree of node indices shared by the many-body and collide forces
+
+use crate::quadtree::{Node, Quadtree};
+
+/// A quadtree cell with room for a per-force aggregate
+pub(super) struct Cell {
+    pub bounds: (f64, f64, f64, f64),
+    /// `(x, y, node index)` for a leaf; empty for an internal cell
+    pub points: Vec<(f64, f64, usize)>,
+    pub children: Vec<Cell>,
+    /// Aggregate position, e.g. the centre of charge
+    pub x: f64,
+    pub y: f64,
+    /// Aggregate value, e.g. the total charge or largest radius
+    pub value: f64,
+}
+
+impl Cell {
+    /// Index positions into a square [`Quadtree`] covering them all
+    pub fn build(positions: impl Iterator<Item = (f64, f64)>) -> Option<Cell> {
+        let positions: Vec<(f64, f64)> = positions.collect();
+        let (mut x0, mut y0) = (f64::INFINITY, f64::INFINITY);
+        let (mut x1, mut y1) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &(x, y) in &positions {
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x);
+            y1 = y1.max(y);
+        }
+        if positions.is_empty() || !(x1 - x0).is_finite() || !(y1 - y0).is_finite() {
+            return None;
+        }
+        // Square cells keep the Barnes–Hut size test meaningful
+        let size = (x1 - x0).max(y1 - y0).max(1.0);
+        let mut quadtree = Quadtree::new((x0, y0, x0 + size, y0 + size));
+        for (i, &(x, y)) in positions.iter().enumerate() {
+            quadtree.insert(x, y, i);
+        }
+        let bounds = quadtree.bounds;
+        quadtree.root.map(|root| Cell::from_node(root, bounds))
+    }
+
+    fn from_node(node: Node<usize>, bounds: (f64, f64, f64, f64)) -> Cell {
+        let (points, children) = match node {
+            Node::Leaf(points) => (points, Vec::new()),
+            Node::Internal(children) => {
+                let children = children
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(i, child)| {
+                        let child_bounds = Quadtree::<usize>::child_bounds(bounds, i);
+                        child.map(|c| Cell::from_node(c, child_bounds))
+                    })
+                    .collect();
+                (Vec::new(), children)
+            }
+        };
+        Cell {
+            bounds,
+            points,
+            children,
+            x: 0.0,
+            y: 0.0,
+            value: 0.0,
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.bounds.2 - self.bounds.0
+    }
+}
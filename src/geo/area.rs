@@ -0,0 +1,109 @@
+//! d3-geo: spherical area
+
+use super::math::{Adder, QUARTER_PI, RADIANS, TAU};
+use super::{GeoStream, GeoStreamable};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    Polygon,
+    RingFirst,
+    Ring,
+}
+
+/// Accumulates the signed area of polygon rings; also used by `geo_bounds`
+/// to tell which side of a ring is inside
+#[derive(Debug, Clone)]
+pub(crate) struct AreaStream {
+    state: State,
+    pub area_sum: Adder,
+    /// Signed area of the rings of the current polygon
+    pub ring_sum: Adder,
+    first: [f64; 2],
+    lambda0: f64,
+    cos_phi0: f64,
+    sin_phi0: f64,
+}
+
+impl AreaStream {
+    pub fn new() -> Self {
+        Self {
+            state: State::Idle,
+            area_sum: Adder::default(),
+            ring_sum: Adder::default(),
+            first: [0.0; 2],
+            lambda0: 0.0,
+            cos_phi0: 0.0,
+            sin_phi0: 0.0,
+        }
+    }
+
+    fn area_point(&mut self, lambda: f64, phi: f64) {
+        let lambda = lambda * RADIANS;
+        let phi = phi * RADIANS / 2.0 + QUARTER_PI;
+        // Area of the spherical triangle formed with the south pole
+        let d_lambda = lambda - self.lambda0;
+        let sd_lambda = if d_lambda >= 0.0 { 1.0 } else { -1.0 };
+        let ad_lambda = sd_lambda * d_lambda;
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let k = self.sin_phi0 * sin_phi;
+        let u = self.cos_phi0 * cos_phi + k * ad_lambda.cos();
+        let v = k * sd_lambda * ad_lambda.sin();
+        self.ring_sum.add(v.atan2(u));
+        self.lambda0 = lambda;
+        self.cos_phi0 = cos_phi;
+        self.sin_phi0 = sin_phi;
+    }
+}
+
+impl GeoStream for AreaStream {
+    fn point(&mut self, lambda: f64, phi: f64) {
+        match self.state {
+            State::RingFirst => {
+                self.state = State::Ring;
+                self.first = [lambda, phi];
+                self.lambda0 = lambda * RADIANS;
+                let phi = phi * RADIANS / 2.0 + QUARTER_PI;
+                (self.sin_phi0, self.cos_phi0) = phi.sin_cos();
+            }
+            State::Ring => self.area_point(lambda, phi),
+            State::Idle | State::Polygon => {}
+        }
+    }
+    fn line_start(&mut self) {
+        if self.state == State::Polygon {
+            self.state = State::RingFirst;
+        }
+    }
+    fn line_end(&mut self) {
+        if self.state == State::Ring {
+            let [lambda, phi] = self.first;
+            self.area_point(lambda, phi);
+        }
+        if self.state != State::Idle {
+            self.state = State::Polygon;
+        }
+    }
+    fn polygon_start(&mut self) {
+        self.ring_sum = Adder::default();
+        self.state = State::Polygon;
+    }
+    fn polygon_end(&mut self) {
+        let area = self.ring_sum.value();
+        self.area_sum
+            .add(if area < 0.0 { TAU + area } else { area });
+        self.state = State::Idle;
+    }
+    fn sphere(&mut self) {
+        self.area_sum.add(TAU);
+    }
+}
+
+/// Spherical area of `object` in steradians; the whole sphere is 4π
+///
+/// Points and lines have no area.
+pub fn geo_area<O: GeoStreamable + ?Sized>(object: &O) -> f64 {
+    let mut stream = AreaStream::new();
+    object.stream(&mut stream);
+    stream.area_sum.value() * 2.0
+}
@@ -0,0 +1,253 @@
+//! d3-geo: spherical bounding box
+
+use super::area::AreaStream;
+use super::math::{Adder, DEGREES, EPSILON, RADIANS, Vec3, cartesian, cartesian_cross};
+use super::math::{cartesian_normalize, spherical};
+use super::{GeoStream, GeoStreamable};
+
+struct BoundsStream {
+    lambda0: f64,
+    phi0: f64,
+    lambda1: f64,
+    phi1: f64,
+    // Previous longitude
+    lambda2: f64,
+    // First point of the current ring
+    first: [f64; 2],
+    // Previous point, in Cartesian coordinates
+    p0: Option<Vec3>,
+    delta_sum: Adder,
+    ranges: Vec<[f64; 2]>,
+    in_polygon: bool,
+    in_line: bool,
+    area: AreaStream,
+}
+
+// Left-to-right distance between two longitudes
+fn angle(lambda0: f64, lambda1: f64) -> f64 {
+    let d = lambda1 - lambda0;
+    if d < 0.0 { d + 360.0 } else { d }
+}
+
+fn range_contains(range: [f64; 2], x: f64) -> bool {
+    if range[0] <= range[1] {
+        range[0] <= x && x <= range[1]
+    } else {
+        x < range[0] || range[1] < x
+    }
+}
+
+impl BoundsStream {
+    fn new() -> Self {
+        Self {
+            lambda0: f64::INFINITY,
+            phi0: f64::INFINITY,
+            lambda1: f64::NEG_INFINITY,
+            phi1: f64::NEG_INFINITY,
+            lambda2: 0.0,
+            first: [0.0; 2],
+            p0: None,
+            delta_sum: Adder::default(),
+            ranges: Vec::new(),
+            in_polygon: false,
+            in_line: false,
+            area: AreaStream::new(),
+        }
+    }
+
+    fn update_range(&mut self) {
+        if let Some(range) = self.ranges.last_mut() {
+            *range = [self.lambda0, self.lambda1];
+        }
+    }
+
+    fn bounds_point(&mut self, lambda: f64, phi: f64) {
+        self.lambda0 = lambda;
+        self.lambda1 = lambda;
+        self.ranges.push([lambda, lambda]);
+        self.phi0 = self.phi0.min(phi);
+        self.phi1 = self.phi1.max(phi);
+    }
+
+    fn line_point(&mut self, lambda: f64, phi: f64) {
+        let p = cartesian([lambda * RADIANS, phi * RADIANS]);
+        if let Some(p0) = self.p0 {
+            // The great arc may bulge past either endpoint's latitude
+            let normal = cartesian_cross(p0, p);
+            let equatorial = [normal[1], -normal[0], 0.0];
+            let inflection = spherical(cartesian_normalize(cartesian_cross(equatorial, normal)));
+            let delta = lambda - self.lambda2;
+            let sign = if delta > 0.0 { 1.0 } else { -1.0 };
+            let lambdai = inflection[0] * DEGREES * sign;
+            // The same meridian seen from the other side of the globe
+            let lambdai_opposite = (lambdai + 360.0) % 360.0 - 180.0;
+            let antimeridian = delta.abs() > 180.0;
+            let between = |l: f64| sign * self.lambda2 < l && l < sign * lambda;
+            if antimeridian ^ between(lambdai) {
+                let phii = inflection[1] * DEGREES;
+                if phii > self.phi1 {
+                    self.phi1 = phii;
+                }
+            } else if antimeridian ^ between(lambdai_opposite) {
+                let phii = -inflection[1] * DEGREES;
+                if phii < self.phi0 {
+                    self.phi0 = phii;
+                }
+            } else {
+                self.phi0 = self.phi0.min(phi);
+                self.phi1 = self.phi1.max(phi);
+            }
+            if antimeridian {
+                if lambda < self.lambda2 {
+                    if angle(self.lambda0, lambda) > angle(self.lambda0, self.lambda1) {
+                        self.lambda1 = lambda;
+                    }
+                } else if angle(lambda, self.lambda1) > angle(self.lambda0, self.lambda1) {
+                    self.lambda0 = lambda;
+                }
+            } else if self.lambda1 >= self.lambda0 {
+                self.lambda0 = self.lambda0.min(lambda);
+                self.lambda1 = self.lambda1.max(lambda);
+            } else if lambda > self.lambda2 {
+                if angle(self.lambda0, lambda) > angle(self.lambda0, self.lambda1) {
+                    self.lambda1 = lambda;
+                }
+            } else if angle(lambda, self.lambda1) > angle(self.lambda0, self.lambda1) {
+                self.lambda0 = lambda;
+            }
+        } else {
+            self.lambda0 = lambda;
+            self.lambda1 = lambda;
+            self.ranges.push([lambda, lambda]);
+        }
+        self.phi0 = self.phi0.min(phi);
+        self.phi1 = self.phi1.max(phi);
+        self.p0 = Some(p);
+        self.lambda2 = lambda;
+    }
+
+    fn ring_point(&mut self, lambda: f64, phi: f64) {
+        if self.p0.is_some() {
+            let delta = lambda - self.lambda2;
+            self.delta_sum.add(if delta.abs() > 180.0 {
+                delta + if delta > 0.0 { 360.0 } else { -360.0 }
+            } else {
+                delta
+            });
+        } else {
+            self.first = [lambda, phi];
+        }
+        self.area.point(lambda, phi);
+        self.line_point(lambda, phi);
+    }
+
+    fn result(mut self) -> [[f64; 2]; 2] {
+        if !self.ranges.is_empty() {
+            // Merge overlapping ranges, sorted by minimum longitude
+            self.ranges.sort_by(|a, b| a[0].total_cmp(&b[0]));
+            let mut merged = vec![self.ranges[0]];
+            for &b in &self.ranges[1..] {
+                let a = merged.last_mut().unwrap();
+                if range_contains(*a, b[0]) || range_contains(*a, b[1]) {
+                    if angle(a[0], b[1]) > angle(a[0], a[1]) {
+                        a[1] = b[1];
+                    }
+                    if angle(b[0], a[1]) > angle(a[0], a[1]) {
+                        a[0] = b[0];
+                    }
+                } else {
+                    merged.push(b);
+                }
+            }
+            // The bounding box is the inverse of the largest gap between ranges
+            let mut delta_max = f64::NEG_INFINITY;
+            let mut a = merged[merged.len() - 1];
+            for &b in &merged {
+                let delta = angle(a[1], b[0]);
+                if delta > delta_max {
+                    delta_max = delta;
+                    self.lambda0 = b[0];
+                    self.lambda1 = a[1];
+                }
+                a = b;
+            }
+        }
+        if self.lambda0 == f64::INFINITY || self.phi0 == f64::INFINITY {
+            [[f64::NAN, f64::NAN], [f64::NAN, f64::NAN]]
+        } else {
+            [[self.lambda0, self.phi0], [self.lambda1, self.phi1]]
+        }
+    }
+}
+
+impl GeoStream for BoundsStream {
+    fn point(&mut self, lambda: f64, phi: f64) {
+        if self.in_polygon {
+            self.ring_point(lambda, phi);
+        } else if self.in_line {
+            self.line_point(lambda, phi);
+        } else {
+            self.bounds_point(lambda, phi);
+        }
+    }
+    fn line_start(&mut self) {
+        if self.in_polygon {
+            self.area.line_start();
+        } else {
+            self.in_line = true;
+        }
+    }
+    fn line_end(&mut self) {
+        if self.in_polygon {
+            let [lambda, phi] = self.first;
+            self.ring_point(lambda, phi);
+            self.area.line_end();
+            if self.delta_sum.value().abs() > EPSILON {
+                self.lambda0 = -180.0;
+                self.lambda1 = 180.0;
+            }
+        } else {
+            self.in_line = false;
+        }
+        self.update_range();
+        self.p0 = None;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+        self.delta_sum = Adder::default();
+        self.area.polygon_start();
+    }
+    fn polygon_end(&mut self) {
+        self.area.polygon_end();
+        self.in_polygon = false;
+        let delta_sum = self.delta_sum.value();
+        if self.area.ring_sum.value() < 0.0 {
+            // The polygon covers more than a hemisphere around a pole
+            self.lambda0 = -180.0;
+            self.lambda1 = 180.0;
+            self.phi0 = -90.0;
+            self.phi1 = 90.0;
+        } else if delta_sum > EPSILON {
+            self.phi1 = 90.0;
+        } else if delta_sum < -EPSILON {
+            self.phi0 = -90.0;
+        }
+        self.update_range();
+    }
+    fn sphere(&mut self) {
+        self.lambda0 = -180.0;
+        self.lambda1 = 180.0;
+        self.phi0 = -90.0;
+        self.phi1 = 90.0;
+    }
+}
+
+/// Spherical bounding box `[[west, south], [east, north]]` in degrees
+///
+/// Boxes crossing the antimeridian have `west > east`. Empty objects give
+/// NaN bounds.
+pub fn geo_bounds<O: GeoStreamable + ?Sized>(object: &O) -> [[f64; 2]; 2] {
+    let mut stream = BoundsStream::new();
+    object.stream(&mut stream);
+    stream.result()
+}
@@ -0,0 +1,179 @@
+//! d3-geo: spherical centroid
+
+use super::math::{Adder, DEGREES, EPSILON, EPSILON2, RADIANS, asin};
+use super::{GeoStream, GeoStreamable};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Point,
+    LineFirst,
+    Line,
+    RingFirst,
+    Ring,
+}
+
+/// Accumulates point, line-length and area weighted centroids; see
+/// J. E. Brock, "The Inertia Tensor for a Spherical Triangle" (1975)
+struct CentroidStream {
+    state: State,
+    in_polygon: bool,
+    // Point centroid
+    w0: f64,
+    x0: f64,
+    y0: f64,
+    z0: f64,
+    // Line centroid
+    w1: f64,
+    x1: f64,
+    y1: f64,
+    z1: f64,
+    // Area centroid
+    x2: Adder,
+    y2: Adder,
+    z2: Adder,
+    first: [f64; 2],
+    // Previous point, in Cartesian coordinates
+    p0: [f64; 3],
+}
+
+fn cartesian_degrees(lambda: f64, phi: f64) -> [f64; 3] {
+    let (lambda, phi) = (lambda * RADIANS, phi * RADIANS);
+    let cos_phi = phi.cos();
+    [cos_phi * lambda.cos(), cos_phi * lambda.sin(), phi.sin()]
+}
+
+impl CentroidStream {
+    fn new() -> Self {
+        Self {
+            state: State::Point,
+            in_polygon: false,
+            w0: 0.0,
+            x0: 0.0,
+            y0: 0.0,
+            z0: 0.0,
+            w1: 0.0,
+            x1: 0.0,
+            y1: 0.0,
+            z1: 0.0,
+            x2: Adder::default(),
+            y2: Adder::default(),
+            z2: Adder::default(),
+            first: [0.0; 2],
+            p0: [0.0; 3],
+        }
+    }
+
+    fn point_cartesian(&mut self, [x, y, z]: [f64; 3]) {
+        self.w0 += 1.0;
+        self.x0 += (x - self.x0) / self.w0;
+        self.y0 += (y - self.y0) / self.w0;
+        self.z0 += (z - self.z0) / self.w0;
+    }
+
+    fn add_segment(&mut self, w: f64, p: [f64; 3]) {
+        let [x0, y0, z0] = self.p0;
+        self.w1 += w;
+        self.x1 += w * (x0 + p[0]);
+        self.y1 += w * (y0 + p[1]);
+        self.z1 += w * (z0 + p[2]);
+        self.p0 = p;
+        self.point_cartesian(p);
+    }
+
+    fn line_point(&mut self, lambda: f64, phi: f64) {
+        let p = cartesian_degrees(lambda, phi);
+        let [x0, y0, z0] = self.p0;
+        let [x, y, z] = p;
+        let cx = y0 * z - z0 * y;
+        let cy = z0 * x - x0 * z;
+        let cz = x0 * y - y0 * x;
+        let w = (cx * cx + cy * cy + cz * cz)
+            .sqrt()
+            .atan2(x0 * x + y0 * y + z0 * z);
+        self.add_segment(w, p);
+    }
+
+    fn ring_point(&mut self, lambda: f64, phi: f64) {
+        let p = cartesian_degrees(lambda, phi);
+        let [x0, y0, z0] = self.p0;
+        let [x, y, z] = p;
+        let cx = y0 * z - z0 * y;
+        let cy = z0 * x - x0 * z;
+        let cz = x0 * y - y0 * x;
+        let m = (cx * cx + cy * cy + cz * cz).sqrt();
+        let w = asin(m);
+        let v = if m == 0.0 { 0.0 } else { -w / m };
+        self.x2.add(v * cx);
+        self.y2.add(v * cy);
+        self.z2.add(v * cz);
+        self.add_segment(w, p);
+    }
+
+    fn result(&self) -> [f64; 2] {
+        let (mut x, mut y, mut z) = (self.x2.value(), self.y2.value(), self.z2.value());
+        let mut m = (x * x + y * y + z * z).sqrt();
+        // Fall back to the length-weighted, then the point-weighted centroid
+        if m < EPSILON2 {
+            (x, y, z) = (self.x1, self.y1, self.z1);
+            if self.w1 < EPSILON {
+                (x, y, z) = (self.x0, self.y0, self.z0);
+            }
+            m = (x * x + y * y + z * z).sqrt();
+            if m < EPSILON2 {
+                return [f64::NAN, f64::NAN];
+            }
+        }
+        [y.atan2(x) * DEGREES, asin(z / m) * DEGREES]
+    }
+}
+
+impl GeoStream for CentroidStream {
+    fn point(&mut self, lambda: f64, phi: f64) {
+        match self.state {
+            State::Point => self.point_cartesian(cartesian_degrees(lambda, phi)),
+            State::LineFirst | State::RingFirst => {
+                if self.state == State::RingFirst {
+                    self.first = [lambda, phi];
+                    self.state = State::Ring;
+                } else {
+                    self.state = State::Line;
+                }
+                self.p0 = cartesian_degrees(lambda, phi);
+                self.point_cartesian(self.p0);
+            }
+            State::Line => self.line_point(lambda, phi),
+            State::Ring => self.ring_point(lambda, phi),
+        }
+    }
+    fn line_start(&mut self) {
+        self.state = if self.in_polygon {
+            State::RingFirst
+        } else {
+            State::LineFirst
+        };
+    }
+    fn line_end(&mut self) {
+        if self.state == State::Ring {
+            let [lambda, phi] = self.first;
+            self.ring_point(lambda, phi);
+        }
+        self.state = State::Point;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+    }
+}
+
+/// Spherical centroid `[longitude, latitude]` in degrees
+///
+/// Polygons are weighted by area, lines by length and points by count, using
+/// the highest-dimensional geometry present. Undefined centroids (such as
+/// that of the sphere or of two antipodal points) are NaN.
+pub fn geo_centroid<O: GeoStreamable + ?Sized>(object: &O) -> [f64; 2] {
+    let mut stream = CentroidStream::new();
+    object.stream(&mut stream);
+    stream.result()
+}
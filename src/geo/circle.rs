@@ -0,0 +1,44 @@
+//! d3-geo: streaming small circles
+
+use super::GeoStream;
+use super::math::{EPSILON, TAU, acos, cartesian, cartesian_normalize, spherical};
+
+/// Stream the points of a circle of `radius` radians around the origin
+/// every `delta` radians, from `t0` to `t1` (both in radians) or the full
+/// circle if they are `None`
+pub(crate) fn circle_stream(
+    stream: &mut dyn GeoStream,
+    radius: f64,
+    delta: f64,
+    direction: f64,
+    t0: Option<[f64; 2]>,
+    t1: Option<[f64; 2]>,
+) {
+    if delta == 0.0 {
+        return;
+    }
+    let (sin_radius, cos_radius) = radius.sin_cos();
+    let step = direction * delta;
+    let (mut t0, t1) = match (t0, t1) {
+        (Some(t0), Some(t1)) => (circle_radius(cos_radius, t0), circle_radius(cos_radius, t1)),
+        _ => (radius + direction * TAU, radius - step / 2.0),
+    };
+    if direction > 0.0 && t0 < t1 || direction < 0.0 && t0 > t1 {
+        t0 += direction * TAU;
+    }
+    let mut t = t0;
+    while if direction > 0.0 { t > t1 } else { t < t1 } {
+        let [x, y] = spherical([cos_radius, -sin_radius * t.cos(), -sin_radius * t.sin()]);
+        stream.point(x, y);
+        t -= step;
+    }
+}
+
+/// Angle of `point` around a circle whose radius has cosine `cos_radius`
+fn circle_radius(cos_radius: f64, point: [f64; 2]) -> f64 {
+    let mut p = cartesian(point);
+    p[0] -= cos_radius;
+    let p = cartesian_normalize(p);
+    let radius = acos(-p[1]);
+    (if -p[2] < 0.0 { -radius } else { radius } + TAU - EPSILON) % TAU
+}
@@ -0,0 +1,135 @@
+//! Clipping at the antimeridian, cutting lines that cross ±180°
+
+use super::{ClipLine, Clipper, LineSink};
+use crate::geo::GeoStream;
+use crate::geo::math::{EPSILON, HALF_PI, PI};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ClipAntimeridian;
+
+pub(crate) struct AntimeridianLine {
+    lambda0: f64,
+    phi0: f64,
+    sign0: f64,
+    clean: u8,
+}
+
+fn intersect(lambda0: f64, phi0: f64, lambda1: f64, phi1: f64) -> f64 {
+    let sin_lambda0_lambda1 = (lambda0 - lambda1).sin();
+    if sin_lambda0_lambda1.abs() > EPSILON {
+        let (cos_phi0, cos_phi1) = (phi0.cos(), phi1.cos());
+        ((phi0.sin() * cos_phi1 * lambda1.sin() - phi1.sin() * cos_phi0 * lambda0.sin())
+            / (cos_phi0 * cos_phi1 * sin_lambda0_lambda1))
+            .atan()
+    } else {
+        (phi0 + phi1) / 2.0
+    }
+}
+
+impl ClipLine for AntimeridianLine {
+    fn line_start(&mut self, sink: &mut dyn LineSink) {
+        sink.line_start();
+        self.clean = 1;
+    }
+
+    fn point(&mut self, mut lambda1: f64, phi1: f64, sink: &mut dyn LineSink) {
+        let sign1 = if lambda1 > 0.0 { PI } else { -PI };
+        let delta = (lambda1 - self.lambda0).abs();
+        if (delta - PI).abs() < EPSILON {
+            // The line crosses a pole
+            self.phi0 = if (self.phi0 + phi1) / 2.0 > 0.0 {
+                HALF_PI
+            } else {
+                -HALF_PI
+            };
+            sink.point(self.lambda0, self.phi0, 0);
+            sink.point(self.sign0, self.phi0, 0);
+            sink.line_end();
+            sink.line_start();
+            sink.point(sign1, self.phi0, 0);
+            sink.point(lambda1, self.phi0, 0);
+            self.clean = 0;
+        } else if self.sign0 != sign1 && delta >= PI {
+            // The line crosses the antimeridian; nudge points lying on it
+            if (self.lambda0 - self.sign0).abs() < EPSILON {
+                self.lambda0 -= self.sign0 * EPSILON;
+            }
+            if (lambda1 - sign1).abs() < EPSILON {
+                lambda1 -= sign1 * EPSILON;
+            }
+            self.phi0 = intersect(self.lambda0, self.phi0, lambda1, phi1);
+            sink.point(self.sign0, self.phi0, 0);
+            sink.line_end();
+            sink.line_start();
+            sink.point(sign1, self.phi0, 0);
+            self.clean = 0;
+        }
+        self.lambda0 = lambda1;
+        self.phi0 = phi1;
+        sink.point(lambda1, phi1, 0);
+        self.sign0 = sign1;
+    }
+
+    fn line_end(&mut self, sink: &mut dyn LineSink) {
+        sink.line_end();
+        self.lambda0 = f64::NAN;
+        self.phi0 = f64::NAN;
+    }
+
+    // With intersections, the first and last segments must be rejoined
+    fn clean(&self) -> u8 {
+        2 - self.clean
+    }
+}
+
+impl Clipper for ClipAntimeridian {
+    type Line = AntimeridianLine;
+
+    fn visible(&self, _lambda: f64, _phi: f64) -> bool {
+        true
+    }
+
+    fn line(&self) -> AntimeridianLine {
+        AntimeridianLine {
+            lambda0: f64::NAN,
+            phi0: f64::NAN,
+            sign0: f64::NAN,
+            clean: 0,
+        }
+    }
+
+    fn interpolate(
+        &self,
+        from: Option<[f64; 2]>,
+        to: Option<[f64; 2]>,
+        direction: f64,
+        stream: &mut dyn GeoStream,
+    ) {
+        match (from, to) {
+            (Some(from), Some(to)) if (from[0] - to[0]).abs() > EPSILON => {
+                let lambda = if from[0] < to[0] { PI } else { -PI };
+                let phi = direction * lambda / 2.0;
+                stream.point(-lambda, phi);
+                stream.point(0.0, phi);
+                stream.point(lambda, phi);
+            }
+            (Some(_), Some(to)) => stream.point(to[0], to[1]),
+            _ => {
+                let phi = direction * HALF_PI;
+                stream.point(-PI, phi);
+                stream.point(0.0, phi);
+                stream.point(PI, phi);
+                stream.point(PI, 0.0);
+                stream.point(PI, -phi);
+                stream.point(0.0, -phi);
+                stream.point(-PI, -phi);
+                stream.point(-PI, 0.0);
+                stream.point(-PI, phi);
+            }
+        }
+    }
+
+    fn start(&self) -> [f64; 2] {
+        [-PI, -HALF_PI]
+    }
+}
@@ -0,0 +1,277 @@
+//! Clipping to a small circle around the projection center
+
+use super::{ClipLine, Clipper, LineSink, point_equal};
+use crate::geo::GeoStream;
+use crate::geo::circle::circle_stream;
+use crate::geo::math::{
+    EPSILON, PI, RADIANS, cartesian, cartesian_add, cartesian_cross, cartesian_dot,
+    cartesian_scale, spherical,
+};
+
+/// Clip region of all points within `radius` radians of the origin
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClipCircle {
+    radius: f64,
+    cr: f64,
+    small_radius: bool,
+    not_hemisphere: bool,
+}
+
+impl ClipCircle {
+    pub fn new(radius: f64) -> Self {
+        let cr = radius.cos();
+        Self {
+            radius,
+            cr,
+            small_radius: cr > 0.0,
+            not_hemisphere: cr.abs() > EPSILON,
+        }
+    }
+
+    fn is_visible(&self, lambda: f64, phi: f64) -> bool {
+        lambda.cos() * phi.cos() > self.cr
+    }
+
+    // Which sides of the clip circle's bounding box the point lies beyond
+    fn code(&self, lambda: f64, phi: f64) -> u8 {
+        let r = if self.small_radius {
+            self.radius
+        } else {
+            PI - self.radius
+        };
+        let mut code = 0;
+        if lambda < -r {
+            code |= 1;
+        } else if lambda > r {
+            code |= 2;
+        }
+        if phi < -r {
+            code |= 4;
+        } else if phi > r {
+            code |= 8;
+        }
+        code
+    }
+
+    /// First intersection of the great arc `a`–`b` with the clip circle
+    fn intersect(&self, a: [f64; 2], b: [f64; 2]) -> Option<[f64; 2]> {
+        self.intersections(a, b, false).map(|(q, _)| q)
+    }
+
+    /// Both intersections, only if the arc actually crosses the circle twice
+    fn intersect_two(&self, a: [f64; 2], b: [f64; 2]) -> Option<([f64; 2], [f64; 2])> {
+        self.intersections(a, b, true)
+            .and_then(|(q0, q1)| q1.map(|q1| (q0, q1)))
+    }
+
+    fn intersections(
+        &self,
+        a: [f64; 2],
+        b: [f64; 2],
+        two: bool,
+    ) -> Option<([f64; 2], Option<[f64; 2]>)> {
+        let pa = cartesian(a);
+        let pb = cartesian(b);
+
+        // The intersection of the plane of the great arc with the plane of
+        // the small circle
+        let n1 = [1.0, 0.0, 0.0];
+        let n2 = cartesian_cross(pa, pb);
+        let n2n2 = cartesian_dot(n2, n2);
+        let n1n2 = n2[0];
+        let determinant = n2n2 - n1n2 * n1n2;
+
+        // Two polar points
+        if determinant == 0.0 {
+            return if two { None } else { Some((a, None)) };
+        }
+
+        let c1 = self.cr * n2n2 / determinant;
+        let c2 = -self.cr * n1n2 / determinant;
+        let n1xn2 = cartesian_cross(n1, n2);
+        let big_a = cartesian_add(cartesian_scale(n1, c1), cartesian_scale(n2, c2));
+
+        // Solve |p(t)|^2 = 1
+        let u = n1xn2;
+        let w = cartesian_dot(big_a, u);
+        let uu = cartesian_dot(u, u);
+        let t2 = w * w - uu * (cartesian_dot(big_a, big_a) - 1.0);
+        if t2 < 0.0 {
+            return None;
+        }
+        let t = t2.sqrt();
+        let q = spherical(cartesian_add(cartesian_scale(u, (-w - t) / uu), big_a));
+        if !two {
+            return Some((q, None));
+        }
+
+        // Is the first intersection on the arc?
+        let (mut lambda0, mut lambda1) = (a[0], b[0]);
+        let (mut phi0, mut phi1) = (a[1], b[1]);
+        if lambda1 < lambda0 {
+            std::mem::swap(&mut lambda0, &mut lambda1);
+        }
+        let delta = lambda1 - lambda0;
+        let polar = (delta - PI).abs() < EPSILON;
+        let meridian = polar || delta < EPSILON;
+        if !polar && phi1 < phi0 {
+            std::mem::swap(&mut phi0, &mut phi1);
+        }
+        let on_arc = if meridian {
+            if polar {
+                let phi = if (q[0] - lambda0).abs() < EPSILON {
+                    phi0
+                } else {
+                    phi1
+                };
+                (phi0 + phi1 > 0.0) ^ (q[1] < phi)
+            } else {
+                phi0 <= q[1] && q[1] <= phi1
+            }
+        } else {
+            (delta > PI) ^ (lambda0 <= q[0] && q[0] <= lambda1)
+        };
+        if on_arc {
+            let q1 = spherical(cartesian_add(cartesian_scale(u, (-w + t) / uu), big_a));
+            Some((q, Some(q1)))
+        } else {
+            Some((q, None))
+        }
+    }
+}
+
+pub(crate) struct CircleLine {
+    clip: ClipCircle,
+    point0: Option<[f64; 2]>,
+    c0: u8,
+    v0: bool,
+    v00: bool,
+    clean: u8,
+}
+
+impl ClipLine for CircleLine {
+    fn line_start(&mut self, _sink: &mut dyn LineSink) {
+        self.v00 = false;
+        self.v0 = false;
+        self.clean = 1;
+    }
+
+    fn point(&mut self, lambda: f64, phi: f64, sink: &mut dyn LineSink) {
+        let clip = self.clip;
+        let point1 = [lambda, phi];
+        let v = clip.is_visible(lambda, phi);
+        let c = if clip.small_radius {
+            if v { 0 } else { clip.code(lambda, phi) }
+        } else if v {
+            clip.code(lambda + if lambda < 0.0 { PI } else { -PI }, phi)
+        } else {
+            0
+        };
+        if self.point0.is_none() {
+            self.v00 = v;
+            self.v0 = v;
+            if v {
+                sink.line_start();
+            }
+        }
+        if v != self.v0 {
+            self.clean = 0;
+            let point0 = self.point0.unwrap_or(point1);
+            let point2 = if v {
+                // Outside going in
+                sink.line_start();
+                let point2 = clip.intersect(point1, point0).unwrap_or(point1);
+                sink.point(point2[0], point2[1], 0);
+                point2
+            } else {
+                // Inside going out
+                let point2 = clip.intersect(point0, point1).unwrap_or(point0);
+                sink.point(point2[0], point2[1], 2);
+                sink.line_end();
+                point2
+            };
+            self.point0 = Some(point2);
+        } else if let Some(point0) = self.point0
+            && clip.not_hemisphere
+            && clip.small_radius ^ v
+        {
+            // A segment whose endpoints are both outside (or both inside, for
+            // a large circle) may still cross the circle twice
+            if c & self.c0 == 0
+                && let Some((t0, t1)) = clip.intersect_two(point1, point0)
+            {
+                self.clean = 0;
+                if clip.small_radius {
+                    sink.line_start();
+                    sink.point(t0[0], t0[1], 0);
+                    sink.point(t1[0], t1[1], 0);
+                    sink.line_end();
+                } else {
+                    sink.point(t1[0], t1[1], 0);
+                    sink.line_end();
+                    sink.line_start();
+                    sink.point(t0[0], t0[1], 3);
+                }
+            }
+        }
+        if v && self
+            .point0
+            .is_none_or(|point0| !point_equal(point0, point1))
+        {
+            sink.point(point1[0], point1[1], 0);
+        }
+        self.point0 = Some(point1);
+        self.v0 = v;
+        self.c0 = c;
+    }
+
+    fn line_end(&mut self, sink: &mut dyn LineSink) {
+        if self.v0 {
+            sink.line_end();
+        }
+        self.point0 = None;
+    }
+
+    // Rejoin the first and last segments if there were intersections and
+    // the first and last points were visible
+    fn clean(&self) -> u8 {
+        self.clean | (((self.v00 && self.v0) as u8) << 1)
+    }
+}
+
+impl Clipper for ClipCircle {
+    type Line = CircleLine;
+
+    fn visible(&self, lambda: f64, phi: f64) -> bool {
+        self.is_visible(lambda, phi)
+    }
+
+    fn line(&self) -> CircleLine {
+        CircleLine {
+            clip: *self,
+            point0: None,
+            c0: 0,
+            v0: false,
+            v00: false,
+            clean: 0,
+        }
+    }
+
+    fn interpolate(
+        &self,
+        from: Option<[f64; 2]>,
+        to: Option<[f64; 2]>,
+        direction: f64,
+        stream: &mut dyn GeoStream,
+    ) {
+        circle_stream(stream, self.radius, 2.0 * RADIANS, direction, from, to);
+    }
+
+    fn start(&self) -> [f64; 2] {
+        if self.small_radius {
+            [0.0, -self.radius]
+        } else {
+            [-PI, self.radius - PI]
+        }
+    }
+}
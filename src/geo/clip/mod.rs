@@ -0,0 +1,338 @@
+//! d3-geo: spherical and planar clipping
+//!
+//! Spherical clipping (at the antimeridian or to a small circle) runs before
+//! projection; rectangular clipping runs on projected coordinates. Both cut
+//! polygon rings into segments at the clip edge and rejoin them along it.
+
+mod antimeridian;
+mod circle;
+mod rectangle;
+mod rejoin;
+
+pub(crate) use antimeridian::ClipAntimeridian;
+pub(crate) use circle::ClipCircle;
+pub(crate) use rectangle::ClipRectangle;
+
+use super::GeoStream;
+use super::math::{
+    Adder, EPSILON, EPSILON2, HALF_PI, PI, QUARTER_PI, TAU, asin, cartesian, cartesian_cross,
+    cartesian_normalize, sign,
+};
+use rejoin::rejoin;
+use std::mem::take;
+
+/// Receiver of clipped lines; the third `point` argument flags points where
+/// a line leaves (2) or re-enters (3) the clip region
+pub(crate) trait LineSink {
+    fn point(&mut self, x: f64, y: f64, flag: u8);
+    fn line_start(&mut self);
+    fn line_end(&mut self);
+}
+
+impl<S: GeoStream> LineSink for S {
+    fn point(&mut self, x: f64, y: f64, _flag: u8) {
+        GeoStream::point(self, x, y);
+    }
+    fn line_start(&mut self) {
+        GeoStream::line_start(self);
+    }
+    fn line_end(&mut self) {
+        GeoStream::line_end(self);
+    }
+}
+
+/// Collects clipped lines as segments of flagged points
+#[derive(Debug, Default)]
+pub(crate) struct Buffer {
+    lines: Vec<Vec<[f64; 3]>>,
+}
+
+impl Buffer {
+    /// Join the last line onto the first, when a ring starts mid-segment
+    pub fn rejoin(&mut self) {
+        if self.lines.len() > 1 {
+            let first = self.lines.remove(0);
+            if let Some(last) = self.lines.last_mut() {
+                last.extend(first);
+            }
+        }
+    }
+
+    pub fn result(&mut self) -> Vec<Vec<[f64; 3]>> {
+        take(&mut self.lines)
+    }
+}
+
+impl LineSink for Buffer {
+    fn point(&mut self, x: f64, y: f64, flag: u8) {
+        if let Some(line) = self.lines.last_mut() {
+            line.push([x, y, flag as f64]);
+        }
+    }
+    fn line_start(&mut self) {
+        self.lines.push(Vec::new());
+    }
+    fn line_end(&mut self) {}
+}
+
+/// Line clipping state of a spherical clip
+pub(crate) trait ClipLine {
+    fn line_start(&mut self, sink: &mut dyn LineSink);
+    fn point(&mut self, lambda: f64, phi: f64, sink: &mut dyn LineSink);
+    fn line_end(&mut self, sink: &mut dyn LineSink);
+    /// Bit 1: the line was not cut; bit 2: the first and last segments
+    /// should be joined
+    fn clean(&self) -> u8;
+}
+
+/// A spherical clip region
+pub(crate) trait Clipper {
+    type Line: ClipLine;
+    fn visible(&self, lambda: f64, phi: f64) -> bool;
+    fn line(&self) -> Self::Line;
+    /// Stream the clip edge from `from` to `to`, or all of it if `None`
+    fn interpolate(
+        &self,
+        from: Option<[f64; 2]>,
+        to: Option<[f64; 2]>,
+        direction: f64,
+        stream: &mut dyn GeoStream,
+    );
+    /// A point known to lie on the clip edge, used for containment tests
+    fn start(&self) -> [f64; 2];
+}
+
+/// Stream adapter clipping spherical geometry (in radians) to a [`Clipper`]
+pub(crate) struct Clip<C: Clipper, S: GeoStream> {
+    clipper: C,
+    line: C::Line,
+    ring_line: C::Line,
+    ring_buffer: Buffer,
+    sink: S,
+    in_line: bool,
+    in_polygon: bool,
+    polygon_started: bool,
+    polygon: Vec<Vec<[f64; 2]>>,
+    segments: Vec<Vec<[f64; 3]>>,
+    ring: Vec<[f64; 2]>,
+}
+
+impl<C: Clipper, S: GeoStream> Clip<C, S> {
+    pub fn new(clipper: C, sink: S) -> Self {
+        Self {
+            line: clipper.line(),
+            ring_line: clipper.line(),
+            clipper,
+            ring_buffer: Buffer::default(),
+            sink,
+            in_line: false,
+            in_polygon: false,
+            polygon_started: false,
+            polygon: Vec::new(),
+            segments: Vec::new(),
+            ring: Vec::new(),
+        }
+    }
+
+    fn start_polygon(&mut self) {
+        if !self.polygon_started {
+            self.sink.polygon_start();
+            self.polygon_started = true;
+        }
+    }
+
+    fn point_ring(&mut self, lambda: f64, phi: f64) {
+        self.ring.push([lambda, phi]);
+        self.ring_line.point(lambda, phi, &mut self.ring_buffer);
+    }
+
+    fn ring_end(&mut self) {
+        if let Some(&[lambda, phi]) = self.ring.first() {
+            self.point_ring(lambda, phi);
+        }
+        self.ring_line.line_end(&mut self.ring_buffer);
+        let clean = self.ring_line.clean();
+        let mut ring_segments = self.ring_buffer.result();
+        let mut ring = take(&mut self.ring);
+        ring.pop();
+        self.polygon.push(ring);
+        if ring_segments.is_empty() {
+            return;
+        }
+        // No intersections
+        if clean & 1 != 0 {
+            let segment = &ring_segments[0];
+            if segment.len() > 1 {
+                self.start_polygon();
+                self.sink.line_start();
+                for p in &segment[..segment.len() - 1] {
+                    self.sink.point(p[0], p[1]);
+                }
+                self.sink.line_end();
+            }
+            return;
+        }
+        // Rejoin connected segments
+        if ring_segments.len() > 1 && clean & 2 != 0 {
+            let first = ring_segments.remove(0);
+            if let Some(last) = ring_segments.last_mut() {
+                last.extend(first);
+            }
+        }
+        self.segments
+            .extend(ring_segments.into_iter().filter(|s| s.len() > 1));
+    }
+}
+
+impl<C: Clipper, S: GeoStream> GeoStream for Clip<C, S> {
+    fn point(&mut self, lambda: f64, phi: f64) {
+        if self.in_polygon {
+            self.point_ring(lambda, phi);
+        } else if self.in_line {
+            self.line.point(lambda, phi, &mut self.sink);
+        } else if self.clipper.visible(lambda, phi) {
+            self.sink.point(lambda, phi);
+        }
+    }
+    fn line_start(&mut self) {
+        if self.in_polygon {
+            self.ring_line.line_start(&mut self.ring_buffer);
+            self.ring.clear();
+        } else {
+            self.in_line = true;
+            self.line.line_start(&mut self.sink);
+        }
+    }
+    fn line_end(&mut self) {
+        if self.in_polygon {
+            self.ring_end();
+        } else {
+            self.in_line = false;
+            self.line.line_end(&mut self.sink);
+        }
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+        self.segments.clear();
+        self.polygon.clear();
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+        let segments = take(&mut self.segments);
+        let start_inside = polygon_contains(&self.polygon, self.clipper.start());
+        if !segments.is_empty() {
+            self.start_polygon();
+            let clipper = &self.clipper;
+            rejoin(
+                segments,
+                &compare_intersection,
+                start_inside,
+                &mut |from, to, direction, stream| clipper.interpolate(from, to, direction, stream),
+                &mut self.sink,
+            );
+        } else if start_inside {
+            self.start_polygon();
+            self.sink.line_start();
+            self.clipper.interpolate(None, None, 1.0, &mut self.sink);
+            self.sink.line_end();
+        }
+        if self.polygon_started {
+            self.sink.polygon_end();
+            self.polygon_started = false;
+        }
+        self.polygon.clear();
+    }
+    fn sphere(&mut self) {
+        self.sink.polygon_start();
+        self.sink.line_start();
+        self.clipper.interpolate(None, None, 1.0, &mut self.sink);
+        self.sink.line_end();
+        self.sink.polygon_end();
+    }
+}
+
+// Intersections are sorted along the clip edge, starting from its start point
+fn compare_intersection(a: &[f64; 2], b: &[f64; 2]) -> f64 {
+    let key = |p: &[f64; 2]| {
+        if p[0] < 0.0 {
+            p[1] - HALF_PI - EPSILON
+        } else {
+            HALF_PI - p[1]
+        }
+    };
+    key(a) - key(b)
+}
+
+pub(crate) fn point_equal(a: [f64; 2], b: [f64; 2]) -> bool {
+    (a[0] - b[0]).abs() < EPSILON && (a[1] - b[1]).abs() < EPSILON
+}
+
+fn longitude(point: [f64; 2]) -> f64 {
+    if point[0].abs() <= PI {
+        point[0]
+    } else {
+        sign(point[0]) * ((point[0].abs() + PI) % TAU - PI)
+    }
+}
+
+/// Whether the spherical `polygon` (rings in radians, without closing points)
+/// contains `point`
+pub(crate) fn polygon_contains(polygon: &[Vec<[f64; 2]>], point: [f64; 2]) -> bool {
+    let lambda = longitude(point);
+    let mut phi = point[1];
+    let sin_phi = phi.sin();
+    let normal = [lambda.sin(), -lambda.cos(), 0.0];
+    let mut angle = 0.0;
+    let mut winding = 0i32;
+    let mut sum = Adder::default();
+
+    if sin_phi == 1.0 {
+        phi = HALF_PI + EPSILON;
+    } else if sin_phi == -1.0 {
+        phi = -HALF_PI - EPSILON;
+    }
+
+    for ring in polygon {
+        let Some(&last) = ring.last() else {
+            continue;
+        };
+        let mut point0 = last;
+        let mut lambda0 = longitude(point0);
+        let (mut sin_phi0, mut cos_phi0) = (point0[1] / 2.0 + QUARTER_PI).sin_cos();
+        for &point1 in ring {
+            let lambda1 = longitude(point1);
+            let (sin_phi1, cos_phi1) = (point1[1] / 2.0 + QUARTER_PI).sin_cos();
+            let delta = lambda1 - lambda0;
+            let sign = if delta >= 0.0 { 1.0 } else { -1.0 };
+            let abs_delta = sign * delta;
+            let antimeridian = abs_delta > PI;
+            let k = sin_phi0 * sin_phi1;
+            sum.add((k * sign * abs_delta.sin()).atan2(cos_phi0 * cos_phi1 + k * abs_delta.cos()));
+            angle += if antimeridian {
+                delta + sign * TAU
+            } else {
+                delta
+            };
+
+            // Do the longitudes straddle the point's meridian, with the arc
+            // passing below the point?
+            if antimeridian ^ (lambda0 >= lambda) ^ (lambda1 >= lambda) {
+                let arc =
+                    cartesian_normalize(cartesian_cross(cartesian(point0), cartesian(point1)));
+                let intersection = cartesian_normalize(cartesian_cross(normal, arc));
+                let forward = antimeridian ^ (delta >= 0.0);
+                let phi_arc = if forward { -1.0 } else { 1.0 } * asin(intersection[2]);
+                if phi > phi_arc || phi == phi_arc && (arc[0] != 0.0 || arc[1] != 0.0) {
+                    winding += if forward { 1 } else { -1 };
+                }
+            }
+
+            lambda0 = lambda1;
+            sin_phi0 = sin_phi1;
+            cos_phi0 = cos_phi1;
+            point0 = point1;
+        }
+    }
+
+    (angle < -EPSILON || angle < EPSILON && sum.value() < -EPSILON2) ^ (winding & 1 != 0)
+}
@@ -0,0 +1,315 @@
+//! Clipping of projected geometry to an axis-aligned rectangle
+
+use super::rejoin::rejoin;
+use super::{Buffer, LineSink};
+use crate::geo::GeoStream;
+use crate::geo::math::EPSILON;
+use std::mem::take;
+
+// Coordinates are clamped before line clipping to avoid overflow
+const CLIP_MAX: f64 = 1e9;
+const CLIP_MIN: f64 = -CLIP_MAX;
+
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+}
+
+impl Rect {
+    fn visible(&self, x: f64, y: f64) -> bool {
+        self.x0 <= x && x <= self.x1 && self.y0 <= y && y <= self.y1
+    }
+
+    // Edge of the rectangle a point lies on, numbered clockwise from the left
+    fn corner(&self, p: [f64; 2], direction: f64) -> i32 {
+        let forward = direction > 0.0;
+        if (p[0] - self.x0).abs() < EPSILON {
+            if forward { 0 } else { 3 }
+        } else if (p[0] - self.x1).abs() < EPSILON {
+            if forward { 2 } else { 1 }
+        } else if (p[1] - self.y0).abs() < EPSILON {
+            if forward { 1 } else { 0 }
+        } else if forward {
+            3
+        } else {
+            2
+        }
+    }
+
+    fn compare_point(&self, a: &[f64; 2], b: &[f64; 2]) -> f64 {
+        let ca = self.corner(*a, 1.0);
+        let cb = self.corner(*b, 1.0);
+        if ca != cb {
+            (ca - cb) as f64
+        } else {
+            match ca {
+                0 => b[1] - a[1],
+                1 => a[0] - b[0],
+                2 => a[1] - b[1],
+                _ => b[0] - a[0],
+            }
+        }
+    }
+
+    fn interpolate(
+        &self,
+        from: Option<[f64; 2]>,
+        to: Option<[f64; 2]>,
+        direction: f64,
+        stream: &mut dyn GeoStream,
+    ) {
+        let (mut a, a1) = match (from, to) {
+            (Some(from), Some(to)) => (self.corner(from, direction), self.corner(to, direction)),
+            _ => (0, 0),
+        };
+        let around = match (from, to) {
+            (Some(from), Some(to)) => {
+                a != a1 || (self.compare_point(&from, &to) < 0.0) ^ (direction > 0.0)
+            }
+            _ => true,
+        };
+        if around {
+            let step = if direction > 0.0 { 1 } else { -1 };
+            loop {
+                stream.point(
+                    if a == 0 || a == 3 { self.x0 } else { self.x1 },
+                    if a > 1 { self.y1 } else { self.y0 },
+                );
+                a = (a + step + 4) % 4;
+                if a == a1 {
+                    break;
+                }
+            }
+        } else if let Some(to) = to {
+            stream.point(to[0], to[1]);
+        }
+    }
+
+    // Winding number of the polygon around the rectangle's top-left corner
+    fn polygon_inside(&self, polygon: &[Vec<[f64; 2]>]) -> i32 {
+        let mut winding = 0;
+        for ring in polygon {
+            for pair in ring.windows(2) {
+                let [a0, a1] = pair[0];
+                let [b0, b1] = pair[1];
+                if a1 <= self.y1 {
+                    if b1 > self.y1 && (b0 - a0) * (self.y1 - a1) > (b1 - a1) * (self.x0 - a0) {
+                        winding += 1;
+                    }
+                } else if b1 <= self.y1 && (b0 - a0) * (self.y1 - a1) < (b1 - a1) * (self.x0 - a0) {
+                    winding -= 1;
+                }
+            }
+        }
+        winding
+    }
+}
+
+/// Liang–Barsky clipping of the segment `a`–`b`, moving its endpoints onto
+/// the rectangle; returns false if the segment lies outside
+pub(crate) fn clip_line(a: &mut [f64; 2], b: &mut [f64; 2], extent: [[f64; 2]; 2]) -> bool {
+    let [[x0, y0], [x1, y1]] = extent;
+    let [ax, ay] = *a;
+    let dx = b[0] - ax;
+    let dy = b[1] - ay;
+    let mut t0: f64 = 0.0;
+    let mut t1: f64 = 1.0;
+    // Clip against the edge where p·t >= q
+    let mut edge = |p: f64, q: f64| {
+        if p == 0.0 {
+            return q <= 0.0;
+        }
+        let r = q / p;
+        if p < 0.0 {
+            if r < t0 {
+                return false;
+            }
+            t1 = t1.min(r);
+        } else {
+            if r > t1 {
+                return false;
+            }
+            t0 = t0.max(r);
+        }
+        true
+    };
+    if !(edge(dx, x0 - ax) && edge(-dx, ax - x1) && edge(dy, y0 - ay) && edge(-dy, ay - y1)) {
+        return false;
+    }
+    if t0 > 0.0 {
+        *a = [ax + t0 * dx, ay + t0 * dy];
+    }
+    if t1 < 1.0 {
+        *b = [ax + t1 * dx, ay + t1 * dy];
+    }
+    true
+}
+
+/// Stream adapter clipping planar geometry to `[[x0, y0], [x1, y1]]`
+pub(crate) struct ClipRectangle<S: GeoStream> {
+    rect: Rect,
+    sink: S,
+    buffer: Buffer,
+    in_polygon: bool,
+    in_line: bool,
+    segments: Vec<Vec<[f64; 3]>>,
+    polygon: Vec<Vec<[f64; 2]>>,
+    clean: bool,
+    first: bool,
+    first_point: [f64; 2],
+    first_visible: bool,
+    prev: [f64; 2],
+    prev_visible: bool,
+}
+
+impl<S: GeoStream> ClipRectangle<S> {
+    pub fn new(extent: [[f64; 2]; 2], sink: S) -> Self {
+        let [[x0, y0], [x1, y1]] = extent;
+        Self {
+            rect: Rect { x0, y0, x1, y1 },
+            sink,
+            buffer: Buffer::default(),
+            in_polygon: false,
+            in_line: false,
+            segments: Vec::new(),
+            polygon: Vec::new(),
+            clean: true,
+            first: true,
+            first_point: [f64::NAN; 2],
+            first_visible: false,
+            prev: [f64::NAN; 2],
+            prev_visible: false,
+        }
+    }
+
+    // Geometry within a polygon is buffered, then clipped en masse
+    fn active(&mut self) -> &mut dyn LineSink {
+        if self.in_polygon {
+            &mut self.buffer
+        } else {
+            &mut self.sink
+        }
+    }
+
+    fn line_point(&mut self, x: f64, y: f64) {
+        let v = self.rect.visible(x, y);
+        if self.in_polygon
+            && let Some(ring) = self.polygon.last_mut()
+        {
+            ring.push([x, y]);
+        }
+        let (mut x, mut y) = (x, y);
+        if self.first {
+            self.first_point = [x, y];
+            self.first_visible = v;
+            self.first = false;
+            if v {
+                let active = self.active();
+                active.line_start();
+                active.point(x, y, 0);
+            }
+        } else if v && self.prev_visible {
+            self.active().point(x, y, 0);
+        } else {
+            let clamp = |c: f64| c.clamp(CLIP_MIN, CLIP_MAX);
+            let mut a = [clamp(self.prev[0]), clamp(self.prev[1])];
+            let mut b = [clamp(x), clamp(y)];
+            (x, y) = (b[0], b[1]);
+            let rect = self.rect;
+            let extent = [[rect.x0, rect.y0], [rect.x1, rect.y1]];
+            if clip_line(&mut a, &mut b, extent) {
+                let prev_visible = self.prev_visible;
+                let active = self.active();
+                if !prev_visible {
+                    active.line_start();
+                    active.point(a[0], a[1], 0);
+                }
+                active.point(b[0], b[1], 0);
+                if !v {
+                    active.line_end();
+                }
+                self.clean = false;
+            } else if v {
+                let active = self.active();
+                active.line_start();
+                active.point(x, y, 0);
+                self.clean = false;
+            }
+        }
+        self.prev = [x, y];
+        self.prev_visible = v;
+    }
+}
+
+impl<S: GeoStream> GeoStream for ClipRectangle<S> {
+    fn point(&mut self, x: f64, y: f64) {
+        if self.in_line {
+            self.line_point(x, y);
+        } else if self.rect.visible(x, y) {
+            self.active().point(x, y, 0);
+        }
+    }
+    fn line_start(&mut self) {
+        self.in_line = true;
+        if self.in_polygon {
+            self.polygon.push(Vec::new());
+        }
+        self.first = true;
+        self.prev_visible = false;
+        self.prev = [f64::NAN; 2];
+    }
+    fn line_end(&mut self) {
+        if self.in_polygon {
+            let [x, y] = self.first_point;
+            self.line_point(x, y);
+            if self.first_visible && self.prev_visible {
+                self.buffer.rejoin();
+            }
+            self.segments.extend(self.buffer.result());
+        }
+        self.in_line = false;
+        if self.prev_visible {
+            self.active().line_end();
+        }
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+        self.segments.clear();
+        self.polygon.clear();
+        self.clean = true;
+    }
+    fn polygon_end(&mut self) {
+        let start_inside = self.rect.polygon_inside(&self.polygon) != 0;
+        let clean_inside = self.clean && start_inside;
+        let segments = take(&mut self.segments);
+        let rect = self.rect;
+        if clean_inside || !segments.is_empty() {
+            self.sink.polygon_start();
+            if clean_inside {
+                self.sink.line_start();
+                rect.interpolate(None, None, 1.0, &mut self.sink);
+                self.sink.line_end();
+            }
+            if !segments.is_empty() {
+                rejoin(
+                    segments,
+                    &|a, b| rect.compare_point(a, b),
+                    start_inside,
+                    &mut |from, to, direction, stream| {
+                        rect.interpolate(from, to, direction, stream)
+                    },
+                    &mut self.sink,
+                );
+            }
+            self.sink.polygon_end();
+        }
+        self.in_polygon = false;
+        self.polygon.clear();
+    }
+    fn sphere(&mut self) {
+        self.sink.sphere();
+    }
+}
@@ -0,0 +1,165 @@
+//! Rejoining of clipped polygon segments along the clip edge
+
+use super::point_equal;
+use crate::geo::GeoStream;
+use crate::geo::math::EPSILON;
+
+pub(crate) type Interpolate<'a> =
+    dyn FnMut(Option<[f64; 2]>, Option<[f64; 2]>, f64, &mut dyn GeoStream) + 'a;
+
+// A segment endpoint, linked both along the subject (the clipped segments)
+// and along the clip edge; links are indices into one arena
+struct Intersection {
+    x: [f64; 2],
+    // Segment starting or ending here, for subject intersections
+    z: Option<usize>,
+    // The twin intersection in the other list
+    o: usize,
+    // Entry (true) or exit
+    e: bool,
+    // Visited
+    v: bool,
+    n: usize,
+    p: usize,
+}
+
+fn link(arena: &mut [Intersection], list: &[usize]) {
+    let n = list.len();
+    for i in 0..n {
+        let (a, b) = (list[i], list[(i + 1) % n]);
+        arena[a].n = b;
+        arena[b].p = a;
+    }
+}
+
+/// Stream the polygons formed by `segments` and the stretches of clip edge
+/// between them
+///
+/// `compare` orders intersection points along the clip edge and
+/// `start_inside` tells whether the start of the clip edge is inside the
+/// polygon.
+pub(crate) fn rejoin(
+    mut segments: Vec<Vec<[f64; 3]>>,
+    compare: &dyn Fn(&[f64; 2], &[f64; 2]) -> f64,
+    mut start_inside: bool,
+    interpolate: &mut Interpolate<'_>,
+    stream: &mut dyn GeoStream,
+) {
+    let mut arena: Vec<Intersection> = Vec::new();
+    let mut subject = Vec::new();
+    let mut clip = Vec::new();
+
+    let mut push = |arena: &mut Vec<Intersection>, x: [f64; 2], z: usize, entry: bool| {
+        let i = arena.len();
+        arena.push(Intersection {
+            x,
+            z: Some(z),
+            o: i + 1,
+            e: entry,
+            v: false,
+            n: 0,
+            p: 0,
+        });
+        arena.push(Intersection {
+            x,
+            z: None,
+            o: i,
+            e: !entry,
+            v: false,
+            n: 0,
+            p: 0,
+        });
+        subject.push(i);
+        clip.push(i + 1);
+    };
+
+    for (index, segment) in segments.iter_mut().enumerate() {
+        let n = segment.len();
+        if n < 2 {
+            continue;
+        }
+        let p0 = segment[0];
+        let p1 = &mut segment[n - 1];
+        if point_equal([p0[0], p0[1]], [p1[0], p1[1]]) {
+            if p0[2] == 0.0 && p1[2] == 0.0 {
+                stream.line_start();
+                for p in &segment[..n - 1] {
+                    stream.point(p[0], p[1]);
+                }
+                stream.line_end();
+                continue;
+            }
+            // Handle degenerate cases by moving the point
+            p1[0] += 2.0 * EPSILON;
+        }
+        let p1 = segment[n - 1];
+        push(&mut arena, [p0[0], p0[1]], index, true);
+        push(&mut arena, [p1[0], p1[1]], index, false);
+    }
+
+    if subject.is_empty() {
+        return;
+    }
+
+    clip.sort_by(|&a, &b| {
+        compare(&arena[a].x, &arena[b].x)
+            .partial_cmp(&0.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    link(&mut arena, &subject);
+    link(&mut arena, &clip);
+    for &i in &clip {
+        start_inside = !start_inside;
+        arena[i].e = start_inside;
+    }
+
+    let start = subject[0];
+    loop {
+        // Find the first unvisited intersection
+        let mut current = start;
+        while arena[current].v {
+            current = arena[current].n;
+            if current == start {
+                return;
+            }
+        }
+        let mut is_subject = true;
+        stream.line_start();
+        loop {
+            let o = arena[current].o;
+            arena[current].v = true;
+            arena[o].v = true;
+            if arena[current].e {
+                let next = arena[current].n;
+                if is_subject {
+                    if let Some(z) = arena[current].z {
+                        for p in &segments[z] {
+                            stream.point(p[0], p[1]);
+                        }
+                    }
+                } else {
+                    interpolate(Some(arena[current].x), Some(arena[next].x), 1.0, stream);
+                }
+                current = next;
+            } else {
+                let prev = arena[current].p;
+                if is_subject {
+                    if let Some(z) = arena[prev].z {
+                        for p in segments[z].iter().rev() {
+                            stream.point(p[0], p[1]);
+                        }
+                    }
+                } else {
+                    interpolate(Some(arena[current].x), Some(arena[prev].x), -1.0, stream);
+                }
+                current = prev;
+            }
+            current = arena[current].o;
+            is_subject = !is_subject;
+            if arena[current].v {
+                break;
+            }
+        }
+        stream.line_end();
+    }
+}
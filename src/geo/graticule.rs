@@ -0,0 +1,170 @@
+//! d3-geo: graticules of meridians and parallels
+
+use super::math::{EPSILON, range};
+use crate::geojson::{Geometry, GeometryValue, Position};
+
+/// Generator for a grid of meridians and parallels (D3 `geoGraticule`)
+///
+/// Major lines span the major extent at the major step, minor lines the
+/// minor extent at the minor step; minor lines that coincide with a major
+/// line are omitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graticule {
+    extent_major: [[f64; 2]; 2],
+    extent_minor: [[f64; 2]; 2],
+    step_major: [f64; 2],
+    step_minor: [f64; 2],
+    precision: f64,
+}
+
+impl Default for Graticule {
+    fn default() -> Self {
+        Self {
+            extent_major: [[-180.0, -90.0 + EPSILON], [180.0, 90.0 - EPSILON]],
+            extent_minor: [[-180.0, -80.0 - EPSILON], [180.0, 80.0 + EPSILON]],
+            step_major: [90.0, 360.0],
+            step_minor: [10.0, 10.0],
+            precision: 2.5,
+        }
+    }
+}
+
+/// Graticule with 10° minor lines between ±80° latitude
+pub fn geo_graticule() -> Graticule {
+    Graticule::default()
+}
+
+/// The default graticule as a single MultiLineString geometry
+pub fn geo_graticule10() -> Geometry {
+    geo_graticule().geometry()
+}
+
+fn positions(points: Vec<[f64; 2]>) -> Vec<Position> {
+    points.into_iter().map(|p| p.to_vec()).collect()
+}
+
+fn normalize([[x0, y0], [x1, y1]]: [[f64; 2]; 2]) -> [[f64; 2]; 2] {
+    [[x0.min(x1), y0.min(y1)], [x0.max(x1), y0.max(y1)]]
+}
+
+// Meridian at longitude x, sampled every `dy` degrees of latitude
+fn meridian(x: f64, y0: f64, y1: f64, dy: f64) -> Vec<[f64; 2]> {
+    let mut ys = range(y0, y1 - EPSILON, dy);
+    ys.push(y1);
+    ys.into_iter().map(|y| [x, y]).collect()
+}
+
+// Parallel at latitude y, sampled every `dx` degrees of longitude
+fn parallel(y: f64, x0: f64, x1: f64, dx: f64) -> Vec<[f64; 2]> {
+    let mut xs = range(x0, x1 - EPSILON, dx);
+    xs.push(x1);
+    xs.into_iter().map(|x| [x, y]).collect()
+}
+
+impl Graticule {
+    /// Set both the major and minor extents
+    pub fn extent(self, extent: [[f64; 2]; 2]) -> Self {
+        self.extent_major(extent).extent_minor(extent)
+    }
+
+    pub fn extent_major(mut self, extent: [[f64; 2]; 2]) -> Self {
+        self.extent_major = normalize(extent);
+        self
+    }
+
+    pub fn extent_minor(mut self, extent: [[f64; 2]; 2]) -> Self {
+        self.extent_minor = normalize(extent);
+        self
+    }
+
+    /// Set both the major and minor `[longitude, latitude]` steps
+    pub fn step(self, step: [f64; 2]) -> Self {
+        self.step_major(step).step_minor(step)
+    }
+
+    pub fn step_major(mut self, step: [f64; 2]) -> Self {
+        self.step_major = step;
+        self
+    }
+
+    pub fn step_minor(mut self, step: [f64; 2]) -> Self {
+        self.step_minor = step;
+        self
+    }
+
+    /// Sampling interval in degrees along parallels
+    pub fn precision(mut self, precision: f64) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn get_extent_major(&self) -> [[f64; 2]; 2] {
+        self.extent_major
+    }
+
+    pub fn get_extent_minor(&self) -> [[f64; 2]; 2] {
+        self.extent_minor
+    }
+
+    pub fn get_step_major(&self) -> [f64; 2] {
+        self.step_major
+    }
+
+    pub fn get_step_minor(&self) -> [f64; 2] {
+        self.step_minor
+    }
+
+    pub fn get_precision(&self) -> f64 {
+        self.precision
+    }
+
+    fn coordinates(&self) -> Vec<Vec<Position>> {
+        let [[big_x0, big_y0], [big_x1, big_y1]] = self.extent_major;
+        let [[x0, y0], [x1, y1]] = self.extent_minor;
+        let [big_dx, big_dy] = self.step_major;
+        let [dx, dy] = self.step_minor;
+        let p = self.precision;
+        let mut lines = Vec::new();
+        for x in range((big_x0 / big_dx).ceil() * big_dx, big_x1, big_dx) {
+            lines.push(meridian(x, big_y0, big_y1, 90.0));
+        }
+        for y in range((big_y0 / big_dy).ceil() * big_dy, big_y1, big_dy) {
+            lines.push(parallel(y, big_x0, big_x1, p));
+        }
+        for x in range((x0 / dx).ceil() * dx, x1, dx) {
+            if (x % big_dx).abs() > EPSILON {
+                lines.push(meridian(x, y0, y1, 90.0));
+            }
+        }
+        for y in range((y0 / dy).ceil() * dy, y1, dy) {
+            if (y % big_dy).abs() > EPSILON {
+                lines.push(parallel(y, x0, x1, p));
+            }
+        }
+        lines.into_iter().map(positions).collect()
+    }
+
+    /// All lines as one MultiLineString geometry
+    pub fn geometry(&self) -> Geometry {
+        Geometry::new(GeometryValue::MultiLineString(self.coordinates()))
+    }
+
+    /// Each meridian and parallel as its own LineString geometry
+    pub fn lines(&self) -> Vec<Geometry> {
+        self.coordinates()
+            .into_iter()
+            .map(|line| Geometry::new(GeometryValue::LineString(line)))
+            .collect()
+    }
+
+    /// Polygon bounding the major extent, e.g. a map's outline
+    pub fn outline(&self) -> Geometry {
+        let [[x0, y0], [x1, y1]] = self.extent_major;
+        let p = self.precision;
+        let mut ring = meridian(x0, y0, y1, 90.0);
+        ring.extend(parallel(y1, x0, x1, p).into_iter().skip(1));
+        ring.extend(meridian(x1, y0, y1, 90.0).into_iter().rev().skip(1));
+        ring.extend(parallel(y0, x0, x1, p).into_iter().rev().skip(1));
+        Geometry::new(GeometryValue::Polygon(vec![positions(ring)]))
+    }
+}
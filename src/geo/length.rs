@@ -0,0 +1,96 @@
+//! d3-geo: great-arc length and distance
+
+use super::math::{Adder, RADIANS};
+use super::{GeoStream, GeoStreamable};
+
+struct LengthStream {
+    sum: Adder,
+    in_polygon: bool,
+    in_line: bool,
+    // First point of the current ring, to close it
+    first: Option<[f64; 2]>,
+    lambda0: f64,
+    sin_phi0: f64,
+    cos_phi0: f64,
+}
+
+impl LengthStream {
+    fn new() -> Self {
+        Self {
+            sum: Adder::default(),
+            in_polygon: false,
+            in_line: false,
+            first: None,
+            lambda0: 0.0,
+            sin_phi0: 0.0,
+            cos_phi0: 0.0,
+        }
+    }
+
+    fn length_point(&mut self, lambda: f64, phi: f64) {
+        let (lambda, phi) = (lambda * RADIANS, phi * RADIANS);
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let delta = (lambda - self.lambda0).abs();
+        let (sin_delta, cos_delta) = delta.sin_cos();
+        let x = cos_phi * sin_delta;
+        let y = self.cos_phi0 * sin_phi - self.sin_phi0 * cos_phi * cos_delta;
+        let z = self.sin_phi0 * sin_phi + self.cos_phi0 * cos_phi * cos_delta;
+        self.sum.add((x * x + y * y).sqrt().atan2(z));
+        self.lambda0 = lambda;
+        self.sin_phi0 = sin_phi;
+        self.cos_phi0 = cos_phi;
+    }
+}
+
+impl GeoStream for LengthStream {
+    fn point(&mut self, lambda: f64, phi: f64) {
+        if !self.in_line {
+            return;
+        }
+        if self.first.is_none() {
+            self.first = Some([lambda, phi]);
+            self.lambda0 = lambda * RADIANS;
+            (self.sin_phi0, self.cos_phi0) = (phi * RADIANS).sin_cos();
+        } else {
+            self.length_point(lambda, phi);
+        }
+    }
+    fn line_start(&mut self) {
+        self.in_line = true;
+        self.first = None;
+    }
+    fn line_end(&mut self) {
+        if self.in_polygon
+            && let Some([lambda, phi]) = self.first
+        {
+            self.length_point(lambda, phi);
+        }
+        self.in_line = false;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+    }
+}
+
+/// Great-arc length of `object` in radians
+///
+/// Lines contribute their length and polygons their perimeter, interior
+/// rings included; points contribute nothing.
+pub fn geo_length<O: GeoStreamable + ?Sized>(object: &O) -> f64 {
+    let mut stream = LengthStream::new();
+    object.stream(&mut stream);
+    stream.sum.value()
+}
+
+/// Great-arc distance in radians between two `[longitude, latitude]` points
+pub fn geo_distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let mut stream = LengthStream::new();
+    stream.line_start();
+    stream.point(a[0], a[1]);
+    stream.point(b[0], b[1]);
+    stream.line_end();
+    stream.sum.value()
+}
@@ -0,0 +1,110 @@
+//! Shared constants and spherical helpers for d3-geo
+
+pub(crate) const EPSILON: f64 = 1e-6;
+pub(crate) const EPSILON2: f64 = 1e-12;
+pub(crate) const PI: f64 = std::f64::consts::PI;
+pub(crate) const HALF_PI: f64 = PI / 2.0;
+pub(crate) const QUARTER_PI: f64 = PI / 4.0;
+pub(crate) const TAU: f64 = PI * 2.0;
+pub(crate) const DEGREES: f64 = 180.0 / PI;
+pub(crate) const RADIANS: f64 = PI / 180.0;
+
+pub(crate) type Vec3 = [f64; 3];
+
+/// `acos` clamped to [-1, 1]
+pub(crate) fn acos(x: f64) -> f64 {
+    if x > 1.0 {
+        0.0
+    } else if x < -1.0 {
+        PI
+    } else {
+        x.acos()
+    }
+}
+
+/// `asin` clamped to [-1, 1]
+pub(crate) fn asin(x: f64) -> f64 {
+    if x > 1.0 {
+        HALF_PI
+    } else if x < -1.0 {
+        -HALF_PI
+    } else {
+        x.asin()
+    }
+}
+
+/// Sign as in JavaScript's `Math.sign`, with zero for zero
+pub(crate) fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+pub(crate) fn spherical(c: Vec3) -> [f64; 2] {
+    [c[1].atan2(c[0]), asin(c[2])]
+}
+
+pub(crate) fn cartesian([lambda, phi]: [f64; 2]) -> Vec3 {
+    let cos_phi = phi.cos();
+    [cos_phi * lambda.cos(), cos_phi * lambda.sin(), phi.sin()]
+}
+
+pub(crate) fn cartesian_dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cartesian_cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub(crate) fn cartesian_add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub(crate) fn cartesian_scale(v: Vec3, k: f64) -> Vec3 {
+    [v[0] * k, v[1] * k, v[2] * k]
+}
+
+pub(crate) fn cartesian_normalize(v: Vec3) -> Vec3 {
+    let l = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    [v[0] / l, v[1] / l, v[2] / l]
+}
+
+/// Compensated (Kahan–Babuška) summation, for sums of many small angles
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Adder {
+    sum: f64,
+    compensation: f64,
+}
+
+impl Adder {
+    pub fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+/// `d3.range(start, stop, step)`
+pub(crate) fn range(start: f64, stop: f64, step: f64) -> Vec<f64> {
+    let n = ((stop - start) / step).ceil().max(0.0);
+    if !n.is_finite() {
+        return Vec::new();
+    }
+    (0..n as usize).map(|i| start + i as f64 * step).collect()
+}
@@ -0,0 +1,307 @@
+//! d3-geo: geographic projections, spherical shapes and trigonometry (Rust port)
+//!
+//! Geometry flows through a [`GeoStream`]: a projection's stream rotates,
+//! clips (at the antimeridian or a small circle), adaptively resamples,
+//! projects and optionally clips to a rectangle before handing planar
+//! coordinates to a sink such as a [`GeoPath`] renderer.
+//!
+//! Like D3, polygon rings wind clockwise around their interior on the sphere;
+//! a counterclockwise ring encloses everything *except* its interior.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::geo::{GeoPath, geo_area, geo_distance, geo_mercator};
+//! use rust_d3::geojson::{GeoJsonFeature, GeoJsonGeometry};
+//! use serde_json::json;
+//!
+//! let square = GeoJsonFeature {
+//!     _type: "Feature".to_string(),
+//!     properties: None,
+//!     geometry: GeoJsonGeometry {
+//!         _type: "Polygon".to_string(),
+//!         coordinates: json!([[[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]]]),
+//!     },
+//! };
+//!
+//! let projection = geo_mercator().fit_size([960.0, 500.0], &square);
+//! let d = GeoPath::new().projection(projection).svg(&square);
+//! assert!(d.starts_with('M') && d.ends_with('Z'));
+//!
+//! assert!(geo_area(&square) > 0.03);
+//! assert!((geo_distance([0.0, 0.0], [0.0, 90.0]) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+//! ```
+
+mod area;
+mod bounds;
+mod centroid;
+mod circle;
+mod clip;
+mod graticule;
+mod length;
+mod math;
+mod path;
+mod path_measure;
+mod projection;
+mod resample;
+mod rotation;
+
+pub use area::geo_area;
+pub use bounds::geo_bounds;
+pub use centroid::geo_centroid;
+pub use graticule::{Graticule, geo_graticule, geo_graticule10};
+pub use length::{geo_distance, geo_length};
+pub use path::GeoPath;
+pub use projection::{
+    Projection, RawProjection, geo_albers, geo_azimuthal_equal_area, geo_azimuthal_equidistant,
+    geo_conic_equal_area, geo_equirectangular, geo_gnomonic, geo_mercator, geo_orthographic,
+    geo_stereographic, geo_transverse_mercator,
+};
+pub use rotation::{GeoRotation, geo_rotation};
+
+use crate::geojson::{
+    Feature, FeatureCollection, GeoJson, GeoJsonFeature, GeoJsonGeometry, Geometry, GeometryValue,
+    Position,
+};
+use serde_json::Value;
+
+/// Receiver of streamed geometry (D3's stream protocol)
+///
+/// Points arrive in degrees for spherical sinks and in pixels for sinks
+/// downstream of a projection. Rings are streamed without their closing
+/// point.
+pub trait GeoStream {
+    fn point(&mut self, x: f64, y: f64);
+    fn line_start(&mut self) {}
+    fn line_end(&mut self) {}
+    fn polygon_start(&mut self) {}
+    fn polygon_end(&mut self) {}
+    /// The whole sphere, streamed by [`Sphere`]
+    fn sphere(&mut self) {}
+}
+
+impl<S: GeoStream + ?Sized> GeoStream for &mut S {
+    fn point(&mut self, x: f64, y: f64) {
+        (**self).point(x, y)
+    }
+    fn line_start(&mut self) {
+        (**self).line_start()
+    }
+    fn line_end(&mut self) {
+        (**self).line_end()
+    }
+    fn polygon_start(&mut self) {
+        (**self).polygon_start()
+    }
+    fn polygon_end(&mut self) {
+        (**self).polygon_end()
+    }
+    fn sphere(&mut self) {
+        (**self).sphere()
+    }
+}
+
+impl<S: GeoStream + ?Sized> GeoStream for Box<S> {
+    fn point(&mut self, x: f64, y: f64) {
+        (**self).point(x, y)
+    }
+    fn line_start(&mut self) {
+        (**self).line_start()
+    }
+    fn line_end(&mut self) {
+        (**self).line_end()
+    }
+    fn polygon_start(&mut self) {
+        (**self).polygon_start()
+    }
+    fn polygon_end(&mut self) {
+        (**self).polygon_end()
+    }
+    fn sphere(&mut self) {
+        (**self).sphere()
+    }
+}
+
+/// A geographic object that can be streamed: features, geometries and
+/// [`Sphere`]
+pub trait GeoStreamable {
+    fn stream(&self, stream: &mut dyn GeoStream);
+}
+
+/// Stream `object` into `stream` (D3 `geoStream`)
+pub fn geo_stream<O: GeoStreamable + ?Sized>(object: &O, stream: &mut dyn GeoStream) {
+    object.stream(stream);
+}
+
+/// The whole globe (D3's `{type: "Sphere"}`), e.g. to draw a map outline or
+/// fit a projection to the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sphere;
+
+impl GeoStreamable for Sphere {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        stream.sphere();
+    }
+}
+
+impl GeoStreamable for GeoJsonFeature {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        self.geometry.stream(stream);
+    }
+}
+
+impl GeoStreamable for [GeoJsonFeature] {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        for feature in self {
+            feature.stream(stream);
+        }
+    }
+}
+
+impl GeoStreamable for Vec<GeoJsonFeature> {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        self.as_slice().stream(stream);
+    }
+}
+
+impl GeoStreamable for GeoJsonGeometry {
+    /// Unknown types and malformed coordinates stream nothing
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        let c = &self.coordinates;
+        match self._type.as_str() {
+            "Sphere" => stream.sphere(),
+            "Point" => {
+                if let Some([x, y]) = position(c) {
+                    stream.point(x, y);
+                }
+            }
+            "MultiPoint" => {
+                for [x, y] in positions(c) {
+                    stream.point(x, y);
+                }
+            }
+            "LineString" => stream_line(&positions(c), stream, false),
+            "MultiLineString" => {
+                for line in children(c) {
+                    stream_line(&positions(line), stream, false);
+                }
+            }
+            "Polygon" => stream_polygon(c, stream),
+            "MultiPolygon" => {
+                for polygon in children(c) {
+                    stream_polygon(polygon, stream);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl GeoStreamable for Geometry {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        match &self.value {
+            GeometryValue::Point(p) => stream.point(p[0], p[1]),
+            GeometryValue::MultiPoint(points) => {
+                for p in points {
+                    stream.point(p[0], p[1]);
+                }
+            }
+            GeometryValue::LineString(line) => stream_line(&typed_positions(line), stream, false),
+            GeometryValue::MultiLineString(lines) => {
+                for line in lines {
+                    stream_line(&typed_positions(line), stream, false);
+                }
+            }
+            GeometryValue::Polygon(rings) => stream_typed_polygon(rings, stream),
+            GeometryValue::MultiPolygon(polygons) => {
+                for rings in polygons {
+                    stream_typed_polygon(rings, stream);
+                }
+            }
+            GeometryValue::GeometryCollection(geometries) => {
+                for geometry in geometries {
+                    geometry.stream(stream);
+                }
+            }
+        }
+    }
+}
+
+impl GeoStreamable for Feature {
+    /// Features without a geometry stream nothing
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        if let Some(geometry) = &self.geometry {
+            geometry.stream(stream);
+        }
+    }
+}
+
+impl GeoStreamable for FeatureCollection {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        for feature in &self.features {
+            feature.stream(stream);
+        }
+    }
+}
+
+impl GeoStreamable for GeoJson {
+    fn stream(&self, stream: &mut dyn GeoStream) {
+        match self {
+            GeoJson::Geometry(g) => g.stream(stream),
+            GeoJson::Feature(f) => f.stream(stream),
+            GeoJson::FeatureCollection(c) => c.stream(stream),
+        }
+    }
+}
+
+fn position(value: &Value) -> Option<[f64; 2]> {
+    match value.as_array()?.as_slice() {
+        [x, y, ..] => Some([x.as_f64()?, y.as_f64()?]),
+        _ => None,
+    }
+}
+
+fn children(value: &Value) -> &[Value] {
+    value.as_array().map_or(&[], Vec::as_slice)
+}
+
+fn positions(value: &Value) -> Vec<[f64; 2]> {
+    children(value).iter().filter_map(position).collect()
+}
+
+/// Stream a line, or a ring without its closing point when `closed`
+pub(crate) fn stream_line(coordinates: &[[f64; 2]], stream: &mut dyn GeoStream, closed: bool) {
+    let n = if closed {
+        coordinates.len().saturating_sub(1)
+    } else {
+        coordinates.len()
+    };
+    stream.line_start();
+    for &[x, y] in &coordinates[..n] {
+        stream.point(x, y);
+    }
+    stream.line_end();
+}
+
+fn stream_polygon(rings: &Value, stream: &mut dyn GeoStream) {
+    stream.polygon_start();
+    for ring in children(rings) {
+        stream_line(&positions(ring), stream, true);
+    }
+    stream.polygon_end();
+}
+
+// Longitude and latitude of each position; altitude is ignored
+fn typed_positions(line: &[Position]) -> Vec<[f64; 2]> {
+    line.iter().map(|p| [p[0], p[1]]).collect()
+}
+
+fn stream_typed_polygon(rings: &[Vec<Position>], stream: &mut dyn GeoStream) {
+    stream.polygon_start();
+    for ring in rings {
+        stream_line(&typed_positions(ring), stream, true);
+    }
+    stream.polygon_end();
+}
+
+#[cfg(test)]
+mod tests;
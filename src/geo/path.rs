@@ -0,0 +1,180 @@
+//! d3-geo: rendering geographic features as planar paths
+
+use super::path_measure::{PathArea, PathBounds, PathCentroid, PathMeasure};
+use super::projection::Projection;
+use super::{GeoStream, GeoStreamable};
+use crate::path::Path;
+
+/// Geographic path generator (D3 `geoPath`)
+///
+/// Projects features and draws them into a [`Path`] or an SVG path string.
+/// Without a projection, coordinates are drawn as they are, e.g. for
+/// pre-projected data. Points are drawn as circles of
+/// [`point_radius`](GeoPath::point_radius).
+#[derive(Debug, Clone)]
+pub struct GeoPath {
+    projection: Option<Projection>,
+    point_radius: f64,
+    digits: Option<usize>,
+}
+
+impl Default for GeoPath {
+    fn default() -> Self {
+        Self {
+            projection: None,
+            point_radius: 4.5,
+            digits: Some(3),
+        }
+    }
+}
+
+impl GeoPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn projection(mut self, projection: Projection) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Radius of the circles drawn for Point and MultiPoint geometries
+    pub fn point_radius(mut self, radius: f64) -> Self {
+        self.point_radius = radius;
+        self
+    }
+
+    /// Decimal places kept in [`svg`](GeoPath::svg) output, or full
+    /// precision if `None`
+    pub fn digits(mut self, digits: Option<usize>) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    pub fn get_projection(&self) -> Option<&Projection> {
+        self.projection.as_ref()
+    }
+
+    pub fn get_point_radius(&self) -> f64 {
+        self.point_radius
+    }
+
+    pub fn get_digits(&self) -> Option<usize> {
+        self.digits
+    }
+
+    fn stream<O: GeoStreamable + ?Sized>(&self, object: &O, sink: &mut dyn GeoStream) {
+        match &self.projection {
+            Some(projection) => object.stream(&mut *projection.stream(sink)),
+            None => object.stream(sink),
+        }
+    }
+
+    /// Draw `object` into `path`
+    pub fn render<O: GeoStreamable + ?Sized>(&self, object: &O, path: &mut Path) {
+        self.stream(object, &mut PathContext::new(path, self.point_radius, None));
+    }
+
+    /// SVG path data for `object`; empty if nothing is visible
+    pub fn svg<O: GeoStreamable + ?Sized>(&self, object: &O) -> String {
+        let mut path = Path::new();
+        self.stream(
+            object,
+            &mut PathContext::new(&mut path, self.point_radius, self.digits),
+        );
+        path.to_string().to_string()
+    }
+
+    /// Projected planar area in square pixels
+    pub fn area<O: GeoStreamable + ?Sized>(&self, object: &O) -> f64 {
+        let mut area = PathArea::default();
+        self.stream(object, &mut area);
+        area.result()
+    }
+
+    /// Projected bounding box `[[x0, y0], [x1, y1]]` in pixels
+    pub fn bounds<O: GeoStreamable + ?Sized>(&self, object: &O) -> [[f64; 2]; 2] {
+        let mut bounds = PathBounds::default();
+        self.stream(object, &mut bounds);
+        bounds.result()
+    }
+
+    /// Projected planar centroid in pixels
+    pub fn centroid<O: GeoStreamable + ?Sized>(&self, object: &O) -> [f64; 2] {
+        let mut centroid = PathCentroid::default();
+        self.stream(object, &mut centroid);
+        centroid.result()
+    }
+
+    /// Projected length of lines and polygon perimeters in pixels
+    pub fn measure<O: GeoStreamable + ?Sized>(&self, object: &O) -> f64 {
+        let mut measure = PathMeasure::default();
+        self.stream(object, &mut measure);
+        measure.result()
+    }
+}
+
+/// Stream sink drawing into a [`Path`]
+struct PathContext<'a> {
+    path: &'a mut Path,
+    radius: f64,
+    // Power of ten to round coordinates with
+    precision: Option<f64>,
+    in_polygon: bool,
+    in_line: bool,
+    line_started: bool,
+}
+
+impl<'a> PathContext<'a> {
+    fn new(path: &'a mut Path, radius: f64, digits: Option<usize>) -> Self {
+        Self {
+            path,
+            radius,
+            precision: digits.map(|d| 10f64.powi(d as i32)),
+            in_polygon: false,
+            in_line: false,
+            line_started: false,
+        }
+    }
+
+    fn round(&self, x: f64) -> f64 {
+        match self.precision {
+            // Adding zero turns -0 into 0
+            Some(k) => (x * k).round() / k + 0.0,
+            None => x,
+        }
+    }
+}
+
+impl GeoStream for PathContext<'_> {
+    fn point(&mut self, x: f64, y: f64) {
+        let (x, y) = (self.round(x), self.round(y));
+        if !self.in_line {
+            let r = self.radius;
+            self.path.move_to(self.round(x + r), y);
+            self.path.arc(r, r, 0.0, false, true, self.round(x - r), y);
+            self.path.arc(r, r, 0.0, false, true, self.round(x + r), y);
+        } else if self.line_started {
+            self.path.line_to(x, y);
+        } else {
+            self.path.move_to(x, y);
+            self.line_started = true;
+        }
+    }
+    fn line_start(&mut self) {
+        self.in_line = true;
+        self.line_started = false;
+    }
+    fn line_end(&mut self) {
+        if self.in_polygon && self.line_started {
+            self.path.close_path();
+        }
+        self.in_line = false;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+    }
+}
@@ -0,0 +1,251 @@
+//! d3-geo: planar area, bounds, centroid and length of projected geometry
+
+use super::GeoStream;
+use super::math::Adder;
+
+/// Planar area of polygons, ignoring ring winding
+#[derive(Debug, Default)]
+pub(crate) struct PathArea {
+    sum: Adder,
+    ring_sum: Adder,
+    in_polygon: bool,
+    in_ring: bool,
+    first: Option<[f64; 2]>,
+    p0: [f64; 2],
+}
+
+impl PathArea {
+    fn ring_point(&mut self, x: f64, y: f64) {
+        let [x0, y0] = self.p0;
+        self.ring_sum.add(y0 * x - x0 * y);
+        self.p0 = [x, y];
+    }
+
+    pub fn result(&self) -> f64 {
+        self.sum.value() / 2.0
+    }
+}
+
+impl GeoStream for PathArea {
+    fn point(&mut self, x: f64, y: f64) {
+        if !self.in_ring {
+            return;
+        }
+        if self.first.is_none() {
+            self.first = Some([x, y]);
+            self.p0 = [x, y];
+        } else {
+            self.ring_point(x, y);
+        }
+    }
+    fn line_start(&mut self) {
+        self.in_ring = self.in_polygon;
+        self.first = None;
+    }
+    fn line_end(&mut self) {
+        if self.in_ring
+            && let Some([x, y]) = self.first
+        {
+            self.ring_point(x, y);
+        }
+        self.in_ring = false;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+        self.sum.add(self.ring_sum.value().abs());
+        self.ring_sum = Adder::default();
+    }
+}
+
+/// Planar bounding box of every streamed point
+#[derive(Debug)]
+pub(crate) struct PathBounds {
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+}
+
+impl Default for PathBounds {
+    fn default() -> Self {
+        Self {
+            x0: f64::INFINITY,
+            y0: f64::INFINITY,
+            x1: f64::NEG_INFINITY,
+            y1: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl PathBounds {
+    pub fn result(&self) -> [[f64; 2]; 2] {
+        [[self.x0, self.y0], [self.x1, self.y1]]
+    }
+}
+
+impl GeoStream for PathBounds {
+    fn point(&mut self, x: f64, y: f64) {
+        self.x0 = self.x0.min(x);
+        self.y0 = self.y0.min(y);
+        self.x1 = self.x1.max(x);
+        self.y1 = self.y1.max(y);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum State {
+    #[default]
+    Point,
+    LineFirst,
+    Line,
+    RingFirst,
+    Ring,
+}
+
+/// Planar centroid, weighted by area, then length, then point count
+#[derive(Debug, Default)]
+pub(crate) struct PathCentroid {
+    state: State,
+    in_polygon: bool,
+    // Sums of points, of line midpoints weighted by length and of triangle
+    // centroids weighted by signed area
+    point: [f64; 3],
+    line: [f64; 3],
+    area: [f64; 3],
+    first: [f64; 2],
+    p0: [f64; 2],
+}
+
+impl PathCentroid {
+    fn add_point(&mut self, x: f64, y: f64) {
+        self.point[0] += x;
+        self.point[1] += y;
+        self.point[2] += 1.0;
+        self.p0 = [x, y];
+    }
+
+    fn add_segment(&mut self, x: f64, y: f64) {
+        let [x0, y0] = self.p0;
+        let (dx, dy) = (x - x0, y - y0);
+        let z = (dx * dx + dy * dy).sqrt();
+        self.line[0] += z * (x0 + x) / 2.0;
+        self.line[1] += z * (y0 + y) / 2.0;
+        self.line[2] += z;
+    }
+
+    fn ring_point(&mut self, x: f64, y: f64) {
+        self.add_segment(x, y);
+        let [x0, y0] = self.p0;
+        let z = y0 * x - x0 * y;
+        self.area[0] += z * (x0 + x);
+        self.area[1] += z * (y0 + y);
+        self.area[2] += z * 3.0;
+        self.add_point(x, y);
+    }
+
+    pub fn result(&self) -> [f64; 2] {
+        [self.area, self.line, self.point]
+            .into_iter()
+            .find(|&[_, _, z]| z != 0.0)
+            .map_or([f64::NAN, f64::NAN], |[x, y, z]| [x / z, y / z])
+    }
+}
+
+impl GeoStream for PathCentroid {
+    fn point(&mut self, x: f64, y: f64) {
+        match self.state {
+            State::Point => self.add_point(x, y),
+            State::LineFirst => {
+                self.state = State::Line;
+                self.add_point(x, y);
+            }
+            State::Line => {
+                self.add_segment(x, y);
+                self.add_point(x, y);
+            }
+            State::RingFirst => {
+                self.state = State::Ring;
+                self.first = [x, y];
+                self.add_point(x, y);
+            }
+            State::Ring => self.ring_point(x, y),
+        }
+    }
+    fn line_start(&mut self) {
+        self.state = if self.in_polygon {
+            State::RingFirst
+        } else {
+            State::LineFirst
+        };
+    }
+    fn line_end(&mut self) {
+        if self.state == State::Ring {
+            let [x, y] = self.first;
+            self.ring_point(x, y);
+        }
+        self.state = State::Point;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+    }
+}
+
+/// Planar length of lines and polygon perimeters
+#[derive(Debug, Default)]
+pub(crate) struct PathMeasure {
+    sum: Adder,
+    in_polygon: bool,
+    in_line: bool,
+    first: Option<[f64; 2]>,
+    p0: [f64; 2],
+}
+
+impl PathMeasure {
+    fn line_point(&mut self, x: f64, y: f64) {
+        let [x0, y0] = self.p0;
+        self.sum.add((x - x0).hypot(y - y0));
+        self.p0 = [x, y];
+    }
+
+    pub fn result(&self) -> f64 {
+        self.sum.value()
+    }
+}
+
+impl GeoStream for PathMeasure {
+    fn point(&mut self, x: f64, y: f64) {
+        if !self.in_line {
+            return;
+        }
+        if self.first.is_none() {
+            self.first = Some([x, y]);
+            self.p0 = [x, y];
+        } else {
+            self.line_point(x, y);
+        }
+    }
+    fn line_start(&mut self) {
+        self.in_line = true;
+        self.first = None;
+    }
+    fn line_end(&mut self) {
+        if self.in_polygon
+            && let Some([x, y]) = self.first
+        {
+            self.line_point(x, y);
+        }
+        self.in_line = false;
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+    }
+}
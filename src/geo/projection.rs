@@ -0,0 +1,674 @@
+//! d3-geo: projections from the sphere to the plane
+
+use super::clip::{Clip, ClipAntimeridian, ClipCircle, ClipRectangle};
+use super::math::{DEGREES, EPSILON, HALF_PI, PI, RADIANS, TAU, acos, asin, sign};
+use super::path_measure::PathBounds;
+use super::resample::Resample;
+use super::rotation::Rotation;
+use super::{GeoStream, GeoStreamable};
+use std::fmt;
+use std::rc::Rc;
+
+/// A raw projection from `(λ, φ)` in radians to unscaled planar coordinates,
+/// with y pointing up
+///
+/// Closures `Fn(f64, f64) -> [f64; 2]` are raw projections without an
+/// inverse.
+pub trait RawProjection {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2];
+    /// The inverse projection, if there is one
+    fn invert(&self, _x: f64, _y: f64) -> Option<[f64; 2]> {
+        None
+    }
+}
+
+impl<F: Fn(f64, f64) -> [f64; 2]> RawProjection for F {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        self(lambda, phi)
+    }
+}
+
+/// Builds a conic raw projection from its two standard parallels (radians)
+type ConicRaw = fn(f64, f64) -> Rc<dyn RawProjection>;
+
+// Mercator variants clip to the square world by default
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Plain,
+    Mercator,
+    TransverseMercator,
+}
+
+/// Scale, translation, rotation and reflection applied after the raw
+/// projection
+#[derive(Debug, Clone, Copy)]
+struct Transform {
+    k: f64,
+    dx: f64,
+    dy: f64,
+    sx: f64,
+    sy: f64,
+    cos_alpha: f64,
+    sin_alpha: f64,
+}
+
+impl Transform {
+    fn new(k: f64, dx: f64, dy: f64, sx: f64, sy: f64, alpha: f64) -> Self {
+        let (sin_alpha, cos_alpha) = alpha.sin_cos();
+        Self {
+            k,
+            dx,
+            dy,
+            sx,
+            sy,
+            cos_alpha,
+            sin_alpha,
+        }
+    }
+
+    fn apply(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let (x, y) = (x * self.sx, y * self.sy);
+        [
+            self.dx + self.k * (self.cos_alpha * x - self.sin_alpha * y),
+            self.dy - self.k * (self.sin_alpha * x + self.cos_alpha * y),
+        ]
+    }
+
+    fn invert(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let u = (x - self.dx) / self.k;
+        let v = (self.dy - y) / self.k;
+        [
+            self.sx * (self.cos_alpha * u + self.sin_alpha * v),
+            self.sy * (self.cos_alpha * v - self.sin_alpha * u),
+        ]
+    }
+}
+
+/// A configurable projection (D3 `geoProjection`)
+///
+/// Geometry streamed through [`Projection::stream`] is rotated, clipped to
+/// the antimeridian or [`clip_angle`](Projection::clip_angle), adaptively
+/// resampled, projected and finally clipped to
+/// [`clip_extent`](Projection::clip_extent). Angles are in degrees.
+#[derive(Clone)]
+pub struct Projection {
+    raw: Rc<dyn RawProjection>,
+    conic: Option<ConicRaw>,
+    kind: Kind,
+    scale: f64,
+    translate: [f64; 2],
+    parallels: [f64; 2],
+    // Radians
+    center: [f64; 2],
+    rotate: [f64; 3],
+    angle: f64,
+    clip_angle: Option<f64>,
+    reflect_x: bool,
+    reflect_y: bool,
+    clip_extent: Option<[[f64; 2]; 2]>,
+    delta2: f64,
+    transform: Transform,
+    rotation: Rotation,
+}
+
+impl fmt::Debug for Projection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Projection")
+            .field("kind", &self.kind)
+            .field("scale", &self.scale)
+            .field("translate", &self.translate)
+            .field("center", &self.get_center())
+            .field("rotate", &self.get_rotate())
+            .field("clip_angle", &self.get_clip_angle())
+            .field("clip_extent", &self.clip_extent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Projection {
+    /// Projection with D3's defaults: scale 150, translated to `[480, 250]`
+    /// and cut at the antimeridian
+    pub fn new<R: RawProjection + 'static>(raw: R) -> Self {
+        Self::from_raw(Rc::new(raw))
+    }
+
+    fn from_raw(raw: Rc<dyn RawProjection>) -> Self {
+        Self {
+            raw,
+            conic: None,
+            kind: Kind::Plain,
+            scale: 150.0,
+            translate: [480.0, 250.0],
+            parallels: [0.0, 60.0],
+            center: [0.0, 0.0],
+            rotate: [0.0, 0.0, 0.0],
+            angle: 0.0,
+            clip_angle: None,
+            reflect_x: false,
+            reflect_y: false,
+            clip_extent: None,
+            delta2: 0.5,
+            transform: Transform::new(150.0, 480.0, 250.0, 1.0, 1.0, 0.0),
+            rotation: Rotation::new(0.0, 0.0, 0.0),
+        }
+        .recenter()
+    }
+
+    fn conic(conic: ConicRaw) -> Self {
+        let mut projection = Self::from_raw(conic(0.0, PI / 3.0));
+        projection.conic = Some(conic);
+        projection
+    }
+
+    fn recenter(mut self) -> Self {
+        let (sx, sy) = (
+            if self.reflect_x { -1.0 } else { 1.0 },
+            if self.reflect_y { -1.0 } else { 1.0 },
+        );
+        let raw_center = self.raw.project(self.center[0], self.center[1]);
+        let center = Transform::new(self.scale, 0.0, 0.0, sx, sy, self.angle).apply(raw_center);
+        self.transform = Transform::new(
+            self.scale,
+            self.translate[0] - center[0],
+            self.translate[1] - center[1],
+            sx,
+            sy,
+            self.angle,
+        );
+        let [delta_lambda, delta_phi, delta_gamma] = self.rotate;
+        self.rotation = Rotation::new(delta_lambda, delta_phi, delta_gamma);
+        self
+    }
+
+    /// Project `[longitude, latitude]` in degrees to pixels
+    pub fn project(&self, [lambda, phi]: [f64; 2]) -> [f64; 2] {
+        let (lambda, phi) = self.rotation.forward(lambda * RADIANS, phi * RADIANS);
+        self.project_rotated(lambda, phi)
+    }
+
+    // Raw projection and transform of rotated coordinates in radians
+    fn project_rotated(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        self.transform.apply(self.raw.project(lambda, phi))
+    }
+
+    /// `[longitude, latitude]` in degrees of a point in pixels; `None` if the
+    /// raw projection has no inverse
+    pub fn invert(&self, point: [f64; 2]) -> Option<[f64; 2]> {
+        let [x, y] = self.transform.invert(point);
+        let [lambda, phi] = self.raw.invert(x, y)?;
+        let (lambda, phi) = self.rotation.invert(lambda, phi);
+        Some([lambda * DEGREES, phi * DEGREES])
+    }
+
+    /// Wrap `sink` in this projection's pipeline: `sink` receives projected,
+    /// clipped and resampled coordinates in pixels
+    pub fn stream<'a, S: GeoStream + 'a>(&'a self, sink: S) -> Box<dyn GeoStream + 'a> {
+        let postclip: Box<dyn GeoStream + 'a> = match self.postclip_extent() {
+            Some(extent) => Box::new(ClipRectangle::new(extent, sink)),
+            None => Box::new(sink),
+        };
+        let resample = Resample::new(
+            Box::new(move |lambda, phi| self.project_rotated(lambda, phi)),
+            self.delta2,
+            postclip,
+        );
+        let preclip: Box<dyn GeoStream + 'a> = match self.clip_angle {
+            Some(theta) => Box::new(Clip::new(ClipCircle::new(theta), resample)),
+            None => Box::new(Clip::new(ClipAntimeridian, resample)),
+        };
+        Box::new(RotateRadians {
+            rotation: self.rotation,
+            sink: preclip,
+        })
+    }
+
+    // Mercator projections are clipped to the square world unless a clip
+    // extent limits them further
+    fn postclip_extent(&self) -> Option<[[f64; 2]; 2]> {
+        if self.kind == Kind::Plain {
+            return self.clip_extent;
+        }
+        let k = PI * self.scale;
+        let (lambda, phi) = self.rotation.invert(0.0, 0.0);
+        let [tx, ty] = self.project([lambda * DEGREES, phi * DEGREES]);
+        Some(match self.clip_extent {
+            None => [[tx - k, ty - k], [tx + k, ty + k]],
+            Some([[x0, y0], [x1, y1]]) if self.kind == Kind::Mercator => {
+                [[(tx - k).max(x0), y0], [(tx + k).min(x1), y1]]
+            }
+            Some([[x0, y0], [x1, y1]]) => [[x0, (ty - k).max(y0)], [x1, (ty + k).min(y1)]],
+        })
+    }
+
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self.recenter()
+    }
+
+    /// Pixel position of the projection's [`center`](Projection::center)
+    pub fn translate(mut self, translate: [f64; 2]) -> Self {
+        self.translate = translate;
+        self.recenter()
+    }
+
+    /// `[longitude, latitude]` that projects to the translation point
+    pub fn center(mut self, center: [f64; 2]) -> Self {
+        let [lambda, phi] = match self.kind {
+            Kind::TransverseMercator => [-center[1], center[0]],
+            _ => center,
+        };
+        self.center = [lambda % 360.0 * RADIANS, phi % 360.0 * RADIANS];
+        self.recenter()
+    }
+
+    /// Spherical rotation `[λ, φ, γ]` applied before projecting
+    pub fn rotate(mut self, rotate: [f64; 3]) -> Self {
+        let gamma = match self.kind {
+            Kind::TransverseMercator => rotate[2] + 90.0,
+            _ => rotate[2],
+        };
+        self.rotate = [
+            rotate[0] % 360.0 * RADIANS,
+            rotate[1] % 360.0 * RADIANS,
+            gamma % 360.0 * RADIANS,
+        ];
+        self.recenter()
+    }
+
+    /// Planar rotation in degrees applied after projecting
+    pub fn angle(mut self, angle: f64) -> Self {
+        self.angle = angle % 360.0 * RADIANS;
+        self.recenter()
+    }
+
+    pub fn reflect_x(mut self, reflect: bool) -> Self {
+        self.reflect_x = reflect;
+        self.recenter()
+    }
+
+    pub fn reflect_y(mut self, reflect: bool) -> Self {
+        self.reflect_y = reflect;
+        self.recenter()
+    }
+
+    /// Standard parallels of a conic projection; no effect on others
+    pub fn parallels(mut self, parallels: [f64; 2]) -> Self {
+        if let Some(conic) = self.conic {
+            self.parallels = parallels;
+            self.raw = conic(parallels[0] * RADIANS, parallels[1] * RADIANS);
+        }
+        self.recenter()
+    }
+
+    /// Clip to a small circle of this radius in degrees around the center,
+    /// or cut at the antimeridian if `None`
+    pub fn clip_angle(mut self, angle: Option<f64>) -> Self {
+        self.clip_angle = angle.filter(|&a| a != 0.0).map(|a| a * RADIANS);
+        self
+    }
+
+    /// Clip projected geometry to `[[x0, y0], [x1, y1]]`, or not at all if
+    /// `None`
+    pub fn clip_extent(mut self, extent: Option<[[f64; 2]; 2]>) -> Self {
+        self.clip_extent = extent;
+        self
+    }
+
+    /// Maximum distance in pixels between resampled lines and the true
+    /// projected curve; zero disables resampling
+    pub fn precision(mut self, precision: f64) -> Self {
+        self.delta2 = precision * precision;
+        self
+    }
+
+    pub fn get_scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn get_translate(&self) -> [f64; 2] {
+        self.translate
+    }
+
+    pub fn get_center(&self) -> [f64; 2] {
+        let [lambda, phi] = [self.center[0] * DEGREES, self.center[1] * DEGREES];
+        match self.kind {
+            Kind::TransverseMercator => [phi, -lambda],
+            _ => [lambda, phi],
+        }
+    }
+
+    pub fn get_rotate(&self) -> [f64; 3] {
+        let [lambda, phi, gamma] = self.rotate.map(|a| a * DEGREES);
+        match self.kind {
+            Kind::TransverseMercator => [lambda, phi, gamma - 90.0],
+            _ => [lambda, phi, gamma],
+        }
+    }
+
+    pub fn get_angle(&self) -> f64 {
+        self.angle * DEGREES
+    }
+
+    pub fn get_reflect_x(&self) -> bool {
+        self.reflect_x
+    }
+
+    pub fn get_reflect_y(&self) -> bool {
+        self.reflect_y
+    }
+
+    /// Standard parallels, for conic projections
+    pub fn get_parallels(&self) -> Option<[f64; 2]> {
+        self.conic.map(|_| self.parallels)
+    }
+
+    pub fn get_clip_angle(&self) -> Option<f64> {
+        self.clip_angle.map(|a| a * DEGREES)
+    }
+
+    pub fn get_clip_extent(&self) -> Option<[[f64; 2]; 2]> {
+        self.clip_extent
+    }
+
+    pub fn get_precision(&self) -> f64 {
+        self.delta2.sqrt()
+    }
+
+    // Project `object` at scale 150 around the origin, then let `fit_bounds`
+    // scale and translate from its bounds
+    fn fit<O, F>(self, object: &O, fit_bounds: F) -> Self
+    where
+        O: GeoStreamable + ?Sized,
+        F: FnOnce(Self, [[f64; 2]; 2]) -> Self,
+    {
+        let clip = self.clip_extent;
+        let projection = self.scale(150.0).translate([0.0, 0.0]).clip_extent(None);
+        let mut bounds = PathBounds::default();
+        object.stream(&mut *projection.stream(&mut bounds));
+        fit_bounds(projection, bounds.result()).clip_extent(clip)
+    }
+
+    /// Scale and translate so `object` fills `[[x0, y0], [x1, y1]]`
+    pub fn fit_extent<O: GeoStreamable + ?Sized>(self, extent: [[f64; 2]; 2], object: &O) -> Self {
+        self.fit(object, |projection, [[bx0, by0], [bx1, by1]]| {
+            let w = extent[1][0] - extent[0][0];
+            let h = extent[1][1] - extent[0][1];
+            let k = (w / (bx1 - bx0)).min(h / (by1 - by0));
+            let x = extent[0][0] + (w - k * (bx1 + bx0)) / 2.0;
+            let y = extent[0][1] + (h - k * (by1 + by0)) / 2.0;
+            projection.scale(150.0 * k).translate([x, y])
+        })
+    }
+
+    /// [`fit_extent`](Projection::fit_extent) with the top-left corner at
+    /// the origin
+    pub fn fit_size<O: GeoStreamable + ?Sized>(self, size: [f64; 2], object: &O) -> Self {
+        self.fit_extent([[0.0, 0.0], size], object)
+    }
+
+    /// Scale and translate so `object` spans `width`, from the top
+    pub fn fit_width<O: GeoStreamable + ?Sized>(self, width: f64, object: &O) -> Self {
+        self.fit(object, |projection, [[bx0, by0], [bx1, _]]| {
+            let k = width / (bx1 - bx0);
+            let x = (width - k * (bx1 + bx0)) / 2.0;
+            let y = -k * by0;
+            projection.scale(150.0 * k).translate([x, y])
+        })
+    }
+
+    /// Scale and translate so `object` spans `height`, from the left
+    pub fn fit_height<O: GeoStreamable + ?Sized>(self, height: f64, object: &O) -> Self {
+        self.fit(object, |projection, [[bx0, by0], [_, by1]]| {
+            let k = height / (by1 - by0);
+            let x = -k * bx0;
+            let y = (height - k * (by1 + by0)) / 2.0;
+            projection.scale(150.0 * k).translate([x, y])
+        })
+    }
+}
+
+/// Converts degrees to radians and rotates, at the head of a projection
+/// stream
+struct RotateRadians<S: GeoStream> {
+    rotation: Rotation,
+    sink: S,
+}
+
+impl<S: GeoStream> GeoStream for RotateRadians<S> {
+    fn point(&mut self, x: f64, y: f64) {
+        let (lambda, phi) = self.rotation.forward(x * RADIANS, y * RADIANS);
+        self.sink.point(lambda, phi);
+    }
+    fn line_start(&mut self) {
+        self.sink.line_start();
+    }
+    fn line_end(&mut self) {
+        self.sink.line_end();
+    }
+    fn polygon_start(&mut self) {
+        self.sink.polygon_start();
+    }
+    fn polygon_end(&mut self) {
+        self.sink.polygon_end();
+    }
+    fn sphere(&mut self) {
+        self.sink.sphere();
+    }
+}
+
+struct Equirectangular;
+
+impl RawProjection for Equirectangular {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        [lambda, phi]
+    }
+    fn invert(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        Some([x, y])
+    }
+}
+
+struct Mercator;
+
+impl RawProjection for Mercator {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        [lambda, ((HALF_PI + phi) / 2.0).tan().ln()]
+    }
+    fn invert(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        Some([x, 2.0 * y.exp().atan() - HALF_PI])
+    }
+}
+
+struct TransverseMercator;
+
+impl RawProjection for TransverseMercator {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        [((HALF_PI + phi) / 2.0).tan().ln(), -lambda]
+    }
+    fn invert(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        Some([-y, 2.0 * x.exp().atan() - HALF_PI])
+    }
+}
+
+/// Lambert cylindrical equal-area, used by conic equal-area when its
+/// parallels are symmetric about the equator
+struct CylindricalEqualArea {
+    cos_phi0: f64,
+}
+
+impl RawProjection for CylindricalEqualArea {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        [lambda * self.cos_phi0, phi.sin() / self.cos_phi0]
+    }
+    fn invert(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        Some([x / self.cos_phi0, asin(y * self.cos_phi0)])
+    }
+}
+
+/// Albers equal-area conic
+struct ConicEqualArea {
+    n: f64,
+    c: f64,
+    r0: f64,
+}
+
+fn conic_equal_area_raw(phi0: f64, phi1: f64) -> Rc<dyn RawProjection> {
+    let sin_phi0 = phi0.sin();
+    let n = (sin_phi0 + phi1.sin()) / 2.0;
+    if n.abs() < EPSILON {
+        return Rc::new(CylindricalEqualArea {
+            cos_phi0: phi0.cos(),
+        });
+    }
+    let c = 1.0 + sin_phi0 * (2.0 * n - sin_phi0);
+    Rc::new(ConicEqualArea {
+        n,
+        c,
+        r0: c.sqrt() / n,
+    })
+}
+
+impl RawProjection for ConicEqualArea {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        let r = (self.c - 2.0 * self.n * phi.sin()).sqrt() / self.n;
+        let (sin, cos) = (lambda * self.n).sin_cos();
+        [r * sin, self.r0 - r * cos]
+    }
+    fn invert(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        let r0y = self.r0 - y;
+        let mut lambda = x.atan2(r0y.abs()) * sign(r0y);
+        if r0y * self.n < 0.0 {
+            lambda -= PI * sign(x) * sign(r0y);
+        }
+        let phi = asin((self.c - (x * x + r0y * r0y) * self.n * self.n) / (2.0 * self.n));
+        Some([lambda / self.n, phi])
+    }
+}
+
+/// Azimuthal projections differ only in how they scale the distance from
+/// the center
+#[derive(Debug, Clone, Copy)]
+enum Azimuthal {
+    EqualArea,
+    Equidistant,
+    Orthographic,
+    Stereographic,
+    Gnomonic,
+}
+
+impl Azimuthal {
+    // Radial scale factor for the cosine of the angular distance
+    fn scale(self, cxcy: f64) -> f64 {
+        match self {
+            Azimuthal::EqualArea => (2.0 / (1.0 + cxcy)).sqrt(),
+            Azimuthal::Equidistant => {
+                let c = acos(cxcy);
+                if c == 0.0 { c } else { c / c.sin() }
+            }
+            Azimuthal::Orthographic => 1.0,
+            Azimuthal::Stereographic => 1.0 / (1.0 + cxcy),
+            Azimuthal::Gnomonic => 1.0 / cxcy,
+        }
+    }
+
+    // Angular distance for a planar distance from the center
+    fn angle(self, z: f64) -> f64 {
+        match self {
+            Azimuthal::EqualArea => 2.0 * asin(z / 2.0),
+            Azimuthal::Equidistant => z,
+            Azimuthal::Orthographic => asin(z),
+            Azimuthal::Stereographic => 2.0 * z.atan(),
+            Azimuthal::Gnomonic => z.atan(),
+        }
+    }
+}
+
+impl RawProjection for Azimuthal {
+    fn project(&self, lambda: f64, phi: f64) -> [f64; 2] {
+        let (cx, cy) = (lambda.cos(), phi.cos());
+        let k = self.scale(cx * cy);
+        if k == f64::INFINITY {
+            return [2.0, 0.0];
+        }
+        [k * cy * lambda.sin(), k * phi.sin()]
+    }
+    fn invert(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        let z = x.hypot(y);
+        let (sc, cc) = self.angle(z).sin_cos();
+        let phi = if z == 0.0 { 0.0 } else { asin(y * sc / z) };
+        Some([(x * sc).atan2(z * cc), phi])
+    }
+}
+
+/// Plate carrée: longitude and latitude map straight to x and y
+pub fn geo_equirectangular() -> Projection {
+    Projection::new(Equirectangular).scale(152.63)
+}
+
+/// Conformal cylindrical projection, clipped to a square world by default
+pub fn geo_mercator() -> Projection {
+    let mut projection = Projection::new(Mercator);
+    projection.kind = Kind::Mercator;
+    projection.scale(961.0 / TAU)
+}
+
+/// Mercator rotated so the central meridian runs horizontally
+///
+/// `center` and `rotate` are given as for the untransformed Mercator.
+pub fn geo_transverse_mercator() -> Projection {
+    let mut projection = Projection::new(TransverseMercator);
+    projection.kind = Kind::TransverseMercator;
+    projection.rotate([0.0, 0.0, 0.0]).scale(159.155)
+}
+
+/// Albers' equal-area conic, with [`parallels`](Projection::parallels)
+/// defaulting to 0° and 60°
+pub fn geo_conic_equal_area() -> Projection {
+    Projection::conic(conic_equal_area_raw)
+        .scale(155.424)
+        .center([0.0, 33.6442])
+}
+
+/// Conic equal-area configured for the contiguous United States
+pub fn geo_albers() -> Projection {
+    geo_conic_equal_area()
+        .parallels([29.5, 45.5])
+        .scale(1070.0)
+        .translate([480.0, 250.0])
+        .rotate([96.0, 0.0, 0.0])
+        .center([-0.6, 38.7])
+}
+
+/// Lambert azimuthal equal-area
+pub fn geo_azimuthal_equal_area() -> Projection {
+    Projection::new(Azimuthal::EqualArea)
+        .scale(124.75)
+        .clip_angle(Some(180.0 - 1e-3))
+}
+
+/// Azimuthal equidistant: distances from the center are preserved
+pub fn geo_azimuthal_equidistant() -> Projection {
+    Projection::new(Azimuthal::Equidistant)
+        .scale(79.4188)
+        .clip_angle(Some(180.0 - 1e-3))
+}
+
+/// The globe as seen from space, clipped to the visible hemisphere
+pub fn geo_orthographic() -> Projection {
+    Projection::new(Azimuthal::Orthographic)
+        .scale(249.5)
+        .clip_angle(Some(90.0 + EPSILON))
+}
+
+/// Conformal azimuthal projection
+pub fn geo_stereographic() -> Projection {
+    Projection::new(Azimuthal::Stereographic)
+        .scale(250.0)
+        .clip_angle(Some(142.0))
+}
+
+/// Gnomonic projection: great circles are straight lines
+pub fn geo_gnomonic() -> Projection {
+    Projection::new(Azimuthal::Gnomonic)
+        .scale(144.049)
+        .clip_angle(Some(60.0))
+}
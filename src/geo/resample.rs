@@ -0,0 +1,172 @@
+//! d3-geo: adaptive resampling of projected lines
+
+use super::GeoStream;
+use super::math::{EPSILON, RADIANS, asin, cartesian};
+
+const MAX_DEPTH: u32 = 16;
+
+/// A projected point with its longitude and Cartesian position on the sphere
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    x: f64,
+    y: f64,
+    lambda: f64,
+    c: [f64; 3],
+}
+
+impl Default for Sample {
+    fn default() -> Self {
+        Self {
+            x: f64::NAN,
+            y: f64::NAN,
+            lambda: f64::NAN,
+            c: [f64::NAN; 3],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Point,
+    Line,
+    RingFirst,
+    Ring,
+}
+
+/// Stream adapter projecting spherical coordinates (in radians), inserting
+/// points along each great arc until the projected line is within
+/// `sqrt(delta2)` pixels of the true curve
+///
+/// A `delta2` of zero projects points without resampling.
+pub(crate) struct Resample<'a, S: GeoStream> {
+    project: Box<dyn Fn(f64, f64) -> [f64; 2] + 'a>,
+    cos_min_distance: f64,
+    delta2: f64,
+    sink: S,
+    state: State,
+    in_polygon: bool,
+    // Previous and first point of the current line
+    p0: Sample,
+    p00: Sample,
+}
+
+impl<'a, S: GeoStream> Resample<'a, S> {
+    pub fn new(project: Box<dyn Fn(f64, f64) -> [f64; 2] + 'a>, delta2: f64, sink: S) -> Self {
+        Self {
+            project,
+            cos_min_distance: (30.0 * RADIANS).cos(),
+            delta2,
+            sink,
+            state: State::Point,
+            in_polygon: false,
+            p0: Sample::default(),
+            p00: Sample::default(),
+        }
+    }
+
+    fn sample(&self, lambda: f64, phi: f64) -> Sample {
+        let [x, y] = (self.project)(lambda, phi);
+        Sample {
+            x,
+            y,
+            lambda,
+            c: cartesian([lambda, phi]),
+        }
+    }
+
+    fn line_to(&mut self, s0: Sample, s1: Sample, depth: u32) {
+        let dx = s1.x - s0.x;
+        let dy = s1.y - s0.y;
+        let d2 = dx * dx + dy * dy;
+        if !(d2 > 4.0 * self.delta2 && depth > 0) {
+            return;
+        }
+        let [a0, b0, c0] = s0.c;
+        let [a1, b1, c1] = s1.c;
+        let (a, b, c) = (a0 + a1, b0 + b1, c0 + c1);
+        let m = (a * a + b * b + c * c).sqrt();
+        let c = c / m;
+        let phi2 = asin(c);
+        let lambda2 =
+            if ((c.abs() - 1.0).abs() < EPSILON) || (s0.lambda - s1.lambda).abs() < EPSILON {
+                (s0.lambda + s1.lambda) / 2.0
+            } else {
+                b.atan2(a)
+            };
+        let [x2, y2] = (self.project)(lambda2, phi2);
+        let dx2 = x2 - s0.x;
+        let dy2 = y2 - s0.y;
+        let dz = dy * dx2 - dx * dy2;
+        // Split if the midpoint is too far from the chord, too close to an
+        // end, or the arc spans too wide an angle
+        if dz * dz / d2 > self.delta2
+            || ((dx * dx2 + dy * dy2) / d2 - 0.5).abs() > 0.3
+            || a0 * a1 + b0 * b1 + c0 * c1 < self.cos_min_distance
+        {
+            let s2 = Sample {
+                x: x2,
+                y: y2,
+                lambda: lambda2,
+                c: [a / m, b / m, c],
+            };
+            self.line_to(s0, s2, depth - 1);
+            self.sink.point(x2, y2);
+            self.line_to(s2, s1, depth - 1);
+        }
+    }
+
+    fn line_point(&mut self, lambda: f64, phi: f64) {
+        let s1 = self.sample(lambda, phi);
+        if self.delta2 > 0.0 {
+            self.line_to(self.p0, s1, MAX_DEPTH);
+        }
+        self.p0 = s1;
+        self.sink.point(s1.x, s1.y);
+    }
+}
+
+impl<S: GeoStream> GeoStream for Resample<'_, S> {
+    fn point(&mut self, lambda: f64, phi: f64) {
+        match self.state {
+            State::Point => {
+                let [x, y] = (self.project)(lambda, phi);
+                self.sink.point(x, y);
+            }
+            State::Line => self.line_point(lambda, phi),
+            State::RingFirst => {
+                self.line_point(lambda, phi);
+                self.p00 = self.p0;
+                self.state = State::Ring;
+            }
+            State::Ring => self.line_point(lambda, phi),
+        }
+    }
+    fn line_start(&mut self) {
+        self.p0 = Sample::default();
+        self.state = if self.in_polygon {
+            State::RingFirst
+        } else {
+            State::Line
+        };
+        self.sink.line_start();
+    }
+    fn line_end(&mut self) {
+        // Close the ring with a resampled arc back to its first point
+        if self.state == State::Ring && self.delta2 > 0.0 {
+            self.line_to(self.p0, self.p00, MAX_DEPTH);
+        }
+        self.state = State::Point;
+        self.sink.line_end();
+    }
+    fn polygon_start(&mut self) {
+        self.in_polygon = true;
+        self.sink.polygon_start();
+    }
+    fn polygon_end(&mut self) {
+        self.in_polygon = false;
+        self.sink.polygon_end();
+    }
+    fn sphere(&mut self) {
+        self.sink.sphere();
+    }
+}
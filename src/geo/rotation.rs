@@ -0,0 +1,96 @@
+//! d3-geo: spherical rotation about the three axes
+
+use super::math::{DEGREES, PI, RADIANS, TAU, asin};
+
+/// Rotation in radians, composed of a yaw (λ) then pitch (φ) and roll (γ)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Rotation {
+    delta_lambda: f64,
+    // cos δφ, sin δφ, cos δγ, sin δγ
+    phi_gamma: Option<[f64; 4]>,
+}
+
+fn wrap(lambda: f64) -> f64 {
+    if lambda.abs() > PI {
+        lambda - (lambda / TAU).round() * TAU
+    } else {
+        lambda
+    }
+}
+
+impl Rotation {
+    pub fn new(delta_lambda: f64, delta_phi: f64, delta_gamma: f64) -> Self {
+        let phi_gamma = (delta_phi != 0.0 || delta_gamma != 0.0).then(|| {
+            [
+                delta_phi.cos(),
+                delta_phi.sin(),
+                delta_gamma.cos(),
+                delta_gamma.sin(),
+            ]
+        });
+        Self {
+            delta_lambda: delta_lambda % TAU,
+            phi_gamma,
+        }
+    }
+
+    pub fn forward(&self, mut lambda: f64, mut phi: f64) -> (f64, f64) {
+        if self.delta_lambda != 0.0 || self.phi_gamma.is_none() {
+            lambda = wrap(lambda + self.delta_lambda);
+        }
+        if let Some([cos_dp, sin_dp, cos_dg, sin_dg]) = self.phi_gamma {
+            let cos_phi = phi.cos();
+            let x = lambda.cos() * cos_phi;
+            let y = lambda.sin() * cos_phi;
+            let z = phi.sin();
+            let k = z * cos_dp + x * sin_dp;
+            lambda = (y * cos_dg - k * sin_dg).atan2(x * cos_dp - z * sin_dp);
+            phi = asin(k * cos_dg + y * sin_dg);
+        }
+        (lambda, phi)
+    }
+
+    pub fn invert(&self, mut lambda: f64, mut phi: f64) -> (f64, f64) {
+        if let Some([cos_dp, sin_dp, cos_dg, sin_dg]) = self.phi_gamma {
+            let cos_phi = phi.cos();
+            let x = lambda.cos() * cos_phi;
+            let y = lambda.sin() * cos_phi;
+            let z = phi.sin();
+            let k = z * cos_dg - y * sin_dg;
+            lambda = (y * cos_dg + z * sin_dg).atan2(x * cos_dp + k * sin_dp);
+            phi = asin(k * cos_dp - x * sin_dp);
+        }
+        if self.delta_lambda != 0.0 || self.phi_gamma.is_none() {
+            lambda = wrap(lambda - self.delta_lambda);
+        }
+        (lambda, phi)
+    }
+}
+
+/// Rotation of spherical coordinates in degrees (D3 `geoRotation`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoRotation {
+    rotation: Rotation,
+}
+
+/// Rotation by `[λ, φ, γ]` degrees of yaw, pitch and roll
+pub fn geo_rotation(angles: [f64; 3]) -> GeoRotation {
+    GeoRotation {
+        rotation: Rotation::new(
+            angles[0] * RADIANS,
+            angles[1] * RADIANS,
+            angles[2] * RADIANS,
+        ),
+    }
+}
+
+impl GeoRotation {
+    pub fn rotate(&self, [lambda, phi]: [f64; 2]) -> [f64; 2] {
+        let (lambda, phi) = self.rotation.forward(lambda * RADIANS, phi * RADIANS);
+        [lambda * DEGREES, phi * DEGREES]
+    }
+    pub fn invert(&self, [lambda, phi]: [f64; 2]) -> [f64; 2] {
+        let (lambda, phi) = self.rotation.invert(lambda * RADIANS, phi * RADIANS);
+        [lambda * DEGREES, phi * DEGREES]
+    }
+}
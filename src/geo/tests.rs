@@ -0,0 +1,416 @@
+//! Tests for d3-geo (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::geo::{
+        GeoPath, Sphere, geo_albers, geo_area, geo_azimuthal_equal_area, geo_azimuthal_equidistant,
+        geo_bounds, geo_centroid, geo_conic_equal_area, geo_distance, geo_equirectangular,
+        geo_gnomonic, geo_graticule, geo_graticule10, geo_length, geo_mercator, geo_orthographic,
+        geo_rotation, geo_stereographic, geo_transverse_mercator,
+    };
+    use crate::geojson::{GeoJson, GeoJsonFeature, GeoJsonGeometry, GeometryValue};
+    use crate::path::Path;
+    use serde_json::{Value, json};
+    use std::f64::consts::PI;
+
+    fn geometry(kind: &str, coordinates: Value) -> GeoJsonGeometry {
+        GeoJsonGeometry {
+            _type: kind.to_string(),
+            coordinates,
+        }
+    }
+
+    fn feature(kind: &str, coordinates: Value) -> GeoJsonFeature {
+        GeoJsonFeature {
+            _type: "Feature".to_string(),
+            properties: None,
+            geometry: geometry(kind, coordinates),
+        }
+    }
+
+    // Clockwise on the sphere: a small square, not its complement
+    fn square() -> GeoJsonGeometry {
+        geometry(
+            "Polygon",
+            json!([[[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]]]),
+        )
+    }
+
+    fn assert_close(actual: [f64; 2], expected: [f64; 2], tolerance: f64) {
+        assert!(
+            (actual[0] - expected[0]).abs() < tolerance
+                && (actual[1] - expected[1]).abs() < tolerance,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_projection_defaults() {
+        assert_close(
+            geo_equirectangular().project([0.0, 0.0]),
+            [480.0, 250.0],
+            1e-9,
+        );
+        assert_close(
+            geo_equirectangular().project([90.0, 45.0]),
+            [480.0 + 152.63 * PI / 2.0, 250.0 - 152.63 * PI / 4.0],
+            1e-9,
+        );
+        assert_close(geo_mercator().project([180.0, 0.0]), [960.5, 250.0], 1e-9);
+        assert_close(
+            geo_orthographic().project([90.0, 0.0]),
+            [729.5, 250.0],
+            1e-9,
+        );
+        assert_close(
+            geo_transverse_mercator().project([0.0, 0.0]),
+            [480.0, 250.0],
+            1e-9,
+        );
+        // San Francisco and New York on the US Albers
+        assert_close(
+            geo_albers().project([-122.4194, 37.775]),
+            [107.4, 214.1],
+            0.1,
+        );
+        assert_close(
+            geo_albers().project([-74.0059, 40.7128]),
+            [794.6, 176.5],
+            0.1,
+        );
+    }
+
+    #[test]
+    fn test_projection_invert_round_trip() {
+        let projections = [
+            geo_equirectangular().rotate([20.0, -10.0, 5.0]),
+            geo_mercator().angle(30.0),
+            geo_transverse_mercator(),
+            geo_conic_equal_area(),
+            geo_albers(),
+            geo_orthographic().rotate([-10.0, -40.0, 0.0]),
+            geo_azimuthal_equal_area(),
+            geo_azimuthal_equidistant(),
+            geo_stereographic().reflect_x(true),
+            geo_gnomonic(),
+        ];
+        for projection in projections {
+            for point in [[0.0, 0.0], [10.0, 20.0], [-30.0, -15.0]] {
+                let inverted = projection.invert(projection.project(point)).unwrap();
+                assert_close(inverted, point, 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_projection_accessors() {
+        let projection = geo_transverse_mercator()
+            .center([10.0, 20.0])
+            .rotate([30.0, 0.0, 0.0]);
+        assert_close(projection.get_center(), [10.0, 20.0], 1e-9);
+        assert!((projection.get_rotate()[0] - 30.0).abs() < 1e-9);
+        assert!(projection.get_rotate()[2].abs() < 1e-9);
+        assert_eq!(geo_albers().get_parallels(), Some([29.5, 45.5]));
+        assert_eq!(geo_mercator().get_parallels(), None);
+        assert_eq!(geo_orthographic().get_clip_angle(), Some(90.0 + 1e-6));
+        assert_eq!(geo_mercator().get_clip_angle(), None);
+        assert!((geo_mercator().get_precision() - 0.5f64.sqrt()).abs() < 1e-12);
+        // The center projects to the translation point
+        let projection = geo_mercator().center([12.0, 34.0]).translate([10.0, 20.0]);
+        assert_close(projection.project([12.0, 34.0]), [10.0, 20.0], 1e-9);
+    }
+
+    #[test]
+    fn test_fit_extent() {
+        let world = [[10.0, 20.0], [950.0, 480.0]];
+        for projection in [geo_equirectangular(), geo_mercator(), geo_orthographic()] {
+            let projection = projection.fit_extent(world, &square());
+            // Bounds are measured at scale 150, so resampling at the fitted
+            // scale may add a pixel or two
+            let [[x0, y0], [x1, y1]] = GeoPath::new().projection(projection).bounds(&square());
+            assert!(x0 >= 8.0 && x1 <= 952.0, "{x0} {x1}");
+            assert!(y0 >= 18.0 && y1 <= 482.0, "{y0} {y1}");
+            // The limiting dimension is filled, centered in the other
+            assert!((y1 - y0 - 460.0).abs() < 2.0);
+            assert!(((x0 + x1) / 2.0 - 480.0).abs() < 1e-6);
+        }
+        let projection = geo_equirectangular().fit_size([960.0, 500.0], &Sphere);
+        assert!((projection.get_scale() - 960.0 / (2.0 * PI)).abs() < 1e-6);
+        assert_close(projection.get_translate(), [480.0, 250.0], 1e-6);
+    }
+
+    #[test]
+    fn test_fit_width_height_keep_clip_extent() {
+        let extent = Some([[0.0, 0.0], [100.0, 100.0]]);
+        let projection = geo_equirectangular()
+            .clip_extent(extent)
+            .fit_width(200.0, &Sphere);
+        assert_eq!(projection.get_clip_extent(), extent);
+        assert_close(projection.get_translate(), [100.0, 50.0], 1e-6);
+        let projection = geo_equirectangular().fit_height(100.0, &Sphere);
+        assert_close(projection.get_translate(), [100.0, 50.0], 1e-6);
+    }
+
+    #[test]
+    fn test_path_without_projection() {
+        let path = GeoPath::new();
+        let line = geometry("LineString", json!([[0, 0], [10, 10], [10, 20]]));
+        assert_eq!(path.svg(&line), "M0 0 L10 10 L10 20");
+        assert_eq!(path.svg(&square()), "M0 0 L0 10 L10 10 L10 0Z");
+        assert_eq!(path.svg(&geometry("Unknown", json!([]))), "");
+        let point = geometry("Point", json!([1, 2]));
+        assert_eq!(
+            path.point_radius(1.0).svg(&point),
+            "M2 2 A1 1 0 0 1 0 2 A1 1 0 0 1 2 2"
+        );
+        // Rendering into a Path keeps full precision
+        let mut rendered = Path::new();
+        GeoPath::new().render(&geometry("Point", json!([0.12345, 0])), &mut rendered);
+        assert!(rendered.to_string().starts_with("M4.62345 0"));
+    }
+
+    #[test]
+    fn test_path_digits() {
+        let line = geometry("LineString", json!([[0.123456, -0.0001], [1, 1]]));
+        assert_eq!(GeoPath::new().svg(&line), "M0.123 0 L1 1");
+        assert_eq!(
+            GeoPath::new().digits(None).svg(&line),
+            "M0.123456 -0.0001 L1 1"
+        );
+    }
+
+    #[test]
+    fn test_path_measures() {
+        let path = GeoPath::new();
+        let polygon = square();
+        assert_eq!(path.area(&polygon), 100.0);
+        assert_eq!(path.bounds(&polygon), [[0.0, 0.0], [10.0, 10.0]]);
+        assert_eq!(path.centroid(&polygon), [5.0, 5.0]);
+        assert_eq!(path.measure(&polygon), 40.0);
+        let line = geometry("LineString", json!([[0, 0], [3, 4]]));
+        assert_eq!(path.area(&line), 0.0);
+        assert_eq!(path.measure(&line), 5.0);
+        assert_eq!(path.centroid(&line), [1.5, 2.0]);
+        let holed = geometry(
+            "Polygon",
+            json!([
+                [[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]],
+                [[2, 2], [4, 2], [4, 4], [2, 4], [2, 2]]
+            ]),
+        );
+        assert_eq!(path.area(&holed), 96.0);
+        assert!(path.centroid(&Vec::<GeoJsonFeature>::new())[0].is_nan());
+    }
+
+    #[test]
+    fn test_path_projected_features() {
+        let features = vec![
+            feature(
+                "Polygon",
+                json!([[[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]]]),
+            ),
+            feature("LineString", json!([[-20, 0], [-10, 5]])),
+        ];
+        let projection = geo_mercator().fit_size([960.0, 500.0], &features);
+        let d = GeoPath::new().projection(projection).svg(&features);
+        assert_eq!(d.matches('M').count(), 2);
+        assert_eq!(d.matches('Z').count(), 1);
+        assert!(GeoPath::new().projection(geo_mercator()).area(&square()) > 0.0);
+    }
+
+    #[test]
+    fn test_path_typed_geojson() {
+        let path = GeoPath::new();
+        let typed: GeoJson = json!({
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "geometry": serde_json::to_value(square()).unwrap(), "properties": null},
+                {"type": "Feature", "geometry": null, "properties": null}
+            ]
+        })
+        .try_into()
+        .unwrap();
+        assert_eq!(path.svg(&typed), path.svg(&square()));
+        assert_eq!(path.area(&typed), 100.0);
+    }
+
+    #[test]
+    fn test_resampling() {
+        let line = geometry("LineString", json!([[0, 0], [90, 60]]));
+        let resampled = GeoPath::new().projection(geo_mercator()).svg(&line);
+        assert!(resampled.matches('L').count() > 4, "{resampled}");
+        let unsampled = GeoPath::new()
+            .projection(geo_mercator().precision(0.0))
+            .svg(&line);
+        assert_eq!(unsampled.matches('L').count(), 1);
+    }
+
+    #[test]
+    fn test_antimeridian_cutting() {
+        let path = GeoPath::new().projection(geo_equirectangular());
+        let line = geometry("LineString", json!([[170, 0], [-170, 0]]));
+        assert_eq!(path.svg(&line).matches('M').count(), 2);
+        // A polygon straddling the antimeridian splits into two rings
+        let polygon = geometry(
+            "Polygon",
+            json!([[[170, -10], [170, 10], [-170, 10], [-170, -10], [170, -10]]]),
+        );
+        let d = path.svg(&polygon);
+        assert_eq!(d.matches('M').count(), 2, "{d}");
+        let [[x0, _], [x1, _]] = path.bounds(&polygon);
+        assert!((x0 - (480.0 - 152.63 * PI)).abs() < 1e-6);
+        assert!((x1 - (480.0 + 152.63 * PI)).abs() < 1e-6);
+        // The whole sphere fills the equirectangular frame
+        let area = path.area(&Sphere);
+        assert!((area - 152.63 * 152.63 * 2.0 * PI * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_small_circle_clipping() {
+        let path = GeoPath::new().projection(geo_orthographic());
+        assert_eq!(path.svg(&geometry("Point", json!([180, 0]))), "");
+        assert!(!path.svg(&geometry("Point", json!([0, 0]))).is_empty());
+        // A line over the horizon stops at the edge of the disc
+        let line = geometry("LineString", json!([[0, 0], [150, 0]]));
+        let [[_, _], [x1, _]] = path.bounds(&line);
+        assert!((x1 - (480.0 + 249.5)).abs() < 1e-3);
+        // The sphere is the visible disc
+        let area = path.area(&Sphere);
+        assert!((area - PI * 249.5 * 249.5).abs() / area < 1e-3);
+        // A polygon straddling the horizon is closed along the edge
+        let straddling = geometry(
+            "Polygon",
+            json!([[[60, -10], [60, 10], [120, 10], [120, -10], [60, -10]]]),
+        );
+        let d = path.svg(&straddling);
+        assert_eq!(
+            (d.matches('M').count(), d.matches('Z').count()),
+            (1, 1),
+            "{d}"
+        );
+        let [[x0, _], [x1, _]] = path.bounds(&straddling);
+        let corner = (10f64).to_radians().cos() * (60f64).to_radians().sin();
+        assert!((x0 - (480.0 + 249.5 * corner)).abs() < 1e-3, "{x0}");
+        // The horizon is interpolated every 2°
+        assert!((x1 - 729.5).abs() < 0.05, "{x1}");
+        // A polygon covering the back of the globe is invisible
+        let back = geometry(
+            "Polygon",
+            json!([[[170, -10], [170, 10], [-170, 10], [-170, -10], [170, -10]]]),
+        );
+        assert_eq!(path.svg(&back), "");
+    }
+
+    #[test]
+    fn test_clip_extent() {
+        let extent = [[400.0, 200.0], [560.0, 300.0]];
+        let path = GeoPath::new().projection(geo_equirectangular().clip_extent(Some(extent)));
+        let [[x0, y0], [x1, y1]] = path.bounds(&Sphere);
+        assert_eq!([[x0, y0], [x1, y1]], extent);
+        let line = geometry("LineString", json!([[-90, 0], [0, 0], [90, 0]]));
+        let [[x0, _], [x1, _]] = path.bounds(&line);
+        assert!(
+            (x0 - 400.0).abs() < 1e-9 && (x1 - 560.0).abs() < 1e-9,
+            "{x0} {x1}"
+        );
+        assert_eq!(path.svg(&geometry("Point", json!([90, 0]))), "");
+        assert!(!path.svg(&geometry("Point", json!([0, 0]))).is_empty());
+        // Mercator clips the poles to a square world
+        let [[_, y0], [_, y1]] = GeoPath::new().projection(geo_mercator()).bounds(&Sphere);
+        assert!((y1 - y0 - 961.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_geo_area() {
+        assert!((geo_area(&Sphere) - 4.0 * PI).abs() < 1e-9);
+        let hemisphere = geometry(
+            "Polygon",
+            json!([[[0, 0], [-90, 0], [180, 0], [90, 0], [0, 0]]]),
+        );
+        assert!((geo_area(&hemisphere) - 2.0 * PI).abs() < 1e-6);
+        // Counterclockwise rings enclose the rest of the sphere
+        let complement = geometry(
+            "Polygon",
+            json!([[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]]]),
+        );
+        assert!((geo_area(&complement) + geo_area(&square()) - 4.0 * PI).abs() < 1e-9);
+        assert_eq!(
+            geo_area(&geometry("LineString", json!([[0, 0], [1, 1]]))),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_geo_bounds() {
+        // The great arc along the northern edge bulges past 10°N
+        let [[west, south], [east, north]] = geo_bounds(&square());
+        assert_eq!([west, south, east], [0.0, 0.0, 10.0]);
+        assert!((north - 10.037423045910709).abs() < 1e-9);
+        let line = geometry("LineString", json!([[170, 0], [-170, 0]]));
+        let [[west, _], [east, _]] = geo_bounds(&line);
+        assert_eq!((west, east), (170.0, -170.0));
+        let arc = geometry("LineString", json!([[-45, 45], [45, 45]]));
+        assert!(geo_bounds(&arc)[1][1] > 54.0);
+        assert_eq!(geo_bounds(&Sphere), [[-180.0, -90.0], [180.0, 90.0]]);
+        assert!(geo_bounds(&Vec::<GeoJsonFeature>::new())[0][0].is_nan());
+    }
+
+    #[test]
+    fn test_geo_centroid() {
+        let points = geometry("MultiPoint", json!([[0, 0], [90, 0]]));
+        assert_close(geo_centroid(&points), [45.0, 0.0], 1e-9);
+        assert_close(geo_centroid(&square()), [5.0, 5.0], 0.1);
+        let line = geometry("LineString", json!([[0, 0], [0, 90]]));
+        assert_close(geo_centroid(&line), [0.0, 45.0], 1e-9);
+        assert!(geo_centroid(&Sphere)[0].is_nan());
+    }
+
+    #[test]
+    fn test_geo_distance_and_length() {
+        assert!((geo_distance([0.0, 0.0], [0.0, 90.0]) - PI / 2.0).abs() < 1e-12);
+        assert!((geo_distance([-180.0, 0.0], [180.0, 0.0])).abs() < 1e-12);
+        assert!((geo_distance([0.0, 0.0], [180.0, 0.0]) - PI).abs() < 1e-12);
+        let line = geometry("LineString", json!([[0, 0], [90, 0], [90, 90]]));
+        assert!((geo_length(&line) - PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_graticule() {
+        // Four major meridians and the equator, then minor lines every 10°
+        let lines = geo_graticule().lines();
+        assert_eq!(lines.len(), 4 + 1 + 32 + 16);
+        let GeometryValue::LineString(first) = &lines[0].value else {
+            panic!("expected a LineString, got {}", lines[0].value.type_name());
+        };
+        assert_eq!(first[0], vec![-180.0, -90.0 + 1e-6]);
+        assert!(lines.iter().all(|l| l.value.type_name() == "LineString"));
+        assert_eq!(geo_graticule10().value.type_name(), "MultiLineString");
+        let outline = geo_graticule()
+            .extent([[-10.0, -10.0], [10.0, 10.0]])
+            .outline();
+        let [[west, south], [east, north]] = geo_bounds(&outline);
+        assert_eq!([west, east], [-10.0, 10.0]);
+        assert!((south + 10.0).abs() < 0.01 && (north - 10.0).abs() < 0.01);
+        let graticule = geo_graticule().step([30.0, 30.0]);
+        assert_eq!(graticule.get_step_minor(), [30.0, 30.0]);
+        assert!(
+            !GeoPath::new()
+                .projection(geo_orthographic())
+                .svg(&geo_graticule10())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_rotation() {
+        let rotation = geo_rotation([90.0, 0.0, 0.0]);
+        assert_close(rotation.rotate([0.0, 0.0]), [90.0, 0.0], 1e-9);
+        assert_close(rotation.invert([90.0, 0.0]), [0.0, 0.0], 1e-9);
+        let rotation = geo_rotation([-45.0, 30.0, 10.0]);
+        assert_close(
+            rotation.invert(rotation.rotate([20.0, 40.0])),
+            [20.0, 40.0],
+            1e-9,
+        );
+    }
+}
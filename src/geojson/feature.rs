@@ -0,0 +1,275 @@
+//! GeoJSON features and feature collections (RFC 7946 §3.2–3.3)
+
+use super::{
+    Bbox, GeoJsonError, Geometry, JsonObject, bbox_from_json, insert_common, object_type,
+    take_foreign_members,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Number, Value, json};
+
+/// A feature identifier: a string or a number
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeatureId {
+    String(String),
+    Number(Number),
+}
+
+impl From<&str> for FeatureId {
+    fn from(id: &str) -> Self {
+        FeatureId::String(id.to_string())
+    }
+}
+
+impl From<String> for FeatureId {
+    fn from(id: String) -> Self {
+        FeatureId::String(id)
+    }
+}
+
+impl From<u64> for FeatureId {
+    fn from(id: u64) -> Self {
+        FeatureId::Number(id.into())
+    }
+}
+
+impl From<i64> for FeatureId {
+    fn from(id: i64) -> Self {
+        FeatureId::Number(id.into())
+    }
+}
+
+impl From<FeatureId> for Value {
+    fn from(id: FeatureId) -> Self {
+        match id {
+            FeatureId::String(s) => Value::String(s),
+            FeatureId::Number(n) => Value::Number(n),
+        }
+    }
+}
+
+/// A spatially bounded thing: an optional geometry with properties
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Feature {
+    pub id: Option<FeatureId>,
+    /// `None` for unlocated features (`"geometry": null`)
+    pub geometry: Option<Geometry>,
+    /// `None` for `"properties": null`
+    pub properties: Option<JsonObject>,
+    pub bbox: Option<Bbox>,
+    /// Members not defined by RFC 7946, kept for round trips
+    pub foreign_members: JsonObject,
+}
+
+impl Feature {
+    pub fn new(geometry: Geometry) -> Self {
+        Self {
+            geometry: Some(geometry),
+            ..Self::default()
+        }
+    }
+
+    pub fn property(&self, key: &str) -> Option<&Value> {
+        self.properties.as_ref()?.get(key)
+    }
+
+    /// Set a property, creating the properties object if needed
+    pub fn set_property(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.properties
+            .get_or_insert_with(JsonObject::new)
+            .insert(key.into(), value.into());
+    }
+
+    /// The GeoJSON object for this feature
+    pub fn to_json_object(&self) -> JsonObject {
+        let mut object = JsonObject::new();
+        object.insert("type".to_string(), json!("Feature"));
+        if let Some(id) = &self.id {
+            object.insert("id".to_string(), id.clone().into());
+        }
+        object.insert(
+            "geometry".to_string(),
+            self.geometry
+                .as_ref()
+                .map_or(Value::Null, |g| Value::Object(g.to_json_object())),
+        );
+        object.insert(
+            "properties".to_string(),
+            self.properties.clone().map_or(Value::Null, Value::Object),
+        );
+        insert_common(&mut object, &self.bbox, &self.foreign_members);
+        object
+    }
+
+    /// Parse and validate a feature object
+    pub fn from_json_object(mut object: JsonObject) -> Result<Self, GeoJsonError> {
+        let kind = object_type(&object)?;
+        if kind != "Feature" {
+            return Err(GeoJsonError::UnexpectedType {
+                expected: "Feature",
+                found: kind,
+            });
+        }
+        object.remove("type");
+        let id = match object.remove("id") {
+            None => None,
+            Some(Value::String(s)) => Some(FeatureId::String(s)),
+            Some(Value::Number(n)) => Some(FeatureId::Number(n)),
+            Some(_) => {
+                return Err(GeoJsonError::InvalidMember {
+                    member: "id",
+                    expected: "a string or number",
+                });
+            }
+        };
+        let geometry = match object.remove("geometry") {
+            None => return Err(GeoJsonError::MissingMember("geometry")),
+            Some(Value::Null) => None,
+            Some(geometry) => Some(Geometry::try_from(geometry)?),
+        };
+        // Missing properties are tolerated and read as null
+        let properties = match object.remove("properties") {
+            None | Some(Value::Null) => None,
+            Some(Value::Object(properties)) => Some(properties),
+            Some(_) => {
+                return Err(GeoJsonError::InvalidMember {
+                    member: "properties",
+                    expected: "an object or null",
+                });
+            }
+        };
+        let bbox = bbox_from_json(object.remove("bbox"))?;
+        Ok(Self {
+            id,
+            geometry,
+            properties,
+            bbox,
+            foreign_members: take_foreign_members(object),
+        })
+    }
+}
+
+impl From<Geometry> for Feature {
+    fn from(geometry: Geometry) -> Self {
+        Self::new(geometry)
+    }
+}
+
+impl TryFrom<Value> for Feature {
+    type Error = GeoJsonError;
+
+    fn try_from(value: Value) -> Result<Self, GeoJsonError> {
+        match value {
+            Value::Object(object) => Self::from_json_object(object),
+            _ => Err(GeoJsonError::ExpectedObject),
+        }
+    }
+}
+
+impl Serialize for Feature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json_object().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Feature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = JsonObject::deserialize(deserializer)?;
+        Self::from_json_object(object).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A list of features
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+    pub bbox: Option<Bbox>,
+    /// Members not defined by RFC 7946, kept for round trips
+    pub foreign_members: JsonObject,
+}
+
+impl FeatureCollection {
+    pub fn new(features: Vec<Feature>) -> Self {
+        Self {
+            features,
+            ..Self::default()
+        }
+    }
+
+    /// The GeoJSON object for this collection
+    pub fn to_json_object(&self) -> JsonObject {
+        let mut object = JsonObject::new();
+        object.insert("type".to_string(), json!("FeatureCollection"));
+        object.insert(
+            "features".to_string(),
+            Value::Array(
+                self.features
+                    .iter()
+                    .map(|f| Value::Object(f.to_json_object()))
+                    .collect(),
+            ),
+        );
+        insert_common(&mut object, &self.bbox, &self.foreign_members);
+        object
+    }
+
+    /// Parse and validate a feature collection object
+    pub fn from_json_object(mut object: JsonObject) -> Result<Self, GeoJsonError> {
+        let kind = object_type(&object)?;
+        if kind != "FeatureCollection" {
+            return Err(GeoJsonError::UnexpectedType {
+                expected: "FeatureCollection",
+                found: kind,
+            });
+        }
+        object.remove("type");
+        let features = match object.remove("features") {
+            None => return Err(GeoJsonError::MissingMember("features")),
+            Some(Value::Array(features)) => features
+                .into_iter()
+                .map(Feature::try_from)
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(GeoJsonError::InvalidMember {
+                    member: "features",
+                    expected: "an array",
+                });
+            }
+        };
+        let bbox = bbox_from_json(object.remove("bbox"))?;
+        Ok(Self {
+            features,
+            bbox,
+            foreign_members: take_foreign_members(object),
+        })
+    }
+}
+
+impl FromIterator<Feature> for FeatureCollection {
+    fn from_iter<I: IntoIterator<Item = Feature>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl TryFrom<Value> for FeatureCollection {
+    type Error = GeoJsonError;
+
+    fn try_from(value: Value) -> Result<Self, GeoJsonError> {
+        match value {
+            Value::Object(object) => Self::from_json_object(object),
+            _ => Err(GeoJsonError::ExpectedObject),
+        }
+    }
+}
+
+impl Serialize for FeatureCollection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json_object().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FeatureCollection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = JsonObject::deserialize(deserializer)?;
+        Self::from_json_object(object).map_err(serde::de::Error::custom)
+    }
+}
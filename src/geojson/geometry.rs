@@ -0,0 +1,209 @@
+//! GeoJSON geometry objects (RFC 7946 §3.1)
+
+use super::{Bbox, GeoJsonError, JsonObject, bbox_from_json, insert_common, take_foreign_members};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Value, json};
+
+/// `[longitude, latitude]` or `[longitude, latitude, altitude]`
+pub type Position = Vec<f64>;
+
+/// The coordinates of a geometry, by type
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeometryValue {
+    Point(Position),
+    MultiPoint(Vec<Position>),
+    LineString(Vec<Position>),
+    MultiLineString(Vec<Vec<Position>>),
+    /// Linear rings: the exterior first, then holes
+    Polygon(Vec<Vec<Position>>),
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+impl GeometryValue {
+    /// The GeoJSON `"type"` member
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            GeometryValue::Point(_) => "Point",
+            GeometryValue::MultiPoint(_) => "MultiPoint",
+            GeometryValue::LineString(_) => "LineString",
+            GeometryValue::MultiLineString(_) => "MultiLineString",
+            GeometryValue::Polygon(_) => "Polygon",
+            GeometryValue::MultiPolygon(_) => "MultiPolygon",
+            GeometryValue::GeometryCollection(_) => "GeometryCollection",
+        }
+    }
+}
+
+/// A geometry with its optional bounding box and foreign members
+#[derive(Debug, Clone, PartialEq)]
+pub struct Geometry {
+    pub value: GeometryValue,
+    pub bbox: Option<Bbox>,
+    /// Members not defined by RFC 7946, kept for round trips
+    pub foreign_members: JsonObject,
+}
+
+impl Geometry {
+    pub fn new(value: GeometryValue) -> Self {
+        Self {
+            value,
+            bbox: None,
+            foreign_members: JsonObject::new(),
+        }
+    }
+
+    /// The GeoJSON object for this geometry
+    pub fn to_json_object(&self) -> JsonObject {
+        let mut object = JsonObject::new();
+        object.insert("type".to_string(), json!(self.value.type_name()));
+        let (member, value) = match &self.value {
+            GeometryValue::Point(c) => ("coordinates", json!(c)),
+            GeometryValue::MultiPoint(c) | GeometryValue::LineString(c) => {
+                ("coordinates", json!(c))
+            }
+            GeometryValue::MultiLineString(c) | GeometryValue::Polygon(c) => {
+                ("coordinates", json!(c))
+            }
+            GeometryValue::MultiPolygon(c) => ("coordinates", json!(c)),
+            GeometryValue::GeometryCollection(geometries) => (
+                "geometries",
+                Value::Array(
+                    geometries
+                        .iter()
+                        .map(|g| Value::Object(g.to_json_object()))
+                        .collect(),
+                ),
+            ),
+        };
+        object.insert(member.to_string(), value);
+        insert_common(&mut object, &self.bbox, &self.foreign_members);
+        object
+    }
+
+    /// Parse and validate a geometry object
+    pub fn from_json_object(mut object: JsonObject) -> Result<Self, GeoJsonError> {
+        let kind = super::object_type(&object)?;
+        let member = if kind == "GeometryCollection" {
+            "geometries"
+        } else {
+            "coordinates"
+        };
+        let coordinates = object
+            .remove(member)
+            .ok_or(GeoJsonError::MissingMember(member))?;
+        let value = match kind.as_str() {
+            "Point" => GeometryValue::Point(position(&coordinates)?),
+            "MultiPoint" => GeometryValue::MultiPoint(positions(&coordinates)?),
+            "LineString" => GeometryValue::LineString(line_string(&coordinates)?),
+            "MultiLineString" => GeometryValue::MultiLineString(
+                array(&coordinates, "coordinates")?
+                    .iter()
+                    .map(line_string)
+                    .collect::<Result<_, _>>()?,
+            ),
+            "Polygon" => GeometryValue::Polygon(polygon(&coordinates)?),
+            "MultiPolygon" => GeometryValue::MultiPolygon(
+                array(&coordinates, "coordinates")?
+                    .iter()
+                    .map(polygon)
+                    .collect::<Result<_, _>>()?,
+            ),
+            "GeometryCollection" => {
+                let Value::Array(geometries) = coordinates else {
+                    return Err(GeoJsonError::InvalidMember {
+                        member: "geometries",
+                        expected: "an array",
+                    });
+                };
+                GeometryValue::GeometryCollection(
+                    geometries
+                        .into_iter()
+                        .map(Geometry::try_from)
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            _ => return Err(GeoJsonError::UnknownType(kind)),
+        };
+        let bbox = bbox_from_json(object.remove("bbox"))?;
+        object.remove("type");
+        Ok(Self {
+            value,
+            bbox,
+            foreign_members: take_foreign_members(object),
+        })
+    }
+}
+
+impl From<GeometryValue> for Geometry {
+    fn from(value: GeometryValue) -> Self {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<Value> for Geometry {
+    type Error = GeoJsonError;
+
+    fn try_from(value: Value) -> Result<Self, GeoJsonError> {
+        match value {
+            Value::Object(object) => Self::from_json_object(object),
+            _ => Err(GeoJsonError::ExpectedObject),
+        }
+    }
+}
+
+impl Serialize for Geometry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json_object().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Geometry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = JsonObject::deserialize(deserializer)?;
+        Self::from_json_object(object).map_err(serde::de::Error::custom)
+    }
+}
+
+fn array<'a>(value: &'a Value, member: &'static str) -> Result<&'a Vec<Value>, GeoJsonError> {
+    value.as_array().ok_or(GeoJsonError::InvalidMember {
+        member,
+        expected: "an array",
+    })
+}
+
+/// Two or more numbers
+pub(super) fn position(value: &Value) -> Result<Position, GeoJsonError> {
+    let position: Option<Position> = value
+        .as_array()
+        .and_then(|a| a.iter().map(Value::as_f64).collect());
+    position
+        .filter(|p| p.len() >= 2)
+        .ok_or(GeoJsonError::InvalidPosition)
+}
+
+fn positions(value: &Value) -> Result<Vec<Position>, GeoJsonError> {
+    array(value, "coordinates")?.iter().map(position).collect()
+}
+
+fn line_string(value: &Value) -> Result<Vec<Position>, GeoJsonError> {
+    let line = positions(value)?;
+    if line.len() < 2 {
+        return Err(GeoJsonError::InvalidLineString);
+    }
+    Ok(line)
+}
+
+// Rings are closed and have at least four positions
+fn polygon(value: &Value) -> Result<Vec<Vec<Position>>, GeoJsonError> {
+    array(value, "coordinates")?
+        .iter()
+        .map(|ring| {
+            let ring = positions(ring)?;
+            if ring.len() < 4 || ring.first() != ring.last() {
+                return Err(GeoJsonError::InvalidLinearRing);
+            }
+            Ok(ring)
+        })
+        .collect()
+}
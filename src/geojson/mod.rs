@@ -0,0 +1,366 @@
+//! GeoJSON (RFC 7946) data model
+//!
+//! [`GeoJson`] is a strongly typed GeoJSON object: a [`Geometry`], a
+//! [`Feature`] or a [`FeatureCollection`]. Parsing validates positions,
+//! line strings and linear rings, and keeps bounding boxes and foreign
+//! members so documents round-trip.
+//!
+//! [`GeoJsonFeature`] and [`GeoJsonGeometry`] are the loosely typed
+//! features produced by `contour`; convert them with `TryFrom`.
+//!
+//! Without serde_json's `preserve_order` feature, members are written in
+//! alphabetical order.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::geojson::{Feature, FeatureCollection, GeoJson, GeometryValue};
+//!
+//! let text = r#"{
+//!     "type": "FeatureCollection",
+//!     "features": [{
+//!         "type": "Feature",
+//!         "id": "nl",
+//!         "geometry": {"type": "Point", "coordinates": [4.9, 52.4]},
+//!         "properties": {"name": "Amsterdam"}
+//!     }],
+//!     "source": "example"
+//! }"#;
+//! let GeoJson::FeatureCollection(collection) = text.parse().unwrap() else {
+//!     panic!("expected a feature collection");
+//! };
+//! let feature = &collection.features[0];
+//! assert_eq!(feature.property("name").unwrap(), "Amsterdam");
+//! let geometry = feature.geometry.as_ref().unwrap();
+//! assert_eq!(geometry.value, GeometryValue::Point(vec![4.9, 52.4]));
+//! assert_eq!(collection.foreign_members["source"], "example");
+//!
+//! // Contour output converts to a typed collection
+//! use rust_d3::contour::contour_density;
+//! let contours = contour_density()
+//!     .size([40, 40])
+//!     .bandwidth(8.0)
+//!     .thresholds(vec![0.01])
+//!     .compute(&[vec![20.0, 20.0]]);
+//! let collection = FeatureCollection::try_from(contours.as_slice()).unwrap();
+//! assert_eq!(collection.features.len(), 1);
+//! ```
+
+mod feature;
+mod geometry;
+
+pub use feature::{Feature, FeatureCollection, FeatureId};
+pub use geometry::{Geometry, GeometryValue, Position};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A JSON object, as used for properties and foreign members
+pub type JsonObject = serde_json::Map<String, Value>;
+
+/// Bounding box: all minimums, then all maximums, e.g. `[west, south, east, north]`
+pub type Bbox = Vec<f64>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonFeature {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub properties: Option<HashMap<String, serde_json::Value>>,
+    pub geometry: GeoJsonGeometry,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonGeometry {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub coordinates: serde_json::Value, // Can be MultiLineString, Polygon, etc.
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonMultiLineString {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub coordinates: Vec<Vec<[f64; 2]>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoJsonPolygon {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub coordinates: Vec<Vec<Vec<[f64; 2]>>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoJsonError {
+    /// The input is not valid JSON
+    Json(String),
+    /// A GeoJSON object is not a JSON object
+    ExpectedObject,
+    /// The `"type"` member is not a GeoJSON type
+    UnknownType(String),
+    /// The object is valid GeoJSON, but not of the requested type
+    UnexpectedType {
+        expected: &'static str,
+        found: String,
+    },
+    /// A required member is missing
+    MissingMember(&'static str),
+    /// A member has the wrong JSON type
+    InvalidMember {
+        member: &'static str,
+        expected: &'static str,
+    },
+    /// A position is not an array of two or more numbers
+    InvalidPosition,
+    /// A line string has fewer than two positions
+    InvalidLineString,
+    /// A linear ring has fewer than four positions or is not closed
+    InvalidLinearRing,
+    /// A bounding box is not an even number (at least four) of numbers
+    InvalidBbox,
+}
+
+impl fmt::Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoJsonError::Json(e) => write!(f, "invalid JSON: {}", e),
+            GeoJsonError::ExpectedObject => write!(f, "expected a JSON object"),
+            GeoJsonError::UnknownType(t) => write!(f, "unknown GeoJSON type '{}'", t),
+            GeoJsonError::UnexpectedType { expected, found } => {
+                write!(f, "expected a {}, found a {}", expected, found)
+            }
+            GeoJsonError::MissingMember(m) => write!(f, "missing member '{}'", m),
+            GeoJsonError::InvalidMember { member, expected } => {
+                write!(f, "member '{}' must be {}", member, expected)
+            }
+            GeoJsonError::InvalidPosition => {
+                write!(f, "a position must be an array of at least two numbers")
+            }
+            GeoJsonError::InvalidLineString => {
+                write!(f, "a line string must have at least two positions")
+            }
+            GeoJsonError::InvalidLinearRing => {
+                write!(
+                    f,
+                    "a linear ring must be closed and have at least four positions"
+                )
+            }
+            GeoJsonError::InvalidBbox => {
+                write!(
+                    f,
+                    "a bounding box must be an even number of at least four numbers"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeoJsonError {}
+
+/// Any GeoJSON object
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoJson {
+    Geometry(Geometry),
+    Feature(Feature),
+    FeatureCollection(FeatureCollection),
+}
+
+impl GeoJson {
+    /// The GeoJSON object as JSON
+    pub fn to_json_object(&self) -> JsonObject {
+        match self {
+            GeoJson::Geometry(g) => g.to_json_object(),
+            GeoJson::Feature(f) => f.to_json_object(),
+            GeoJson::FeatureCollection(c) => c.to_json_object(),
+        }
+    }
+
+    /// Parse and validate any GeoJSON object
+    pub fn from_json_object(object: JsonObject) -> Result<Self, GeoJsonError> {
+        match object_type(&object)?.as_str() {
+            "Feature" => Feature::from_json_object(object).map(GeoJson::Feature),
+            "FeatureCollection" => {
+                FeatureCollection::from_json_object(object).map(GeoJson::FeatureCollection)
+            }
+            _ => Geometry::from_json_object(object).map(GeoJson::Geometry),
+        }
+    }
+
+    /// All features: the collection's, the feature itself, or the geometry
+    /// wrapped in a feature without properties
+    pub fn into_features(self) -> Vec<Feature> {
+        match self {
+            GeoJson::Geometry(g) => vec![Feature::new(g)],
+            GeoJson::Feature(f) => vec![f],
+            GeoJson::FeatureCollection(c) => c.features,
+        }
+    }
+}
+
+impl From<Geometry> for GeoJson {
+    fn from(geometry: Geometry) -> Self {
+        GeoJson::Geometry(geometry)
+    }
+}
+
+impl From<Feature> for GeoJson {
+    fn from(feature: Feature) -> Self {
+        GeoJson::Feature(feature)
+    }
+}
+
+impl From<FeatureCollection> for GeoJson {
+    fn from(collection: FeatureCollection) -> Self {
+        GeoJson::FeatureCollection(collection)
+    }
+}
+
+impl TryFrom<Value> for GeoJson {
+    type Error = GeoJsonError;
+
+    fn try_from(value: Value) -> Result<Self, GeoJsonError> {
+        match value {
+            Value::Object(object) => Self::from_json_object(object),
+            _ => Err(GeoJsonError::ExpectedObject),
+        }
+    }
+}
+
+impl FromStr for GeoJson {
+    type Err = GeoJsonError;
+
+    fn from_str(s: &str) -> Result<Self, GeoJsonError> {
+        let value: Value =
+            serde_json::from_str(s).map_err(|e| GeoJsonError::Json(e.to_string()))?;
+        Self::try_from(value)
+    }
+}
+
+/// Compact JSON text
+impl fmt::Display for GeoJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Value::Object(self.to_json_object()))
+    }
+}
+
+impl Serialize for GeoJson {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json_object().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoJson {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let object = JsonObject::deserialize(deserializer)?;
+        Self::from_json_object(object).map_err(serde::de::Error::custom)
+    }
+}
+
+impl TryFrom<&GeoJsonGeometry> for Geometry {
+    type Error = GeoJsonError;
+
+    /// Open rings, as emitted by `contour`, are closed before validation
+    fn try_from(geometry: &GeoJsonGeometry) -> Result<Self, GeoJsonError> {
+        let mut coordinates = geometry.coordinates.clone();
+        match geometry._type.as_str() {
+            "Polygon" => close_rings(&mut coordinates),
+            "MultiPolygon" => {
+                if let Value::Array(polygons) = &mut coordinates {
+                    polygons.iter_mut().for_each(close_rings);
+                }
+            }
+            _ => {}
+        }
+        let mut object = JsonObject::new();
+        object.insert("type".to_string(), Value::String(geometry._type.clone()));
+        object.insert("coordinates".to_string(), coordinates);
+        Geometry::from_json_object(object)
+    }
+}
+
+fn close_rings(rings: &mut Value) {
+    let Value::Array(rings) = rings else {
+        return;
+    };
+    for ring in rings {
+        if let Value::Array(ring) = ring
+            && let Some(first) = ring.first().cloned()
+            && ring.last() != Some(&first)
+        {
+            ring.push(first);
+        }
+    }
+}
+
+impl TryFrom<&GeoJsonFeature> for Feature {
+    type Error = GeoJsonError;
+
+    fn try_from(feature: &GeoJsonFeature) -> Result<Self, GeoJsonError> {
+        Ok(Feature {
+            geometry: Some(Geometry::try_from(&feature.geometry)?),
+            properties: feature
+                .properties
+                .as_ref()
+                .map(|p| p.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+            ..Feature::default()
+        })
+    }
+}
+
+/// Typed collection of, e.g., `ContourGenerator::contours` or
+/// `ContourDensity::compute` output
+impl TryFrom<&[GeoJsonFeature]> for FeatureCollection {
+    type Error = GeoJsonError;
+
+    fn try_from(features: &[GeoJsonFeature]) -> Result<Self, GeoJsonError> {
+        features.iter().map(Feature::try_from).collect()
+    }
+}
+
+/// The `"type"` member of a GeoJSON object
+fn object_type(object: &JsonObject) -> Result<String, GeoJsonError> {
+    match object.get("type") {
+        Some(Value::String(kind)) => Ok(kind.clone()),
+        Some(_) => Err(GeoJsonError::InvalidMember {
+            member: "type",
+            expected: "a string",
+        }),
+        None => Err(GeoJsonError::MissingMember("type")),
+    }
+}
+
+fn bbox_from_json(value: Option<Value>) -> Result<Option<Bbox>, GeoJsonError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let bbox: Option<Bbox> = value
+        .as_array()
+        .and_then(|a| a.iter().map(Value::as_f64).collect());
+    match bbox {
+        Some(bbox) if bbox.len() >= 4 && bbox.len() % 2 == 0 => Ok(Some(bbox)),
+        _ => Err(GeoJsonError::InvalidBbox),
+    }
+}
+
+// Whatever members remain once the known ones are removed
+fn take_foreign_members(object: JsonObject) -> JsonObject {
+    object
+}
+
+fn insert_common(object: &mut JsonObject, bbox: &Option<Bbox>, foreign_members: &JsonObject) {
+    if let Some(bbox) = bbox {
+        object.insert("bbox".to_string(), serde_json::json!(bbox));
+    }
+    for (key, value) in foreign_members {
+        object.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests;
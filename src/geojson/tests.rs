@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use crate::contour::contour_density;
+    use crate::geojson::*;
+    use serde_json::{Value, json};
+
+    fn round_trip(value: Value) {
+        let parsed = GeoJson::try_from(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn test_geometry_types_round_trip() {
+        round_trip(json!({"type": "Point", "coordinates": [1.0, 2.0, 3.0]}));
+        round_trip(json!({"type": "MultiPoint", "coordinates": [[1.0, 2.0], [3.0, 4.0]]}));
+        round_trip(json!({"type": "LineString", "coordinates": [[1.0, 2.0], [3.0, 4.0]]}));
+        round_trip(json!({
+            "type": "MultiLineString",
+            "coordinates": [[[1.0, 2.0], [3.0, 4.0]], [[5.0, 6.0], [7.0, 8.0]]]
+        }));
+        round_trip(json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]
+        }));
+        round_trip(json!({
+            "type": "MultiPolygon",
+            "coordinates": [[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]]
+        }));
+        round_trip(json!({
+            "type": "GeometryCollection",
+            "geometries": [
+                {"type": "Point", "coordinates": [1.0, 2.0]},
+                {"type": "LineString", "coordinates": [[1.0, 2.0], [3.0, 4.0]]}
+            ]
+        }));
+    }
+
+    #[test]
+    fn test_bbox_and_foreign_members_round_trip() {
+        let value = json!({
+            "type": "FeatureCollection",
+            "bbox": [-10.0, -10.0, 10.0, 10.0],
+            "name": "collection",
+            "features": [{
+                "type": "Feature",
+                "id": 7,
+                "bbox": [1.0, 2.0, 1.0, 2.0],
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [1.0, 2.0],
+                    "crs": {"name": "legacy"}
+                },
+                "properties": {"kind": "city", "rank": 1},
+                "title": "Example"
+            }]
+        });
+        round_trip(value.clone());
+
+        let collection = FeatureCollection::try_from(value).unwrap();
+        assert_eq!(collection.bbox, Some(vec![-10.0, -10.0, 10.0, 10.0]));
+        assert_eq!(collection.foreign_members["name"], "collection");
+        let feature = &collection.features[0];
+        assert_eq!(feature.id, Some(FeatureId::from(7u64)));
+        assert_eq!(feature.foreign_members["title"], "Example");
+        assert_eq!(feature.property("kind").unwrap(), "city");
+        let geometry = feature.geometry.as_ref().unwrap();
+        assert_eq!(geometry.foreign_members["crs"]["name"], "legacy");
+    }
+
+    #[test]
+    fn test_feature_null_members() {
+        let feature: Feature =
+            serde_json::from_str(r#"{"type": "Feature", "id": "a", "geometry": null}"#).unwrap();
+        assert_eq!(feature.id, Some(FeatureId::from("a")));
+        assert!(feature.geometry.is_none());
+        assert!(feature.properties.is_none());
+        assert_eq!(
+            serde_json::to_value(&feature).unwrap(),
+            json!({"type": "Feature", "id": "a", "geometry": null, "properties": null})
+        );
+
+        let mut feature = Feature::new(Geometry::new(GeometryValue::Point(vec![0.0, 0.0])));
+        feature.set_property("name", "origin");
+        assert_eq!(feature.property("name").unwrap(), "origin");
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = r#"{"type":"Point","coordinates":[1.5,2.5]}"#;
+        let geojson: GeoJson = text.parse().unwrap();
+        assert_eq!(
+            geojson,
+            GeoJson::Geometry(GeometryValue::Point(vec![1.5, 2.5]).into())
+        );
+        assert_eq!(geojson.to_string().parse::<GeoJson>().unwrap(), geojson);
+        assert_eq!(geojson.into_features().len(), 1);
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let parse = |value: Value| GeoJson::try_from(value).unwrap_err();
+        assert!(matches!("{".parse::<GeoJson>(), Err(GeoJsonError::Json(_))));
+        assert_eq!(parse(json!([1, 2])), GeoJsonError::ExpectedObject);
+        assert_eq!(
+            parse(json!({"coordinates": [0, 0]})),
+            GeoJsonError::MissingMember("type")
+        );
+        assert_eq!(
+            parse(json!({"type": "Circle", "coordinates": [0, 0]})),
+            GeoJsonError::UnknownType("Circle".to_string())
+        );
+        assert_eq!(
+            parse(json!({"type": "Point", "coordinates": [0]})),
+            GeoJsonError::InvalidPosition
+        );
+        assert_eq!(
+            parse(json!({"type": "LineString", "coordinates": [[0, 0]]})),
+            GeoJsonError::InvalidLineString
+        );
+        assert_eq!(
+            parse(json!({"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]]})),
+            GeoJsonError::InvalidLinearRing
+        );
+        assert_eq!(
+            parse(json!({"type": "Point", "coordinates": [0, 0], "bbox": [0, 0, 1]})),
+            GeoJsonError::InvalidBbox
+        );
+        assert_eq!(
+            parse(json!({"type": "Feature", "properties": {}})),
+            GeoJsonError::MissingMember("geometry")
+        );
+        assert_eq!(
+            Feature::try_from(json!({"type": "Point", "coordinates": [0, 0]})).unwrap_err(),
+            GeoJsonError::UnexpectedType {
+                expected: "Feature",
+                found: "Point".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_contour_output() {
+        let contours = contour_density()
+            .size([40, 40])
+            .bandwidth(8.0)
+            .thresholds(vec![0.01])
+            .compute(&[vec![20.0, 20.0]]);
+        let collection = FeatureCollection::try_from(contours.as_slice()).unwrap();
+        assert_eq!(collection.features.len(), 1);
+        let feature = &collection.features[0];
+        assert_eq!(feature.property("value").unwrap(), 0.01);
+        let geometry = feature.geometry.as_ref().unwrap();
+        let GeometryValue::MultiPolygon(polygons) = &geometry.value else {
+            panic!(
+                "expected a MultiPolygon, got {}",
+                geometry.value.type_name()
+            );
+        };
+        assert!(!polygons.is_empty());
+        for ring in polygons.iter().flatten() {
+            assert_eq!(ring.first(), ring.last());
+        }
+    }
+
+    #[test]
+    fn test_from_legacy_feature() {
+        let mut properties = std::collections::HashMap::new();
+        properties.insert("value".to_string(), json!(1));
+        let legacy = GeoJsonFeature {
+            _type: "Feature".to_string(),
+            properties: Some(properties),
+            geometry: GeoJsonGeometry {
+                _type: "Polygon".to_string(),
+                coordinates: json!([[[0, 0], [1, 0], [1, 1]]]),
+            },
+        };
+        let feature = Feature::try_from(&legacy).unwrap();
+        assert_eq!(feature.property("value").unwrap(), 1);
+        assert_eq!(
+            feature.geometry.unwrap().value,
+            GeometryValue::Polygon(vec![vec![
+                vec![0.0, 0.0],
+                vec![1.0, 0.0],
+                vec![1.0, 1.0],
+                vec![0.0, 0.0]
+            ]])
+        );
+    }
+}
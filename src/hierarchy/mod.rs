@@ -3,14 +3,19 @@
 pub mod cluster;
 pub mod node;
 pub mod partition;
+pub mod stratify;
 pub mod tree;
 pub mod treemap;
 
 pub use cluster::ClusterLayout;
-pub use node::Node;
+pub use node::{Hierarchy, Node, NodeId};
 pub use partition::PartitionLayout;
+pub use stratify::{Stratify, StratifyError, hierarchy, stratify};
 pub use tree::TreeLayout;
-pub use treemap::TreemapLayout;
+pub use treemap::{
+    Tile, TreemapLayout, treemap_binary, treemap_dice, treemap_resquarify, treemap_slice,
+    treemap_slice_dice, treemap_squarify,
+};
 
 #[cfg(test)]
 pub mod tests;
@@ -1,87 +1,283 @@
-//! Hierarchy Node structure (d3-hierarchy)
-///
+//! Hierarchy nodes and the arena that links them (d3-hierarchy)
+//!
+//! A [`Hierarchy`] owns every node of a tree; nodes refer to their parent and
+//! children by [`NodeId`], so walking up or down the tree is safe and cheap.
+//! Traversals and aggregations take the id of the node to start from, like
+//! the node methods of D3.
+//!
+//! # Example
+//! ```rust
+//! use rust_d3::hierarchy::Hierarchy;
+//!
+//! let mut h = Hierarchy::new("root");
+//! let docs = h.add_child(h.root(), "docs");
+//! let guide = h.add_child(docs, "guide.md");
+//! h.add_child(h.root(), "readme.md");
+//!
+//! // Breadcrumbs, from the root down to a node
+//! let mut crumbs: Vec<&str> = h.ancestors(guide).map(|id| h[id].data).collect();
+//! crumbs.reverse();
+//! assert_eq!(crumbs, ["root", "docs", "guide.md"]);
+//! assert_eq!(h[guide].depth, 2);
+//! assert_eq!(h[h.root()].height, 2);
+//! ```
+
+use super::treemap::SquarifyRows;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+/// Identifies a node within its [`Hierarchy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Position of the node in [`Hierarchy::nodes`]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
 /// A node in a tree or hierarchy.
 ///
-///
-//// # Fields
-//// - `data`: The user data for this node.
-//// - `children`: Child nodes.
-//// - `parent`: Optional pointer to parent node.
-//// - `depth`: Depth from root (0 for root).
-//// - `height`: Height to furthest leaf.
-//// - `value`: Aggregated value (e.g., for sum).
-//// - `x`, `y`: Layout coordinates (if computed).
-
+/// # Fields
+/// - `data`: The user data for this node.
+/// - `id`: Identifier assigned by `stratify`.
+/// - `depth`: Depth from root (0 for root).
+/// - `height`: Height to furthest leaf.
+/// - `value`: Aggregated value (e.g., for sum).
+/// - `x`, `y`: Layout coordinates (if computed).
+/// - `x0`, `y0`, `x1`, `y1`: Rectangle from the treemap layout.
 #[derive(Debug, Clone)]
 pub struct Node<T> {
     pub data: T,
-    pub children: Vec<Node<T>>,
-    pub parent: Option<*const Node<T>>,
+    pub id: Option<String>,
     pub depth: usize,
     pub height: usize,
     pub value: Option<f64>,
     pub x: Option<f64>,
     pub y: Option<f64>,
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    // Rows from the last resquarify tiling of the children
+    pub(crate) squarify: Option<SquarifyRows>,
 }
 
 impl<T> Node<T> {
-    /// Create a new node with the given data.
-    pub fn new(data: T) -> Self {
+    fn new(data: T, parent: Option<NodeId>, depth: usize) -> Self {
         Node {
             data,
-            children: Vec::new(),
-            parent: None,
-            depth: 0,
+            id: None,
+            depth,
             height: 0,
             value: None,
             x: None,
             y: None,
+            x0: 0.0,
+            y0: 0.0,
+            x1: 0.0,
+            y1: 0.0,
+            parent,
+            children: Vec::new(),
+            squarify: None,
         }
     }
-    /// Add a child node.
-    pub fn add_child(&mut self, mut child: Node<T>) {
-        child.parent = Some(self as *const _);
-        self.children.push(child);
+    /// The parent, or `None` for the root
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+    /// The children, in order
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
     }
     /// Returns true if this node is a leaf.
     pub fn is_leaf(&self) -> bool {
         self.children.is_empty()
     }
-    /// Recursively compute depths for this node and its children.
-    pub fn compute_depths(&mut self, depth: usize) {
-        self.depth = depth;
-        for child in &mut self.children {
-            child.compute_depths(depth + 1);
+}
+
+/// A tree of [`Node`]s with a single root
+#[derive(Debug, Clone)]
+pub struct Hierarchy<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> Hierarchy<T> {
+    /// A hierarchy with only a root holding `data`
+    pub fn new(data: T) -> Self {
+        Hierarchy {
+            nodes: vec![Node::new(data, None, 0)],
         }
     }
-    /// Recursively compute heights for this node and its children.
-    pub fn compute_heights(&mut self) -> usize {
-        if self.children.is_empty() {
-            self.height = 0;
-        } else {
-            self.height = 1 + self
-                .children
-                .iter_mut()
-                .map(|c| c.compute_heights())
-                .max()
-                .unwrap_or(0);
+    /// Add a child holding `data` after the other children of `parent`;
+    /// depths and heights are kept up to date
+    pub fn add_child(&mut self, parent: NodeId, data: T) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        let depth = self.nodes[parent.0].depth + 1;
+        self.nodes.push(Node::new(data, Some(parent), depth));
+        self.nodes[parent.0].children.push(id);
+        let (mut node, mut height) = (Some(parent), 1);
+        while let Some(n) = node
+            && self.nodes[n.0].height < height
+        {
+            self.nodes[n.0].height = height;
+            node = self.nodes[n.0].parent;
+            height += 1;
         }
-        self.height
+        id
+    }
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+    /// All nodes, in the order they were added; [`NodeId::index`] is the
+    /// position of a node here
+    pub fn nodes(&self) -> &[Node<T>] {
+        &self.nodes
+    }
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes[node.0].parent
+    }
+    pub fn children(&self, node: NodeId) -> &[NodeId] {
+        &self.nodes[node.0].children
     }
-    /// Traverse the tree, calling `f` on each node (preorder).
-    pub fn each<F: FnMut(&mut Node<T>)>(&mut self, f: &mut F) {
-        f(self);
-        for child in &mut self.children {
-            child.each(f);
+    /// `node`, its parent, and so on up to the root
+    pub fn ancestors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(Some(node), move |&n| self.nodes[n.0].parent)
+    }
+    /// `node` and its descendants, breadth-first
+    pub fn descendants(&self, node: NodeId) -> Vec<NodeId> {
+        self.each_bfs(node).collect()
+    }
+    /// The leaves under `node` (or `node` itself), in pre-order
+    pub fn leaves(&self, node: NodeId) -> Vec<NodeId> {
+        self.each_before(node)
+            .filter(|&n| self.nodes[n.0].is_leaf())
+            .collect()
+    }
+    /// `(parent, child)` pairs for every link under `node`, breadth-first
+    pub fn links(&self, node: NodeId) -> Vec<(NodeId, NodeId)> {
+        self.each_bfs(node)
+            .skip(1)
+            .map(|n| (self.nodes[n.0].parent.unwrap(), n))
+            .collect()
+    }
+    /// The shortest path from `from` to `to`: up to their least common
+    /// ancestor, then down
+    pub fn path(&self, from: NodeId, to: NodeId) -> Vec<NodeId> {
+        let mut up: Vec<NodeId> = self.ancestors(from).collect();
+        let mut down: Vec<NodeId> = self.ancestors(to).collect();
+        // Drop the shared ancestors above the least common one
+        while up.len() > 1 && down.len() > 1 && up[up.len() - 2] == down[down.len() - 2] {
+            up.pop();
+            down.pop();
+        }
+        down.pop();
+        up.extend(down.into_iter().rev());
+        up
+    }
+    /// The first node under `node` (or `node` itself), breadth-first, for
+    /// which `predicate` is true
+    pub fn find<F>(&self, node: NodeId, mut predicate: F) -> Option<NodeId>
+    where
+        F: FnMut(&Node<T>) -> bool,
+    {
+        self.each_bfs(node).find(|&n| predicate(&self.nodes[n.0]))
+    }
+    /// Pre-order: each node before its children
+    pub fn each_before(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack = vec![node];
+        std::iter::from_fn(move || {
+            let n = stack.pop()?;
+            stack.extend(self.nodes[n.0].children.iter().rev());
+            Some(n)
+        })
+    }
+    /// Post-order: each node after its children
+    pub fn each_after(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack = vec![node];
+        let mut order = Vec::new();
+        while let Some(n) = stack.pop() {
+            order.push(n);
+            stack.extend(&self.nodes[n.0].children);
+        }
+        order.into_iter().rev()
+    }
+    /// Breadth-first: each depth in turn
+    pub fn each_bfs(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut queue = VecDeque::from([node]);
+        std::iter::from_fn(move || {
+            let n = queue.pop_front()?;
+            queue.extend(&self.nodes[n.0].children);
+            Some(n)
+        })
+    }
+    /// Call `f` on each node, breadth-first
+    pub fn each<F: FnMut(&mut Node<T>)>(&mut self, mut f: F) {
+        for n in self.descendants(self.root()) {
+            f(&mut self.nodes[n.0]);
         }
     }
-    /// Aggregate values for this node and its children using `value_fn`.
+    /// Aggregate values for each node from `value_fn` of its own data plus
+    /// its children's values; returns the root's value
     pub fn sum<F: Fn(&T) -> f64>(&mut self, value_fn: &F) -> f64 {
-        let mut sum = value_fn(&self.data);
-        for child in &mut self.children {
-            sum += child.sum(value_fn);
+        let order: Vec<NodeId> = self.each_after(self.root()).collect();
+        for n in order {
+            let children: f64 = self.nodes[n.0]
+                .children
+                .iter()
+                .map(|c| self.nodes[c.0].value.unwrap_or(0.0))
+                .sum();
+            let node = &mut self.nodes[n.0];
+            node.value = Some(value_fn(&node.data) + children);
         }
-        self.value = Some(sum);
-        sum
+        self.nodes[0].value.unwrap_or(0.0)
+    }
+    /// Set each node's value to the number of leaves under it (1 for a leaf)
+    pub fn count(&mut self) -> &mut Self {
+        let order: Vec<NodeId> = self.each_after(self.root()).collect();
+        for n in order {
+            let node = &self.nodes[n.0];
+            let count = if node.is_leaf() {
+                1.0
+            } else {
+                node.children
+                    .iter()
+                    .map(|c| self.nodes[c.0].value.unwrap_or(0.0))
+                    .sum()
+            };
+            self.nodes[n.0].value = Some(count);
+        }
+        self
+    }
+    /// Sort the children of every node with `compare`, e.g. by descending
+    /// value before a treemap layout
+    pub fn sort<F>(&mut self, mut compare: F) -> &mut Self
+    where
+        F: FnMut(&Node<T>, &Node<T>) -> Ordering,
+    {
+        for i in 0..self.nodes.len() {
+            let mut children = std::mem::take(&mut self.nodes[i].children);
+            children.sort_by(|a, b| compare(&self.nodes[a.0], &self.nodes[b.0]));
+            self.nodes[i].children = children;
+        }
+        self
+    }
+}
+
+impl<T> Index<NodeId> for Hierarchy<T> {
+    type Output = Node<T>;
+
+    fn index(&self, id: NodeId) -> &Node<T> {
+        &self.nodes[id.0]
+    }
+}
+
+impl<T> IndexMut<NodeId> for Hierarchy<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut Node<T> {
+        &mut self.nodes[id.0]
     }
 }
@@ -1,6 +1,6 @@
 //! d3-hierarchy partition layout
 
-use super::node::Node;
+use super::node::{Hierarchy, NodeId};
 
 pub struct PartitionLayout {
     pub size: (f64, f64),
@@ -14,23 +14,23 @@ impl PartitionLayout {
         self.size = size;
         self
     }
-    pub fn layout<T>(&self, root: &mut Node<T>) {
-        let n = root.children.len();
-        if n == 0 {
-            root.x = Some(0.0);
-            root.y = Some(0.0);
-            return;
-        }
-        let w = self.size.0;
-        let h = self.size.1;
-        let child_h = h / n as f64;
-        for (i, child) in root.children.iter_mut().enumerate() {
-            child.x = Some(0.0);
-            child.y = Some(i as f64 * child_h);
-            // Recursively layout children (horizontal split)
-            PartitionLayout { size: (w, child_h) }.layout(child);
+    pub fn layout<T>(&self, h: &mut Hierarchy<T>) {
+        self.layout_node(h, h.root());
+    }
+    fn layout_node<T>(&self, h: &mut Hierarchy<T>, node: NodeId) {
+        let children = h.children(node).to_vec();
+        let n = children.len();
+        if n > 0 {
+            let (w, height) = self.size;
+            let child_h = height / n as f64;
+            for (i, &child) in children.iter().enumerate() {
+                h[child].x = Some(0.0);
+                h[child].y = Some(i as f64 * child_h);
+                // Recursively layout children (horizontal split)
+                PartitionLayout { size: (w, child_h) }.layout_node(h, child);
+            }
         }
-        root.x = Some(0.0);
-        root.y = Some(0.0);
+        h[node].x = Some(0.0);
+        h[node].y = Some(0.0);
     }
 }
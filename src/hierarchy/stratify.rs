@@ -0,0 +1,330 @@
+//! d3-hierarchy stratify: trees from flat tables
+//!
+//! Rows name their own id and their parent's id (or a path such as
+//! `/a/b/c`); [`Stratify::call`] links them into a single rooted
+//! [`Hierarchy`].
+
+use super::node::Hierarchy;
+use std::collections::HashMap;
+use std::fmt;
+
+type Accessor<T> = Box<dyn Fn(&T, usize) -> Option<String>>;
+type Impute<T> = Box<dyn Fn(&str) -> T>;
+
+/// Why rows could not be linked into a tree; `index` is the row's position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StratifyError {
+    /// A row's parent id matches no row
+    MissingParent { index: usize, parent_id: String },
+    /// Several rows share an id that another row names as its parent
+    AmbiguousParent { index: usize, parent_id: String },
+    /// Every row has a parent
+    NoRoot,
+    /// More than one row has no parent
+    MultipleRoots { first: usize, second: usize },
+    /// Rows whose parents lead back to themselves rather than to the root
+    Cycle { index: usize },
+}
+
+impl fmt::Display for StratifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StratifyError::MissingParent { index, parent_id } => {
+                write!(f, "row {}: missing parent '{}'", index, parent_id)
+            }
+            StratifyError::AmbiguousParent { index, parent_id } => {
+                write!(f, "row {}: ambiguous parent '{}'", index, parent_id)
+            }
+            StratifyError::NoRoot => write!(f, "no root"),
+            StratifyError::MultipleRoots { first, second } => {
+                write!(f, "multiple roots: rows {} and {}", first, second)
+            }
+            StratifyError::Cycle { index } => write!(f, "row {}: cycle", index),
+        }
+    }
+}
+
+impl std::error::Error for StratifyError {}
+
+/// Builds a [`Hierarchy`] from rows with ids and parent ids, or with paths
+///
+/// # Example
+/// ```rust
+/// use rust_d3::hierarchy::stratify;
+///
+/// let rows = vec![("Eve", ""), ("Cain", "Eve"), ("Seth", "Eve"), ("Enos", "Seth")];
+/// let h = stratify()
+///     .id(|d: &(&str, &str), _| Some(d.0.to_string()))
+///     .parent_id(|d, _| Some(d.1.to_string()))
+///     .call(rows)
+///     .unwrap();
+/// let root = &h[h.root()];
+/// assert_eq!(root.id.as_deref(), Some("Eve"));
+/// assert_eq!(root.height, 2);
+/// let enos = h.find(h.root(), |n| n.data.0 == "Enos").unwrap();
+/// assert_eq!(h[h.parent(enos).unwrap()].data.0, "Seth");
+/// ```
+pub struct Stratify<T> {
+    id: Accessor<T>,
+    parent_id: Accessor<T>,
+    path: Option<Accessor<T>>,
+    impute: Option<Impute<T>>,
+}
+
+/// A [`Stratify`] with no accessors; set [`id`](Stratify::id) and
+/// [`parent_id`](Stratify::parent_id), or [`path`](Stratify::path)
+pub fn stratify<T>() -> Stratify<T> {
+    Stratify {
+        id: Box::new(|_, _| None),
+        parent_id: Box::new(|_, _| None),
+        path: None,
+        impute: None,
+    }
+}
+
+impl<T> Stratify<T> {
+    /// The row's id; rows without one (or with an empty one) cannot be parents
+    pub fn id<F>(mut self, id: F) -> Self
+    where
+        F: Fn(&T, usize) -> Option<String> + 'static,
+    {
+        self.id = Box::new(id);
+        self
+    }
+
+    /// The row's parent id; the row without one (or with an empty one) is the
+    /// root
+    pub fn parent_id<F>(mut self, parent_id: F) -> Self
+    where
+        F: Fn(&T, usize) -> Option<String> + 'static,
+    {
+        self.parent_id = Box::new(parent_id);
+        self
+    }
+
+    /// Derive ids and parent ids from slash-separated paths such as `/a/b/c`
+    /// instead, overriding [`id`](Self::id) and [`parent_id`](Self::parent_id)
+    ///
+    /// Missing ancestors are imputed with `T::default()` data, and imputed
+    /// roots with a single child are dropped. A leading slash is implied,
+    /// a trailing one ignored, and `\/` is a literal slash.
+    pub fn path<F>(mut self, path: F) -> Self
+    where
+        F: Fn(&T, usize) -> Option<String> + 'static,
+        T: Default,
+    {
+        self.path = Some(Box::new(path));
+        self.impute = Some(Box::new(|_| T::default()));
+        self
+    }
+
+    /// Link `data` into a tree, returning its root
+    pub fn call(&self, data: Vec<T>) -> Result<Hierarchy<T>, StratifyError> {
+        let mut ids: Vec<Option<String>>;
+        let mut parent_ids: Vec<Option<String>>;
+        let mut imputed = vec![false; data.len()];
+        let mut data: Vec<Option<T>> = data.into_iter().map(Some).collect();
+
+        match (&self.path, &self.impute) {
+            (Some(path), Some(impute)) => {
+                ids = data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| path(d.as_ref().unwrap(), i).map(|p| normalize(&p)))
+                    .collect();
+                parent_ids = ids.iter().map(|id| id.as_deref().map(parent_of)).collect();
+                // Impute every missing ancestor, in the order they are found
+                let mut known: std::collections::HashSet<String> =
+                    ids.iter().flatten().cloned().collect();
+                known.insert(String::new());
+                let mut i = 0;
+                while i < parent_ids.len() {
+                    if let Some(parent) = parent_ids[i].clone()
+                        && known.insert(parent.clone())
+                    {
+                        parent_ids.push(Some(parent_of(&parent)));
+                        data.push(Some(impute(&parent)));
+                        ids.push(Some(parent));
+                        imputed.push(true);
+                    }
+                    i += 1;
+                }
+            }
+            _ => {
+                ids = data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| (self.id)(d.as_ref().unwrap(), i))
+                    .collect();
+                parent_ids = data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| (self.parent_id)(d.as_ref().unwrap(), i))
+                    .collect();
+            }
+        }
+        let ids: Vec<Option<String>> = ids
+            .into_iter()
+            .map(|id| id.filter(|s| !s.is_empty()))
+            .collect();
+
+        // Index the rows by id; `None` marks an id shared by several rows
+        let mut by_id: HashMap<&str, Option<usize>> = HashMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            if let Some(id) = id {
+                by_id
+                    .entry(id)
+                    .and_modify(|entry| *entry = None)
+                    .or_insert(Some(i));
+            }
+        }
+
+        let n = data.len();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut root = None;
+        for (i, parent_id) in parent_ids.iter().enumerate() {
+            match parent_id.as_deref().filter(|p| !p.is_empty()) {
+                Some(parent_id) => match by_id.get(parent_id) {
+                    Some(Some(parent)) => children[*parent].push(i),
+                    Some(None) => {
+                        return Err(StratifyError::AmbiguousParent {
+                            index: i,
+                            parent_id: parent_id.to_string(),
+                        });
+                    }
+                    None => {
+                        return Err(StratifyError::MissingParent {
+                            index: i,
+                            parent_id: parent_id.to_string(),
+                        });
+                    }
+                },
+                None => match root {
+                    Some(first) => {
+                        return Err(StratifyError::MultipleRoots { first, second: i });
+                    }
+                    None => root = Some(i),
+                },
+            }
+        }
+        let mut root = root.ok_or(StratifyError::NoRoot)?;
+        if self.path.is_some() {
+            while imputed[root] && children[root].len() == 1 {
+                root = children[root][0];
+            }
+        }
+
+        // Rows not reached from the root are in a cycle
+        let mut reached = vec![false; n];
+        let mut stack = vec![root];
+        while let Some(i) = stack.pop() {
+            reached[i] = true;
+            stack.extend(&children[i]);
+        }
+        if let Some(index) = (0..n).find(|&i| !reached[i] && !imputed[i]) {
+            return Err(StratifyError::Cycle { index });
+        }
+
+        let mut ids = ids;
+        let mut take = |i: usize| {
+            (
+                data[i].take().expect("each row is linked once"),
+                ids[i].take(),
+            )
+        };
+        let (root_data, root_id) = take(root);
+        let mut hierarchy = Hierarchy::new(root_data);
+        let top = hierarchy.root();
+        hierarchy[top].id = root_id;
+        let mut stack = vec![(root, top)];
+        while let Some((row, node)) = stack.pop() {
+            for &child in &children[row] {
+                let (data, id) = take(child);
+                let added = hierarchy.add_child(node, data);
+                hierarchy[added].id = id;
+                stack.push((child, added));
+            }
+        }
+        Ok(hierarchy)
+    }
+}
+
+// Whether `path[i]` is a slash not escaped by an odd number of backslashes
+fn is_slash(path: &[u8], i: usize) -> bool {
+    if path.get(i) != Some(&b'/') {
+        return false;
+    }
+    let backslashes = path[..i].iter().rev().take_while(|&&c| c == b'\\').count();
+    backslashes % 2 == 0
+}
+
+/// A path with a leading slash and without a trailing one
+fn normalize(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let n = bytes.len();
+    let path = if n >= 1 && is_slash(bytes, n - 1) && !(n >= 2 && is_slash(bytes, n - 2)) {
+        &path[..n - 1]
+    } else {
+        path
+    };
+    if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+/// The parent of a normalized path: `/a` for `/a/b`, `/` for `/a`, and the
+/// empty string for the root `/`
+fn parent_of(path: &str) -> String {
+    let bytes = path.as_bytes();
+    if bytes.len() < 2 {
+        return String::new();
+    }
+    let mut i = bytes.len() - 1;
+    while i > 1 && !is_slash(bytes, i) {
+        i -= 1;
+    }
+    path[..i].to_string()
+}
+
+/// A tree from nested data: `children` moves each datum's children out of it
+///
+/// Children keep their order.
+///
+/// # Example
+/// ```rust
+/// use rust_d3::hierarchy::hierarchy;
+///
+/// #[derive(Default)]
+/// struct Entry { name: &'static str, size: f64, children: Vec<Entry> }
+///
+/// let tree = Entry {
+///     name: "src",
+///     children: vec![
+///         Entry { name: "lib.rs", size: 120.0, ..Default::default() },
+///         Entry { name: "main.rs", size: 80.0, ..Default::default() },
+///     ],
+///     ..Default::default()
+/// };
+/// let mut h = hierarchy(tree, |d| std::mem::take(&mut d.children));
+/// let main = h.children(h.root())[1];
+/// assert_eq!(h[main].data.name, "main.rs");
+/// assert_eq!(h.sum(&|d| d.size), 200.0);
+/// ```
+pub fn hierarchy<T, F>(mut data: T, mut children: F) -> Hierarchy<T>
+where
+    F: FnMut(&mut T) -> Vec<T>,
+{
+    let kids = children(&mut data);
+    let mut hierarchy = Hierarchy::new(data);
+    let mut stack = vec![(hierarchy.root(), kids)];
+    while let Some((parent, kids)) = stack.pop() {
+        for mut kid in kids {
+            let grandkids = children(&mut kid);
+            let id = hierarchy.add_child(parent, kid);
+            stack.push((id, grandkids));
+        }
+    }
+    hierarchy
+}
@@ -4,47 +4,70 @@
 mod tests {
     use super::super::*;
 
+    // root
+    // ├── a
+    // │   ├── a1
+    // │   └── a2
+    // └── b
+    fn sample() -> (Hierarchy<&'static str>, [NodeId; 5]) {
+        let mut h = Hierarchy::new("root");
+        let root = h.root();
+        let a = h.add_child(root, "a");
+        let a1 = h.add_child(a, "a1");
+        let a2 = h.add_child(a, "a2");
+        let b = h.add_child(root, "b");
+        (h, [root, a, a1, a2, b])
+    }
+
+    fn names(
+        h: &Hierarchy<&'static str>,
+        nodes: impl IntoIterator<Item = NodeId>,
+    ) -> Vec<&'static str> {
+        nodes.into_iter().map(|n| h[n].data).collect()
+    }
+
     #[test]
     fn test_tree_layout_basic() {
-        let mut root = Node::new("root");
-        root.add_child(Node::new("a"));
-        root.add_child(Node::new("b"));
+        let mut h = Hierarchy::new("root");
+        let a = h.add_child(h.root(), "a");
+        h.add_child(h.root(), "b");
         let tree = TreeLayout::new();
-        tree.layout(&mut root);
+        tree.layout(&mut h);
+        let root = &h[h.root()];
         assert_eq!(root.depth, 0);
-        assert_eq!(root.children[0].depth, 1);
+        assert_eq!(h[a].depth, 1);
         assert!(root.x.is_some() && root.y.is_some());
     }
 
     #[test]
     fn test_tree_layout_deep() {
-        let mut root = Node::new("root");
-        let mut c1 = Node::new("a");
-        c1.add_child(Node::new("a1"));
-        root.add_child(c1);
+        let mut h = Hierarchy::new("root");
+        let a = h.add_child(h.root(), "a");
+        let a1 = h.add_child(a, "a1");
         let tree = TreeLayout::new();
-        tree.layout(&mut root);
-        assert_eq!(root.depth, 0);
-        assert_eq!(root.children[0].children[0].depth, 2);
+        tree.layout(&mut h);
+        assert_eq!(h[h.root()].depth, 0);
+        assert_eq!(h[a1].depth, 2);
+        assert_eq!(h[a1].y, Some(200.0));
     }
 
     #[test]
     fn test_node_sum() {
-        let mut root = Node::new(1);
-        root.add_child(Node::new(2));
-        root.add_child(Node::new(3));
-        let sum = root.sum(&|v| *v as f64);
+        let mut h = Hierarchy::new(1);
+        h.add_child(h.root(), 2);
+        h.add_child(h.root(), 3);
+        let sum = h.sum(&|v| *v as f64);
         assert_eq!(sum, 6.0);
-        assert_eq!(root.value, Some(6.0));
+        assert_eq!(h[h.root()].value, Some(6.0));
     }
 
     #[test]
     fn test_each_traversal() {
-        let mut root = Node::new(0);
-        root.add_child(Node::new(1));
-        root.add_child(Node::new(2));
+        let mut h = Hierarchy::new(0);
+        h.add_child(h.root(), 1);
+        h.add_child(h.root(), 2);
         let mut count = 0;
-        root.each(&mut |_n| {
+        h.each(|_n| {
             count += 1;
         });
         assert_eq!(count, 3);
@@ -52,21 +75,459 @@ mod tests {
 
     #[test]
     fn test_treemap_layout_basic() {
-        let mut root = Node::new("root");
-        root.add_child(Node::new("a"));
-        root.add_child(Node::new("b"));
+        let mut h = Hierarchy::new("root");
+        h.add_child(h.root(), "a");
+        h.add_child(h.root(), "b");
         let treemap = TreemapLayout::new().size((200.0, 100.0));
-        treemap.layout(&mut root);
-        assert!(root.x.is_some() && root.y.is_some());
+        treemap.layout(&mut h);
+        let root = &h[h.root()];
+        assert_eq!(
+            (root.x0, root.y0, root.x1, root.y1),
+            (0.0, 0.0, 200.0, 100.0)
+        );
+    }
+
+    // A flat hierarchy with one leaf per value, summed
+    fn flat(values: &[f64]) -> Hierarchy<f64> {
+        let mut h = Hierarchy::new(0.0);
+        for &value in values {
+            h.add_child(h.root(), value);
+        }
+        h.sum(&|d| *d);
+        h
+    }
+
+    fn rects<T>(h: &Hierarchy<T>, nodes: &[NodeId]) -> Vec<[f64; 4]> {
+        nodes
+            .iter()
+            .map(|&n| {
+                let r = |v: f64| (v * 100.0).round() / 100.0;
+                [r(h[n].x0), r(h[n].y0), r(h[n].x1), r(h[n].y1)]
+            })
+            .collect()
+    }
+
+    fn tiled(values: &[f64], tile: Tile, size: (f64, f64)) -> Vec<[f64; 4]> {
+        let mut h = flat(values);
+        TreemapLayout::new().size(size).tile(tile).layout(&mut h);
+        rects(&h, h.children(h.root()))
+    }
+
+    #[test]
+    fn test_treemap_dice_slice_binary() {
+        assert_eq!(
+            tiled(&[1.0, 2.0, 1.0], treemap_dice(), (400.0, 100.0)),
+            [
+                [0.0, 0.0, 100.0, 100.0],
+                [100.0, 0.0, 300.0, 100.0],
+                [300.0, 0.0, 400.0, 100.0]
+            ]
+        );
+        assert_eq!(
+            tiled(&[1.0, 3.0], treemap_slice(), (100.0, 400.0)),
+            [[0.0, 0.0, 100.0, 100.0], [0.0, 100.0, 100.0, 400.0]]
+        );
+        // Wide rectangles are split left and right, tall ones top and bottom
+        assert_eq!(
+            tiled(&[1.0, 1.0, 1.0, 1.0], treemap_binary(), (200.0, 200.0)),
+            [
+                [0.0, 0.0, 100.0, 100.0],
+                [100.0, 0.0, 200.0, 100.0],
+                [0.0, 100.0, 100.0, 200.0],
+                [100.0, 100.0, 200.0, 200.0]
+            ]
+        );
+        assert_eq!(
+            tiled(&[2.0, 1.0, 1.0], treemap_binary(), (400.0, 100.0)),
+            [
+                [0.0, 0.0, 200.0, 100.0],
+                [200.0, 0.0, 300.0, 100.0],
+                [300.0, 0.0, 400.0, 100.0]
+            ]
+        );
+
+        // Slice-dice alternates with depth
+        let mut h = Hierarchy::new(0.0);
+        let a = h.add_child(h.root(), 0.0);
+        let b = h.add_child(h.root(), 0.0);
+        let leaves = [
+            h.add_child(a, 1.0),
+            h.add_child(a, 1.0),
+            h.add_child(b, 2.0),
+        ];
+        h.sum(&|d| *d);
+        TreemapLayout::new()
+            .size((100.0, 100.0))
+            .tile(treemap_slice_dice())
+            .layout(&mut h);
+        assert_eq!(
+            rects(&h, &[a, b]),
+            [[0.0, 0.0, 50.0, 100.0], [50.0, 0.0, 100.0, 100.0]]
+        );
+        assert_eq!(
+            rects(&h, &leaves[..2]),
+            [[0.0, 0.0, 50.0, 50.0], [0.0, 50.0, 50.0, 100.0]]
+        );
+    }
+
+    #[test]
+    fn test_treemap_squarify() {
+        let values = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        assert_eq!(
+            tiled(&values, treemap_squarify(), (6.0, 4.0)),
+            [
+                [0.0, 0.0, 3.0, 2.0],
+                [0.0, 2.0, 3.0, 4.0],
+                [3.0, 0.0, 4.71, 2.33],
+                [4.71, 0.0, 6.0, 2.33],
+                [3.0, 2.33, 5.4, 3.17],
+                [3.0, 3.17, 5.4, 4.0],
+                [5.4, 2.33, 6.0, 4.0]
+            ]
+        );
+        // Empty nodes get empty rectangles
+        let rects = tiled(&[0.0, 2.0, 0.0, 2.0], treemap_squarify(), (4.0, 4.0));
+        assert_eq!(rects[0][1], rects[0][3]);
+        assert_eq!(rects[1], [0.0, 0.0, 2.0, 4.0]);
+        assert_eq!(rects[3], [2.0, 0.0, 4.0, 4.0]);
+
+        assert_eq!(treemap_squarify().ratio(0.5), Tile::Squarify { ratio: 1.0 });
+        assert_eq!(
+            treemap_resquarify().ratio(2.0),
+            Tile::Resquarify { ratio: 2.0 }
+        );
+        assert_eq!(treemap_dice().ratio(2.0), Tile::Dice);
+    }
+
+    #[test]
+    fn test_treemap_resquarify_is_stable() {
+        let values = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let mut h = flat(&values);
+        let layout = TreemapLayout::new()
+            .size((6.0, 4.0))
+            .tile(treemap_resquarify());
+        layout.layout(&mut h);
+        let children = h.children(h.root()).to_vec();
+        assert_eq!(
+            rects(&h, &children),
+            tiled(&values, treemap_squarify(), (6.0, 4.0))
+        );
+
+        // Growing the last node keeps every row and orientation
+        let last = children[6];
+        h[last].data = 7.0;
+        h.sum(&|d| *d);
+        layout.layout(&mut h);
+        let after = rects(&h, &children);
+        assert_eq!(after[0], [0.0, 0.0, 2.4, 2.0]);
+        assert_eq!(after[1], [0.0, 2.0, 2.4, 4.0]);
+        assert_eq!((after[6][1], after[6][3]), (after[4][1], 4.0));
+        assert_ne!(
+            after,
+            tiled(
+                &[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 7.0],
+                treemap_squarify(),
+                (6.0, 4.0)
+            )
+        );
+
+        // A new ratio lays the rows out afresh
+        let squarified = tiled(
+            &[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 7.0],
+            treemap_squarify().ratio(1.0),
+            (6.0, 4.0),
+        );
+        layout.tile(treemap_resquarify().ratio(1.0)).layout(&mut h);
+        assert_eq!(rects(&h, &children), squarified);
+    }
+
+    #[test]
+    fn test_treemap_padding_and_round() {
+        let mut h = flat(&[1.0, 1.0]);
+        TreemapLayout::new()
+            .size((100.0, 100.0))
+            .tile(treemap_dice())
+            .padding_outer(5.0)
+            .padding_top(20.0)
+            .padding_inner(10.0)
+            .layout(&mut h);
+        assert_eq!(
+            rects(&h, h.children(h.root())),
+            [[5.0, 20.0, 45.0, 95.0], [55.0, 20.0, 95.0, 95.0]]
+        );
+
+        // Padding wider than a node collapses it to its center
+        let mut h = flat(&[1.0, 1.0]);
+        TreemapLayout::new()
+            .size((100.0, 10.0))
+            .tile(treemap_dice())
+            .padding(8.0)
+            .layout(&mut h);
+        assert_eq!(rects(&h, h.children(h.root()))[0][1], 5.0);
+
+        let mut h = flat(&[1.0, 1.0, 1.0]);
+        TreemapLayout::new()
+            .size((100.0, 100.0))
+            .tile(treemap_dice())
+            .round(true)
+            .layout(&mut h);
+        assert_eq!(
+            rects(&h, h.children(h.root())),
+            [
+                [0.0, 0.0, 33.0, 100.0],
+                [33.0, 0.0, 67.0, 100.0],
+                [67.0, 0.0, 100.0, 100.0]
+            ]
+        );
     }
 
     #[test]
     fn test_partition_layout_basic() {
-        let mut root = Node::new("root");
-        root.add_child(Node::new("a"));
-        root.add_child(Node::new("b"));
+        let mut h = Hierarchy::new("root");
+        h.add_child(h.root(), "a");
+        h.add_child(h.root(), "b");
         let partition = PartitionLayout::new().size((200.0, 100.0));
-        partition.layout(&mut root);
+        partition.layout(&mut h);
+        let root = &h[h.root()];
         assert!(root.x.is_some() && root.y.is_some());
     }
+
+    #[test]
+    fn test_parent_links() {
+        let (h, [root, a, a1, a2, b]) = sample();
+        assert_eq!(h.parent(root), None);
+        assert_eq!(h.parent(a1), Some(a));
+        assert_eq!(h[a2].parent(), Some(a));
+        assert_eq!(h.children(root), [a, b]);
+        assert!(h[b].is_leaf() && !h[a].is_leaf());
+        assert_eq!((h[a2].depth, h[a].height, h[root].height), (2, 1, 2));
+        assert_eq!(names(&h, h.ancestors(a2)), ["a2", "a", "root"]);
+        assert_eq!(names(&h, h.ancestors(root)), ["root"]);
+        assert_eq!(h.nodes().len(), 5);
+        assert_eq!(h.nodes()[a1.index()].data, "a1");
+    }
+
+    #[test]
+    fn test_traversal_orders() {
+        let (h, [root, a, ..]) = sample();
+        assert_eq!(
+            names(&h, h.each_before(root)),
+            ["root", "a", "a1", "a2", "b"]
+        );
+        assert_eq!(
+            names(&h, h.each_after(root)),
+            ["a1", "a2", "a", "b", "root"]
+        );
+        assert_eq!(names(&h, h.each_bfs(root)), ["root", "a", "b", "a1", "a2"]);
+        assert_eq!(
+            names(&h, h.descendants(root)),
+            ["root", "a", "b", "a1", "a2"]
+        );
+        assert_eq!(names(&h, h.descendants(a)), ["a", "a1", "a2"]);
+        assert_eq!(names(&h, h.leaves(root)), ["a1", "a2", "b"]);
+        let links: Vec<_> = h
+            .links(root)
+            .into_iter()
+            .map(|(source, target)| (h[source].data, h[target].data))
+            .collect();
+        assert_eq!(
+            links,
+            [("root", "a"), ("root", "b"), ("a", "a1"), ("a", "a2")]
+        );
+    }
+
+    #[test]
+    fn test_path_and_find() {
+        let (h, [root, a, a1, a2, b]) = sample();
+        assert_eq!(h.path(a1, b), [a1, a, root, b]);
+        assert_eq!(h.path(a1, a2), [a1, a, a2]);
+        assert_eq!(h.path(a1, a), [a1, a]);
+        assert_eq!(h.path(root, a2), [root, a, a2]);
+        assert_eq!(h.path(b, b), [b]);
+        assert_eq!(
+            h.find(root, |n| n.data.starts_with('a') && n.is_leaf()),
+            Some(a1)
+        );
+        assert_eq!(h.find(a, |n| n.data == "b"), None);
+    }
+
+    #[test]
+    fn test_count_and_sort() {
+        let (mut h, [root, a, _, _, b]) = sample();
+        h.count();
+        assert_eq!(
+            (h[root].value, h[a].value, h[b].value),
+            (Some(3.0), Some(2.0), Some(1.0))
+        );
+        h.sort(|x, y| {
+            x.value
+                .partial_cmp(&y.value)
+                .unwrap()
+                .then(y.data.cmp(x.data))
+        });
+        assert_eq!(h.children(root), [b, a]);
+        assert_eq!(
+            names(&h, h.each_before(root)),
+            ["root", "b", "a", "a2", "a1"]
+        );
+    }
+
+    fn rows(pairs: &[(&'static str, &'static str)]) -> Vec<(&'static str, &'static str)> {
+        pairs.to_vec()
+    }
+
+    fn by_columns() -> Stratify<(&'static str, &'static str)> {
+        stratify()
+            .id(|d: &(&str, &str), _| Some(d.0.to_string()))
+            .parent_id(|d, _| Some(d.1.to_string()))
+    }
+
+    // Ids under `node`, in pre-order
+    fn ids<T>(h: &Hierarchy<T>, node: NodeId) -> Vec<String> {
+        h.each_before(node)
+            .map(|n| h[n].id.clone().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_stratify_ids() {
+        let h = by_columns()
+            .call(rows(&[("b", "a"), ("a", ""), ("c", "a"), ("d", "b")]))
+            .unwrap();
+        let root = h.root();
+        assert_eq!(ids(&h, root), ["a", "b", "d", "c"]);
+        assert_eq!(h[root].data, ("a", ""));
+        assert_eq!((h[root].depth, h[root].height), (0, 2));
+        let d = h.children(h.children(root)[0])[0];
+        assert_eq!((h[d].data.0, h[d].depth), ("d", 2));
+    }
+
+    #[test]
+    fn test_stratify_errors() {
+        let err = |pairs| by_columns().call(rows(pairs)).unwrap_err();
+        assert_eq!(
+            err(&[("a", ""), ("b", "x")]),
+            StratifyError::MissingParent {
+                index: 1,
+                parent_id: "x".into()
+            }
+        );
+        assert_eq!(
+            err(&[("a", ""), ("b", ""), ("c", "a")]),
+            StratifyError::MultipleRoots {
+                first: 0,
+                second: 1
+            }
+        );
+        assert_eq!(err(&[("a", "b"), ("b", "a")]), StratifyError::NoRoot);
+        assert_eq!(
+            err(&[("r", ""), ("a", "b"), ("b", "a")]),
+            StratifyError::Cycle { index: 1 }
+        );
+        assert_eq!(
+            err(&[("r", ""), ("a", "r"), ("a", "r"), ("c", "a")]),
+            StratifyError::AmbiguousParent {
+                index: 3,
+                parent_id: "a".into()
+            }
+        );
+        // Duplicate ids are fine while nothing refers to them
+        assert!(
+            by_columns()
+                .call(rows(&[("r", ""), ("a", "r"), ("a", "r")]))
+                .is_ok()
+        );
+        assert_eq!(
+            StratifyError::MissingParent {
+                index: 1,
+                parent_id: "x".into()
+            }
+            .to_string(),
+            "row 1: missing parent 'x'"
+        );
+    }
+
+    #[test]
+    fn test_stratify_path() {
+        let files = vec![
+            "/src/lib.rs",
+            "src/bin/ui.rs",
+            "/src/hierarchy/",
+            "/src/hierarchy/mod.rs",
+        ];
+        let h = stratify()
+            .path(|d: &&str, _| Some(d.to_string()))
+            .call(files)
+            .unwrap();
+        // The imputed "/" root with a single child is dropped
+        assert_eq!(
+            ids(&h, h.root()),
+            [
+                "/src",
+                "/src/lib.rs",
+                "/src/hierarchy",
+                "/src/hierarchy/mod.rs",
+                "/src/bin",
+                "/src/bin/ui.rs"
+            ]
+        );
+        // Imputed directories get default data
+        assert_eq!(h[h.root()].data, "");
+        assert_eq!(h[h.children(h.root())[1]].data, "/src/hierarchy/");
+
+        let h = stratify()
+            .path(|d: &String, _| Some(d.clone()))
+            .call(vec![
+                "a/x\\/y".to_string(),
+                "b".to_string(),
+                "a/x\\/y/z".to_string(),
+            ])
+            .unwrap();
+        let children = h.children(h.root());
+        assert_eq!(h[h.root()].id.as_deref(), Some("/"));
+        // Imputed directories follow the rows, and the escaped slash is part
+        // of a name
+        assert_eq!(ids(&h, children[0]), ["/b"]);
+        assert_eq!(ids(&h, children[1]), ["/a", "/a/x\\/y", "/a/x\\/y/z"]);
+    }
+
+    #[derive(Debug, Default)]
+    struct Entry {
+        name: &'static str,
+        size: f64,
+        children: Vec<Entry>,
+    }
+
+    #[test]
+    fn test_hierarchy_nested() {
+        let leaf = |name, size| Entry {
+            name,
+            size,
+            ..Default::default()
+        };
+        let data = Entry {
+            name: "root",
+            children: vec![
+                Entry {
+                    name: "a",
+                    children: vec![leaf("a1", 1.0), leaf("a2", 2.0)],
+                    ..Default::default()
+                },
+                leaf("b", 4.0),
+            ],
+            ..Default::default()
+        };
+        let mut h = hierarchy(data, |d| std::mem::take(&mut d.children));
+        let root = h.root();
+        let [a, b] = h.children(root) else {
+            panic!("root has two children");
+        };
+        let (a, b) = (*a, *b);
+        let a2 = h.children(a)[1];
+        assert_eq!((h[root].depth, h[root].height), (0, 2));
+        assert_eq!((h[a2].data.name, h[a2].depth), ("a2", 2));
+        assert!(h[b].is_leaf());
+        assert_eq!(h.sum(&|d| d.size), 7.0);
+        assert_eq!(h[a].value, Some(3.0));
+    }
 }
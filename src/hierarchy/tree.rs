@@ -1,6 +1,6 @@
 //! d3-hierarchy tree layout
 
-use super::node::Node;
+use super::node::{Hierarchy, NodeId};
 
 pub struct TreeLayout {
     pub node_size: Option<(f64, f64)>,
@@ -14,31 +14,25 @@ impl TreeLayout {
         self.node_size = Some(size);
         self
     }
-    pub fn layout<T>(&self, root: &mut Node<T>) {
-        root.compute_depths(0);
-        root.compute_heights();
+    pub fn layout<T>(&self, h: &mut Hierarchy<T>) {
         // Reingold-Tilford tidy tree layout (simple version)
         let mut next_x = 0.0;
-        Self::assign_x(root, &mut next_x);
-        Self::assign_y(root, 0.0, self.node_size.map(|(_, y)| y).unwrap_or(100.0));
+        Self::assign_x(h, h.root(), &mut next_x);
+        let y_step = self.node_size.map(|(_, y)| y).unwrap_or(100.0);
+        h.each(|node| node.y = Some(node.depth as f64 * y_step));
     }
-    fn assign_x<T>(node: &mut Node<T>, next_x: &mut f64) {
-        if node.children.is_empty() {
-            node.x = Some(*next_x);
+    fn assign_x<T>(h: &mut Hierarchy<T>, node: NodeId, next_x: &mut f64) {
+        let children = h.children(node).to_vec();
+        if children.is_empty() {
+            h[node].x = Some(*next_x);
             *next_x += 1.0;
         } else {
-            for child in &mut node.children {
-                Self::assign_x(child, next_x);
+            for &child in &children {
+                Self::assign_x(h, child, next_x);
             }
-            let first = node.children.first().unwrap().x.unwrap();
-            let last = node.children.last().unwrap().x.unwrap();
-            node.x = Some((first + last) / 2.0);
-        }
-    }
-    fn assign_y<T>(node: &mut Node<T>, y: f64, y_step: f64) {
-        node.y = Some(y + node.depth as f64 * y_step);
-        for child in &mut node.children {
-            Self::assign_y(child, y, y_step);
+            let first = h[children[0]].x.unwrap();
+            let last = h[children[children.len() - 1]].x.unwrap();
+            h[node].x = Some((first + last) / 2.0);
         }
     }
 }
@@ -1,36 +1,457 @@
 //! d3-hierarchy treemap layout
+//!
+//! Each node is given a rectangle `x0, y0, x1, y1` with an area proportional
+//! to its value, so call [`Hierarchy::sum`] (and usually [`Hierarchy::sort`]
+//! by descending value) first. How a node's rectangle is divided among its
+//! children is decided by the [`Tile`] method.
+//!
+//! # Example
+//! ```rust
+//! use rust_d3::hierarchy::{Hierarchy, TreemapLayout, treemap_squarify};
+//!
+//! let mut h = Hierarchy::new(0.0);
+//! for size in [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0] {
+//!     h.add_child(h.root(), size);
+//! }
+//! h.sum(&|d| *d);
+//! TreemapLayout::new()
+//!     .size((600.0, 400.0))
+//!     .tile(treemap_squarify())
+//!     .padding_inner(2.0)
+//!     .round(true)
+//!     .layout(&mut h);
+//! let first = &h[h.children(h.root())[0]];
+//! assert_eq!((first.x0, first.y0), (0.0, 0.0));
+//! ```
 
-use super::node::Node;
+use super::node::{Hierarchy, NodeId};
 
+/// The golden ratio, the aspect ratio [`treemap_squarify`] aims for
+pub const PHI: f64 = 1.618_033_988_749_895;
+
+/// How a node's rectangle is divided among its children
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tile {
+    /// Recursively halve the children by value, splitting horizontally for
+    /// wide rectangles and vertically for tall ones
+    Binary,
+    /// Side by side, left to right
+    Dice,
+    /// Stacked, top to bottom
+    Slice,
+    /// Slice at odd depths and dice at even ones
+    SliceDice,
+    /// Rows of children with aspect ratios close to `ratio`
+    Squarify { ratio: f64 },
+    /// Like `Squarify`, but re-uses the rows from the previous layout of the
+    /// same node, so rectangles stay in place as values change
+    Resquarify { ratio: f64 },
+}
+
+/// [`Tile::Binary`]
+pub fn treemap_binary() -> Tile {
+    Tile::Binary
+}
+
+/// [`Tile::Dice`]
+pub fn treemap_dice() -> Tile {
+    Tile::Dice
+}
+
+/// [`Tile::Slice`]
+pub fn treemap_slice() -> Tile {
+    Tile::Slice
+}
+
+/// [`Tile::SliceDice`]
+pub fn treemap_slice_dice() -> Tile {
+    Tile::SliceDice
+}
+
+/// [`Tile::Squarify`] with the golden ratio
+pub fn treemap_squarify() -> Tile {
+    Tile::Squarify { ratio: PHI }
+}
+
+/// [`Tile::Resquarify`] with the golden ratio
+pub fn treemap_resquarify() -> Tile {
+    Tile::Resquarify { ratio: PHI }
+}
+
+impl Tile {
+    /// Set the target aspect ratio (width over height, at least 1) of
+    /// `Squarify` and `Resquarify`; other methods are unchanged
+    pub fn ratio(self, ratio: f64) -> Self {
+        let ratio = if ratio > 1.0 { ratio } else { 1.0 };
+        match self {
+            Tile::Squarify { .. } => Tile::Squarify { ratio },
+            Tile::Resquarify { .. } => Tile::Resquarify { ratio },
+            tile => tile,
+        }
+    }
+
+    /// Divide `rect`, as `[x0, y0, x1, y1]`, among the children of `parent`
+    pub fn tile<T>(&self, h: &mut Hierarchy<T>, parent: NodeId, rect: [f64; 4]) {
+        let children = h.children(parent).to_vec();
+        if children.is_empty() {
+            return;
+        }
+        let value = value_of(h, parent);
+        match *self {
+            Tile::Binary => binary(h, &children, value, rect),
+            Tile::Dice => dice(h, &children, value, rect),
+            Tile::Slice => slice(h, &children, value, rect),
+            Tile::SliceDice if h[parent].depth % 2 == 1 => slice(h, &children, value, rect),
+            Tile::SliceDice => dice(h, &children, value, rect),
+            Tile::Squarify { ratio } => {
+                squarify(h, &children, value, ratio, rect);
+            }
+            Tile::Resquarify { ratio } => resquarify(h, parent, &children, value, ratio, rect),
+        }
+    }
+}
+
+/// Rows laid out by the last resquarify tiling of a node's children
+#[derive(Debug, Clone)]
+pub(crate) struct SquarifyRows {
+    ratio: f64,
+    rows: Vec<Row>,
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    value: f64,
+    dice: bool,
+    children: Vec<NodeId>,
+}
+
+fn value_of<T>(h: &Hierarchy<T>, node: NodeId) -> f64 {
+    h[node].value.unwrap_or(0.0)
+}
+
+fn dice<T>(h: &mut Hierarchy<T>, nodes: &[NodeId], value: f64, [x0, y0, x1, y1]: [f64; 4]) {
+    let k = if value != 0.0 { (x1 - x0) / value } else { 0.0 };
+    let mut x = x0;
+    for &node in nodes {
+        let n = &mut h[node];
+        n.y0 = y0;
+        n.y1 = y1;
+        n.x0 = x;
+        x += n.value.unwrap_or(0.0) * k;
+        n.x1 = x;
+    }
+}
+
+fn slice<T>(h: &mut Hierarchy<T>, nodes: &[NodeId], value: f64, [x0, y0, x1, y1]: [f64; 4]) {
+    let k = if value != 0.0 { (y1 - y0) / value } else { 0.0 };
+    let mut y = y0;
+    for &node in nodes {
+        let n = &mut h[node];
+        n.x0 = x0;
+        n.x1 = x1;
+        n.y0 = y;
+        y += n.value.unwrap_or(0.0) * k;
+        n.y1 = y;
+    }
+}
+
+fn binary<T>(h: &mut Hierarchy<T>, nodes: &[NodeId], value: f64, rect: [f64; 4]) {
+    // sums[i] is the total value of the first i nodes
+    let mut sums = vec![0.0; nodes.len() + 1];
+    for (i, &node) in nodes.iter().enumerate() {
+        sums[i + 1] = sums[i] + value_of(h, node);
+    }
+    partition(h, nodes, &sums, value, rect);
+}
+
+// Split `nodes` where their running total is closest to half of `value`
+fn partition<T>(
+    h: &mut Hierarchy<T>,
+    nodes: &[NodeId],
+    sums: &[f64],
+    value: f64,
+    [x0, y0, x1, y1]: [f64; 4],
+) {
+    if nodes.len() == 1 {
+        let node = &mut h[nodes[0]];
+        (node.x0, node.y0, node.x1, node.y1) = (x0, y0, x1, y1);
+        return;
+    }
+    let offset = sums[0];
+    let target = value / 2.0 + offset;
+    let (mut k, mut hi) = (1, nodes.len() - 1);
+    while k < hi {
+        let mid = (k + hi) / 2;
+        if sums[mid] < target {
+            k = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    if target - sums[k - 1] < sums[k] - target && k > 1 {
+        k -= 1;
+    }
+    let left = sums[k] - offset;
+    let right = value - left;
+    let (a, b) = if x1 - x0 > y1 - y0 {
+        let xk = if value != 0.0 {
+            (x0 * right + x1 * left) / value
+        } else {
+            x1
+        };
+        ([x0, y0, xk, y1], [xk, y0, x1, y1])
+    } else {
+        let yk = if value != 0.0 {
+            (y0 * right + y1 * left) / value
+        } else {
+            y1
+        };
+        ([x0, y0, x1, yk], [x0, yk, x1, y1])
+    };
+    partition(h, &nodes[..k], &sums[..=k], left, a);
+    partition(h, &nodes[k..], &sums[k..], right, b);
+}
+
+fn squarify<T>(
+    h: &mut Hierarchy<T>,
+    nodes: &[NodeId],
+    value: f64,
+    ratio: f64,
+    [mut x0, mut y0, x1, y1]: [f64; 4],
+) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut value = value;
+    let (mut i0, mut i1, n) = (0, 0, nodes.len());
+    while i0 < n {
+        let (dx, dy) = (x1 - x0, y1 - y0);
+
+        // Find the next non-empty node
+        let mut sum_value;
+        loop {
+            sum_value = value_of(h, nodes[i1]);
+            i1 += 1;
+            if sum_value != 0.0 || i1 >= n {
+                break;
+            }
+        }
+        let (mut min_value, mut max_value) = (sum_value, sum_value);
+        let alpha = (dy / dx).max(dx / dy) / (value * ratio);
+        let mut beta = sum_value * sum_value * alpha;
+        let mut min_ratio = (max_value / beta).max(beta / min_value);
+
+        // Keep adding nodes while the aspect ratio maintains or improves
+        while i1 < n {
+            let node_value = value_of(h, nodes[i1]);
+            sum_value += node_value;
+            min_value = min_value.min(node_value);
+            max_value = max_value.max(node_value);
+            beta = sum_value * sum_value * alpha;
+            let new_ratio = (max_value / beta).max(beta / min_value);
+            if new_ratio > min_ratio {
+                sum_value -= node_value;
+                break;
+            }
+            min_ratio = new_ratio;
+            i1 += 1;
+        }
+
+        // Position the row and record its orientation
+        let row = Row {
+            value: sum_value,
+            dice: dx < dy,
+            children: nodes[i0..i1].to_vec(),
+        };
+        if row.dice {
+            let y = if dy != 0.0 {
+                y0 + dy * sum_value / value
+            } else {
+                y1
+            };
+            dice(h, &row.children, sum_value, [x0, y0, x1, y]);
+            y0 = y;
+        } else {
+            let x = if dx != 0.0 {
+                x0 + dx * sum_value / value
+            } else {
+                x1
+            };
+            slice(h, &row.children, sum_value, [x0, y0, x, y1]);
+            x0 = x;
+        }
+        rows.push(row);
+        value -= sum_value;
+        i0 = i1;
+    }
+    rows
+}
+
+fn resquarify<T>(
+    h: &mut Hierarchy<T>,
+    parent: NodeId,
+    nodes: &[NodeId],
+    value: f64,
+    ratio: f64,
+    rect: [f64; 4],
+) {
+    let Some(mut squarified) = h[parent].squarify.take().filter(|s| s.ratio == ratio) else {
+        let rows = squarify(h, nodes, value, ratio, rect);
+        h[parent].squarify = Some(SquarifyRows { ratio, rows });
+        return;
+    };
+    // Keep the previous rows and orientations, resized to the new values
+    let [mut x0, mut y0, x1, y1] = rect;
+    let mut value = value;
+    for row in &mut squarified.rows {
+        row.value = row.children.iter().map(|&c| value_of(h, c)).sum();
+        if row.dice {
+            let y = if value != 0.0 {
+                y0 + (y1 - y0) * row.value / value
+            } else {
+                y1
+            };
+            dice(h, &row.children, row.value, [x0, y0, x1, y]);
+            y0 = y;
+        } else {
+            let x = if value != 0.0 {
+                x0 + (x1 - x0) * row.value / value
+            } else {
+                x1
+            };
+            slice(h, &row.children, row.value, [x0, y0, x, y1]);
+            x0 = x;
+        }
+        value -= row.value;
+    }
+    h[parent].squarify = Some(squarified);
+}
+
+/// Lays out nodes as nested rectangles sized by their values
+///
+/// # Fields
+/// - `size`: Width and height of the root rectangle.
+/// - `tile`: How each node is divided among its children.
+/// - `padding_inner`: Gap between siblings.
+/// - `padding_top`, `padding_right`, `padding_bottom`, `padding_left`:
+///   Gap between a parent's edges and its children.
+/// - `round`: Round rectangle edges to whole numbers.
 pub struct TreemapLayout {
     pub size: (f64, f64),
+    pub tile: Tile,
+    pub padding_inner: f64,
+    pub padding_top: f64,
+    pub padding_right: f64,
+    pub padding_bottom: f64,
+    pub padding_left: f64,
+    pub round: bool,
 }
 
 impl TreemapLayout {
     pub fn new() -> Self {
-        TreemapLayout { size: (1.0, 1.0) }
+        TreemapLayout {
+            size: (1.0, 1.0),
+            tile: treemap_squarify(),
+            padding_inner: 0.0,
+            padding_top: 0.0,
+            padding_right: 0.0,
+            padding_bottom: 0.0,
+            padding_left: 0.0,
+            round: false,
+        }
     }
     pub fn size(mut self, size: (f64, f64)) -> Self {
         self.size = size;
         self
     }
-    pub fn layout<T>(&self, root: &mut Node<T>) {
-        let n = root.children.len();
-        if n == 0 {
-            root.x = Some(0.0);
-            root.y = Some(0.0);
-            return;
+    pub fn tile(mut self, tile: Tile) -> Self {
+        self.tile = tile;
+        self
+    }
+    /// Set both the inner and the outer padding
+    pub fn padding(self, padding: f64) -> Self {
+        self.padding_inner(padding).padding_outer(padding)
+    }
+    pub fn padding_inner(mut self, padding: f64) -> Self {
+        self.padding_inner = padding;
+        self
+    }
+    /// Set the top, right, bottom and left padding
+    pub fn padding_outer(self, padding: f64) -> Self {
+        self.padding_top(padding)
+            .padding_right(padding)
+            .padding_bottom(padding)
+            .padding_left(padding)
+    }
+    /// Room above the children, e.g. for a parent's label
+    pub fn padding_top(mut self, padding: f64) -> Self {
+        self.padding_top = padding;
+        self
+    }
+    pub fn padding_right(mut self, padding: f64) -> Self {
+        self.padding_right = padding;
+        self
+    }
+    pub fn padding_bottom(mut self, padding: f64) -> Self {
+        self.padding_bottom = padding;
+        self
+    }
+    pub fn padding_left(mut self, padding: f64) -> Self {
+        self.padding_left = padding;
+        self
+    }
+    pub fn round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+    /// Set `x0, y0, x1, y1` of every node from its value
+    pub fn layout<T>(&self, h: &mut Hierarchy<T>) {
+        let root = h.root();
+        let node = &mut h[root];
+        (node.x0, node.y0, node.x1, node.y1) = (0.0, 0.0, self.size.0, self.size.1);
+
+        // Half the inner padding of each depth's parent, trimmed from every
+        // side of the nodes at that depth
+        let mut padding_stack = vec![0.0; h[root].height + 2];
+        let order: Vec<NodeId> = h.each_before(root).collect();
+        for &id in &order {
+            let depth = h[id].depth;
+            let p = padding_stack[depth];
+            let node = &h[id];
+            let [x0, y0, x1, y1] = collapse([node.x0 + p, node.y0 + p, node.x1 - p, node.y1 - p]);
+            let node = &mut h[id];
+            (node.x0, node.y0, node.x1, node.y1) = (x0, y0, x1, y1);
+            if !node.is_leaf() {
+                let p = self.padding_inner / 2.0;
+                padding_stack[depth + 1] = p;
+                let rect = collapse([
+                    x0 + self.padding_left - p,
+                    y0 + self.padding_top - p,
+                    x1 - (self.padding_right - p),
+                    y1 - (self.padding_bottom - p),
+                ]);
+                self.tile.tile(h, id, rect);
+            }
         }
-        let w = self.size.0;
-        let h = self.size.1;
-        let child_w = w / n as f64;
-        for (i, child) in root.children.iter_mut().enumerate() {
-            child.x = Some(i as f64 * child_w);
-            child.y = Some(0.0);
-            // Recursively layout children (vertical split)
-            TreemapLayout { size: (child_w, h) }.layout(child);
+        if self.round {
+            for &id in &order {
+                let node = &mut h[id];
+                node.x0 = node.x0.round();
+                node.y0 = node.y0.round();
+                node.x1 = node.x1.round();
+                node.y1 = node.y1.round();
+            }
         }
-        root.x = Some(0.0);
-        root.y = Some(0.0);
     }
 }
+
+// Collapse an inverted extent to its midpoint
+fn collapse([mut x0, mut y0, mut x1, mut y1]: [f64; 4]) -> [f64; 4] {
+    if x1 < x0 {
+        x0 = (x0 + x1) / 2.0;
+        x1 = x0;
+    }
+    if y1 < y0 {
+        y0 = (y0 + y1) / 2.0;
+        y1 = y0;
+    }
+    [x0, y0, x1, y1]
+}
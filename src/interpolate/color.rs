@@ -0,0 +1,78 @@
+//! d3-interpolate: Interpolation between `color::Color` values in RGB, Lab and HCL
+
+use crate::color::Color;
+use crate::color::hcl::Hcl;
+use crate::color::lab::Lab;
+use crate::color::rgb::Rgb;
+
+fn lerp(a: f32, b: f32, t: f64) -> f32 {
+    a + (b - a) * t as f32
+}
+
+/// Blend two colours channel by channel in RGB (D3 `interpolateRgb`)
+pub fn interpolate_color_rgb(a: &Color, b: &Color, t: f64) -> Color {
+    let (a, b) = (a.rgb(), b.rgb());
+    let channel = |x: u8, y: u8| lerp(x as f32, y as f32, t).round().clamp(0.0, 255.0) as u8;
+    Color::Rgb(Rgb::new(
+        channel(a.r, b.r),
+        channel(a.g, b.g),
+        channel(a.b, b.b),
+        lerp(a.opacity, b.opacity, t),
+    ))
+}
+
+/// Blend two colours in CIELAB (D3 `interpolateLab`)
+pub fn interpolate_color_lab(a: &Color, b: &Color, t: f64) -> Color {
+    let (a, b) = (a.lab(), b.lab());
+    Color::Lab(Lab::new(
+        lerp(a.l, b.l, t),
+        lerp(a.a, b.a, t),
+        lerp(a.b, b.b, t),
+        lerp(a.opacity, b.opacity, t),
+    ))
+}
+
+/// Blend two colours in CIELCh, taking the shorter way around the hue circle
+/// (D3 `interpolateHcl`)
+pub fn interpolate_color_hcl(a: &Color, b: &Color, t: f64) -> Color {
+    let (a, b) = (a.hcl(), b.hcl());
+    // Greys have no meaningful hue; borrow the other end's
+    let (ha, hb) = match (a.c == 0.0, b.c == 0.0) {
+        (true, false) => (b.h, b.h),
+        (false, true) => (a.h, a.h),
+        _ => (a.h, b.h),
+    };
+    let mut dh = (hb - ha).rem_euclid(360.0);
+    if dh > 180.0 {
+        dh -= 360.0;
+    }
+    Color::Hcl(Hcl::new(
+        (ha + dh * t as f32).rem_euclid(360.0),
+        lerp(a.c, b.c, t),
+        lerp(a.l, b.l, t),
+        lerp(a.opacity, b.opacity, t),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_interpolators() {
+        let black: Color = "#000000".parse().unwrap();
+        let white: Color = "#ffffff".parse().unwrap();
+        assert_eq!(
+            interpolate_color_rgb(&black, &white, 0.5).format_hex(),
+            "#808080"
+        );
+        let mid = interpolate_color_lab(&black, &white, 0.5).lab();
+        assert!((mid.l - 50.0).abs() < 0.5);
+
+        let red: Color = "#ff0000".parse().unwrap();
+        let blue: Color = "#0000ff".parse().unwrap();
+        let hue = interpolate_color_hcl(&red, &blue, 0.5).hcl().h;
+        // Red (~40°) to blue (~306°) goes backwards through 0°
+        assert!(!(60.0..300.0).contains(&hue));
+    }
+}
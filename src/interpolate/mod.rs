@@ -1,16 +1,22 @@
 //! d3-interpolate: Interpolators for numbers, colors, arrays, objects, strings, etc.
 
 pub mod array;
+pub mod color;
 pub mod hsl;
 pub mod number;
 pub mod rgb;
 pub mod string;
+pub mod value;
+pub mod zoom;
 
 pub use array::interpolate_array;
+pub use color::{interpolate_color_hcl, interpolate_color_lab, interpolate_color_rgb};
 pub use hsl::interpolate_hsl;
 pub use number::interpolate_number;
 pub use rgb::{hex_to_hsl, hsl_to_hex, interpolate_rgb};
 pub use string::interpolate_string;
+pub use value::Interpolate;
+pub use zoom::{ZoomInterpolator, interpolate_zoom};
 
 // Piecewise interpolation (array of stops)
 pub fn interpolate_piecewise<T, F>(interpolator: F, values: &[T], t: f64) -> T
@@ -44,4 +50,40 @@ mod tests {
         assert_eq!(interpolate_piecewise(interp, &arr, 0.5), 10.0);
         assert_eq!(interpolate_piecewise(interp, &arr, 1.0), 20.0);
     }
+
+    #[test]
+    fn test_interpolate_trait() {
+        assert_eq!(f64::interpolate(&0.0, &10.0, 0.25), 2.5);
+        let c = String::interpolate(&"#000000".to_string(), &"#ffffff".to_string(), 0.5);
+        assert_eq!(c, "#808080");
+        let s = String::interpolate(&"0px".to_string(), &"10px".to_string(), 0.5);
+        assert_eq!(s, "5px");
+    }
+
+    #[test]
+    fn test_string_multiple_numbers() {
+        let s = interpolate_string("translate(0,0)", "translate(100,50)", 0.5);
+        assert_eq!(s, "translate(50,25)");
+    }
+
+    #[test]
+    fn test_interpolate_zoom() {
+        let close = |a: [f64; 3], b: [f64; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9);
+        // Panning zooms out mid-way
+        let i = interpolate_zoom([0.0, 0.0, 1.0], [10.0, 0.0, 1.0]);
+        assert!(close(i.at(0.0), [0.0, 0.0, 1.0]));
+        assert!(close(i.at(1.0), [10.0, 0.0, 1.0]));
+        let mid = i.at(0.5);
+        assert!((mid[0] - 5.0).abs() < 1e-9 && mid[2] > 1.0);
+        assert!(i.duration() > 0.0);
+
+        // A pure zoom about a fixed centre, in either direction
+        let i = interpolate_zoom([5.0, 5.0, 4.0], [5.0, 5.0, 1.0]);
+        assert!(close(i.at(0.5), [5.0, 5.0, 2.0]));
+        assert!(close(i.at(1.0), [5.0, 5.0, 1.0]));
+        assert_eq!(
+            i.duration(),
+            interpolate_zoom([5.0, 5.0, 1.0], [5.0, 5.0, 4.0]).duration()
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! d3-interpolate: String interpolation (number extraction only)
 
-use regex::Regex;
+use regex::{Captures, Regex};
 
 /// Interpolate numbers in a string (e.g., "foo0" to "foo1")
 pub fn interpolate_string(a: &str, b: &str, t: f64) -> String {
@@ -18,9 +18,11 @@ pub fn interpolate_string(a: &str, b: &str, t: f64) -> String {
             a_nums[i] = a_nums[i] + (b_num - a_nums[i]) * t;
         }
     }
-    let mut result = a.to_string();
-    for num in a_nums {
-        result = re.replace(&result, format!("{}", num)).to_string();
-    }
-    result
+    // Substitute each number in place, in order of appearance
+    let mut nums = a_nums.into_iter();
+    re.replace_all(a, |_: &Captures| match nums.next() {
+        Some(num) => format!("{}", num),
+        None => String::new(),
+    })
+    .to_string()
 }
@@ -0,0 +1,68 @@
+//! d3-interpolate: Typed interpolation between two values of the same type
+
+use crate::color::Color;
+use crate::interpolate::{
+    interpolate_color_rgb, interpolate_number, interpolate_rgb, interpolate_string,
+};
+
+/// A value that can be blended with another of its type, `t` in `[0, 1]`
+///
+/// Scales with typed ranges (e.g. `ScaleLinear<Color>`, `ScaleSequential::range`)
+/// use this as their default interpolator. Implement it for your own types to
+/// use them as scale outputs.
+pub trait Interpolate: Clone {
+    fn interpolate(a: &Self, b: &Self, t: f64) -> Self;
+}
+
+// f64 is the only float impl, so float literals in scale ranges infer to f64
+impl Interpolate for f64 {
+    fn interpolate(a: &Self, b: &Self, t: f64) -> Self {
+        interpolate_number(*a, *b, t)
+    }
+}
+
+/// Blends in RGB; use `interpolate_color_lab`/`interpolate_color_hcl` for
+/// other colour spaces
+impl Interpolate for Color {
+    fn interpolate(a: &Self, b: &Self, t: f64) -> Self {
+        interpolate_color_rgb(a, b, t)
+    }
+}
+
+/// Points such as `(x, y)` blend per coordinate
+impl Interpolate for (f64, f64) {
+    fn interpolate(a: &Self, b: &Self, t: f64) -> Self {
+        (
+            interpolate_number(a.0, b.0, t),
+            interpolate_number(a.1, b.1, t),
+        )
+    }
+}
+
+impl<const N: usize> Interpolate for [f64; N] {
+    fn interpolate(a: &Self, b: &Self, t: f64) -> Self {
+        std::array::from_fn(|i| interpolate_number(a[i], b[i], t))
+    }
+}
+
+/// `#rrggbb` colours blend in RGB, numbers numerically, and other strings by
+/// the numbers embedded in them (D3 `interpolate` on strings)
+impl Interpolate for String {
+    fn interpolate(a: &Self, b: &Self, t: f64) -> Self {
+        if let (Ok(x), Ok(y)) = (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+            return format!("{}", interpolate_number(x, y, t));
+        }
+        if is_hex_color(a) && is_hex_color(b) {
+            return interpolate_rgb(a, b, t.clamp(0.0, 1.0));
+        }
+        if t >= 1.0 {
+            b.clone()
+        } else {
+            interpolate_string(a, b, t)
+        }
+    }
+}
+
+fn is_hex_color(s: &str) -> bool {
+    s.len() == 7 && s.starts_with('#') && s[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
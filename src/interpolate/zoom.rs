@@ -0,0 +1,83 @@
+//! d3-interpolate: Smooth zooming between two views
+
+const EPSILON2: f64 = 1e-12;
+
+/// Interpolator between two views `[cx, cy, width]`, following the path of
+/// van Wijk and Nuij's "Smooth and efficient zooming and panning"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoomInterpolator {
+    p0: [f64; 3],
+    p1: [f64; 3],
+    rho: f64,
+    // Path length, and the path parameters r0 (zero for a pure zoom)
+    s: f64,
+    r0: f64,
+    d1: f64,
+}
+
+/// Interpolate between the views `p0` and `p1`, each `[cx, cy, width]`, with
+/// the default curvature `rho` of √2
+pub fn interpolate_zoom(p0: [f64; 3], p1: [f64; 3]) -> ZoomInterpolator {
+    ZoomInterpolator::new(p0, p1, std::f64::consts::SQRT_2)
+}
+
+impl ZoomInterpolator {
+    /// `rho` trades zooming out against panning: higher values zoom out
+    /// further mid-way; it is clamped to at least 0.001
+    pub fn new(p0: [f64; 3], p1: [f64; 3], rho: f64) -> Self {
+        let rho = rho.max(1e-3);
+        let (rho2, rho4) = (rho * rho, rho * rho * rho * rho);
+        let [ux0, uy0, w0] = p0;
+        let [ux1, uy1, w1] = p1;
+        let (dx, dy) = (ux1 - ux0, uy1 - uy0);
+        let d2 = dx * dx + dy * dy;
+        if d2 < EPSILON2 {
+            return Self {
+                p0,
+                p1,
+                rho,
+                s: (w1 / w0).ln() / rho,
+                r0: 0.0,
+                d1: 0.0,
+            };
+        }
+        let d1 = d2.sqrt();
+        let b0 = (w1 * w1 - w0 * w0 + rho4 * d2) / (2.0 * w0 * rho2 * d1);
+        let b1 = (w1 * w1 - w0 * w0 - rho4 * d2) / (2.0 * w1 * rho2 * d1);
+        let r0 = ((b0 * b0 + 1.0).sqrt() - b0).ln();
+        let r1 = ((b1 * b1 + 1.0).sqrt() - b1).ln();
+        Self {
+            p0,
+            p1,
+            rho,
+            s: (r1 - r0) / rho,
+            r0,
+            d1,
+        }
+    }
+
+    /// The view at `t` in [0, 1]
+    pub fn at(&self, t: f64) -> [f64; 3] {
+        let [ux0, uy0, w0] = self.p0;
+        let [ux1, uy1, _] = self.p1;
+        let (dx, dy) = (ux1 - ux0, uy1 - uy0);
+        let (rho, s) = (self.rho, t * self.s);
+        if self.d1 == 0.0 {
+            return [ux0 + t * dx, uy0 + t * dy, w0 * (rho * s).exp()];
+        }
+        let cosh_r0 = self.r0.cosh();
+        let u =
+            w0 / (rho * rho * self.d1) * (cosh_r0 * (rho * s + self.r0).tanh() - self.r0.sinh());
+        [
+            ux0 + u * dx,
+            uy0 + u * dy,
+            w0 * cosh_r0 / (rho * s + self.r0).cosh(),
+        ]
+    }
+
+    /// Recommended duration in milliseconds, proportional to the length of
+    /// the path
+    pub fn duration(&self) -> f64 {
+        (self.s * 1000.0 * self.rho / std::f64::consts::SQRT_2).abs()
+    }
+}
@@ -1,13 +1,20 @@
 // Only export the array module for isolated testing
 pub mod array;
 pub mod axis;
+pub mod brush;
 pub mod chord;
 pub mod collection;
 pub mod color;
 pub mod contour;
+pub mod delaunay;
 pub mod dispatch;
+pub mod drag;
+pub mod dsv;
 pub mod ease;
+pub mod fetch;
+pub mod force;
 pub mod format;
+pub mod geo;
 pub mod geojson;
 pub mod hierarchy;
 pub mod interpolate;
@@ -21,6 +28,9 @@ pub mod selection;
 pub mod shape;
 pub mod time;
 pub mod timer;
+pub mod topojson;
+pub mod transition;
+pub mod zoom;
 
 pub use array::bisector::bisector;
 pub use array::quickselect::quickselect;
@@ -41,7 +41,9 @@ impl<T> Quadtree<T> {
         match node {
             None => Node::Leaf(vec![(x, y, value)]),
             Some(Node::Leaf(mut pts)) => {
-                if pts.len() < bucket_size {
+                // Coincident points can never be split apart, so they share
+                // one leaf however many there are
+                if pts.len() < bucket_size || pts.iter().all(|p| p.0 == x && p.1 == y) {
                     pts.push((x, y, value));
                     Node::Leaf(pts)
                 } else {
@@ -98,7 +100,7 @@ impl<T> Quadtree<T> {
             (true, true) => 3,   // SE
         }
     }
-    fn child_bounds((x0, y0, x1, y1): (f64, f64, f64, f64), idx: usize) -> (f64, f64, f64, f64) {
+    pub(crate) fn child_bounds((x0, y0, x1, y1): (f64, f64, f64, f64), idx: usize) -> (f64, f64, f64, f64) {
         let xm = (x0 + x1) / 2.0;
         let ym = (y0 + y1) / 2.0;
         match idx {
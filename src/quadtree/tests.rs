@@ -74,4 +74,15 @@ mod tests {
         assert!(vals.contains(&"b"));
         assert!(!vals.contains(&"c"));
     }
+
+    #[test]
+    fn test_quadtree_coincident_points() {
+        let mut qt = Quadtree::new((0.0, 0.0, 10.0, 10.0));
+        for i in 0..10 {
+            qt.insert(5.0, 5.0, i);
+        }
+        qt.insert(1.0, 1.0, 10);
+        assert_eq!(qt.len(), 11);
+        assert_eq!(qt.query_range((4.0, 4.0, 6.0, 6.0)).len(), 10);
+    }
 }
@@ -43,6 +43,31 @@ pub fn random_uniform_seeded(seed: u64) -> f64 {
     rng.random()
 }
 
+/// Seeded linear congruential generator of uniform numbers in [0, 1) (D3's `randomLcg`)
+///
+/// Fast and reproducible across platforms, matching D3's sequence for the same
+/// seed. A seed in [0, 1) is scaled to the full 32-bit state; any other seed
+/// uses its integer part.
+pub fn random_lcg(seed: f64) -> impl FnMut() -> f64 + Clone + Send + 'static {
+    const MUL: u32 = 0x19660D;
+    const INC: u32 = 0x3C6EF35F;
+    const EPS: f64 = 1.0 / 4294967296.0;
+    let s = if (0.0..1.0).contains(&seed) {
+        seed / EPS
+    } else {
+        seed.abs()
+    };
+    let mut state = if s.is_finite() {
+        s.trunc().rem_euclid(4294967296.0) as u32
+    } else {
+        0
+    };
+    move || {
+        state = state.wrapping_mul(MUL).wrapping_add(INC);
+        state as f64 * EPS
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +103,16 @@ mod tests {
         let x2 = random_uniform_seeded(42);
         assert_eq!(x1, x2);
     }
+    #[test]
+    fn test_random_lcg() {
+        let mut a = random_lcg(0.0);
+        assert_eq!(a(), 1013904223.0 / 4294967296.0);
+        let mut b = random_lcg(0.5);
+        let mut c = random_lcg(2147483648.0);
+        for _ in 0..100 {
+            let x = b();
+            assert_eq!(x, c());
+            assert!((0.0..1.0).contains(&x));
+        }
+    }
 }
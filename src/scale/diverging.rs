@@ -0,0 +1,184 @@
+// d3-scale: ScaleDiverging
+// Maps [d0, d1, d2] to [0, 0.5, 1] and through an interpolator
+use super::sequential::continuous_ticks;
+use super::transform::Transform;
+use crate::interpolate::Interpolate;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct ScaleDiverging<T: Clone> {
+    pub domain: [f64; 3],
+    pub clamp: bool,
+    pub unknown: Option<T>,
+    pub transform: Transform,
+    interpolator: Rc<dyn Fn(f64) -> T>,
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for ScaleDiverging<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScaleDiverging")
+            .field("domain", &self.domain)
+            .field("clamp", &self.clamp)
+            .field("unknown", &self.unknown)
+            .field("transform", &self.transform)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> ScaleDiverging<T> {
+    /// Linear diverging scale (D3 `scaleDiverging`)
+    pub fn new(domain: [f64; 3], interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self {
+            domain,
+            clamp: false,
+            unknown: None,
+            transform: Transform::Identity,
+            interpolator: Rc::new(interpolator),
+        }
+    }
+    /// D3 `scaleDivergingLog`; the domain must not cross zero
+    pub fn log(domain: [f64; 3], base: f64, interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self::new(domain, interpolator).with_transform(Transform::Log { base })
+    }
+    /// D3 `scaleDivergingPow`
+    pub fn pow(domain: [f64; 3], exponent: f64, interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self::new(domain, interpolator).with_transform(Transform::Pow { exponent })
+    }
+    /// D3 `scaleDivergingSqrt`
+    pub fn sqrt(domain: [f64; 3], interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self::pow(domain, 0.5, interpolator)
+    }
+    /// D3 `scaleDivergingSymlog`
+    pub fn symlog(
+        domain: [f64; 3],
+        constant: f64,
+        interpolator: impl Fn(f64) -> T + 'static,
+    ) -> Self {
+        Self::new(domain, interpolator).with_transform(Transform::Symlog { constant })
+    }
+
+    fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Returns `unknown` for NaN input or values outside a log domain
+    pub fn scale(&self, x: f64) -> Option<T> {
+        if x.is_nan() {
+            return self.unknown.clone();
+        }
+        let negative = self.transform.reflects(&self.domain);
+        let [t0, t1, t2] = self.domain.map(|d| self.transform.apply(d, negative));
+        let tx = self.transform.apply(x, negative);
+        if tx.is_nan() {
+            return self.unknown.clone();
+        }
+        let k10 = if t0 == t1 { 0.0 } else { 0.5 / (t1 - t0) };
+        let k21 = if t1 == t2 { 0.0 } else { 0.5 / (t2 - t1) };
+        let s = if t1 < t0 { -1.0 } else { 1.0 };
+        let k = if s * tx < s * t1 { k10 } else { k21 };
+        let mut t = 0.5 + (tx - t1) * k;
+        if self.clamp {
+            t = t.clamp(0.0, 1.0);
+        }
+        Some((self.interpolator)(t))
+    }
+
+    pub fn domain(&self) -> [f64; 3] {
+        self.domain
+    }
+
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    pub fn unknown(mut self, value: T) -> Self {
+        self.unknown = Some(value);
+        self
+    }
+
+    pub fn interpolator(mut self, interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        self.interpolator = Rc::new(interpolator);
+        self
+    }
+
+    /// Replace the interpolator with a piecewise one through the three values
+    pub fn range(mut self, range: [T; 3]) -> Self
+    where
+        T: Interpolate + 'static,
+    {
+        let [a, b, c] = range;
+        self.interpolator = Rc::new(move |t| {
+            if t < 0.5 {
+                T::interpolate(&a, &b, t * 2.0)
+            } else {
+                T::interpolate(&b, &c, t * 2.0 - 1.0)
+            }
+        });
+        self
+    }
+
+    /// The interpolator at `[0, 0.5, 1]`
+    pub fn get_range(&self) -> [T; 3] {
+        [0.0, 0.5, 1.0].map(|t| (self.interpolator)(t))
+    }
+
+    /// Ticks over the domain extent; log scales tick at powers of the base
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        continuous_ticks(self.transform, [self.domain[0], self.domain[2]], count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale_chromatic::interpolate_rdyblu;
+
+    #[test]
+    fn test_diverging_midpoint() {
+        let s = ScaleDiverging::new([-1.0, 0.0, 3.0], |t| t);
+        assert_eq!(s.scale(-1.0), Some(0.0));
+        assert_eq!(s.scale(-0.5), Some(0.25));
+        assert_eq!(s.scale(0.0), Some(0.5));
+        assert_eq!(s.scale(1.5), Some(0.75));
+        assert_eq!(s.scale(3.0), Some(1.0));
+    }
+
+    #[test]
+    fn test_diverging_reversed_and_clamped() {
+        let s = ScaleDiverging::new([1.0, 0.0, -1.0], |t| t).clamp(true);
+        assert_eq!(s.scale(0.5), Some(0.25));
+        assert_eq!(s.scale(-2.0), Some(1.0));
+        let flat = ScaleDiverging::new([0.0, 0.0, 1.0], |t| t);
+        assert_eq!(flat.scale(-3.0), Some(0.5));
+    }
+
+    #[test]
+    fn test_diverging_range_and_chromatic() {
+        let s = ScaleDiverging::new([-1.0, 0.0, 1.0], |_| String::new()).range([
+            "#ff0000".to_string(),
+            "#ffffff".to_string(),
+            "#00ff00".to_string(),
+        ]);
+        assert_eq!(s.scale(-0.5).unwrap(), "#ff8080");
+        assert_eq!(s.scale(0.0).unwrap(), "#ffffff");
+        assert_eq!(s.get_range()[2], "#00ff00");
+
+        let rdylbu = ScaleDiverging::new([-10.0, 0.0, 10.0], interpolate_rdyblu).unknown("#ccc");
+        assert_eq!(rdylbu.scale(0.0), Some(interpolate_rdyblu(0.5)));
+        assert_eq!(rdylbu.scale(f64::NAN), Some("#ccc"));
+    }
+
+    #[test]
+    fn test_diverging_transforms() {
+        let log = ScaleDiverging::log([1.0, 10.0, 1000.0], 10.0, |t| t);
+        assert!((log.scale(100.0).unwrap() - 0.75).abs() < 1e-12);
+        let sqrt = ScaleDiverging::sqrt([-100.0, 0.0, 100.0], |t| t);
+        assert!((sqrt.scale(25.0).unwrap() - 0.75).abs() < 1e-12);
+        let symlog = ScaleDiverging::symlog([-100.0, 0.0, 100.0], 1.0, |t| t);
+        assert!((symlog.scale(-100.0).unwrap()).abs() < 1e-12);
+        assert_eq!(log.ticks(3), vec![1.0, 10.0, 100.0, 1000.0]);
+    }
+}
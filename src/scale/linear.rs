@@ -1,62 +1,117 @@
 // d3-scale: ScaleLinear
+use super::transform::{extent, piecewise};
 use crate::array::{tick_step::tick_step, ticks::ticks};
 use crate::format::format::format;
+use crate::interpolate::Interpolate;
 
+/// Continuous linear scale
+///
+/// Domain and range may have more than two stops; each domain segment maps
+/// onto the matching range segment (a piecewise, or "polylinear", scale).
+///
+/// The range can be any [`Interpolate`] type, such as `color::Color`, points
+/// or strings. [`ScaleLinear::interpolate`] swaps the blending function,
+/// e.g. for Lab or HCL colour ramps. `invert` and `range_round` are only
+/// available for numeric ranges.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_d3::color::Color;
+/// use rust_d3::interpolate::interpolate_color_lab;
+/// use rust_d3::scale::ScaleLinear;
+///
+/// // Diverging axis with a fixed midpoint
+/// let s = ScaleLinear::new([-1.0, 0.0, 1.0], [0.0, 50.0, 200.0]);
+/// assert_eq!(s.scale(-0.5), 25.0);
+/// assert_eq!(s.scale(0.5), 125.0);
+/// assert_eq!(s.invert(125.0), 0.5);
+///
+/// let ramp: ScaleLinear<Color> = ScaleLinear::new(
+///     [0.0, 100.0],
+///     ["#000000".parse::<Color>().unwrap(), "#ffffff".parse().unwrap()],
+/// );
+/// assert_eq!(ramp.scale(50.0).format_hex(), "#808080");
+/// let lab = ramp.interpolate(interpolate_color_lab);
+/// assert_eq!(lab.scale(100.0).format_hex(), "#ffffff");
+/// ```
 #[derive(Debug, Clone)]
-pub struct ScaleLinear {
-    pub domain: [f64; 2],
-    pub range: [f64; 2],
+pub struct ScaleLinear<R = f64> {
+    pub domain: Vec<f64>,
+    pub range: Vec<R>,
     pub clamp: bool,
-    pub interpolate: fn(f64, f64, f64) -> f64,
-    pub unknown: Option<f64>,
+    pub interpolate: fn(&R, &R, f64) -> R,
+    pub unknown: Option<R>,
 }
 
-impl ScaleLinear {
-    pub fn new(domain: [f64; 2], range: [f64; 2]) -> Self {
+impl<R: Interpolate> ScaleLinear<R> {
+    pub fn new(domain: impl Into<Vec<f64>>, range: impl Into<Vec<R>>) -> Self {
         Self {
-            domain,
-            range,
+            domain: domain.into(),
+            range: range.into(),
             clamp: false,
-            interpolate: linear_interpolate,
+            interpolate: R::interpolate,
             unknown: None,
         }
     }
+}
 
-    pub fn scale(&self, x: f64) -> f64 {
-        if x.is_nan() {
-            return self.unknown.unwrap_or(f64::NAN);
+impl ScaleLinear<f64> {
+    pub fn invert(&self, y: f64) -> f64 {
+        if y.is_nan() {
+            return f64::NAN;
         }
 
-        let mut x = x;
+        let result =
+            piecewise(&self.range, &self.domain, y, linear_interpolate).unwrap_or(f64::NAN);
+
         if self.clamp {
-            x = x.max(self.domain[0]).min(self.domain[1]);
+            let (lo, hi) = extent(&self.domain);
+            result.max(lo).min(hi)
+        } else {
+            result
         }
+    }
 
-        let t = (x - self.domain[0]) / (self.domain[1] - self.domain[0]);
-        (self.interpolate)(self.range[0], self.range[1], t)
+    pub fn range_round(mut self, range: impl Into<Vec<f64>>) -> Self {
+        self.range = range.into();
+        self.interpolate = round_interpolate;
+        self
     }
+}
 
-    pub fn invert(&self, y: f64) -> f64 {
-        if y.is_nan() {
-            return f64::NAN;
+impl<R: Clone> ScaleLinear<R> {
+    /// Map `x` onto the range
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty and `unknown` is unset;
+    /// [`Scale::scale`](crate::scale::Scale::scale) returns `None` for an empty
+    /// range instead.
+    pub fn scale(&self, x: f64) -> R {
+        if x.is_nan()
+            && let Some(ref unknown) = self.unknown
+        {
+            return unknown.clone();
         }
 
-        let t = (y - self.range[0]) / (self.range[1] - self.range[0]);
-        let result = self.domain[0] + t * (self.domain[1] - self.domain[0]);
-
+        let mut x = x;
         if self.clamp {
-            result.max(self.domain[0]).min(self.domain[1])
-        } else {
-            result
+            let (lo, hi) = extent(&self.domain);
+            x = x.max(lo).min(hi);
         }
+
+        piecewise(&self.domain, &self.range, x, self.interpolate)
+            .or_else(|| self.unknown.clone())
+            .expect("scale range is empty")
     }
 
-    pub fn domain(&self) -> [f64; 2] {
-        self.domain
+    pub fn domain(&self) -> &[f64] {
+        &self.domain
     }
 
-    pub fn range(&self) -> [f64; 2] {
-        self.range
+    pub fn range(&self) -> &[R] {
+        &self.range
     }
 
     pub fn clamp(mut self, clamp: bool) -> Self {
@@ -64,18 +119,19 @@ impl ScaleLinear {
         self
     }
 
-    pub fn interpolate(mut self, interpolate: fn(f64, f64, f64) -> f64) -> Self {
+    pub fn interpolate(mut self, interpolate: fn(&R, &R, f64) -> R) -> Self {
         self.interpolate = interpolate;
         self
     }
 
-    pub fn unknown(mut self, value: f64) -> Self {
+    pub fn unknown(mut self, value: R) -> Self {
         self.unknown = Some(value);
         self
     }
 
     pub fn ticks(&self, count: usize) -> Vec<f64> {
-        let domain = self.domain;
+        // Ticks span the outer stops of a piecewise domain
+        let domain = [self.first(), self.last()];
         
         // If domain[0] == domain[1], return [domain[0]]
         if domain[0] == domain[1] {
@@ -97,9 +153,13 @@ impl ScaleLinear {
         }
     }
 
-    pub fn tick_format(&self, count: usize, specifier: Option<&str>) -> impl Fn(f64) -> String {
+    pub fn tick_format(
+        &self,
+        count: usize,
+        specifier: Option<&str>,
+    ) -> impl Fn(f64) -> String + use<R> {
         let spec = specifier.unwrap_or("");
-        let step = tick_step(self.domain[0], self.domain[1], count);
+        let step = tick_step(self.first(), self.last(), count);
         let precision = if spec.is_empty() {
             Self::default_precision(step)
         } else {
@@ -107,11 +167,7 @@ impl ScaleLinear {
         };
 
         let spec = if spec.is_empty() {
-            if precision > 0 {
-                format!(".{}f", precision)
-            } else {
-                "g".to_string()
-            }
+            format!(".{}f", precision)
         } else {
             spec.to_string()
         };
@@ -130,42 +186,52 @@ impl ScaleLinear {
 
     pub fn nice(&mut self, count: Option<usize>) {
         let count = count.unwrap_or(10);
-        let step = tick_step(self.domain[0], self.domain[1], count);
+        let (lo, hi) = extent(&self.domain);
+        let step = tick_step(lo, hi, count);
 
-        if step.is_finite() && step > 0.0 {
-            self.domain[0] = (self.domain[0] / step).floor() * step;
-            self.domain[1] = (self.domain[1] / step).ceil() * step;
+        // Only the outer stops move; interior stops of a piecewise domain stay put
+        if step.is_finite() && step > 0.0 && !self.domain.is_empty() {
+            let last = self.domain.len() - 1;
+            let (i_lo, i_hi) = if self.domain[last] < self.domain[0] {
+                (last, 0)
+            } else {
+                (0, last)
+            };
+            self.domain[i_lo] = (lo / step).floor() * step;
+            self.domain[i_hi] = (hi / step).ceil() * step;
         }
     }
 
     pub fn copy(&self) -> Self {
         Self {
-            domain: self.domain,
-            range: self.range,
+            domain: self.domain.clone(),
+            range: self.range.clone(),
             clamp: self.clamp,
             interpolate: self.interpolate,
-            unknown: self.unknown,
+            unknown: self.unknown.clone(),
         }
     }
 
-    pub fn range_round(mut self, range: [f64; 2]) -> Self {
-        self.range = range;
-        self.interpolate = round_interpolate;
-        self
+    fn first(&self) -> f64 {
+        self.domain.first().copied().unwrap_or(f64::NAN)
+    }
+
+    fn last(&self) -> f64 {
+        self.domain.last().copied().unwrap_or(f64::NAN)
     }
 }
 
 // Default linear interpolation
-fn linear_interpolate(a: f64, b: f64, t: f64) -> f64 {
+fn linear_interpolate(a: &f64, b: &f64, t: f64) -> f64 {
     a + t * (b - a)
 }
 
 // Rounding interpolation
-fn round_interpolate(a: f64, b: f64, t: f64) -> f64 {
+fn round_interpolate(a: &f64, b: &f64, t: f64) -> f64 {
     (a + t * (b - a)).round()
 }
 
-impl Default for ScaleLinear {
+impl Default for ScaleLinear<f64> {
     fn default() -> Self {
         Self::new([0.0, 1.0], [0.0, 1.0])
     }
@@ -328,4 +394,49 @@ mod tests {
         assert_eq!(ticks1, ticks2);
         assert_eq!(ticks2, ticks3);
     }
+
+    #[test]
+    fn test_polylinear() {
+        let s = ScaleLinear::new([-1.0, 0.0, 1.0], [0.0, 50.0, 200.0]);
+        assert_eq!(s.scale(-1.0), 0.0);
+        assert_eq!(s.scale(0.0), 50.0);
+        assert_eq!(s.scale(0.5), 125.0);
+        assert_eq!(s.scale(2.0), 350.0);
+        assert_eq!(s.invert(25.0), -0.5);
+        assert_eq!(s.invert(125.0), 0.5);
+        assert_eq!(s.clamp(true).scale(2.0), 200.0);
+    }
+
+    #[test]
+    fn test_polylinear_descending_and_ticks() {
+        let s = ScaleLinear::new(vec![10.0, 5.0, 0.0], vec![0.0, 80.0, 100.0]);
+        assert_eq!(s.scale(7.5), 40.0);
+        assert_eq!(s.scale(2.5), 90.0);
+        assert_eq!(s.invert(90.0), 2.5);
+        assert_eq!(s.ticks(2), vec![10.0, 5.0, 0.0]);
+
+        let mut s = ScaleLinear::new([-0.9, 0.0, 1.1], [0.0, 50.0, 100.0]);
+        s.nice(Some(5));
+        assert_eq!(s.domain(), [-1.0, 0.0, 1.5]);
+    }
+
+    #[test]
+    fn test_generic_range() {
+        use crate::color::Color;
+        use crate::interpolate::interpolate_color_hcl;
+
+        let color = |s: &str| s.parse::<Color>().unwrap();
+        let s = ScaleLinear::new([-1.0, 0.0, 1.0], [color("#ff0000"), color("#ffffff"), color("#0000ff")]);
+        assert_eq!(s.scale(0.0).format_hex(), "#ffffff");
+        assert_eq!(s.scale(-0.5).format_hex(), "#ff8080");
+        assert_eq!(s.scale(0.5).format_hex(), "#8080ff");
+        let hcl = s.clone().interpolate(interpolate_color_hcl);
+        assert_eq!(hcl.scale(1.0).format_hex(), "#0000ff");
+        assert_eq!(s.unknown(color("#000000")).scale(f64::NAN).format_hex(), "#000000");
+
+        let points = ScaleLinear::new([0.0, 10.0], [(0.0, 0.0), (100.0, 50.0)]);
+        assert_eq!(points.scale(5.0), (50.0, 25.0));
+        let labels = ScaleLinear::new([0.0, 1.0], ["0px".to_string(), "20px".to_string()]);
+        assert_eq!(labels.scale(0.25), "5px");
+    }
 }
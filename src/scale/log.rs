@@ -1,34 +1,81 @@
 // d3-scale: ScaleLog
+use super::transform::{Transform, extent, piecewise};
+use crate::format::{format, parse_specifier};
+use crate::interpolate::Interpolate;
+
+/// Log scale; the domain may have more than two stops, all of the same sign
+///
+/// The range can be any [`Interpolate`] type; `invert` needs a numeric range.
 #[derive(Debug, Clone)]
-pub struct ScaleLog {
-    pub domain: [f64; 2],
-    pub range: [f64; 2],
+pub struct ScaleLog<R = f64> {
+    pub domain: Vec<f64>,
+    pub range: Vec<R>,
     pub base: f64,
+    pub interpolate: fn(&R, &R, f64) -> R,
 }
 
-impl ScaleLog {
-    pub fn new(domain: [f64; 2], range: [f64; 2], base: f64) -> Self {
+impl<R: Interpolate> ScaleLog<R> {
+    pub fn new(domain: impl Into<Vec<f64>>, range: impl Into<Vec<R>>, base: f64) -> Self {
         Self {
-            domain,
-            range,
+            domain: domain.into(),
+            range: range.into(),
             base,
+            interpolate: R::interpolate,
         }
     }
-    pub fn scale(&self, x: f64) -> f64 {
-        let t = (x.ln() - self.domain[0].ln()) / (self.domain[1].ln() - self.domain[0].ln());
-        self.range[0] + t * (self.range[1] - self.range[0])
-    }
+}
+
+impl ScaleLog<f64> {
     pub fn invert(&self, y: f64) -> f64 {
-        let t = (y - self.range[0]) / (self.range[1] - self.range[0]);
-        (self.domain[0].ln() + t * (self.domain[1].ln() - self.domain[0].ln())).exp()
+        let (transform, negative) = self.transform();
+        let y = piecewise(&self.range, &self.transformed_domain(), y, |a, b, t| {
+            a + t * (b - a)
+        })
+        .unwrap_or(f64::NAN);
+        transform.invert(y, negative)
+    }
+}
+
+impl<R: Clone> ScaleLog<R> {
+    /// Map `x` onto the range
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty;
+    /// [`Scale::scale`](crate::scale::Scale::scale) returns `None` instead.
+    pub fn scale(&self, x: f64) -> R {
+        let (transform, negative) = self.transform();
+        piecewise(
+            &self.transformed_domain(),
+            &self.range,
+            transform.apply(x, negative),
+            self.interpolate,
+        )
+        .expect("scale range is empty")
+    }
+    pub fn interpolate(mut self, interpolate: fn(&R, &R, f64) -> R) -> Self {
+        self.interpolate = interpolate;
+        self
+    }
+    fn transform(&self) -> (Transform, bool) {
+        let transform = Transform::Log { base: self.base };
+        (transform, transform.reflects(&self.domain))
+    }
+    fn transformed_domain(&self) -> Vec<f64> {
+        let (transform, negative) = self.transform();
+        self.domain
+            .iter()
+            .map(|&d| transform.apply(d, negative))
+            .collect()
     }
     pub fn ticks(&self, count: usize) -> Vec<f64> {
-        // Generate log-spaced ticks between domain[0] and domain[1]
+        // Generate log-spaced ticks between the outer stops of the domain
         let mut ticks = Vec::new();
+        let (lo, hi) = extent(&self.domain);
         
         // Use the specified base for logarithm calculations
-        let start = self.domain[0].ln() / self.base.ln();
-        let end = self.domain[1].ln() / self.base.ln();
+        let start = lo.ln() / self.base.ln();
+        let end = hi.ln() / self.base.ln();
         
         // For logarithmic scales, we want ticks at nice powers of the base
         let start_power = start.floor() as i32;
@@ -37,7 +84,7 @@ impl ScaleLog {
         // Generate ticks at powers of the base
         for power in start_power..=end_power {
             let tick_value = self.base.powi(power);
-            if tick_value >= self.domain[0] && tick_value <= self.domain[1] {
+            if tick_value >= lo && tick_value <= hi {
                 ticks.push(tick_value);
             }
         }
@@ -52,7 +99,7 @@ impl ScaleLog {
                 // Add intermediate ticks (2x, 3x, 4x, etc. of base powers)
                 for multiplier in 2..=9 {
                     let tick_value = base_tick * multiplier as f64;
-                    if tick_value >= self.domain[0] && tick_value <= self.domain[1] {
+                    if tick_value >= lo && tick_value <= hi {
                         additional_ticks.push(tick_value);
                     }
                 }
@@ -64,14 +111,77 @@ impl ScaleLog {
         
         // Always include domain boundaries
         if !ticks.is_empty() {
-            if ticks[0] > self.domain[0] {
-                ticks.insert(0, self.domain[0]);
+            if ticks[0] > lo {
+                ticks.insert(0, lo);
             }
-            if ticks.last().unwrap() < &self.domain[1] {
-                ticks.push(self.domain[1]);
+            if ticks.last().unwrap() < &hi {
+                ticks.push(hi);
             }
         }
         
+        if self.domain.last() < self.domain.first() {
+            ticks.reverse();
+        }
         ticks
     }
+
+    /// Formatter for log ticks, as in D3: ticks whose leading digit is at
+    /// most `base * count / ticks(10).len()` (at least 1) get a label, so
+    /// fewer requested labels keep only 1, 2, … × base^k; the rest format as
+    /// the empty string
+    ///
+    /// `specifier` defaults to `s` for base 10 and `,` otherwise;
+    /// insignificant trailing zeros are trimmed unless it gives a precision.
+    pub fn tick_format(
+        &self,
+        count: usize,
+        specifier: Option<&str>,
+    ) -> impl Fn(f64) -> String + use<R> {
+        let base = self.base;
+        let spec = specifier
+            .unwrap_or(if base == 10.0 { "s" } else { "," })
+            .to_string();
+        let trim = base.fract() == 0.0 && parse_specifier(&spec).precision.is_none();
+        let k = (base * count as f64 / self.ticks(10).len() as f64).max(1.0);
+        move |d| {
+            let mut i = d.abs() / base.powf(log(d.abs(), base).round());
+            if i * base < base - 0.5 {
+                i *= base;
+            }
+            if i <= k {
+                let s = format(&spec, d);
+                if trim { trim_zeros(&s) } else { s }
+            } else {
+                String::new()
+            }
+        }
+    }
+}
+
+fn log(x: f64, base: f64) -> f64 {
+    if base == 10.0 {
+        x.log10()
+    } else if base == 2.0 {
+        x.log2()
+    } else {
+        x.ln() / base.ln()
+    }
+}
+
+// Drop trailing zeros after the decimal point, and the point itself if
+// nothing is left: "2.00k" becomes "2k", "1.50" becomes "1.5"
+fn trim_zeros(s: &str) -> String {
+    let Some(dot) = s.find('.') else {
+        return s.to_string();
+    };
+    let end = s[dot + 1..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(s.len(), |n| dot + 1 + n);
+    let kept = s[dot + 1..end].trim_end_matches('0');
+    let head = if kept.is_empty() {
+        &s[..dot]
+    } else {
+        &s[..dot + 1 + kept.len()]
+    };
+    format!("{}{}", head, &s[end..])
 }
@@ -31,6 +31,14 @@ pub mod quantize;
 pub use quantize::ScaleQuantize;
 pub mod threshold;
 pub use threshold::ScaleThreshold;
+pub mod transform;
+pub use transform::Transform;
+pub mod sequential;
+pub use sequential::ScaleSequential;
+pub mod diverging;
+pub use diverging::ScaleDiverging;
+pub mod traits;
+pub use traits::{BandLikeScale, ContinuousScale, Scale, TickFormatter};
 
 #[cfg(test)]
 mod tests {
@@ -59,6 +67,68 @@ mod tests {
         assert_eq!(s.invert(25.0), 0.5);
     }
 
+    #[test]
+    fn test_piecewise_continuous_scales() {
+        let pow = ScalePow::new([-2.0, 0.0, 1.0], [0.0, 40.0, 100.0], 2.0);
+        assert_eq!(pow.scale(-1.0), 30.0);
+        assert_eq!(pow.scale(0.5), 55.0);
+        assert_eq!(pow.invert(30.0), -1.0);
+
+        let log = ScaleLog::new([1.0, 10.0, 1000.0], [0.0, 50.0, 100.0], 10.0);
+        assert!((log.scale(100.0) - 75.0).abs() < 1e-9);
+        assert!((log.invert(25.0) - 10f64.sqrt()).abs() < 1e-9);
+        assert_eq!(log.ticks(2), vec![1.0, 10.0, 100.0, 1000.0]);
+    }
+
+    #[test]
+    fn test_empty_range() {
+        let mut linear = ScaleLinear::new([0.0, 10.0], Vec::<f64>::new());
+        assert_eq!(Scale::scale(&linear, &5.0), None);
+        linear.unknown = Some(-1.0);
+        assert_eq!(linear.scale(5.0), -1.0);
+        // Too few range stops invert to the first domain stop
+        assert_eq!(linear.invert(5.0), 0.0);
+
+        let log = ScaleLog::new([1.0, 10.0], Vec::<f64>::new(), 10.0);
+        assert_eq!(Scale::scale(&log, &5.0), None);
+        let pow = ScalePow::new([0.0, 1.0], Vec::<String>::new(), 2.0);
+        assert_eq!(Scale::scale(&pow, &0.5), None);
+        let symlog = ScaleSymlog::new([0.0, 1.0], Vec::<f64>::new(), 1.0);
+        assert_eq!(Scale::scale(&symlog, &0.5), None);
+
+        // An empty domain maps to the first range stop and inverts to NaN
+        let linear = ScaleLinear::new(Vec::new(), [3.0, 4.0]);
+        assert_eq!(linear.scale(1.0), 3.0);
+        assert!(linear.invert(3.5).is_nan());
+        assert!(ScaleLog::new(Vec::new(), [0.0, 1.0], 10.0).invert(0.5).is_nan());
+    }
+
+    #[test]
+    #[should_panic(expected = "scale range is empty")]
+    fn test_empty_range_panics() {
+        ScaleLinear::new([0.0, 10.0], Vec::<f64>::new()).scale(5.0);
+    }
+
+    #[test]
+    fn test_log_tick_format() {
+        let log = ScaleLog::new([1.0, 1000.0], [0.0, 100.0], 10.0);
+        let format = log.tick_format(10, None);
+        let labels: Vec<String> = [1.0, 2.0, 3.0, 4.0, 20.0, 300.0, 900.0, 1000.0]
+            .into_iter()
+            .map(&format)
+            .collect();
+        assert_eq!(labels, ["1", "2", "3", "", "20", "300", "", "1k"]);
+        // Fewer labels keep only the smallest leading digits
+        let format = ContinuousScale::tick_format(&log, 2, None);
+        assert_eq!((format(&10.0), format(&20.0)), ("10".into(), String::new()));
+        assert_eq!(log.tick_format(10, Some(","))(1000.0), "1,000");
+
+        let negative = ScaleLog::new([-100.0, -1.0], [0.0, 1.0], 10.0);
+        assert_eq!(negative.tick_format(10, None)(-20.0), "-20");
+        let binary = ScaleLog::new([1.0, 1024.0], [0.0, 1.0], 2.0);
+        assert_eq!(binary.tick_format(10, None)(1024.0), "1,024");
+    }
+
     #[test]
     fn test_sqrt_export() {
         let s = ScaleSqrt::new([0.0, 1.0], [0.0, 100.0], 0.5);
@@ -98,6 +168,39 @@ mod tests {
         assert!((s.scale(&"c").unwrap() - 83.33333333333334).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_scale_traits() {
+        fn midpoint<S: ContinuousScale<Domain = f64, Output = f64>>(s: &S) -> Option<f64> {
+            let domain = s.domain();
+            s.scale(&((domain[0] + domain[domain.len() - 1]) / 2.0))
+        }
+        assert_eq!(midpoint(&ScaleLinear::new([0.0, 10.0], [0.0, 100.0])), Some(50.0));
+        assert_eq!(midpoint(&ScalePow::new([0.0, 2.0], [0.0, 100.0], 2.0)), Some(25.0));
+        assert_eq!(midpoint(&ScaleIdentity::new([0.0, 4.0], [0.0, 4.0])), Some(2.0));
+
+        let pow = ScalePow::new([0.0, 1.0], [0.0, 100.0], 2.0);
+        assert_eq!(ContinuousScale::ticks(&pow, 2), vec![0.0, 0.5, 1.0]);
+        assert_eq!(ContinuousScale::tick_format(&pow, 2, None)(&0.5), "0.5");
+
+        fn outputs<S: Scale>(s: &S) -> Vec<Option<S::Output>> {
+            s.domain().iter().map(|d| s.scale(d)).collect()
+        }
+        let ordinal = ScaleOrdinal::new(vec!["a", "b"], vec!["red", "blue"]);
+        assert_eq!(outputs(&ordinal), vec![Some("red"), Some("blue")]);
+        let threshold = ScaleThreshold::new(vec![0.5], vec!["low", "high"]);
+        assert_eq!(Scale::range(&threshold), vec!["low", "high"]);
+
+        fn centres<S: BandLikeScale>(s: &S) -> Vec<f64> {
+            let half = s.bandwidth() / 2.0;
+            s.domain().iter().filter_map(|d| s.scale(d)).map(|x| x + half).collect()
+        }
+        let band = ScaleBand::new(vec!["a", "b"], [0.0, 100.0], 0.0, 0.0, 0.5);
+        assert_eq!(centres(&band), vec![25.0, 75.0]);
+        let point = ScalePoint::new(vec!["a", "b", "c"], [0.0, 100.0], 0.0);
+        assert_eq!(BandLikeScale::step(&point), 50.0);
+        assert_eq!(centres(&point), vec![0.0, 50.0, 100.0]);
+    }
+
     #[test]
     fn test_identity_export() {
         let s = ScaleIdentity::new([0.0, 1.0], [0.0, 1.0]);
@@ -129,6 +232,19 @@ mod tests {
         assert_eq!(s.scale(0.75), Some("b"));
     }
 
+    #[test]
+    fn test_sequential_export() {
+        let s = ScaleSequential::new([0.0, 10.0], crate::scale_chromatic::inferno).clamp(true);
+        assert_eq!(s.scale(0.0), Some(crate::scale_chromatic::inferno(0.0)));
+        assert_eq!(s.scale(20.0), Some(crate::scale_chromatic::inferno(1.0)));
+    }
+
+    #[test]
+    fn test_diverging_export() {
+        let s = ScaleDiverging::new([-1.0, 0.0, 1.0], |t| t);
+        assert_eq!(s.scale(0.0), Some(0.5));
+    }
+
     #[test]
     fn test_threshold_export() {
         let s = ScaleThreshold::new(vec![0.0, 1.0], vec!["red", "white", "blue"]);
@@ -14,18 +14,23 @@ impl<T: Clone + PartialEq> ScalePoint<T> {
             align,
         }
     }
-    pub fn scale(&self, x: &T) -> Option<f64> {
+    /// Distance between adjacent points
+    pub fn step(&self) -> f64 {
         let n = self.domain.len();
         if n == 0 {
+            return 0.0;
+        }
+        // Align used directly as padding factor
+        let denominator = ((n - 1) as f64 + 2.0 * self.align).max(1.0);
+        (self.range[1] - self.range[0]) / denominator
+    }
+    pub fn scale(&self, x: &T) -> Option<f64> {
+        if self.domain.is_empty() {
             return None;
         }
         
-        // Calculate the step size with padding
-        let range_size = self.range[1] - self.range[0];
-        let padding = self.align; // Align used directly as padding factor
-        let denominator = ((n - 1) as f64 + 2.0 * padding).max(1.0);
-        let step = range_size / denominator;
-        let start = self.range[0] + padding * step;
+        let step = self.step();
+        let start = self.range[0] + self.align * step;
         
         self.domain
             .iter()
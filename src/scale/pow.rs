@@ -1,25 +1,73 @@
 // d3-scale: ScalePow
+use super::ScaleLinear;
+use super::transform::{Transform, piecewise};
+use crate::interpolate::Interpolate;
+
+/// Power scale: `sign(x) * |x|^exponent` mapped linearly onto the range
+///
+/// As with [`ScaleLinear`](super::ScaleLinear), domain and range may have
+/// more than two stops.
 #[derive(Debug, Clone)]
-pub struct ScalePow {
-    pub domain: [f64; 2],
-    pub range: [f64; 2],
+pub struct ScalePow<R = f64> {
+    pub domain: Vec<f64>,
+    pub range: Vec<R>,
     pub exponent: f64,
+    pub interpolate: fn(&R, &R, f64) -> R,
 }
 
-impl ScalePow {
-    pub fn new(domain: [f64; 2], range: [f64; 2], exponent: f64) -> Self {
+impl<R: Interpolate> ScalePow<R> {
+    pub fn new(domain: impl Into<Vec<f64>>, range: impl Into<Vec<R>>, exponent: f64) -> Self {
         Self {
-            domain,
-            range,
+            domain: domain.into(),
+            range: range.into(),
             exponent,
+            interpolate: R::interpolate,
         }
     }
-    pub fn scale(&self, x: f64) -> f64 {
-        let t = ((x - self.domain[0]) / (self.domain[1] - self.domain[0])).powf(self.exponent);
-        self.range[0] + t * (self.range[1] - self.range[0])
-    }
+}
+
+impl ScalePow<f64> {
     pub fn invert(&self, y: f64) -> f64 {
-        let t = (y - self.range[0]) / (self.range[1] - self.range[0]);
-        self.domain[0] + t.powf(1.0 / self.exponent) * (self.domain[1] - self.domain[0])
+        let y = piecewise(&self.range, &self.transformed_domain(), y, |a, b, t| {
+            a + t * (b - a)
+        })
+        .unwrap_or(f64::NAN);
+        self.transform().invert(y, false)
+    }
+}
+
+impl<R: Clone> ScalePow<R> {
+    pub fn interpolate(mut self, interpolate: fn(&R, &R, f64) -> R) -> Self {
+        self.interpolate = interpolate;
+        self
+    }
+    /// Map `x` onto the range
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty;
+    /// [`Scale::scale`](crate::scale::Scale::scale) returns `None` instead.
+    pub fn scale(&self, x: f64) -> R {
+        let transform = self.transform();
+        piecewise(
+            &self.transformed_domain(),
+            &self.range,
+            transform.apply(x, false),
+            self.interpolate,
+        )
+        .expect("scale range is empty")
+    }
+    /// Ticks spaced as for a linear scale over the same domain (D3 `linearish`)
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        ScaleLinear::new(self.domain.clone(), [0.0, 1.0]).ticks(count)
+    }
+    fn transform(&self) -> Transform {
+        Transform::Pow {
+            exponent: self.exponent,
+        }
+    }
+    fn transformed_domain(&self) -> Vec<f64> {
+        let transform = self.transform();
+        self.domain.iter().map(|&d| transform.apply(d, false)).collect()
     }
 }
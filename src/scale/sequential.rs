@@ -0,0 +1,194 @@
+// d3-scale: ScaleSequential
+// Maps a continuous domain through an interpolator over [0, 1]
+use super::transform::Transform;
+use super::{ScaleLinear, ScaleLog};
+use crate::interpolate::Interpolate;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct ScaleSequential<T: Clone> {
+    pub domain: [f64; 2],
+    pub clamp: bool,
+    pub unknown: Option<T>,
+    pub transform: Transform,
+    interpolator: Rc<dyn Fn(f64) -> T>,
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for ScaleSequential<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScaleSequential")
+            .field("domain", &self.domain)
+            .field("clamp", &self.clamp)
+            .field("unknown", &self.unknown)
+            .field("transform", &self.transform)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> ScaleSequential<T> {
+    /// Linear sequential scale (D3 `scaleSequential`)
+    pub fn new(domain: [f64; 2], interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self {
+            domain,
+            clamp: false,
+            unknown: None,
+            transform: Transform::Identity,
+            interpolator: Rc::new(interpolator),
+        }
+    }
+    /// D3 `scaleSequentialLog`; the domain must not cross zero
+    pub fn log(domain: [f64; 2], base: f64, interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self::new(domain, interpolator).with_transform(Transform::Log { base })
+    }
+    /// D3 `scaleSequentialPow`
+    pub fn pow(domain: [f64; 2], exponent: f64, interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self::new(domain, interpolator).with_transform(Transform::Pow { exponent })
+    }
+    /// D3 `scaleSequentialSqrt`
+    pub fn sqrt(domain: [f64; 2], interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        Self::pow(domain, 0.5, interpolator)
+    }
+    /// D3 `scaleSequentialSymlog`
+    pub fn symlog(
+        domain: [f64; 2],
+        constant: f64,
+        interpolator: impl Fn(f64) -> T + 'static,
+    ) -> Self {
+        Self::new(domain, interpolator).with_transform(Transform::Symlog { constant })
+    }
+
+    fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Returns `unknown` for NaN input or values outside a log domain
+    pub fn scale(&self, x: f64) -> Option<T> {
+        if x.is_nan() {
+            return self.unknown.clone();
+        }
+        let negative = self.transform.reflects(&self.domain);
+        let t0 = self.transform.apply(self.domain[0], negative);
+        let t1 = self.transform.apply(self.domain[1], negative);
+        let tx = self.transform.apply(x, negative);
+        if tx.is_nan() {
+            return self.unknown.clone();
+        }
+        let mut t = if t0 == t1 { 0.5 } else { (tx - t0) / (t1 - t0) };
+        if self.clamp {
+            t = t.clamp(0.0, 1.0);
+        }
+        Some((self.interpolator)(t))
+    }
+
+    pub fn domain(&self) -> [f64; 2] {
+        self.domain
+    }
+
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    pub fn unknown(mut self, value: T) -> Self {
+        self.unknown = Some(value);
+        self
+    }
+
+    pub fn interpolator(mut self, interpolator: impl Fn(f64) -> T + 'static) -> Self {
+        self.interpolator = Rc::new(interpolator);
+        self
+    }
+
+    /// Replace the interpolator with one between `range[0]` and `range[1]`
+    pub fn range(mut self, range: [T; 2]) -> Self
+    where
+        T: Interpolate + 'static,
+    {
+        let [a, b] = range;
+        self.interpolator = Rc::new(move |t| T::interpolate(&a, &b, t));
+        self
+    }
+
+    /// The interpolator's endpoints, `[f(0), f(1)]`
+    pub fn get_range(&self) -> [T; 2] {
+        [(self.interpolator)(0.0), (self.interpolator)(1.0)]
+    }
+
+    /// Ticks over the domain; log scales tick at powers of the base
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        continuous_ticks(self.transform, self.domain, count)
+    }
+}
+
+pub(crate) fn continuous_ticks(transform: Transform, domain: [f64; 2], count: usize) -> Vec<f64> {
+    match transform {
+        Transform::Log { base } => ScaleLog::new(domain, [0.0, 1.0], base).ticks(count),
+        _ => ScaleLinear::new(domain, [0.0, 1.0]).ticks(count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpolate::interpolate_rgb;
+
+    #[test]
+    fn test_sequential_interpolator() {
+        let s = ScaleSequential::new([0.0, 100.0], |t| interpolate_rgb("#000000", "#ffffff", t));
+        assert_eq!(s.scale(0.0).unwrap(), "#000000");
+        assert_eq!(s.scale(50.0).unwrap(), "#808080");
+        assert_eq!(s.scale(100.0).unwrap(), "#ffffff");
+        assert_eq!(s.get_range(), ["#000000", "#ffffff"]);
+    }
+
+    #[test]
+    fn test_sequential_clamp_and_unknown() {
+        let s = ScaleSequential::new([0.0, 10.0], |t| t).unknown(-1.0);
+        assert_eq!(s.scale(20.0), Some(2.0));
+        assert_eq!(s.scale(f64::NAN), Some(-1.0));
+        let s = s.clamp(true);
+        assert_eq!(s.scale(20.0), Some(1.0));
+        assert_eq!(s.scale(-5.0), Some(0.0));
+        assert_eq!(
+            ScaleSequential::new([0.0, 1.0], |t| t).scale(f64::NAN),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sequential_range() {
+        let s = ScaleSequential::new([0.0, 1.0], |t| t).range([10.0, 20.0]);
+        assert_eq!(s.scale(0.5), Some(15.0));
+        let c = ScaleSequential::new([0.0, 1.0], |_| String::new())
+            .range(["#ff0000".to_string(), "#0000ff".to_string()]);
+        assert_eq!(c.scale(0.5).unwrap(), "#800080");
+    }
+
+    #[test]
+    fn test_sequential_transforms() {
+        let log = ScaleSequential::log([1.0, 100.0], 10.0, |t| t);
+        assert!((log.scale(10.0).unwrap() - 0.5).abs() < 1e-12);
+        assert_eq!(log.scale(-1.0), None);
+        let neg = ScaleSequential::log([-100.0, -1.0], 10.0, |t| t);
+        assert!((neg.scale(-10.0).unwrap() - 0.5).abs() < 1e-12);
+
+        let sqrt = ScaleSequential::sqrt([0.0, 100.0], |t| t);
+        assert!((sqrt.scale(25.0).unwrap() - 0.5).abs() < 1e-12);
+        let pow = ScaleSequential::pow([0.0, 2.0], 2.0, |t| t);
+        assert!((pow.scale(1.0).unwrap() - 0.25).abs() < 1e-12);
+
+        let symlog = ScaleSequential::symlog([-10.0, 10.0], 1.0, |t| t);
+        assert!((symlog.scale(0.0).unwrap() - 0.5).abs() < 1e-12);
+        assert!(symlog.scale(1.0).unwrap() < 0.75);
+    }
+
+    #[test]
+    fn test_sequential_ticks() {
+        let s = ScaleSequential::new([0.0, 10.0], |t| t);
+        assert_eq!(s.ticks(5), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        let log = ScaleSequential::log([1.0, 1000.0], 10.0, |t| t);
+        assert_eq!(log.ticks(2), vec![1.0, 10.0, 100.0, 1000.0]);
+    }
+}
@@ -1,3 +1,3 @@
 // d3-scale: ScaleSqrt
 use super::pow::ScalePow;
-pub type ScaleSqrt = ScalePow;
+pub type ScaleSqrt<R = f64> = ScalePow<R>;
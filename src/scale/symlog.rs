@@ -1,38 +1,70 @@
 // d3-scale: ScaleSymlog
+use super::ScaleLinear;
+use super::transform::{Transform, piecewise};
+use crate::interpolate::Interpolate;
+
+/// Bi-symmetric log scale; domain and range may have more than two stops
 #[derive(Debug, Clone)]
-pub struct ScaleSymlog {
-    pub domain: [f64; 2],
-    pub range: [f64; 2],
+pub struct ScaleSymlog<R = f64> {
+    pub domain: Vec<f64>,
+    pub range: Vec<R>,
     pub constant: f64, // typically 1.0 in D3
+    pub interpolate: fn(&R, &R, f64) -> R,
 }
 
-impl ScaleSymlog {
-    pub fn new(domain: [f64; 2], range: [f64; 2], constant: f64) -> Self {
+impl<R: Interpolate> ScaleSymlog<R> {
+    pub fn new(domain: impl Into<Vec<f64>>, range: impl Into<Vec<R>>, constant: f64) -> Self {
         Self {
-            domain,
-            range,
+            domain: domain.into(),
+            range: range.into(),
             constant,
+            interpolate: R::interpolate,
         }
     }
-    fn symlog(&self, x: f64) -> f64 {
-        let c = self.constant;
-        x.signum() * (x.abs() + c).ln() - c.ln()
+}
+
+impl ScaleSymlog<f64> {
+    pub fn invert(&self, y: f64) -> f64 {
+        let y = piecewise(&self.range, &self.transformed_domain(), y, |a, b, t| {
+            a + t * (b - a)
+        })
+        .unwrap_or(f64::NAN);
+        self.transform().invert(y, false)
     }
-    fn symexp(&self, y: f64) -> f64 {
-        let c = self.constant;
-        (y + c.ln()).exp() - c
+}
+
+impl<R: Clone> ScaleSymlog<R> {
+    pub fn interpolate(mut self, interpolate: fn(&R, &R, f64) -> R) -> Self {
+        self.interpolate = interpolate;
+        self
     }
-    pub fn scale(&self, x: f64) -> f64 {
-        let d0 = self.symlog(self.domain[0]);
-        let d1 = self.symlog(self.domain[1]);
-        let t = (self.symlog(x) - d0) / (d1 - d0);
-        self.range[0] + t * (self.range[1] - self.range[0])
+    /// Ticks spaced as for a linear scale over the same domain (D3 `linearish`)
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        ScaleLinear::new(self.domain.clone(), [0.0, 1.0]).ticks(count)
     }
-    pub fn invert(&self, y: f64) -> f64 {
-        let d0 = self.symlog(self.domain[0]);
-        let d1 = self.symlog(self.domain[1]);
-        let t = (y - self.range[0]) / (self.range[1] - self.range[0]);
-        self.symexp(d0 + t * (d1 - d0))
+    fn transform(&self) -> Transform {
+        Transform::Symlog {
+            constant: self.constant,
+        }
+    }
+    fn transformed_domain(&self) -> Vec<f64> {
+        let transform = self.transform();
+        self.domain.iter().map(|&d| transform.apply(d, false)).collect()
+    }
+    /// Map `x` onto the range
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty;
+    /// [`Scale::scale`](crate::scale::Scale::scale) returns `None` instead.
+    pub fn scale(&self, x: f64) -> R {
+        piecewise(
+            &self.transformed_domain(),
+            &self.range,
+            self.transform().apply(x, false),
+            self.interpolate,
+        )
+        .expect("scale range is empty")
     }
 }
 
@@ -47,4 +79,14 @@ mod tests {
         let inv = s.invert(mid);
         assert!((inv - 0.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_symlog_negative_and_piecewise() {
+        let s = ScaleSymlog::new([-10.0, 10.0], [0.0, 100.0], 1.0);
+        assert!((s.invert(s.scale(-5.0)) + 5.0).abs() < 1e-9);
+        let s = ScaleSymlog::new([-100.0, 0.0, 10.0], [0.0, 50.0, 100.0], 1.0);
+        assert_eq!(s.scale(0.0), 50.0);
+        assert!((s.scale(10.0) - 100.0).abs() < 1e-9);
+        assert!((s.invert(75.0) - (11f64.sqrt() - 1.0)).abs() < 1e-9);
+    }
 }
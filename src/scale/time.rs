@@ -3,6 +3,18 @@ mod tests {
     use chrono::{NaiveDate, Datelike};
     use super::*;
 
+    #[test]
+    fn test_piecewise_time_scale() {
+        let day = |d| NaiveDate::from_ymd_opt(2020, 1, d).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        // First day takes half of the range, the following two days the rest
+        let s = ScaleTime::new([day(1), day(2), day(4)], [0.0, 50.0, 100.0]);
+        assert_eq!(s.scale(day(3)), 75.0);
+        assert_eq!(s.invert(25.0), day(1) + Duration::try_hours(12).unwrap());
+        let ticks = s.ticks(3);
+        assert_eq!(ticks.first(), Some(&day(1)));
+        assert_eq!(ticks.last(), Some(&day(4)));
+    }
+
     #[test]
     fn test_ticks_time_scale() {
         let start_date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
@@ -276,66 +288,105 @@ mod tests {
 
 // d3-scale: ScaleTime
 use crate::time::{Day, Hour, Minute, Month, Second, TimeInterval, Week, Year, utc_format};
+use super::transform::{extent, piecewise};
+use crate::interpolate::Interpolate;
 use chrono::{Datelike, Duration, NaiveDateTime};
+/// Time scale; domain and range may have more than two stops
+///
+/// Like [`ScaleLinear`](super::ScaleLinear), the range can be any
+/// [`Interpolate`] type; `invert` and `range_round` need a numeric range.
 #[derive(Debug, Clone)]
-pub struct ScaleTime {
-    pub domain: [NaiveDateTime; 2],
-    pub range: [f64; 2],
+pub struct ScaleTime<R = f64> {
+    pub domain: Vec<NaiveDateTime>,
+    pub range: Vec<R>,
     pub clamp: bool,
-    pub interpolate: fn(f64, f64, f64) -> f64,
-    pub unknown: Option<f64>,
+    pub interpolate: fn(&R, &R, f64) -> R,
+    pub unknown: Option<R>,
 }
 
-impl ScaleTime {
-    pub fn new(domain: [NaiveDateTime; 2], range: [f64; 2]) -> Self {
+impl<R: Interpolate> ScaleTime<R> {
+    pub fn new(domain: impl Into<Vec<NaiveDateTime>>, range: impl Into<Vec<R>>) -> Self {
         Self {
-            domain,
-            range,
+            domain: domain.into(),
+            range: range.into(),
             clamp: false,
-            interpolate: linear_interpolate,
+            interpolate: R::interpolate,
             unknown: None,
         }
     }
+}
 
-    pub fn scale(&self, x: NaiveDateTime) -> f64 {
-        let mut x_millis = x.and_utc().timestamp_millis() as f64;
-        let domain_start = self.domain[0].and_utc().timestamp_millis() as f64;
-        let domain_end = self.domain[1].and_utc().timestamp_millis() as f64;
+impl ScaleTime<f64> {
+    pub fn invert(&self, y: f64) -> NaiveDateTime {
+        let domain = self.domain_millis();
+        let mut result_millis =
+            piecewise(&self.range, &domain, y, linear_interpolate).unwrap_or(f64::NAN);
 
         if self.clamp {
-            x_millis = x_millis.max(domain_start).min(domain_end);
+            let (lo, hi) = extent(&domain);
+            result_millis = result_millis.max(lo).min(hi);
         }
 
-        let t = (x_millis - domain_start) / (domain_end - domain_start);
-        (self.interpolate)(self.range[0], self.range[1], t)
+        chrono::DateTime::<chrono::Utc>::from_timestamp(
+            (result_millis / 1000.0) as i64,
+            ((result_millis % 1000.0) * 1_000_000.0) as u32,
+        )
+        .unwrap_or_else(|| self.start().and_utc())
+        .naive_utc()
     }
 
-    pub fn invert(&self, y: f64) -> NaiveDateTime {
-        let t = (y - self.range[0]) / (self.range[1] - self.range[0]);
-        let domain_start = self.domain[0].and_utc().timestamp_millis() as f64;
-        let domain_end = self.domain[1].and_utc().timestamp_millis() as f64;
+    pub fn range_round(mut self, range: impl Into<Vec<f64>>) -> Self {
+        self.range = range.into();
+        self.interpolate = round_interpolate;
+        self
+    }
+}
 
-        let millis = domain_start + t * (domain_end - domain_start);
-        let mut result_millis = millis;
+impl<R: Clone> ScaleTime<R> {
+    /// Map `x` onto the range
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty and `unknown` is unset;
+    /// [`Scale::scale`](crate::scale::Scale::scale) returns `None` for an empty
+    /// range instead.
+    pub fn scale(&self, x: NaiveDateTime) -> R {
+        let mut x_millis = x.and_utc().timestamp_millis() as f64;
+        let domain = self.domain_millis();
 
         if self.clamp {
-            result_millis = result_millis.max(domain_start).min(domain_end);
+            let (lo, hi) = extent(&domain);
+            x_millis = x_millis.max(lo).min(hi);
         }
 
-        chrono::DateTime::<chrono::Utc>::from_timestamp(
-            (result_millis / 1000.0) as i64,
-            ((result_millis % 1000.0) * 1_000_000.0) as u32,
-        )
-        .unwrap_or_else(|| self.domain[0].and_utc())
-        .naive_utc()
+        piecewise(&domain, &self.range, x_millis, self.interpolate)
+            .or_else(|| self.unknown.clone())
+            .expect("scale range is empty")
+    }
+
+    pub fn domain(&self) -> &[NaiveDateTime] {
+        &self.domain
+    }
+
+    pub fn range(&self) -> &[R] {
+        &self.range
     }
 
-    pub fn domain(&self) -> [NaiveDateTime; 2] {
+    fn domain_millis(&self) -> Vec<f64> {
         self.domain
+            .iter()
+            .map(|d| d.and_utc().timestamp_millis() as f64)
+            .collect()
     }
 
-    pub fn range(&self) -> [f64; 2] {
-        self.range
+    /// First stop of the domain
+    fn start(&self) -> NaiveDateTime {
+        self.domain.first().copied().unwrap_or_default()
+    }
+
+    /// Last stop of the domain
+    fn stop(&self) -> NaiveDateTime {
+        self.domain.last().copied().unwrap_or_default()
     }
 
     pub fn clamp(mut self, clamp: bool) -> Self {
@@ -343,19 +394,19 @@ impl ScaleTime {
         self
     }
 
-    pub fn interpolate(mut self, interpolate: fn(f64, f64, f64) -> f64) -> Self {
+    pub fn interpolate(mut self, interpolate: fn(&R, &R, f64) -> R) -> Self {
         self.interpolate = interpolate;
         self
     }
 
-    pub fn unknown(mut self, value: f64) -> Self {
+    pub fn unknown(mut self, value: R) -> Self {
         self.unknown = Some(value);
         self
     }
 
     pub fn ticks(&self, count: usize) -> Vec<NaiveDateTime> {
-        let start = self.domain[0];
-        let stop = self.domain[1];
+        let start = self.start();
+        let stop = self.stop();
 
         // Handle reverse case
         let reverse = stop < start;
@@ -404,8 +455,8 @@ impl ScaleTime {
     }
 
     pub fn tick_interval(&self, count: usize) -> TimeTickInterval {
-        let start = self.domain[0];
-        let stop = self.domain[1];
+        let start = self.start();
+        let stop = self.stop();
         
         // Handle reverse case for calculations
         let (start_calc, stop_calc) = if stop < start { (stop, start) } else { (start, stop) };
@@ -559,7 +610,7 @@ impl ScaleTime {
         &self,
         count: usize,
         specifier: Option<&str>,
-    ) -> impl Fn(&NaiveDateTime) -> String {
+    ) -> impl Fn(&NaiveDateTime) -> String + use<R> {
         let spec = specifier.unwrap_or(self.default_format_specifier(count));
         let spec = spec.to_string();
 
@@ -570,8 +621,8 @@ impl ScaleTime {
         let interval = self.tick_interval(count);
         
         // Check if this is a very short span (less than 10 seconds) - use date format
-        let start = self.domain[0];
-        let stop = self.domain[1];
+        let start = self.start();
+        let stop = self.stop();
         let duration_secs = (stop - start).num_seconds().abs();
         
         match interval {
@@ -594,61 +645,64 @@ impl ScaleTime {
     pub fn nice(&mut self, count: Option<usize>) {
         let count = count.unwrap_or(10);
         let interval = self.tick_interval(count);
+        if self.domain.is_empty() {
+            return;
+        }
+        // Only the outer stops move; interior stops of a piecewise domain stay put
+        let (lo, hi) = if self.stop() < self.start() {
+            (self.domain.len() - 1, 0)
+        } else {
+            (0, self.domain.len() - 1)
+        };
 
         match interval {
             TimeTickInterval::Second(_) => {
                 let sec = Second;
-                self.domain[0] = sec.floor(self.domain[0]);
-                self.domain[1] = sec.ceil(self.domain[1]);
+                self.domain[lo] = sec.floor(self.domain[lo]);
+                self.domain[hi] = sec.ceil(self.domain[hi]);
             }
             TimeTickInterval::Minute(_) => {
                 let min = Minute;
-                self.domain[0] = min.floor(self.domain[0]);
-                self.domain[1] = min.ceil(self.domain[1]);
+                self.domain[lo] = min.floor(self.domain[lo]);
+                self.domain[hi] = min.ceil(self.domain[hi]);
             }
             TimeTickInterval::Hour(_) => {
                 let hour = Hour;
-                self.domain[0] = hour.floor(self.domain[0]);
-                self.domain[1] = hour.ceil(self.domain[1]);
+                self.domain[lo] = hour.floor(self.domain[lo]);
+                self.domain[hi] = hour.ceil(self.domain[hi]);
             }
             TimeTickInterval::Day(_) => {
                 let day = Day;
-                self.domain[0] = day.floor(self.domain[0]);
-                self.domain[1] = day.ceil(self.domain[1]);
+                self.domain[lo] = day.floor(self.domain[lo]);
+                self.domain[hi] = day.ceil(self.domain[hi]);
             }
             TimeTickInterval::Week(_) => {
                 let week = Week;
-                self.domain[0] = week.floor(self.domain[0]);
-                self.domain[1] = week.ceil(self.domain[1]);
+                self.domain[lo] = week.floor(self.domain[lo]);
+                self.domain[hi] = week.ceil(self.domain[hi]);
             }
             TimeTickInterval::Month(_) => {
                 let month = Month;
-                self.domain[0] = month.floor(self.domain[0]);
-                self.domain[1] = month.ceil(self.domain[1]);
+                self.domain[lo] = month.floor(self.domain[lo]);
+                self.domain[hi] = month.ceil(self.domain[hi]);
             }
             TimeTickInterval::Year(_) => {
                 let year = Year;
-                self.domain[0] = year.floor(self.domain[0]);
-                self.domain[1] = year.ceil(self.domain[1]);
+                self.domain[lo] = year.floor(self.domain[lo]);
+                self.domain[hi] = year.ceil(self.domain[hi]);
             }
         }
     }
 
     pub fn copy(&self) -> Self {
         Self {
-            domain: self.domain,
-            range: self.range,
+            domain: self.domain.clone(),
+            range: self.range.clone(),
             clamp: self.clamp,
             interpolate: self.interpolate,
-            unknown: self.unknown,
+            unknown: self.unknown.clone(),
         }
     }
-
-    pub fn range_round(mut self, range: [f64; 2]) -> Self {
-        self.range = range;
-        self.interpolate = round_interpolate;
-        self
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -663,16 +717,16 @@ pub enum TimeTickInterval {
 }
 
 // Default linear interpolation
-fn linear_interpolate(a: f64, b: f64, t: f64) -> f64 {
+fn linear_interpolate(a: &f64, b: &f64, t: f64) -> f64 {
     a + t * (b - a)
 }
 
 // Rounding interpolation
-fn round_interpolate(a: f64, b: f64, t: f64) -> f64 {
+fn round_interpolate(a: &f64, b: &f64, t: f64) -> f64 {
     (a + t * (b - a)).round()
 }
 
-impl Default for ScaleTime {
+impl Default for ScaleTime<f64> {
     fn default() -> Self {
         let now = chrono::Utc::now().naive_utc();
         Self::new([now, now + Duration::try_hours(1).unwrap()], [0.0, 1.0])
@@ -0,0 +1,346 @@
+// d3-scale: common traits implemented by every scale
+//
+// Inherent methods stay the primary API; these traits let axis, grid and
+// legend code be written once against any scale.
+
+use super::{
+    ScaleBand, ScaleDiverging, ScaleIdentity, ScaleLinear, ScaleLog, ScaleOrdinal, ScalePoint,
+    ScalePow, ScaleQuantile, ScaleQuantize, ScaleSequential, ScaleSymlog, ScaleThreshold,
+    ScaleTime, ScaleUtc,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::hash::Hash;
+
+/// Boxed formatter returned by [`ContinuousScale::tick_format`]
+pub type TickFormatter<'a, D> = Box<dyn Fn(&D) -> String + 'a>;
+
+/// A mapping from an input domain to an output range
+pub trait Scale {
+    type Domain;
+    type Output;
+
+    /// Map a domain value; `None` where the scale itself would return no value
+    fn scale(&self, value: &Self::Domain) -> Option<Self::Output>;
+    fn domain(&self) -> Vec<Self::Domain>;
+    /// The range stops; band and point scales return their two-element extent
+    fn range(&self) -> Vec<Self::Output>;
+}
+
+/// A scale over an ordered, continuous domain that can generate ticks
+pub trait ContinuousScale: Scale {
+    fn ticks(&self, count: usize) -> Vec<Self::Domain>;
+    /// Formatter for the ticks returned by `ticks(count)`
+    ///
+    /// `specifier` is a d3-format specifier for numeric scales and a
+    /// strftime-style pattern for time scales.
+    fn tick_format(&self, count: usize, specifier: Option<&str>)
+    -> TickFormatter<'_, Self::Domain>;
+}
+
+/// A scale that divides its range into evenly spaced bands (or points)
+pub trait BandLikeScale: Scale<Output = f64> {
+    /// Width of each band; zero for point scales
+    fn bandwidth(&self) -> f64;
+    /// Distance between the starts of adjacent bands
+    fn step(&self) -> f64;
+}
+
+// Numeric tick formatting shared by the pow, symlog, sequential and
+// diverging scales, which format their ticks the way a linear scale over the
+// same extent would
+fn linear_tick_format(
+    domain: &[f64],
+    count: usize,
+    specifier: Option<&str>,
+) -> TickFormatter<'static, f64> {
+    let format = ScaleLinear::new(domain, [0.0, 1.0]).tick_format(count, specifier);
+    Box::new(move |x| format(*x))
+}
+
+macro_rules! numeric_continuous_scale {
+    ($scale:ident) => {
+        numeric_continuous_scale!($scale, |scale, count, specifier| {
+            linear_tick_format(&scale.domain, count, specifier)
+        });
+    };
+    ($scale:ident, |$this:ident, $count:ident, $specifier:ident| $tick_format:expr) => {
+        impl<R: Clone> Scale for $scale<R> {
+            type Domain = f64;
+            type Output = R;
+
+            fn scale(&self, value: &f64) -> Option<R> {
+                (!self.range.is_empty()).then(|| $scale::scale(self, *value))
+            }
+            fn domain(&self) -> Vec<f64> {
+                self.domain.to_vec()
+            }
+            fn range(&self) -> Vec<R> {
+                self.range.to_vec()
+            }
+        }
+
+        impl<R: Clone> ContinuousScale for $scale<R> {
+            fn ticks(&self, count: usize) -> Vec<f64> {
+                $scale::ticks(self, count)
+            }
+            fn tick_format(
+                &self,
+                $count: usize,
+                $specifier: Option<&str>,
+            ) -> TickFormatter<'_, f64> {
+                let $this = self;
+                $tick_format
+            }
+        }
+    };
+}
+
+numeric_continuous_scale!(ScaleLinear);
+// Log ticks are labelled sparsely, see `ScaleLog::tick_format`
+numeric_continuous_scale!(ScaleLog, |scale, count, specifier| {
+    let format = ScaleLog::tick_format(scale, count, specifier);
+    Box::new(move |x| format(*x))
+});
+numeric_continuous_scale!(ScalePow);
+numeric_continuous_scale!(ScaleSymlog);
+
+impl<R: Clone> Scale for ScaleTime<R> {
+    type Domain = NaiveDateTime;
+    type Output = R;
+
+    fn scale(&self, value: &NaiveDateTime) -> Option<R> {
+        (!self.range.is_empty()).then(|| ScaleTime::scale(self, *value))
+    }
+    fn domain(&self) -> Vec<NaiveDateTime> {
+        ScaleTime::domain(self).to_vec()
+    }
+    fn range(&self) -> Vec<R> {
+        ScaleTime::range(self).to_vec()
+    }
+}
+
+impl<R: Clone> ContinuousScale for ScaleTime<R> {
+    fn ticks(&self, count: usize) -> Vec<NaiveDateTime> {
+        ScaleTime::ticks(self, count)
+    }
+    fn tick_format(
+        &self,
+        count: usize,
+        specifier: Option<&str>,
+    ) -> TickFormatter<'_, NaiveDateTime> {
+        Box::new(ScaleTime::tick_format(self, count, specifier))
+    }
+}
+
+impl<R: Clone> Scale for ScaleUtc<R> {
+    type Domain = DateTime<Utc>;
+    type Output = R;
+
+    fn scale(&self, value: &DateTime<Utc>) -> Option<R> {
+        (!ScaleUtc::range(self).is_empty()).then(|| ScaleUtc::scale(self, *value))
+    }
+    fn domain(&self) -> Vec<DateTime<Utc>> {
+        ScaleUtc::domain(self).to_vec()
+    }
+    fn range(&self) -> Vec<R> {
+        ScaleUtc::range(self).to_vec()
+    }
+}
+
+impl<R: Clone> ContinuousScale for ScaleUtc<R> {
+    fn ticks(&self, count: usize) -> Vec<DateTime<Utc>> {
+        ScaleUtc::ticks(self, count)
+    }
+    fn tick_format(
+        &self,
+        count: usize,
+        specifier: Option<&str>,
+    ) -> TickFormatter<'_, DateTime<Utc>> {
+        Box::new(ScaleUtc::tick_format(
+            self,
+            count,
+            specifier.unwrap_or("%Y-%m-%dT%H:%M:%SZ"),
+        ))
+    }
+}
+
+impl Scale for ScaleIdentity {
+    type Domain = f64;
+    type Output = f64;
+
+    fn scale(&self, value: &f64) -> Option<f64> {
+        Some(ScaleIdentity::scale(self, *value))
+    }
+    fn domain(&self) -> Vec<f64> {
+        self.domain.to_vec()
+    }
+    fn range(&self) -> Vec<f64> {
+        self.range.to_vec()
+    }
+}
+
+impl ContinuousScale for ScaleIdentity {
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        ScaleIdentity::ticks(self, count)
+    }
+    fn tick_format(&self, count: usize, specifier: Option<&str>) -> TickFormatter<'_, f64> {
+        linear_tick_format(&self.domain, count, specifier)
+    }
+}
+
+impl<T: Clone> Scale for ScaleSequential<T> {
+    type Domain = f64;
+    type Output = T;
+
+    fn scale(&self, value: &f64) -> Option<T> {
+        ScaleSequential::scale(self, *value)
+    }
+    fn domain(&self) -> Vec<f64> {
+        ScaleSequential::domain(self).to_vec()
+    }
+    fn range(&self) -> Vec<T> {
+        self.get_range().to_vec()
+    }
+}
+
+impl<T: Clone> ContinuousScale for ScaleSequential<T> {
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        ScaleSequential::ticks(self, count)
+    }
+    fn tick_format(&self, count: usize, specifier: Option<&str>) -> TickFormatter<'_, f64> {
+        linear_tick_format(&ScaleSequential::domain(self), count, specifier)
+    }
+}
+
+impl<T: Clone> Scale for ScaleDiverging<T> {
+    type Domain = f64;
+    type Output = T;
+
+    fn scale(&self, value: &f64) -> Option<T> {
+        ScaleDiverging::scale(self, *value)
+    }
+    fn domain(&self) -> Vec<f64> {
+        ScaleDiverging::domain(self).to_vec()
+    }
+    fn range(&self) -> Vec<T> {
+        self.get_range().to_vec()
+    }
+}
+
+impl<T: Clone> ContinuousScale for ScaleDiverging<T> {
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        ScaleDiverging::ticks(self, count)
+    }
+    fn tick_format(&self, count: usize, specifier: Option<&str>) -> TickFormatter<'_, f64> {
+        let [d0, _, d1] = ScaleDiverging::domain(self);
+        linear_tick_format(&[d0, d1], count, specifier)
+    }
+}
+
+impl<T: Clone + PartialEq> Scale for ScaleBand<T> {
+    type Domain = T;
+    type Output = f64;
+
+    fn scale(&self, value: &T) -> Option<f64> {
+        ScaleBand::scale(self, value)
+    }
+    fn domain(&self) -> Vec<T> {
+        self.domain.clone()
+    }
+    fn range(&self) -> Vec<f64> {
+        self.range.to_vec()
+    }
+}
+
+impl<T: Clone + PartialEq> BandLikeScale for ScaleBand<T> {
+    fn bandwidth(&self) -> f64 {
+        ScaleBand::bandwidth(self)
+    }
+    fn step(&self) -> f64 {
+        ScaleBand::step(self)
+    }
+}
+
+impl<T: Clone + PartialEq> Scale for ScalePoint<T> {
+    type Domain = T;
+    type Output = f64;
+
+    fn scale(&self, value: &T) -> Option<f64> {
+        ScalePoint::scale(self, value)
+    }
+    fn domain(&self) -> Vec<T> {
+        self.domain.clone()
+    }
+    fn range(&self) -> Vec<f64> {
+        self.range.to_vec()
+    }
+}
+
+impl<T: Clone + PartialEq> BandLikeScale for ScalePoint<T> {
+    fn bandwidth(&self) -> f64 {
+        0.0
+    }
+    fn step(&self) -> f64 {
+        ScalePoint::step(self)
+    }
+}
+
+impl<T: Clone + Hash + Eq, R: Clone> Scale for ScaleOrdinal<T, R> {
+    type Domain = T;
+    type Output = R;
+
+    fn scale(&self, value: &T) -> Option<R> {
+        ScaleOrdinal::scale(self, value)
+    }
+    fn domain(&self) -> Vec<T> {
+        self.domain.clone()
+    }
+    fn range(&self) -> Vec<R> {
+        self.range.clone()
+    }
+}
+
+impl<R: Clone> Scale for ScaleQuantize<R> {
+    type Domain = f64;
+    type Output = R;
+
+    fn scale(&self, value: &f64) -> Option<R> {
+        ScaleQuantize::scale(self, *value)
+    }
+    fn domain(&self) -> Vec<f64> {
+        ScaleQuantize::domain(self).to_vec()
+    }
+    fn range(&self) -> Vec<R> {
+        ScaleQuantize::range(self).clone()
+    }
+}
+
+impl<R: Clone> Scale for ScaleQuantile<R> {
+    type Domain = f64;
+    type Output = R;
+
+    fn scale(&self, value: &f64) -> Option<R> {
+        ScaleQuantile::scale(self, *value)
+    }
+    fn domain(&self) -> Vec<f64> {
+        ScaleQuantile::domain(self).clone()
+    }
+    fn range(&self) -> Vec<R> {
+        ScaleQuantile::range(self).clone()
+    }
+}
+
+impl<R: Clone> Scale for ScaleThreshold<R> {
+    type Domain = f64;
+    type Output = R;
+
+    fn scale(&self, value: &f64) -> Option<R> {
+        ScaleThreshold::scale(self, *value)
+    }
+    fn domain(&self) -> Vec<f64> {
+        ScaleThreshold::domain(self).clone()
+    }
+    fn range(&self) -> Vec<R> {
+        ScaleThreshold::range(self).clone()
+    }
+}
@@ -0,0 +1,147 @@
+// d3-scale: continuous transforms and piecewise mapping shared by continuous scales
+
+/// Maps domain values into the space where interpolation is linear
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transform {
+    Identity,
+    /// Log of the given base; negative domains use `-log(-x)` as in D3
+    Log {
+        base: f64,
+    },
+    /// `sign(x) * |x|^exponent`
+    Pow {
+        exponent: f64,
+    },
+    /// `sign(x) * ln(1 + |x| / constant)`
+    Symlog {
+        constant: f64,
+    },
+}
+
+impl Transform {
+    /// Transform `x`; `negative` selects the reflected log for domains below zero
+    pub fn apply(&self, x: f64, negative: bool) -> f64 {
+        match *self {
+            Transform::Identity => x,
+            Transform::Log { .. } if negative => -(-x).ln(),
+            Transform::Log { .. } => x.ln(),
+            Transform::Pow { exponent: 1.0 } => x,
+            Transform::Pow { exponent: 0.5 } => x.signum() * x.abs().sqrt(),
+            Transform::Pow { exponent } => x.signum() * x.abs().powf(exponent),
+            Transform::Symlog { constant } => x.signum() * (x.abs() / constant).ln_1p(),
+        }
+    }
+
+    /// Inverse of [`Transform::apply`]
+    pub fn invert(&self, y: f64, negative: bool) -> f64 {
+        match *self {
+            Transform::Identity => y,
+            Transform::Log { .. } if negative => -(-y).exp(),
+            Transform::Log { .. } => y.exp(),
+            Transform::Pow { exponent } => y.signum() * y.abs().powf(1.0 / exponent),
+            Transform::Symlog { constant } => y.signum() * y.abs().exp_m1() * constant,
+        }
+    }
+
+    /// Whether a log transform must be reflected for this domain
+    pub fn reflects(&self, domain: &[f64]) -> bool {
+        matches!(self, Transform::Log { .. }) && domain.first().is_some_and(|&d| d < 0.0)
+    }
+}
+
+/// First and last stop of a domain, in ascending order
+pub(crate) fn extent(stops: &[f64]) -> (f64, f64) {
+    let (a, b) = match (stops.first(), stops.last()) {
+        (Some(&a), Some(&b)) => (a, b),
+        _ => return (f64::NAN, f64::NAN),
+    };
+    if b < a { (b, a) } else { (a, b) }
+}
+
+/// Position of `x` between `a` and `b`; a zero-width segment maps to its middle
+pub(crate) fn normalize(a: f64, b: f64, x: f64) -> f64 {
+    let span = b - a;
+    if span != 0.0 {
+        (x - a) / span
+    } else if span.is_nan() {
+        f64::NAN
+    } else {
+        0.5
+    }
+}
+
+/// Map `x` from `domain` onto `range` one segment at a time (D3 `polymap`)
+///
+/// Segment `i` maps `domain[i]..domain[i + 1]` onto `range[i]..range[i + 1]`;
+/// stops beyond the shorter of the two are ignored. The domain may be
+/// ascending or descending. Values outside it extrapolate the first or last
+/// segment. With fewer than two stops every value maps to the first range
+/// stop, and to `None` if the range is empty.
+pub(crate) fn piecewise<T: Clone>(
+    domain: &[f64],
+    range: &[T],
+    x: f64,
+    interpolate: impl Fn(&T, &T, f64) -> T,
+) -> Option<T> {
+    let n = domain.len().min(range.len());
+    if n < 2 {
+        return range.first().cloned();
+    }
+    // Interior stops at or below `x` decide the segment
+    let below = domain[1..n - 1].iter().filter(|&&d| d <= x).count();
+    let i = if domain[n - 1] < domain[0] {
+        n - 2 - below
+    } else {
+        below
+    };
+    let t = normalize(domain[i], domain[i + 1], x);
+    Some(interpolate(&range[i], &range[i + 1], t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let transforms = [
+            Transform::Identity,
+            Transform::Log { base: 10.0 },
+            Transform::Pow { exponent: 2.0 },
+            Transform::Pow { exponent: 0.5 },
+            Transform::Symlog { constant: 1.0 },
+        ];
+        for t in transforms {
+            for x in [0.5, 3.0, 250.0] {
+                assert!((t.invert(t.apply(x, false), false) - x).abs() < 1e-9);
+            }
+        }
+        let log = Transform::Log { base: 10.0 };
+        assert!((log.invert(log.apply(-5.0, true), true) + 5.0).abs() < 1e-9);
+        let pow = Transform::Pow { exponent: 2.0 };
+        assert_eq!(pow.apply(-3.0, false), -9.0);
+    }
+
+    #[test]
+    fn test_piecewise_segments() {
+        let lerp = |a: &f64, b: &f64, t: f64| a + t * (b - a);
+        let domain = [-1.0, 0.0, 1.0];
+        let range = [0.0, 50.0, 200.0];
+        assert_eq!(piecewise(&domain, &range, -0.5, lerp), Some(25.0));
+        assert_eq!(piecewise(&domain, &range, 0.5, lerp), Some(125.0));
+        assert_eq!(piecewise(&domain, &range, 2.0, lerp), Some(350.0));
+        let reversed = [1.0, 0.0, -1.0];
+        assert_eq!(piecewise(&reversed, &range, 0.5, lerp), Some(25.0));
+        assert_eq!(piecewise(&reversed, &range, -0.5, lerp), Some(125.0));
+        // Extra range stops are ignored
+        assert_eq!(
+            piecewise(&[0.0, 1.0], &[0.0, 10.0, 99.0], 0.5, lerp),
+            Some(5.0)
+        );
+        assert_eq!(piecewise(&[3.0, 3.0], &[0.0, 10.0], 3.0, lerp), Some(5.0));
+        // Too few stops map to the first range stop, if any
+        assert_eq!(piecewise(&[0.0], &[7.0, 9.0], 0.5, lerp), Some(7.0));
+        assert_eq!(piecewise(&[0.0, 1.0], &[], 0.5, lerp), None);
+        assert_eq!(piecewise(&[], &[], 0.5, lerp), None);
+    }
+}
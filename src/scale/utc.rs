@@ -1,29 +1,55 @@
 // d3-scale: scaleUtc (UTC time scale)
 
+use super::transform::{extent, piecewise};
+use crate::interpolate::Interpolate;
 use chrono::{DateTime, Duration, TimeZone, Utc};
 
+/// UTC time scale; domain and range may have more than two stops
+///
+/// The range can be any [`Interpolate`] type; `invert` needs a numeric range.
 #[derive(Debug, Clone)]
-pub struct ScaleUtc {
-    domain: [DateTime<Utc>; 2],
-    range: [f64; 2],
+pub struct ScaleUtc<R = f64> {
+    domain: Vec<DateTime<Utc>>,
+    range: Vec<R>,
     clamp: bool,
     nice: bool,
+    interpolate: fn(&R, &R, f64) -> R,
 }
 
-impl ScaleUtc {
-    pub fn new(domain: [DateTime<Utc>; 2], range: [f64; 2]) -> Self {
+impl<R: Interpolate> ScaleUtc<R> {
+    pub fn new(domain: impl Into<Vec<DateTime<Utc>>>, range: impl Into<Vec<R>>) -> Self {
         Self {
-            domain,
-            range,
+            domain: domain.into(),
+            range: range.into(),
             clamp: false,
             nice: false,
+            interpolate: R::interpolate,
         }
     }
-    pub fn domain(&self) -> [DateTime<Utc>; 2] {
-        self.domain
+}
+
+impl ScaleUtc<f64> {
+    pub fn invert(&self, r: f64) -> DateTime<Utc> {
+        let domain = self.domain_secs();
+        let mut v =
+            piecewise(&self.range, &domain, r, |a, b, t| a + (b - a) * t).unwrap_or(f64::NAN);
+        if self.clamp {
+            let (lo, hi) = extent(&domain);
+            v = v.max(lo).min(hi);
+        }
+        // Use unwrap_or_else to avoid panic if timestamp is out of range
+        Utc.timestamp_opt(v as i64, 0)
+            .single()
+            .unwrap_or_else(|| self.domain[0])
+    }
+}
+
+impl<R: Clone> ScaleUtc<R> {
+    pub fn domain(&self) -> &[DateTime<Utc>] {
+        &self.domain
     }
-    pub fn range(&self) -> [f64; 2] {
-        self.range
+    pub fn range(&self) -> &[R] {
+        &self.range
     }
     pub fn clamp(mut self, clamp: bool) -> Self {
         self.clamp = clamp;
@@ -33,42 +59,32 @@ impl ScaleUtc {
         self.nice = true;
         self
     }
-    pub fn scale(&self, t: DateTime<Utc>) -> f64 {
-        let (d0, d1) = (
-            self.domain[0].timestamp() as f64,
-            self.domain[1].timestamp() as f64,
-        );
-        let (r0, r1) = (self.range[0], self.range[1]);
-        let mut v = t.timestamp() as f64;
-        if self.clamp {
-            v = v.max(d0).min(d1);
-        }
-        if (d1 - d0).abs() < std::f64::EPSILON {
-            return r0;
-        }
-        r0 + (r1 - r0) * (v - d0) / (d1 - d0)
+    pub fn interpolate(mut self, interpolate: fn(&R, &R, f64) -> R) -> Self {
+        self.interpolate = interpolate;
+        self
     }
-    pub fn invert(&self, r: f64) -> DateTime<Utc> {
-        let (d0, d1) = (
-            self.domain[0].timestamp() as f64,
-            self.domain[1].timestamp() as f64,
-        );
-        let (r0, r1) = (self.range[0], self.range[1]);
-        let mut v = if (r1 - r0).abs() < std::f64::EPSILON {
-            d0
-        } else {
-            d0 + (d1 - d0) * (r - r0) / (r1 - r0)
-        };
+    fn domain_secs(&self) -> Vec<f64> {
+        self.domain.iter().map(|d| d.timestamp() as f64).collect()
+    }
+    /// Map `t` onto the range
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty;
+    /// [`Scale::scale`](crate::scale::Scale::scale) returns `None` instead.
+    pub fn scale(&self, t: DateTime<Utc>) -> R {
+        let domain = self.domain_secs();
+        let mut v = t.timestamp() as f64;
         if self.clamp {
-            v = v.max(d0).min(d1);
+            let (lo, hi) = extent(&domain);
+            v = v.max(lo).min(hi);
         }
-        // Use unwrap_or_else to avoid panic if timestamp is out of range
-        Utc.timestamp_opt(v as i64, 0)
-            .single()
-            .unwrap_or_else(|| self.domain[0])
+        piecewise(&domain, &self.range, v, self.interpolate).expect("scale range is empty")
     }
     pub fn ticks(&self, count: usize) -> Vec<DateTime<Utc>> {
-        let (d0, d1) = (self.domain[0], self.domain[1]);
+        let (Some(&d0), Some(&d1)) = (self.domain.first(), self.domain.last()) else {
+            return Vec::new();
+        };
         let mut ticks = Vec::new();
         let total_secs = d1.timestamp() - d0.timestamp();
         if count == 0 || total_secs <= 0 {
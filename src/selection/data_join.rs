@@ -1,29 +1,46 @@
 use crate::selection::selection::Selection;
 
 /// D3-like DataJoin: holds update, enter, and exit selections
+///
+/// `update` and `enter` are in data order, and together cover every datum;
+/// `exit` holds the nodes left without data, in their original order.
 pub struct DataJoin {
     pub update: Selection,
     pub enter: Selection,
     pub exit: Selection,
+    /// Data index of each node in `enter`
+    pub(crate) enter_index: Vec<usize>,
+    /// Data index of each node in `update`
+    pub(crate) update_index: Vec<usize>,
 }
 
 impl DataJoin {
-    /// Create a new DataJoin from a parent selection and new data
-    pub fn new(parent: &Selection, _data: &[impl Clone]) -> Self {
-        // Pseudocode: actual logic depends on Selection internals
-        // 1. Find existing nodes and match to data
-        // 2. update: nodes with data
-        // 3. enter: data without nodes
-        // 4. exit: nodes without data
-        let update = parent.clone(); // placeholder
-        let enter = parent.clone(); // placeholder
-        let exit = parent.clone(); // placeholder
-        // TODO: implement matching logic
-        DataJoin {
-            update,
-            enter,
-            exit,
-        }
+    /// Join `data` to the existing children of `parent`, matching by key
+    ///
+    /// Equivalent to `parent.children().data_with_key(data, key_fn)`, with
+    /// entering nodes appended to the first parent node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::{DataJoin, Selection};
+    /// let mut list = Selection::create("ul");
+    /// let mut join = DataJoin::new(&list, &["a", "b", "c"], |d, _| d.to_string());
+    /// join.join("li");
+    ///
+    /// let join = DataJoin::new(&list, &["c", "d"], |d, _| d.to_string());
+    /// assert_eq!(join.update().len(), 1);
+    /// assert_eq!(join.enter().len(), 1);
+    /// assert_eq!(join.exit().len(), 2);
+    /// # let _ = &mut list;
+    /// ```
+    pub fn new<T, K, F>(parent: &Selection, data: &[T], key_fn: F) -> Self
+    where
+        T: ToString,
+        K: ToString,
+        F: Fn(&T, usize) -> K,
+    {
+        parent.clone_shallow().children().data_with_key(data, key_fn)
     }
 
     /// Get the enter selection
@@ -38,4 +55,29 @@ impl DataJoin {
     pub fn exit(&self) -> &Selection {
         &self.exit
     }
+
+    /// Merge the appended enter nodes with the update nodes, in data order
+    ///
+    /// `entered` is the selection returned by appending to `enter`, so its
+    /// nodes line up one-to-one with the enter placeholders.
+    pub fn merge(&self, entered: &Selection) -> Selection {
+        let mut slots: Vec<_> = vec![None; self.enter_index.len() + self.update_index.len()];
+        for (&i, &key) in self.update_index.iter().zip(self.update.iter()) {
+            slots[i] = Some(key);
+        }
+        for (&i, &key) in self.enter_index.iter().zip(entered.iter()) {
+            slots[i] = Some(key);
+        }
+        Selection::new(self.update.arena(), slots.into_iter().flatten().collect())
+    }
+
+    /// Append `tag` for entering data, remove exiting nodes, and return the
+    /// merged selection with document order matching data order (D3 selection.join)
+    pub fn join(&mut self, tag: &str) -> Selection {
+        let entered = self.enter.append(tag);
+        self.exit.remove();
+        let mut merged = self.merge(&entered);
+        merged.order();
+        merged
+    }
 }
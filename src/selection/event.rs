@@ -0,0 +1,193 @@
+//! Event listeners on selection nodes and dispatch through the node tree
+//!
+//! Events travel like DOM events: a capture phase from the root down to the
+//! target's parent, the target itself, then a bubble phase back up to the
+//! root along [`Node::parent`]. Listeners are registered by typename,
+//! `type` or `type.name`; the name distinguishes several listeners for the
+//! same type and lets them be removed together (`.name`).
+
+use crate::dispatch::Event;
+use crate::selection::arena::{Arena, NodeKey};
+use crate::selection::node::Node;
+use crate::selection::selection::Selection;
+use std::cell::RefCell;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// A listener callback, given a selection of the node it is registered on
+/// (like `d3.select(this)`) and the event
+pub type EventHandler = Box<dyn FnMut(&mut Selection, &NodeEvent)>;
+
+/// A listener registered on a node
+pub struct EventListener {
+    pub handler: Rc<RefCell<EventHandler>>,
+    /// Whether the listener runs in the capture phase rather than the bubble
+    /// phase; at the target both kinds run, capture listeners first
+    pub capture: bool,
+    // Registration order on the node; listeners run in this order
+    pub(crate) order: usize,
+}
+
+/// Where an event is in its journey through the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+    Capturing,
+    AtTarget,
+    Bubbling,
+}
+
+/// The event as seen by a node's listener
+///
+/// Dereferences to the dispatched [`Event`], so listeners can call
+/// `stop_propagation` and `prevent_default` directly.
+pub struct NodeEvent<'a> {
+    pub event: &'a Event<'a>,
+    /// The node the event was dispatched to
+    pub target: NodeKey,
+    /// The node whose listener is running
+    pub current_target: NodeKey,
+    pub phase: EventPhase,
+}
+
+impl<'a> NodeEvent<'a> {
+    /// The event's detail payload as a `T`, if one of that type was given
+    pub fn detail<T: 'static>(&self) -> Option<&T> {
+        self.event
+            .data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<T>())
+    }
+}
+
+impl<'a> Deref for NodeEvent<'a> {
+    type Target = Event<'a>;
+
+    fn deref(&self) -> &Event<'a> {
+        self.event
+    }
+}
+
+/// Split `type.name` at the first period
+pub(crate) fn parse_typename(typename: &str) -> (&str, &str) {
+    typename.split_once('.').unwrap_or((typename, ""))
+}
+
+/// Register `handler` for `typename` on `node`, replacing a listener with the
+/// same typename in place
+pub(crate) fn add_listener(node: &mut Node, typename: &str, handler: EventHandler, capture: bool) {
+    let order = match node.event_handlers.get(typename) {
+        Some(existing) => existing.order,
+        None => node
+            .event_handlers
+            .values()
+            .map(|l| l.order + 1)
+            .max()
+            .unwrap_or(0),
+    };
+    node.event_handlers.insert(
+        typename.to_string(),
+        EventListener {
+            handler: Rc::new(RefCell::new(handler)),
+            capture,
+            order,
+        },
+    );
+}
+
+/// Remove the listeners matching `typename`: `type.name` and `type` remove
+/// that exact listener, `.name` every listener with that name
+pub(crate) fn remove_listeners(node: &mut Node, typename: &str) {
+    let (kind, name) = parse_typename(typename);
+    if kind.is_empty() {
+        node.event_handlers
+            .retain(|registered, _| parse_typename(registered).1 != name);
+    } else {
+        node.event_handlers.remove(typename);
+    }
+}
+
+/// Dispatch `event` to `target` through the capture, target and bubble
+/// phases; returns whether the default action should proceed
+///
+/// The arena is not borrowed while listeners run, so they may use any
+/// selection on the document, including dispatching further events.
+pub(crate) fn dispatch_event(arena: &Rc<RefCell<Arena>>, target: NodeKey, event: &Event) -> bool {
+    let kind = parse_typename(&event.event_type).0.to_string();
+    let path = {
+        let arena = arena.borrow();
+        let mut path = vec![target];
+        let mut current = arena.nodes.get(target).and_then(|n| n.parent);
+        while let Some(key) = current {
+            path.push(key);
+            current = arena.nodes.get(key).and_then(|n| n.parent);
+        }
+        path
+    };
+
+    let ancestors = &path[1..];
+    let phases = ancestors
+        .iter()
+        .rev()
+        .map(|&key| (key, EventPhase::Capturing))
+        .chain(std::iter::once((target, EventPhase::AtTarget)))
+        .chain(ancestors.iter().map(|&key| (key, EventPhase::Bubbling)));
+    for (key, phase) in phases {
+        if event.is_propagation_stopped() {
+            break;
+        }
+        let node_event = NodeEvent {
+            event,
+            target,
+            current_target: key,
+            phase,
+        };
+        invoke(arena, key, &kind, &node_event);
+    }
+    !event.is_default_prevented()
+}
+
+// Run the node's listeners for `kind` that apply to the event's phase. The
+// listeners are snapshotted first; one removed or replaced by an earlier
+// listener is skipped, and one already running (a nested dispatch reaching
+// it again) is not re-entered.
+fn invoke(arena: &Rc<RefCell<Arena>>, key: NodeKey, kind: &str, event: &NodeEvent) {
+    let listeners = {
+        let arena = arena.borrow();
+        let Some(node) = arena.nodes.get(key) else {
+            return;
+        };
+        let mut listeners: Vec<(&String, &EventListener)> = node
+            .event_handlers
+            .iter()
+            .filter(|(typename, listener)| {
+                let applies = match event.phase {
+                    EventPhase::Capturing => listener.capture,
+                    EventPhase::AtTarget => true,
+                    EventPhase::Bubbling => !listener.capture,
+                };
+                applies && parse_typename(typename).0 == kind
+            })
+            .collect();
+        listeners
+            .sort_by_key(|(_, l)| (event.phase == EventPhase::AtTarget && !l.capture, l.order));
+        listeners
+            .into_iter()
+            .map(|(typename, l)| (typename.clone(), Rc::clone(&l.handler)))
+            .collect::<Vec<_>>()
+    };
+    for (typename, handler) in listeners {
+        let registered = arena
+            .borrow()
+            .nodes
+            .get(key)
+            .and_then(|node| node.event_handlers.get(&typename))
+            .is_some_and(|l| Rc::ptr_eq(&l.handler, &handler));
+        if !registered {
+            continue;
+        }
+        if let Ok(mut handler) = handler.try_borrow_mut() {
+            let mut selection = Selection::new(Rc::clone(arena), vec![key]);
+            handler(&mut selection, event);
+        }
+    }
+}
@@ -4,12 +4,23 @@
 
 pub mod arena;
 pub mod data_join;
+pub mod event;
 pub mod node;
+pub mod parse;
+pub mod raster;
 pub mod selection;
+pub mod serialize;
 pub mod utils;
 
 pub use arena::{Arena, NodeKey};
 pub use data_join::DataJoin;
-pub use node::Node;
+pub use event::{EventHandler, EventListener, EventPhase, NodeEvent};
+pub use node::{Datum, Node};
+pub use parse::{ParseError, ParseMode};
+pub use raster::{RasterError, RasterOptions};
 pub use selection::Selection;
-pub use utils::{Selector, parse_selector};
+pub use serialize::Serializer;
+pub use utils::{
+    AttrOp, AttrSelector, Combinator, ComplexSelector, PseudoClass, Selector, SelectorError,
+    SelectorList, matches_selector, parse_selector, parse_selector_list,
+};
@@ -1,15 +1,30 @@
 use crate::selection::NodeKey;
+use crate::selection::event::EventListener;
+use std::any::Any;
 use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Typed datum bound to a node; cheap to clone and shared with appended children
+pub type Datum = Rc<dyn Any>;
 
 pub struct Node {
     pub tag: String,
     pub attributes: HashMap<String, String>,
     pub properties: HashMap<String, String>,
+    /// String form of the bound datum (`None` for data bound without `ToString`)
     pub data: Option<String>,
+    /// The datum bound by the `*_typed` methods, see [`Node::datum`]
+    pub datum: Option<Datum>,
+    /// Key this node was bound with by a keyed data join
+    pub join_key: Option<String>,
+    /// For enter placeholders: the sibling that new elements are inserted before (D3 `_next`)
+    pub enter_before: Option<NodeKey>,
     pub children: Vec<NodeKey>,
     pub parent: Option<NodeKey>,
     pub text: Option<String>,
-    pub event_handlers: HashMap<String, Vec<Box<dyn FnMut(&mut Node)>>>,
+    /// Listeners by typename (`type` or `type.name`), see
+    /// [`Selection::on`](crate::selection::Selection::on)
+    pub event_handlers: HashMap<String, EventListener>,
 }
 
 impl Node {
@@ -19,12 +34,32 @@ impl Node {
             attributes: HashMap::new(),
             properties: HashMap::new(),
             data: None,
+            datum: None,
+            join_key: None,
+            enter_before: None,
             children: vec![],
             parent: None,
             text: None,
             event_handlers: HashMap::new(),
         }
     }
+
+    /// The bound datum as a `T`, if one of that type is bound
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// struct Sale { region: &'static str, amount: f64 }
+    /// let mut svg = Selection::create("svg");
+    /// svg.datum_typed(Sale { region: "north", amount: 12.5 });
+    /// let node = svg.node().unwrap();
+    /// assert_eq!(node.datum::<Sale>().unwrap().amount, 12.5);
+    /// assert!(node.datum::<String>().is_none());
+    /// ```
+    pub fn datum<T: 'static>(&self) -> Option<&T> {
+        self.datum.as_ref().and_then(|d| d.downcast_ref::<T>())
+    }
 }
 
 impl Clone for Node {
@@ -34,6 +69,9 @@ impl Clone for Node {
             attributes: self.attributes.clone(),
             properties: self.properties.clone(),
             data: self.data.clone(),
+            datum: self.datum.clone(),
+            join_key: self.join_key.clone(),
+            enter_before: self.enter_before,
             children: self.children.clone(),
             parent: self.parent.clone(),
             text: self.text.clone(),
@@ -0,0 +1,376 @@
+//! SVG/HTML parsing into selection arenas
+//!
+//! Two modes are supported: [`ParseMode::Xml`] for SVG documents, which
+//! rejects mismatched tags, and [`ParseMode::Html`], which accepts void
+//! elements (`<br>`), unquoted and boolean attributes, and closes unclosed
+//! elements the way browsers do.
+//!
+//! Nodes hold a single `text` field, so text that is interleaved with child
+//! elements (`<p>a<b>b</b>c</p>`) is concatenated into the parent's text.
+//! Whitespace-only text between elements is dropped.
+
+use crate::selection::arena::{Arena, NodeKey};
+use crate::selection::node::Node;
+use std::fmt;
+
+/// How strictly markup is parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    Xml,
+    Html,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// Input ended inside a tag, comment or CDATA section
+    UnexpectedEof { pos: usize },
+    /// A `<` that does not start a valid tag
+    InvalidTag { pos: usize },
+    /// A closing tag that does not match the open element
+    UnmatchedClose { tag: String, pos: usize },
+    /// An element that was never closed
+    UnclosedElement { tag: String },
+    /// The input contains no elements
+    NoRootElement,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEof { pos } => write!(f, "unexpected end of input at {}", pos),
+            ParseError::InvalidTag { pos } => write!(f, "invalid tag at {}", pos),
+            ParseError::UnmatchedClose { tag, pos } => {
+                write!(
+                    f,
+                    "closing tag </{}> at {} does not match an open element",
+                    tag, pos
+                )
+            }
+            ParseError::UnclosedElement { tag } => write!(f, "element <{}> is never closed", tag),
+            ParseError::NoRootElement => write!(f, "no element found"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Parse `markup` into `arena`, appending top-level elements under `parent`
+///
+/// Returns the keys of the top-level elements. Top-level text is added to the
+/// parent's text when a parent is given and ignored otherwise.
+pub fn parse_into(
+    arena: &mut Arena,
+    parent: Option<NodeKey>,
+    markup: &str,
+    mode: ParseMode,
+) -> Result<Vec<NodeKey>, ParseError> {
+    Parser {
+        src: markup,
+        pos: 0,
+        mode,
+    }
+    .run(arena, parent)
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    mode: ParseMode,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_past(&mut self, pattern: &str) -> Result<&'a str, ParseError> {
+        match self.rest().find(pattern) {
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.pos += i + pattern.len();
+                Ok(skipped)
+            }
+            None if self.mode == ParseMode::Html => {
+                let skipped = self.rest();
+                self.pos = self.src.len();
+                Ok(skipped)
+            }
+            None => Err(ParseError::UnexpectedEof {
+                pos: self.src.len(),
+            }),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.src.len() - trimmed.len();
+    }
+
+    fn read_name(&mut self) -> &'a str {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '='))
+            .unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn same_tag(&self, a: &str, b: &str) -> bool {
+        match self.mode {
+            ParseMode::Xml => a == b,
+            ParseMode::Html => a.eq_ignore_ascii_case(b),
+        }
+    }
+
+    fn run(
+        mut self,
+        arena: &mut Arena,
+        parent: Option<NodeKey>,
+    ) -> Result<Vec<NodeKey>, ParseError> {
+        let mut top = Vec::new();
+        let mut open: Vec<NodeKey> = Vec::new();
+
+        while self.pos < self.src.len() {
+            let rest = self.rest();
+            let current = open.last().copied().or(parent);
+            if rest.starts_with("<!--") {
+                self.pos += 4;
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                let text = self.skip_past("]]>")?;
+                push_text(arena, current, text);
+            } else if rest.starts_with("<?") {
+                self.pos += 2;
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!") {
+                self.pos += 2;
+                self.skip_past(">")?;
+            } else if rest.starts_with("</") {
+                let start = self.pos;
+                self.pos += 2;
+                let name = self.read_name();
+                self.skip_past(">")?;
+                match open
+                    .iter()
+                    .rposition(|&k| self.same_tag(&arena.nodes[k].tag, name))
+                {
+                    Some(i) if i + 1 == open.len() || self.mode == ParseMode::Html => {
+                        open.truncate(i);
+                    }
+                    // Stray closing tags are ignored in HTML
+                    None if self.mode == ParseMode::Html => {}
+                    _ => {
+                        return Err(ParseError::UnmatchedClose {
+                            tag: name.to_string(),
+                            pos: start,
+                        });
+                    }
+                }
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_alphabetic() || c == '_')
+            {
+                let (key, self_closing) = self.start_tag(arena, current)?;
+                if let Some(p) = current {
+                    arena.nodes[p].children.push(key);
+                }
+                if current == parent {
+                    top.push(key);
+                }
+                let tag = arena.nodes[key].tag.clone();
+                let is_void = self.mode == ParseMode::Html
+                    && VOID_ELEMENTS.iter().any(|v| v.eq_ignore_ascii_case(&tag));
+                if self_closing || is_void {
+                    continue;
+                }
+                if RAW_TEXT_ELEMENTS
+                    .iter()
+                    .any(|r| r.eq_ignore_ascii_case(&tag))
+                {
+                    let text = self.raw_text(&tag)?;
+                    if !text.is_empty() {
+                        arena.nodes[key].text = Some(text.to_string());
+                    }
+                } else {
+                    open.push(key);
+                }
+            } else if rest.starts_with('<') && self.mode == ParseMode::Xml {
+                return Err(ParseError::InvalidTag { pos: self.pos });
+            } else {
+                // Text runs to the next tag; a lone '<' in HTML is literal text
+                let end = rest
+                    .char_indices()
+                    .skip(1)
+                    .find(|&(_, c)| c == '<')
+                    .map_or(rest.len(), |(i, _)| i);
+                self.pos += end;
+                push_text(arena, current, &decode_entities(&rest[..end]));
+            }
+        }
+
+        if let Some(&unclosed) = open.first()
+            && self.mode == ParseMode::Xml
+        {
+            return Err(ParseError::UnclosedElement {
+                tag: arena.nodes[unclosed].tag.clone(),
+            });
+        }
+        Ok(top)
+    }
+
+    /// Parse `<name attr="v" ...>` and insert the element; returns its key and
+    /// whether it was self-closing
+    fn start_tag(
+        &mut self,
+        arena: &mut Arena,
+        parent: Option<NodeKey>,
+    ) -> Result<(NodeKey, bool), ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut node = Node::new(self.read_name());
+        node.parent = parent;
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(ParseError::UnexpectedEof { pos: self.pos });
+            }
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok((arena.nodes.insert(node), true));
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok((arena.nodes.insert(node), false));
+            }
+            if rest.starts_with('/') {
+                self.pos += 1;
+                continue;
+            }
+            let name = self.read_name();
+            if name.is_empty() {
+                return Err(ParseError::InvalidTag { pos: start });
+            }
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.attr_value(start)?
+            } else if self.mode == ParseMode::Html {
+                String::new()
+            } else {
+                return Err(ParseError::InvalidTag { pos: start });
+            };
+            node.attributes.entry(name.to_string()).or_insert(value);
+        }
+    }
+
+    fn attr_value(&mut self, tag_start: usize) -> Result<String, ParseError> {
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some(q @ ('"' | '\'')) => {
+                let end = rest[1..].find(q).ok_or(ParseError::UnexpectedEof {
+                    pos: self.src.len(),
+                })?;
+                self.pos += end + 2;
+                Ok(decode_entities(&rest[1..end + 1]))
+            }
+            Some(_) if self.mode == ParseMode::Html => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                Ok(decode_entities(&rest[..end]))
+            }
+            _ => Err(ParseError::InvalidTag { pos: tag_start }),
+        }
+    }
+
+    /// Content of `<script>`/`<style>` up to the matching close tag, unparsed
+    fn raw_text(&mut self, tag: &str) -> Result<&'a str, ParseError> {
+        let rest = self.rest();
+        let lower = rest.to_ascii_lowercase();
+        let close = format!("</{}", tag.to_ascii_lowercase());
+        match lower.find(&close) {
+            Some(i) => {
+                self.pos += i;
+                self.skip_past(">")?;
+                let text = &rest[..i];
+                // CDATA wrappers written by the serializer are unwrapped
+                Ok(text
+                    .strip_prefix("<![CDATA[")
+                    .and_then(|t| t.strip_suffix("]]>"))
+                    .unwrap_or(text))
+            }
+            None if self.mode == ParseMode::Html => {
+                self.pos = self.src.len();
+                Ok(rest)
+            }
+            None => Err(ParseError::UnclosedElement {
+                tag: tag.to_string(),
+            }),
+        }
+    }
+}
+
+fn push_text(arena: &mut Arena, target: Option<NodeKey>, text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    if let Some(key) = target {
+        arena.nodes[key]
+            .text
+            .get_or_insert_with(String::new)
+            .push_str(text);
+    }
+}
+
+/// Decode character references (`&amp;`, `&#60;`, `&#x3C;`); unknown ones are kept
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse::<u32>()))
+                    .and_then(|n| n.ok())
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,224 @@
+//! Rasterization of rendered selections to pixmaps and PNG via resvg
+//!
+//! The first node of the selection is serialized with namespace declarations,
+//! parsed by `usvg` and drawn with `resvg`. Text needs fonts: start from
+//! [`RasterOptions::system_fonts`] or add files and data explicitly.
+
+use crate::color::Color;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tiny_skia::Pixmap;
+use usvg::fontdb;
+
+#[derive(Debug)]
+pub enum RasterError {
+    /// The selection has no node to render
+    EmptySelection,
+    /// The requested output has no pixels
+    InvalidSize { width: u32, height: u32, scale: f32 },
+    /// The background is not a colour `crate::color` can parse
+    InvalidColor(String),
+    /// `usvg` rejected the serialized markup
+    Svg(usvg::Error),
+    /// A font file could not be read
+    Font(std::io::Error),
+    /// PNG encoding failed
+    Encode(String),
+}
+
+impl fmt::Display for RasterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RasterError::EmptySelection => write!(f, "selection is empty"),
+            RasterError::InvalidSize {
+                width,
+                height,
+                scale,
+            } => write!(
+                f,
+                "invalid output size {}x{} at scale {}",
+                width, height, scale
+            ),
+            RasterError::InvalidColor(c) => write!(f, "invalid background colour '{}'", c),
+            RasterError::Svg(e) => write!(f, "invalid SVG: {}", e),
+            RasterError::Font(e) => write!(f, "failed to load font: {}", e),
+            RasterError::Encode(e) => write!(f, "failed to encode PNG: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RasterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RasterError::Svg(e) => Some(e),
+            RasterError::Font(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<usvg::Error> for RasterError {
+    fn from(e: usvg::Error) -> Self {
+        RasterError::Svg(e)
+    }
+}
+
+/// Fonts and background used when rasterizing
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_d3::selection::{RasterOptions, Selection};
+///
+/// let mut svg = Selection::create("svg");
+/// svg.attr("width", "40").attr("height", "20");
+/// svg.append("rect").attr("width", "20").attr("height", "20").attr("fill", "#ff0000");
+///
+/// let options = RasterOptions::new().background("#ffffff");
+/// let pixmap = svg.render_pixmap_with(40, 20, 2.0, &options).unwrap();
+/// assert_eq!((pixmap.width(), pixmap.height()), (80, 40));
+/// let right = pixmap.pixel(60, 20).unwrap();
+/// assert_eq!((right.red(), right.green(), right.blue()), (255, 255, 255));
+/// ```
+#[derive(Clone)]
+pub struct RasterOptions {
+    fontdb: Arc<fontdb::Database>,
+    font_family: Option<String>,
+    background: Option<String>,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        RasterOptions::new()
+    }
+}
+
+impl RasterOptions {
+    /// Empty font database and a transparent background
+    pub fn new() -> Self {
+        RasterOptions {
+            fontdb: Arc::new(fontdb::Database::new()),
+            font_family: None,
+            background: None,
+        }
+    }
+    /// Load the fonts installed on this machine
+    pub fn system_fonts(mut self) -> Self {
+        Arc::make_mut(&mut self.fontdb).load_system_fonts();
+        self
+    }
+    /// Use a shared, pre-populated font database
+    pub fn fontdb(mut self, db: Arc<fontdb::Database>) -> Self {
+        self.fontdb = db;
+        self
+    }
+    /// Add a font file (TTF, OTF or a collection)
+    pub fn font_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self, RasterError> {
+        Arc::make_mut(&mut self.fontdb)
+            .load_font_file(path)
+            .map_err(RasterError::Font)?;
+        Ok(self)
+    }
+    /// Add every font found in a directory, recursively
+    pub fn font_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        Arc::make_mut(&mut self.fontdb).load_fonts_dir(dir);
+        self
+    }
+    /// Add a font from memory
+    pub fn font_data(mut self, data: Vec<u8>) -> Self {
+        Arc::make_mut(&mut self.fontdb).load_font_data(data);
+        self
+    }
+    /// Family used when `font-family` is not set or not available
+    pub fn font_family(mut self, family: &str) -> Self {
+        self.font_family = Some(family.to_string());
+        self
+    }
+    /// Fill the pixmap before drawing, e.g. `"#ffffff"` or `"rgba(0,0,0,0.5)"`
+    pub fn background(mut self, color: &str) -> Self {
+        self.background = Some(color.to_string());
+        self
+    }
+
+    fn background_color(&self) -> Result<Option<tiny_skia::Color>, RasterError> {
+        let Some(ref value) = self.background else {
+            return Ok(None);
+        };
+        let rgb = value
+            .parse::<Color>()
+            .map_err(|_| RasterError::InvalidColor(value.clone()))?
+            .rgb();
+        Ok(Some(tiny_skia::Color::from_rgba8(
+            rgb.r,
+            rgb.g,
+            rgb.b,
+            (rgb.opacity.clamp(0.0, 1.0) * 255.0).round() as u8,
+        )))
+    }
+}
+
+/// Rasterize SVG markup into a `width * scale` by `height * scale` pixmap
+///
+/// The drawing is scaled uniformly to fit `width` x `height` and centered.
+/// An SVG without `width`/`height` or `viewBox` is given the requested size.
+pub fn rasterize(
+    svg: &str,
+    width: u32,
+    height: u32,
+    scale: f32,
+    options: &RasterOptions,
+) -> Result<Pixmap, RasterError> {
+    let invalid = RasterError::InvalidSize {
+        width,
+        height,
+        scale,
+    };
+    let pw = (width as f32 * scale).round();
+    let ph = (height as f32 * scale).round();
+    if !(pw >= 1.0 && ph >= 1.0) {
+        return Err(invalid);
+    }
+    let mut pixmap = Pixmap::new(pw as u32, ph as u32).ok_or(invalid)?;
+    if let Some(color) = options.background_color()? {
+        pixmap.fill(color);
+    }
+
+    let mut opt = usvg::Options {
+        fontdb: Arc::clone(&options.fontdb),
+        ..usvg::Options::default()
+    };
+    if let Some(ref family) = options.font_family {
+        opt.font_family = family.clone();
+    }
+    if let Some(size) = usvg::Size::from_wh(width as f32, height as f32) {
+        opt.default_size = size;
+    }
+    let tree = usvg::Tree::from_str(svg, &opt)?;
+
+    let size = tree.size();
+    let fit = (pw / size.width()).min(ph / size.height());
+    let transform = tiny_skia::Transform::from_row(
+        fit,
+        0.0,
+        0.0,
+        fit,
+        (pw - size.width() * fit) / 2.0,
+        (ph - size.height() * fit) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Rasterize SVG markup and encode it as PNG
+pub fn rasterize_png(
+    svg: &str,
+    width: u32,
+    height: u32,
+    scale: f32,
+    options: &RasterOptions,
+) -> Result<Vec<u8>, RasterError> {
+    rasterize(svg, width, height, scale, options)?
+        .encode_png()
+        .map_err(|e| RasterError::Encode(e.to_string()))
+}
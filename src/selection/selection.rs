@@ -1,11 +1,22 @@
 use crate::selection::arena::{Arena, NodeKey};
+use crate::dispatch::Event;
 use crate::selection::data_join::DataJoin;
-use crate::selection::node::Node;
-use crate::selection::utils::{parse_selector, remove_node_recursively};
+use crate::selection::event::{self, NodeEvent};
+use crate::selection::node::{Datum, Node};
+use crate::selection::parse::{ParseError, ParseMode, parse_into};
+use crate::selection::raster::{RasterError, RasterOptions, rasterize, rasterize_png};
+use crate::selection::serialize::Serializer;
+use crate::selection::utils::{SelectorList, parse_selector_list, remove_node_recursively};
+use crate::transition::Transition;
 use slotmap::SlotMap;
+use std::any::Any;
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use tiny_skia::Pixmap;
 
 /// Validates if a style value is valid CSS
 /// This is a basic validation that checks for common invalid patterns
@@ -43,6 +54,25 @@ fn is_valid_style_value(value: &str) -> bool {
     has_valid_pattern
 }
 
+/// Parse a `style` attribute into declarations, keeping their order
+fn parse_style(style: &str) -> Vec<(String, String)> {
+    style
+        .split(';')
+        .filter_map(|decl| {
+            let (key, val) = decl.split_once(':')?;
+            (!key.trim().is_empty()).then(|| (key.trim().to_string(), val.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Update a declaration in place, or append it if new
+fn set_style(styles: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match styles.iter_mut().find(|(k, _)| k == name) {
+        Some((_, v)) => *v = value.to_string(),
+        None => styles.push((name.to_string(), value.to_string())),
+    }
+}
+
 pub struct Selection {
     arena: Rc<RefCell<Arena>>,
     keys: Vec<NodeKey>,
@@ -50,6 +80,38 @@ pub struct Selection {
     enter_keys: Option<Vec<NodeKey>>,
     update_keys: Option<Vec<NodeKey>>,
     exit_keys: Option<Vec<NodeKey>>,
+    /// Parent node and number of selected nodes of each group, in order
+    /// (D3 groups); empty when the nodes were not selected from parents
+    groups: Vec<(NodeKey, usize)>,
+}
+
+/// The nodes of a data join being built group by group
+#[derive(Default)]
+struct JoinParts {
+    enter: Vec<NodeKey>,
+    update: Vec<NodeKey>,
+    exit: Vec<NodeKey>,
+    enter_index: Vec<usize>,
+    update_index: Vec<usize>,
+    enter_groups: Vec<(NodeKey, usize)>,
+    update_groups: Vec<(NodeKey, usize)>,
+    exit_groups: Vec<(NodeKey, usize)>,
+}
+
+impl JoinParts {
+    fn lens(&self) -> [usize; 3] {
+        [self.enter.len(), self.update.len(), self.exit.len()]
+    }
+    /// Close the current group, whose nodes were added since `start`
+    fn end_group(&mut self, parent: Option<NodeKey>, start: [usize; 3]) {
+        if let Some(parent) = parent {
+            self.enter_groups
+                .push((parent, self.enter.len() - start[0]));
+            self.update_groups
+                .push((parent, self.update.len() - start[1]));
+            self.exit_groups.push((parent, self.exit.len() - start[2]));
+        }
+    }
 }
 
 // Full impl Selection moved from mod.rs
@@ -76,19 +138,11 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn root(arena: Rc<RefCell<Arena>>, tag: &str) -> Self {
-        let root = Node {
-            tag: tag.to_string(),
-            attributes: HashMap::new(),
-            properties: HashMap::new(),
-            data: None,
-            children: vec![],
-            parent: None,
-            text: None,
-            event_handlers: HashMap::new(),
-        };
+        let root = Node::new(tag);
         let root_key = arena.borrow_mut().nodes.insert(root);
         Selection {
             arena,
@@ -97,6 +151,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn len(&self) -> usize {
@@ -108,6 +163,30 @@ impl Selection {
     pub fn iter(&self) -> impl Iterator<Item = &NodeKey> {
         self.keys.iter()
     }
+    /// The arena shared by this selection
+    pub fn arena(&self) -> Rc<RefCell<Arena>> {
+        Rc::clone(&self.arena)
+    }
+    /// Start a transition on the selected nodes (D3 selection.transition)
+    ///
+    /// The transition runs on wall time; call [`Transition::clock`] with a
+    /// [`ManualClock`](crate::timer::ManualClock) to step frames yourself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// let mut svg = Selection::create("svg");
+    /// let mut circle = svg.append("circle");
+    /// circle.attr("r", "1");
+    /// let mut t = circle.transition();
+    /// t.duration(500.0).attr("r", "10");
+    /// t.finish();
+    /// assert_eq!(circle.get_attr("r"), Some("10".to_string()));
+    /// ```
+    pub fn transition(&self) -> Transition {
+        Transition::new(self)
+    }
     
     /// Get an attribute value from the first node in the selection
     pub fn get_attr(&self, name: &str) -> Option<String> {
@@ -139,30 +218,39 @@ impl Selection {
         let mut new_keys = Vec::new();
         
         for &key in &self.keys {
-            let (parent_key, data) = {
+            let (parent_key, data, datum, join_key, before) = {
                 let arena = self.arena.borrow();
                 let node = &arena.nodes[key];
                 // If the node has a parent and is a placeholder (empty tag), use the parent
                 // Otherwise, use the node itself as the parent
                 if node.tag.is_empty() && node.parent.is_some() {
-                    (node.parent.unwrap(), node.data.clone())
+                    (
+                        node.parent.unwrap(),
+                        node.data.clone(),
+                        node.datum.clone(),
+                        node.join_key.clone(),
+                        node.enter_before,
+                    )
                 } else {
-                    (key, node.data.clone())
+                    (key, node.data.clone(), node.datum.clone(), None, None)
                 }
             };
             
             let child = Node {
-                tag: tag.to_string(),
-                attributes: HashMap::new(),
-                properties: HashMap::new(),
                 data: data.clone(),
-                children: vec![],
+                datum,
+                join_key,
                 parent: Some(parent_key),
-                text: None,
-                event_handlers: HashMap::new(),
+                ..Node::new(tag)
             };
             let child_key = self.arena.borrow_mut().nodes.insert(child);
-            self.arena.borrow_mut().nodes[parent_key].children.push(child_key);
+            let mut arena = self.arena.borrow_mut();
+            let siblings = &mut arena.nodes[parent_key].children;
+            // Entering nodes go before the next updating node to keep data order
+            match before.and_then(|b| siblings.iter().position(|&c| c == b)) {
+                Some(pos) => siblings.insert(pos, child_key),
+                None => siblings.push(child_key),
+            }
             new_keys.push(child_key);
         }
         
@@ -173,6 +261,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn attr(&mut self, name: &str, value: &str) -> &mut Self {
@@ -209,11 +298,46 @@ impl Selection {
         }
         self
     }
+    /// Set an attribute from each node's typed datum; nodes without a `T` datum are skipped
+    pub fn attr_typed<T, F>(&mut self, name: &str, mut f: F) -> &mut Self
+    where
+        T: 'static,
+        F: FnMut(&T, usize) -> String,
+    {
+        for (i, key, datum) in self.typed_data() {
+            if let Some(d) = datum.downcast_ref::<T>() {
+                let value = f(d, i);
+                Selection::new(Rc::clone(&self.arena), vec![key]).attr(name, &value);
+            }
+        }
+        self
+    }
+    /// Select all descendants matching a CSS selector, in document order
+    ///
+    /// `None` selects every descendant. Invalid selectors match nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// let mut svg = Selection::create("svg");
+    /// let mut axis = svg.append("g");
+    /// axis.attr("class", "axis");
+    /// for _ in 0..3 {
+    ///     axis.append("g").attr("class", "tick").append("line");
+    /// }
+    /// assert_eq!(svg.select_all(Some(".axis .tick line")).len(), 3);
+    /// assert_eq!(svg.select_all(Some(".tick:first-child > line")).len(), 1);
+    /// ```
     pub fn select_all(&mut self, tag: Option<&str>) -> Selection {
         let mut found = Vec::new();
+        let mut groups = Vec::new();
+        let list = tag.map(|s| parse_selector_list(s).unwrap_or_default());
         let arena = self.arena.borrow();
         for &key in &self.keys {
-            self.traverse_children_ordered(&arena, key, &mut found, tag);
+            let start = found.len();
+            self.traverse_children_ordered(&arena, key, &mut found, list.as_ref());
+            groups.push((key, found.len() - start));
         }
         Selection {
             arena: Rc::clone(&self.arena),
@@ -222,6 +346,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups,
         }
     }
     
@@ -230,192 +355,281 @@ impl Selection {
         arena: &Arena,
         key: NodeKey,
         found: &mut Vec<NodeKey>,
-        tag: Option<&str>,
+        selector: Option<&SelectorList>,
     ) {
         if let Some(node) = arena.nodes.get(key) {
             // Process children in order
             for &child_key in &node.children {
-                if let Some(child_node) = arena.nodes.get(child_key) {
-                    if tag.map_or(true, |t| child_node.tag == t) {
-                        found.push(child_key);
-                    }
+                if arena.nodes.contains_key(child_key)
+                    && selector.is_none_or(|s| s.matches(arena, child_key))
+                {
+                    found.push(child_key);
                 }
                 // Recursively traverse children
-                self.traverse_children_ordered(arena, child_key, found, tag);
+                self.traverse_children_ordered(arena, child_key, found, selector);
             }
         }
     }
     pub fn data<T: ToString>(&mut self, data: &[T]) -> DataJoin {
-        let mut enter_keys = Vec::new();
-        let mut update_keys = Vec::new();
-        let mut exit_keys = Vec::new();
-        let mut data_iter = data.iter().map(|d| d.to_string()).peekable();
-        let mut node_iter = self.keys.iter().peekable();
+        self.join_by_index(data, |d| (Some(d.to_string()), None))
+    }
+
+    /// Bind typed data by index without requiring `ToString`
+    ///
+    /// Read the data back with [`Node::datum`] or the `*_typed` methods.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// #[derive(Clone)]
+    /// struct Point { x: f64, y: f64 }
+    /// let mut svg = Selection::create("svg");
+    /// let points = vec![Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }];
+    /// let mut join = svg.select_all(Some("circle")).data_typed(&points);
+    /// let mut circles = join.join("circle");
+    /// circles.attr_typed("cx", |p: &Point, _| p.x.to_string());
+    /// assert_eq!(circles.map(|n| n.attributes["cx"].clone()), vec!["1", "3"]);
+    /// ```
+    pub fn data_typed<T: Clone + 'static>(&mut self, data: &[T]) -> DataJoin {
+        self.join_by_index(data, |d| (None, Some(Rc::new(d.clone()) as Datum)))
+    }
+
+    /// Index join shared by [`Selection::data`] and [`Selection::data_typed`];
+    /// `bind` gives the string form and typed datum stored for each datum
+    fn join_by_index<T>(
+        &mut self,
+        data: &[T],
+        bind: impl Fn(&T) -> (Option<String>, Option<Datum>),
+    ) -> DataJoin {
+        let mut parts = JoinParts::default();
         let mut arena = self.arena.borrow_mut();
-        let mut _i = 0;
-        
-        // Find the parent node for enter selections
-        let parent_key = if let Some(&first_key) = self.keys.first() {
-            arena.nodes.get(first_key).and_then(|node| node.parent)
-        } else {
-            None
-        };
-        
-        while let Some(d) = data_iter.next() {
-            if let Some(&node_key) = node_iter.peek() {
-                arena.nodes[*node_key].data = Some(d);
-                update_keys.push(*node_key);
-                node_iter.next();
-            } else {
-                let placeholder_node = Node {
-                    tag: "".to_string(),
-                    attributes: HashMap::new(),
-                    properties: HashMap::new(),
-                    data: Some(d),
-                    children: vec![],
-                    parent: parent_key,
-                    text: None,
-                    event_handlers: HashMap::new(),
-                };
-                let placeholder_key = arena.nodes.insert(placeholder_node);
-                enter_keys.push(placeholder_key);
+
+        for (g, (parent_key, keys)) in self.join_groups(&arena).into_iter().enumerate() {
+            let start = parts.lens();
+            let offset = g * data.len();
+            let mut node_iter = keys.into_iter();
+            for (i, d) in data.iter().enumerate() {
+                let (label, datum) = bind(d);
+                if let Some(node_key) = node_iter.next() {
+                    let node = &mut arena.nodes[node_key];
+                    node.data = label;
+                    node.datum = datum;
+                    node.join_key = None;
+                    parts.update.push(node_key);
+                    parts.update_index.push(offset + i);
+                } else {
+                    let placeholder_node = Node {
+                        data: label,
+                        datum,
+                        parent: parent_key,
+                        ..Node::new("")
+                    };
+                    parts.enter.push(arena.nodes.insert(placeholder_node));
+                    parts.enter_index.push(offset + i);
+                }
             }
-            _i += 1;
-        }
-        while let Some(&node_key) = node_iter.next() {
-            exit_keys.push(node_key);
-        }
-        
-        self.enter_keys = Some(enter_keys.clone());
-        self.update_keys = Some(update_keys.clone());
-        self.exit_keys = Some(exit_keys.clone());
-        let update_selection = Selection {
-            arena: Rc::clone(&self.arena),
-            keys: update_keys,
-            pending_data: None,
-            enter_keys: None,
-            update_keys: None,
-            exit_keys: None,
-        };
-        let enter_selection = Selection {
-            arena: Rc::clone(&self.arena),
-            keys: enter_keys,
-            pending_data: None,
-            enter_keys: None,
-            update_keys: None,
-            exit_keys: None,
-        };
-        let exit_selection = Selection {
-            arena: Rc::clone(&self.arena),
-            keys: exit_keys,
-            pending_data: None,
-            enter_keys: None,
-            update_keys: None,
-            exit_keys: None,
-        };
-        DataJoin {
-            update: update_selection,
-            enter: enter_selection,
-            exit: exit_selection,
+            parts.exit.extend(node_iter);
+            parts.end_group(parent_key, start);
         }
+        drop(arena);
+
+        self.finish_join(parts)
     }
 
     /// Data join with optional key function for matching data to nodes
-    pub fn data_with_key<T: ToString, K: ToString, F>(&mut self, data: &[T], key_fn: F) -> DataJoin
+    ///
+    /// Follows D3's keyed join: a node's key comes from applying `key_fn` to its
+    /// bound datum when that datum is a `T`, otherwise from the key it was last
+    /// bound with (or its string data). A node whose key was already claimed by
+    /// an earlier node goes to exit, and a datum whose key was already claimed
+    /// by an earlier datum goes to enter. Update and enter are in data order;
+    /// exit keeps the original node order. As in D3, each group (the nodes
+    /// selected from one parent) is joined to all of `data`, and its entering
+    /// nodes go under that parent.
+    ///
+    /// Nodes bound by [`Selection::data`] keep only the string form of their
+    /// datum, so they are keyed by that string rather than by `key_fn`. Bind
+    /// with [`Selection::data_typed`] and rejoin with
+    /// [`Selection::data_typed_with_key`] to key them by their datum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// let mut svg = Selection::create("svg");
+    /// let mut join = svg.select_all(Some("circle")).data_with_key(&["a", "b"], |d, _| d.to_string());
+    /// join.enter.append("circle");
+    ///
+    /// let join = svg.select_all(Some("circle")).data_with_key(&["b", "c"], |d, _| d.to_string());
+    /// assert_eq!(join.update.len(), 1);
+    /// assert_eq!(join.enter.len(), 1);
+    /// assert_eq!(join.exit.len(), 1);
+    /// ```
+    pub fn data_with_key<T, K, F>(&mut self, data: &[T], key_fn: F) -> DataJoin
+    where
+        T: ToString,
+        K: ToString,
+        F: Fn(&T, usize) -> K,
+    {
+        self.join_by_key(data, &key_fn, |_, _| None, |d| (Some(d.to_string()), None))
+    }
+
+    /// Keyed data join for typed data without requiring `ToString`
+    pub fn data_typed_with_key<T, K, F>(&mut self, data: &[T], key_fn: F) -> DataJoin
     where
+        T: Clone + 'static,
+        K: ToString,
         F: Fn(&T, usize) -> K,
     {
-        let mut enter_keys = Vec::new();
-        let mut update_keys = Vec::new();
-        let mut exit_keys = Vec::new();
+        self.join_by_key(
+            data,
+            &key_fn,
+            |node, i| node.datum::<T>().map(|d| key_fn(d, i).to_string()),
+            |d| (None, Some(Rc::new(d.clone()) as Datum)),
+        )
+    }
 
+    /// Keyed join shared by [`Selection::data_with_key`] and
+    /// [`Selection::data_typed_with_key`]; `node_key_of` keys an existing node
+    /// from its typed datum, and `bind` is as in `join_by_index`
+    fn join_by_key<T, K, F>(
+        &mut self,
+        data: &[T],
+        key_fn: F,
+        node_key_of: impl Fn(&Node, usize) -> Option<String>,
+        bind: impl Fn(&T) -> (Option<String>, Option<Datum>),
+    ) -> DataJoin
+    where
+        K: ToString,
+        F: Fn(&T, usize) -> K,
+    {
+        let mut parts = JoinParts::default();
         let mut arena = self.arena.borrow_mut();
 
-        // Find the parent node for enter selections
-        let parent_key = self.keys.first().copied();
+        for (g, (parent_key, keys)) in self.join_groups(&arena).into_iter().enumerate() {
+            let start = parts.lens();
+            let offset = g * data.len();
 
-        // Create data items with keys and indices
-        let data_items: Vec<_> = data
-            .iter()
-            .enumerate()
-            .map(|(i, d)| (key_fn(d, i).to_string(), d.to_string(), i))
-            .collect();
+            // Index existing nodes by key; later nodes with a duplicate key exit
+            let mut exiting = vec![false; keys.len()];
+            let mut node_by_key: HashMap<String, usize> = HashMap::new();
+            for (i, &node_key) in keys.iter().enumerate() {
+                let key = arena.nodes.get(node_key).and_then(|node| {
+                    node_key_of(node, i)
+                        .or_else(|| node.join_key.clone())
+                        .or_else(|| node.data.clone())
+                });
+                match key {
+                    Some(key) if !node_by_key.contains_key(&key) => {
+                        node_by_key.insert(key, i);
+                    }
+                    _ => exiting[i] = true,
+                }
+            }
 
-        // Create existing nodes map with keys
-        // Since the node data is already a string, we need to use a different approach
-        // We'll create a map where the key is the node's current data, and we'll match it against
-        // the data items using the key function
-        let mut existing_nodes: HashMap<String, (NodeKey, usize)> = HashMap::new();
-        for (i, &node_key) in self.keys.iter().enumerate() {
-            if let Some(node) = arena.nodes.get(node_key) {
-                if let Some(ref data) = node.data {
-                    // We need to find which data item this node corresponds to
-                    // We'll use the node's data as the key directly for now
-                    // This assumes that the key function would produce the same key for the same data
-                    existing_nodes.insert(data.clone(), (node_key, i));
+            // Match data to nodes; each key can be claimed once
+            let mut matched: Vec<Option<NodeKey>> = Vec::with_capacity(data.len());
+            let mut data_keys: Vec<String> = Vec::with_capacity(data.len());
+            for (i, d) in data.iter().enumerate() {
+                let key = key_fn(d, i).to_string();
+                match node_by_key.remove(&key) {
+                    Some(node_index) => {
+                        let node_key = keys[node_index];
+                        let node = &mut arena.nodes[node_key];
+                        (node.data, node.datum) = bind(d);
+                        node.join_key = Some(key.clone());
+                        matched.push(Some(node_key));
+                    }
+                    None => matched.push(None),
                 }
+                data_keys.push(key);
+            }
+            for node_index in node_by_key.into_values() {
+                exiting[node_index] = true;
             }
-        }
 
-        // Process data items to create enter/update selections
-        for (data_key, data_value, _data_index) in data_items.into_iter() {
-            if let Some((node_key, _)) = existing_nodes.remove(&data_key) {
-                // Update existing node
-                arena.nodes[node_key].data = Some(data_value.clone());
-                update_keys.push(node_key);
-            } else {
-                // Place new data in enter selection
-                let enter_node = Node {
-                    tag: "".to_string(),
-                    attributes: HashMap::new(),
-                    properties: HashMap::new(),
-                    data: Some(data_value.clone()),
-                    children: vec![],
-                    parent: parent_key,
-                    text: None,
-                    event_handlers: HashMap::new(),
-                };
-                let enter_key = arena.nodes.insert(enter_node);
-                enter_keys.push(enter_key);
+            // The next matched node after each datum, which entering nodes are
+            // inserted before
+            let mut next_matched = vec![None; data.len()];
+            let mut next = None;
+            for i in (0..data.len()).rev() {
+                next_matched[i] = next;
+                next = matched[i].or(next);
             }
+            for (i, (d, key)) in data.iter().zip(data_keys).enumerate() {
+                match matched[i] {
+                    Some(node_key) => {
+                        parts.update.push(node_key);
+                        parts.update_index.push(offset + i);
+                    }
+                    None => {
+                        let (data, datum) = bind(d);
+                        let enter_node = Node {
+                            data,
+                            datum,
+                            join_key: Some(key),
+                            parent: parent_key,
+                            enter_before: next_matched[i],
+                            ..Node::new("")
+                        };
+                        parts.enter.push(arena.nodes.insert(enter_node));
+                        parts.enter_index.push(offset + i);
+                    }
+                }
+            }
+            let exit = keys.iter().zip(&exiting).filter(|(_, exit)| **exit);
+            parts.exit.extend(exit.map(|(&key, _)| key));
+            parts.end_group(parent_key, start);
         }
+        drop(arena);
 
-        // Remaining nodes go to exit selection
-        exit_keys.extend(existing_nodes.into_values().map(|(key, _)| key));
+        self.finish_join(parts)
+    }
 
-        self.enter_keys = Some(enter_keys.clone());
-        self.update_keys = Some(update_keys.clone());
-        self.exit_keys = Some(exit_keys.clone());
+    /// Each group's parent for entering nodes and its selected nodes; a
+    /// selection without groups is one group entering under the parent of its
+    /// first node
+    fn join_groups(&self, arena: &Arena) -> Vec<(Option<NodeKey>, Vec<NodeKey>)> {
+        let grouped: usize = self.groups.iter().map(|&(_, n)| n).sum();
+        if self.groups.is_empty() || grouped != self.keys.len() {
+            let parent = self
+                .keys
+                .first()
+                .and_then(|&key| arena.nodes.get(key))
+                .and_then(|node| node.parent);
+            return vec![(parent, self.keys.clone())];
+        }
+        let mut rest = self.keys.as_slice();
+        self.groups
+            .iter()
+            .map(|&(parent, n)| {
+                let (keys, tail) = rest.split_at(n);
+                rest = tail;
+                (Some(parent), keys.to_vec())
+            })
+            .collect()
+    }
 
-        let update_selection = Selection {
-            arena: Rc::clone(&self.arena),
-            keys: update_keys,
-            pending_data: None,
-            enter_keys: None,
-            update_keys: None,
-            exit_keys: None,
-        };
-        let enter_selection = Selection {
-            arena: Rc::clone(&self.arena),
-            keys: enter_keys,
-            pending_data: None,
-            enter_keys: None,
-            update_keys: None,
-            exit_keys: None,
-        };
-        let exit_selection = Selection {
+    fn finish_join(&mut self, parts: JoinParts) -> DataJoin {
+        self.enter_keys = Some(parts.enter.clone());
+        self.update_keys = Some(parts.update.clone());
+        self.exit_keys = Some(parts.exit.clone());
+        let derive = |keys: Vec<NodeKey>, groups: Vec<(NodeKey, usize)>| Selection {
             arena: Rc::clone(&self.arena),
-            keys: exit_keys,
+            keys,
             pending_data: None,
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups,
         };
-
         DataJoin {
-            update: update_selection,
-            enter: enter_selection,
-            exit: exit_selection,
+            update: derive(parts.update, parts.update_groups),
+            enter: derive(parts.enter, parts.enter_groups),
+            exit: derive(parts.exit, parts.exit_groups),
+            enter_index: parts.enter_index,
+            update_index: parts.update_index,
         }
     }
     pub fn datum<T: ToString>(&mut self, value: T) -> &mut Self {
@@ -424,6 +638,19 @@ impl Selection {
             let mut arena = self.arena.borrow_mut();
             for &key in &self.keys {
                 arena.nodes[key].data = Some(data_str.clone());
+                arena.nodes[key].datum = None;
+            }
+        }
+        self
+    }
+    /// Set the same typed datum on every node without requiring `ToString`
+    pub fn datum_typed<T: 'static>(&mut self, value: T) -> &mut Self {
+        let datum: Datum = Rc::new(value);
+        {
+            let mut arena = self.arena.borrow_mut();
+            for &key in &self.keys {
+                arena.nodes[key].data = None;
+                arena.nodes[key].datum = Some(Rc::clone(&datum));
             }
         }
         self
@@ -451,32 +678,33 @@ impl Selection {
             }
 
             if let Some(parent_key) = parent_key {
-                let data_vec: Vec<Option<String>> = if let Some(ref pd) = self.pending_data {
-                    pd.iter().map(|d| Some(d.clone())).collect()
-                } else {
-                    self.keys
-                        .iter()
-                        .map(|k| arena.nodes.get(*k).and_then(|n| n.data.clone()))
-                        .collect()
-                };
+                let data_vec: Vec<(Option<String>, Option<Datum>)> =
+                    if let Some(ref pd) = self.pending_data {
+                        pd.iter().map(|d| (Some(d.clone()), None)).collect()
+                    } else {
+                        self.keys
+                            .iter()
+                            .map(|k| match arena.nodes.get(*k) {
+                                Some(n) => (n.data.clone(), n.datum.clone()),
+                                None => (None, None),
+                            })
+                            .collect()
+                    };
 
                 let mut new_keys = Vec::new();
-                for data in data_vec {
+                for (data, datum) in data_vec {
                     let node = Node {
-                        tag: tag.to_string(),
-                        attributes: HashMap::new(),
-                        properties: HashMap::new(),
                         data,
-                        children: vec![],
+                        datum,
                         parent: Some(parent_key),
-                        text: None,
-                        event_handlers: HashMap::new(),
+                        ..Node::new(tag)
                     };
                     let new_key = arena.nodes.insert(node);
                     arena.nodes[parent_key].children.push(new_key);
                     new_keys.push(new_key);
                 }
                 self.keys = new_keys;
+                self.groups.clear();
                 self.pending_data = None;
             }
         }
@@ -507,8 +735,45 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
+    /// Parse an SVG/XML document into a new arena, selecting its root element
+    ///
+    /// The XML declaration, comments, doctype and processing instructions are
+    /// skipped. Mismatched or unclosed tags are reported as [`ParseError`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// let mut doc = Selection::parse_svg(
+    ///     r#"<svg width="100"><text id="title">Placeholder</text></svg>"#,
+    /// )
+    /// .unwrap();
+    /// doc.select_by("#title").text("Revenue");
+    /// assert_eq!(doc.render(), r#"<svg width="100"><text id="title">Revenue</text></svg>"#);
+    /// ```
+    pub fn parse_svg(markup: &str) -> Result<Selection, ParseError> {
+        Self::parse_document(markup, ParseMode::Xml)
+    }
+    /// Parse an HTML fragment into a new arena, selecting its top-level elements
+    ///
+    /// Void elements, unquoted and boolean attributes and unclosed elements are
+    /// accepted.
+    pub fn parse_html(markup: &str) -> Result<Selection, ParseError> {
+        Self::parse_document(markup, ParseMode::Html)
+    }
+    fn parse_document(markup: &str, mode: ParseMode) -> Result<Selection, ParseError> {
+        let mut arena = Arena {
+            nodes: SlotMap::with_key(),
+        };
+        let keys = parse_into(&mut arena, None, markup, mode)?;
+        if keys.is_empty() {
+            return Err(ParseError::NoRootElement);
+        }
+        Ok(Selection::new(Rc::new(RefCell::new(arena)), keys))
+    }
     pub fn nodes(&self) -> Vec<Node> {
         let arena = self.arena.borrow();
         self.keys.iter().map(|k| arena.nodes[*k].clone()).collect()
@@ -546,25 +811,9 @@ impl Selection {
                     .attributes
                     .entry("style".to_string())
                     .or_insert_with(String::new);
-                let mut styles: HashMap<String, String> = if style_attr.is_empty() {
-                    HashMap::new()
-                } else {
-                    style_attr
-                        .split(';')
-                        .filter(|s| !s.is_empty())
-                        .filter_map(|s| {
-                            let mut parts = s.splitn(2, ':');
-                            match (parts.next(), parts.next()) {
-                                (Some(key), Some(val)) if !key.trim().is_empty() => {
-                                    Some((key.trim().to_string(), val.trim().to_string()))
-                                }
-                                _ => None,
-                            }
-                        })
-                        .collect()
-                };
+                let mut styles = parse_style(style_attr);
                 if value.is_empty() {
-                    styles.remove(name);
+                    styles.retain(|(k, _)| k != name);
                 } else {
                     #[cfg(debug_assertions)]
                     {
@@ -574,12 +823,12 @@ impl Selection {
                                 name, value
                             );
                         } else {
-                            styles.insert(name.to_string(), value.to_string());
+                            set_style(&mut styles, name, value);
                         }
                     }
                     #[cfg(not(debug_assertions))]
                     {
-                        styles.insert(name.to_string(), value.to_string());
+                        set_style(&mut styles, name, value);
                     }
                 }
                 *style_attr = styles
@@ -607,27 +856,11 @@ impl Selection {
                     .attributes
                     .entry("style".to_string())
                     .or_insert_with(String::new);
-                let mut styles: HashMap<String, String> = if style_attr.is_empty() {
-                    HashMap::new()
-                } else {
-                    style_attr
-                        .split(';')
-                        .filter(|s| !s.is_empty())
-                        .filter_map(|s| {
-                            let mut parts = s.splitn(2, ':');
-                            match (parts.next(), parts.next()) {
-                                (Some(key), Some(val)) if !key.trim().is_empty() => {
-                                    Some((key.trim().to_string(), val.trim().to_string()))
-                                }
-                                _ => None,
-                            }
-                        })
-                        .collect()
-                };
-                let previous_value = styles.get(name).cloned();
+                let mut styles = parse_style(style_attr);
+                let previous_value = styles.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
                 let value = f(&node_clone, i, previous_value);
                 if value.is_empty() {
-                    styles.remove(name);
+                    styles.retain(|(k, _)| k != name);
                 } else {
                     #[cfg(debug_assertions)]
                     {
@@ -637,12 +870,12 @@ impl Selection {
                                 name, value
                             );
                         } else {
-                            styles.insert(name.to_string(), value);
+                            set_style(&mut styles, name, &value);
                         }
                     }
                     #[cfg(not(debug_assertions))]
                     {
-                        styles.insert(name.to_string(), value);
+                        set_style(&mut styles, name, &value);
                     }
                 }
                 *style_attr = styles
@@ -654,6 +887,20 @@ impl Selection {
         }
         self
     }
+    /// Set a style from each node's typed datum; nodes without a `T` datum are skipped
+    pub fn style_typed<T, F>(&mut self, name: &str, mut f: F) -> &mut Self
+    where
+        T: 'static,
+        F: FnMut(&T, usize) -> String,
+    {
+        for (i, key, datum) in self.typed_data() {
+            if let Some(d) = datum.downcast_ref::<T>() {
+                let value = f(d, i);
+                Selection::new(Rc::clone(&self.arena), vec![key]).style(name, &value);
+            }
+        }
+        self
+    }
     pub fn property(&mut self, name: &str, value: &str) -> &mut Self {
         {
             let mut arena = self.arena.borrow_mut();
@@ -674,16 +921,19 @@ impl Selection {
             let mut arena = self.arena.borrow_mut();
             for &key in &self.keys {
                 let node = &mut arena.nodes[key];
-                let mut classes: HashSet<String> =
-                    node.attributes.get("class").map_or_else(HashSet::new, |c| {
+                // Keep existing class order so rendering is stable
+                let mut classes: Vec<String> =
+                    node.attributes.get("class").map_or_else(Vec::new, |c| {
                         c.split_whitespace().map(|s| s.to_string()).collect()
                     });
                 if on {
-                    classes.insert(name.to_string());
+                    if !classes.iter().any(|c| c == name) {
+                        classes.push(name.to_string());
+                    }
                 } else {
-                    classes.remove(name);
+                    classes.retain(|c| c != name);
                 }
-                let new_class = classes.into_iter().collect::<Vec<_>>().join(" ");
+                let new_class = classes.join(" ");
                 node.attributes.insert("class".to_string(), new_class);
             }
         }
@@ -699,16 +949,51 @@ impl Selection {
         }
         self
     }
-    /// Set inner HTML (for SVG/text, sets the text field)
+    /// Set inner HTML, replacing each node's children with the parsed markup
+    ///
+    /// Markup is parsed in [`ParseMode::Html`]; text outside child elements
+    /// becomes the node's text. Input the parser rejects is stored as text.
     pub fn html(&mut self, value: &str) -> &mut Self {
         {
             let mut arena = self.arena.borrow_mut();
             for &key in &self.keys {
-                arena.nodes[key].text = Some(value.to_string());
+                let children = std::mem::take(&mut arena.nodes[key].children);
+                for child in children {
+                    remove_node_recursively(&mut arena, child);
+                }
+                arena.nodes[key].text = None;
+                if parse_into(&mut arena, Some(key), value, ParseMode::Html).is_err() {
+                    arena.nodes[key].text = Some(value.to_string());
+                }
             }
         }
         self
     }
+    /// Parse markup and append the resulting elements to every node
+    ///
+    /// Returns a selection of the newly created top-level elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// let mut svg = Selection::create("svg");
+    /// let added = svg
+    ///     .append_markup("<g class=\"axis\"><line x2=\"10\"/></g>")
+    ///     .unwrap();
+    /// assert_eq!(added.len(), 1);
+    /// assert_eq!(svg.render(), "<svg><g class=\"axis\"><line x2=\"10\"/></g></svg>");
+    /// ```
+    pub fn append_markup(&mut self, markup: &str) -> Result<Selection, ParseError> {
+        let mut keys = Vec::new();
+        {
+            let mut arena = self.arena.borrow_mut();
+            for &key in &self.keys {
+                keys.extend(parse_into(&mut arena, Some(key), markup, ParseMode::Xml)?);
+            }
+        }
+        Ok(Selection::new(Rc::clone(&self.arena), keys))
+    }
 
     /// Insert a new child node before a reference node (by tag or index)
     pub fn insert(&mut self, tag: &str, before: Option<&str>) -> &mut Self {
@@ -716,14 +1001,8 @@ impl Selection {
             let mut arena = self.arena.borrow_mut();
             for &parent_key in &self.keys {
                 let new_node = Node {
-                    tag: tag.to_string(),
-                    attributes: HashMap::new(),
-                    properties: HashMap::new(),
-                    data: None,
-                    children: vec![],
                     parent: Some(parent_key),
-                    text: None,
-                    event_handlers: HashMap::new(),
+                    ..Node::new(tag)
                 };
                 let new_key = arena.nodes.insert(new_node);
                 let pos = if let Some(before_tag) = before {
@@ -746,23 +1025,111 @@ impl Selection {
         self
     }
 
-    /// Attach an event handler (stores handler in node, for API completeness)
-    pub fn on<F>(&mut self, event: &str, handler: F) -> &mut Self
+    /// Add a listener for `typenames` to each node, replacing any listener
+    /// with the same typename
+    ///
+    /// Typenames are `type` or `type.name`, separated by whitespace; the name
+    /// lets several listeners share a type (`click.tooltip`, `click.select`).
+    /// Listeners run when an event is dispatched to the node or bubbles up
+    /// from a descendant, see [`dispatch`](Self::dispatch).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use rust_d3::selection::Selection;
+    /// let mut svg = Selection::create("svg");
+    /// let mut rect = svg.append("rect");
+    /// svg.on("click.select", |this, _event| {
+    ///     this.attr("data-clicked", "true");
+    /// });
+    /// rect.dispatch("click", None);
+    /// assert_eq!(svg.get_attr("data-clicked").as_deref(), Some("true"));
+    /// ```
+    pub fn on<F>(&mut self, typenames: &str, handler: F) -> &mut Self
     where
-        F: FnMut(&mut Node) + Clone + 'static,
+        F: FnMut(&mut Selection, &NodeEvent) + Clone + 'static,
+    {
+        self.add_listener(typenames, handler, false)
+    }
+    /// Like [`on`](Self::on), but the listener runs in the capture phase,
+    /// before listeners on descendants
+    pub fn on_capture<F>(&mut self, typenames: &str, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut Selection, &NodeEvent) + Clone + 'static,
+    {
+        self.add_listener(typenames, handler, true)
+    }
+    fn add_listener<F>(&mut self, typenames: &str, handler: F, capture: bool) -> &mut Self
+    where
+        F: FnMut(&mut Selection, &NodeEvent) + Clone + 'static,
     {
         {
             let mut arena = self.arena.borrow_mut();
             for &key in &self.keys {
-                let node = &mut arena.nodes[key];
-                node.event_handlers
-                    .entry(event.to_string())
-                    .or_insert_with(Vec::new)
-                    .push(Box::new(handler.clone()));
+                for typename in typenames.split_whitespace() {
+                    event::add_listener(
+                        &mut arena.nodes[key],
+                        typename,
+                        Box::new(handler.clone()),
+                        capture,
+                    );
+                }
             }
         }
         self
     }
+    /// Remove listeners from each node: `type` and `type.name` remove that
+    /// listener, `.name` every listener with that name
+    pub fn off(&mut self, typenames: &str) -> &mut Self {
+        {
+            let mut arena = self.arena.borrow_mut();
+            for &key in &self.keys {
+                for typename in typenames.split_whitespace() {
+                    event::remove_listeners(&mut arena.nodes[key], typename);
+                }
+            }
+        }
+        self
+    }
+    /// Dispatch an event of type `event_type` to each node in turn, with an
+    /// optional `detail` payload for listeners ([`NodeEvent::detail`])
+    ///
+    /// The event runs capture listeners from the root down, the node's own
+    /// listeners, then bubbles back up through its ancestors, stopping early
+    /// if a listener calls `stop_propagation`. Each node gets a fresh event.
+    ///
+    /// Listeners may use any selection on the document while they run, and
+    /// listeners removed by an earlier listener are not invoked.
+    pub fn dispatch(
+        &mut self,
+        event_type: &str,
+        detail: Option<Arc<dyn Any + Send + Sync>>,
+    ) -> &mut Self {
+        for &key in &self.keys {
+            let event = Event {
+                event_type: Cow::Owned(event_type.to_string()),
+                data: detail.clone(),
+                timestamp: std::time::Instant::now(),
+                source: Some(Cow::Borrowed("selection")),
+                propagation_stopped: Arc::new(AtomicBool::new(false)),
+                default_prevented: Arc::new(AtomicBool::new(false)),
+            };
+            event::dispatch_event(&self.arena, key, &event);
+        }
+        self
+    }
+    /// Dispatch `event` to each node, like [`dispatch`](Self::dispatch), and
+    /// report whether no listener called `prevent_default`
+    ///
+    /// The event is shared, so once propagation is stopped later nodes are
+    /// not reached; this is meant for single-node selections such as the
+    /// element under a pointer.
+    pub fn dispatch_event(&mut self, event: &Event) -> bool {
+        for &key in &self.keys {
+            event::dispatch_event(&self.arena, key, event);
+        }
+        !event.is_default_prevented()
+    }
     pub fn call<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self {
         f(self);
         self
@@ -789,6 +1156,19 @@ impl Selection {
         }
         self
     }
+    /// Call `f` with each node and its typed datum; nodes without a `T` datum are skipped
+    pub fn each_typed<T, F>(&mut self, mut f: F) -> &mut Self
+    where
+        T: 'static,
+        F: FnMut(&mut Node, &T),
+    {
+        for (_, key, datum) in self.typed_data() {
+            if let Some(d) = datum.downcast_ref::<T>() {
+                f(&mut self.arena.borrow_mut().nodes[key], d);
+            }
+        }
+        self
+    }
     pub fn map<F, T>(&self, mut f: F) -> Vec<T>
     where
         F: FnMut(&Node) -> T,
@@ -814,8 +1194,17 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
+    /// Keep nodes whose typed datum satisfies `f`; nodes without a `T` datum are dropped
+    pub fn filter_typed<T, F>(&mut self, mut f: F) -> Selection
+    where
+        T: 'static,
+        F: FnMut(&T) -> bool,
+    {
+        self.filter(|node| node.datum::<T>().is_some_and(&mut f))
+    }
     pub fn merge(&mut self, other: &Selection) -> Selection {
         let mut merged = self.keys.clone();
         merged.extend(other.keys.iter().cloned());
@@ -826,13 +1215,17 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn children(&mut self) -> Selection {
         let mut child_keys = Vec::new();
+        let mut groups = Vec::new();
         let arena = self.arena.borrow();
         for &key in &self.keys {
-            child_keys.extend(arena.nodes[key].children.iter().cloned());
+            let children = &arena.nodes[key].children;
+            child_keys.extend(children.iter().cloned());
+            groups.push((key, children.len()));
         }
         Selection {
             arena: Rc::clone(&self.arena),
@@ -841,6 +1234,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups,
         }
     }
     pub fn select_child(&mut self, tag: &str) -> Selection {
@@ -860,6 +1254,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn parent(&mut self) -> Selection {
@@ -877,6 +1272,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn select_parent(&mut self, tag: &str) -> Selection {
@@ -896,6 +1292,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn clone_selection(&mut self) -> Selection {
@@ -906,14 +1303,17 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
-    pub fn find_all(&mut self, tag: &str) -> Selection {
+    /// Select the direct children matching a CSS selector
+    pub fn find_all(&mut self, selector: &str) -> Selection {
         let mut found = Vec::new();
+        let list = parse_selector_list(selector).unwrap_or_default();
         let arena = self.arena.borrow();
         for &key in &self.keys {
             for &child_key in &arena.nodes[key].children {
-                if arena.nodes[child_key].tag == tag {
+                if list.matches(&arena, child_key) {
                     found.push(child_key);
                 }
             }
@@ -925,18 +1325,19 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
-    pub fn select(&mut self, tag: &str) -> Selection {
+    /// Select the first descendant of each node matching a CSS selector
+    pub fn select(&mut self, selector: &str) -> Selection {
         let mut found = Vec::new();
+        let list = parse_selector_list(selector).unwrap_or_default();
         let arena = self.arena.borrow();
         for &key in &self.keys {
-            if let Some(&child_key) = arena.nodes[key]
-                .children
-                .iter()
-                .find(|&&c| arena.nodes[c].tag == tag)
-            {
-                found.push(child_key);
+            let mut descendants = Vec::new();
+            self.traverse_children_ordered(&arena, key, &mut descendants, None);
+            if let Some(&first) = descendants.iter().find(|&&k| list.matches(&arena, k)) {
+                found.push(first);
             }
         }
         Selection {
@@ -946,28 +1347,22 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
+    /// Select the nodes themselves and their descendants matching a CSS
+    /// selector, in document order
     pub fn select_by(&mut self, selector: &str) -> Selection {
         let mut found = Vec::new();
-        let parsed_selector = parse_selector(selector);
+        let list = parse_selector_list(selector).unwrap_or_default();
         let arena = self.arena.borrow();
         for &root_key in &self.keys {
-            let mut stack = vec![root_key];
-            while let Some(key) = stack.pop() {
-                if let Some(node) = arena.nodes.get(key) {
-                    let node_id = node.attributes.get("id").map(|s| s.as_str());
-                    let node_classes: Vec<String> =
-                        node.attributes.get("class").map_or_else(Vec::new, |cls| {
-                            cls.split_whitespace().map(|s| s.to_string()).collect()
-                        });
-
-                    if parsed_selector.matches(&node.tag, node_id, &node_classes) {
-                        found.push(key);
-                    }
-                    stack.extend(&node.children);
-                }
+            if list.matches(&arena, root_key) {
+                found.push(root_key);
             }
+            let mut descendants = Vec::new();
+            self.traverse_children_ordered(&arena, root_key, &mut descendants, Some(&list));
+            found.extend(descendants);
         }
         Selection {
             arena: Rc::clone(&self.arena),
@@ -976,6 +1371,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     pub fn sort_by<F>(&mut self, mut cmp: F) -> &mut Self
@@ -989,49 +1385,52 @@ impl Selection {
         }
         self
     }
+    /// Sort by comparing typed data; nodes without a `T` datum sort last
+    pub fn sort_by_typed<T, F>(&mut self, mut cmp: F) -> &mut Self
+    where
+        T: 'static,
+        F: FnMut(&T, &T) -> std::cmp::Ordering,
+    {
+        self.sort_by(|a, b| match (a.datum::<T>(), b.datum::<T>()) {
+            (Some(a), Some(b)) => cmp(a, b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        })
+    }
+    /// Index, key and datum of each node with a bound datum
+    fn typed_data(&self) -> Vec<(usize, NodeKey, Datum)> {
+        let arena = self.arena.borrow();
+        self.keys
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &key)| Some((i, key, arena.nodes.get(key)?.datum.clone()?)))
+            .collect()
+    }
     pub fn order(&mut self) -> &mut Self {
-        // In D3, order() reorders the DOM elements to match the selection order
-        // We need to update parent.children to reflect the current selection order
+        // In D3, order() reorders the DOM elements to match the selection order.
+        // Walking backwards, each node is moved before its successor unless it
+        // already precedes it, so unselected siblings keep their positions.
         {
             let mut arena = self.arena.borrow_mut();
-            // Group keys by their parent
-            let mut parent_groups: HashMap<Option<NodeKey>, Vec<NodeKey>> = HashMap::new();
-
-            for &key in &self.keys {
-                if let Some(node) = arena.nodes.get(key) {
-                    let parent_key = node.parent;
-                    parent_groups
-                        .entry(parent_key)
-                        .or_insert_with(Vec::new)
-                        .push(key);
-                }
-            }
-
-            // For each parent, reorder its children to match the selection order
-            for (parent_key, selected_keys) in parent_groups {
-                if let Some(parent_key) = parent_key {
-                    if let Some(parent) = arena.nodes.get_mut(parent_key) {
-                        // Create a new children vector with selected keys in selection order
-                        let mut new_children = Vec::new();
-                        let mut remaining_children = parent.children.clone();
-
-                        // First, add the selected keys in their selection order
-                        for &selected_key in &selected_keys {
-                            if let Some(pos) =
-                                remaining_children.iter().position(|&c| c == selected_key)
-                            {
-                                remaining_children.remove(pos);
-                                new_children.push(selected_key);
-                            }
-                        }
-
-                        // Then, add any remaining children that weren't in the selection
-                        new_children.extend(remaining_children);
-
-                        // Update the parent's children
-                        parent.children = new_children;
+            let mut next: Option<NodeKey> = None;
+            for &key in self.keys.iter().rev() {
+                let Some(parent_key) = arena.nodes.get(key).and_then(|n| n.parent) else {
+                    continue;
+                };
+                if let Some(next_key) = next
+                    && let Some(parent) = arena.nodes.get_mut(parent_key)
+                {
+                    let pos = parent.children.iter().position(|&c| c == key);
+                    let next_pos = parent.children.iter().position(|&c| c == next_key);
+                    if let (Some(pos), Some(next_pos)) = (pos, next_pos)
+                        && pos > next_pos
+                    {
+                        parent.children.remove(pos);
+                        parent.children.insert(next_pos, key);
                     }
                 }
+                next = Some(key);
             }
         }
         self
@@ -1071,6 +1470,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     /// Returns the enter selection from the last data join
@@ -1082,6 +1482,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     /// Returns the exit selection from the last data join
@@ -1093,6 +1494,7 @@ impl Selection {
             enter_keys: None,
             update_keys: None,
             exit_keys: None,
+            groups: Vec::new(),
         }
     }
     /// Clone selection and nodes (D3.js parity)
@@ -1110,6 +1512,7 @@ impl Selection {
             enter_keys: self.enter_keys.clone(),
             update_keys: self.update_keys.clone(),
             exit_keys: self.exit_keys.clone(),
+            groups: self.groups.clone(),
         }
     }
 
@@ -1172,6 +1575,7 @@ impl Selection {
             enter_keys: self.enter_keys.clone(),
             update_keys: self.update_keys.clone(),
             exit_keys: self.exit_keys.clone(),
+            groups: self.groups.clone(),
         }
     }
     /// Deep clone: clones all nodes and structure
@@ -1198,6 +1602,7 @@ impl Selection {
             enter_keys: self.enter_keys.clone(),
             update_keys: self.update_keys.clone(),
             exit_keys: self.exit_keys.clone(),
+            groups: Vec::new(),
         }
     }
     fn clone_node_recursive(
@@ -1219,60 +1624,20 @@ impl Selection {
         }
     }
 
-    /// Find first matching descendant by tag or class (D3 .find)
+    /// Find the first node, in document order, matching a CSS selector among
+    /// the selected nodes and their descendants
     pub fn find(&self, selector: &str) -> Option<Node> {
-        let parsed_selector = parse_selector(selector);
-        let arena = self.arena.borrow();
-        for &key in &self.keys {
-            let mut stack = vec![key];
-            while let Some(k) = stack.pop() {
-                let node = &arena.nodes[k];
-                let node_id = node.attributes.get("id").map(|s| s.as_str());
-                let node_classes: Vec<String> =
-                    node.attributes.get("class").map_or_else(Vec::new, |cls| {
-                        cls.split_whitespace().map(|s| s.to_string()).collect()
-                    });
-
-                if parsed_selector.matches(&node.tag, node_id, &node_classes) {
-                    return Some(node.clone());
-                }
-                stack.extend(&node.children);
-            }
-        }
-        None
+        self.clone_shallow()
+            .select_by(selector)
+            .keys
+            .first()
+            .map(|&k| self.arena.borrow().nodes[k].clone())
     }
 
     /// Documentation for all selection methods (D3 parity)
     // select, select_all, filter, data, datum, append, insert, remove, attr, style, property, classed, text, html, on, each, call, merge, order, raise, lower, node, nodes, size, empty, parent, children, clone, deep_clone, find, find_all, select_by, sort_by, map, select_child, select_parent, transition, interrupt, dispatch, enter, update, exit, join
     pub fn render_node(arena: &Rc<RefCell<Arena>>, key: NodeKey) -> String {
-        let arena_borrow = arena.borrow();
-        if !arena_borrow.nodes.contains_key(key) {
-            return String::new(); // Node was removed, skip rendering
-        }
-        let node = &arena_borrow.nodes[key];
-        let mut s = String::new();
-        s.push('<');
-        s.push_str(&node.tag);
-        for (k, v) in &node.attributes {
-            s.push(' ');
-            s.push_str(k);
-            s.push_str("=\"");
-            s.push_str(v);
-            s.push('"');
-        }
-        if node.children.is_empty() && node.text.is_none() {
-            s.push_str("/>");
-            return s;
-        }
-        s.push('>');
-        if let Some(ref text) = node.text {
-            s.push_str(text);
-        }
-        for &child in &node.children {
-            s.push_str(&Self::render_node(&Rc::clone(arena), child));
-        }
-        s.push_str(&format!("</{}>", node.tag));
-        s
+        Serializer::new().serialize(&arena.borrow(), key)
     }
 
     /// Debug helper: print tag and class of each child node in the selection
@@ -1297,12 +1662,69 @@ impl Selection {
         self.debug_print_children(label, |_| true);
     }
     
-    /// Render the first node in the selection as an HTML string
+    /// Render the first node in the selection as a minified markup string
     pub fn render(&self) -> String {
-        if let Some(&key) = self.keys.first() {
-            Self::render_node(&self.arena, key)
-        } else {
-            String::new()
+        self.render_with(&Serializer::new())
+    }
+
+    /// Render the first node in the selection with a configured [`Serializer`]
+    pub fn render_with(&self, serializer: &Serializer) -> String {
+        match self.keys.first() {
+            Some(&key) => serializer.serialize(&self.arena.borrow(), key),
+            None => String::new(),
+        }
+    }
+
+    /// Rasterize the first node into a `width * scale` by `height * scale` pixmap
+    ///
+    /// Uses default [`RasterOptions`]: no fonts and a transparent background.
+    pub fn render_pixmap(
+        &self,
+        width: u32,
+        height: u32,
+        scale: f32,
+    ) -> Result<Pixmap, RasterError> {
+        self.render_pixmap_with(width, height, scale, &RasterOptions::new())
+    }
+
+    /// Rasterize the first node with configured fonts and background
+    pub fn render_pixmap_with(
+        &self,
+        width: u32,
+        height: u32,
+        scale: f32,
+        options: &RasterOptions,
+    ) -> Result<Pixmap, RasterError> {
+        let svg = self.render_for_raster()?;
+        rasterize(&svg, width, height, scale, options)
+    }
+
+    /// Rasterize the first node and encode it as PNG
+    pub fn render_png(
+        &self,
+        width: u32,
+        height: u32,
+        scale: f32,
+    ) -> Result<Vec<u8>, RasterError> {
+        self.render_png_with(width, height, scale, &RasterOptions::new())
+    }
+
+    /// Rasterize the first node with configured fonts and background, encoded as PNG
+    pub fn render_png_with(
+        &self,
+        width: u32,
+        height: u32,
+        scale: f32,
+        options: &RasterOptions,
+    ) -> Result<Vec<u8>, RasterError> {
+        let svg = self.render_for_raster()?;
+        rasterize_png(&svg, width, height, scale, options)
+    }
+
+    fn render_for_raster(&self) -> Result<String, RasterError> {
+        if self.keys.is_empty() {
+            return Err(RasterError::EmptySelection);
         }
+        Ok(self.render_with(&Serializer::new().namespaces(true)))
     }
 }
@@ -0,0 +1,259 @@
+//! XML/SVG serialization for selection arenas
+//!
+//! Attribute values and text are escaped, attributes are written in a stable
+//! order (namespace declarations first, then by name), and `<style>` content is
+//! wrapped in CDATA.
+
+use crate::selection::arena::{Arena, NodeKey};
+use std::fmt::Write;
+
+pub const SVG_NS: &str = "http://www.w3.org/2000/svg";
+pub const XHTML_NS: &str = "http://www.w3.org/1999/xhtml";
+pub const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+pub const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+pub const XMLNS_NS: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Namespace URI for a well-known prefix (D3 `namespaces`)
+pub fn namespace(prefix: &str) -> Option<&'static str> {
+    match prefix {
+        "svg" => Some(SVG_NS),
+        "xhtml" => Some(XHTML_NS),
+        "xlink" => Some(XLINK_NS),
+        "xml" => Some(XML_NS),
+        "xmlns" => Some(XMLNS_NS),
+        _ => None,
+    }
+}
+
+/// Escape text content (`&`, `<`, `>`)
+pub fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escape a double-quoted attribute value
+pub fn escape_attr(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Configurable serializer for a node tree
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_d3::selection::{Selection, Serializer};
+/// let mut svg = Selection::create("svg");
+/// svg.append("text").attr("x", "10").text("a < b & c");
+///
+/// assert_eq!(svg.render(), "<svg><text x=\"10\">a &lt; b &amp; c</text></svg>");
+///
+/// let pretty = Serializer::new().pretty(2).namespaces(true);
+/// assert_eq!(
+///     svg.render_with(&pretty),
+///     "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <text x=\"10\">a &lt; b &amp; c</text>\n</svg>\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Serializer {
+    xml_declaration: bool,
+    indent: Option<usize>,
+    namespaces: bool,
+}
+
+impl Serializer {
+    /// Minified output, no XML declaration, tags and attributes written as stored
+    pub fn new() -> Self {
+        Serializer::default()
+    }
+    /// Prefix the output with `<?xml version="1.0" encoding="UTF-8"?>`
+    pub fn xml_declaration(mut self, on: bool) -> Self {
+        self.xml_declaration = on;
+        self
+    }
+    /// Pretty-print with `indent` spaces per level
+    pub fn pretty(mut self, indent: usize) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+    /// Single-line output without whitespace between elements (the default)
+    pub fn minified(mut self) -> Self {
+        self.indent = None;
+        self
+    }
+    /// Resolve `svg:`/`xhtml:` tag prefixes and declare `xmlns`/`xmlns:xlink` where needed
+    pub fn namespaces(mut self, on: bool) -> Self {
+        self.namespaces = on;
+        self
+    }
+
+    /// Serialize the subtree rooted at `key`
+    pub fn serialize(&self, arena: &Arena, key: NodeKey) -> String {
+        let mut out = String::new();
+        if self.xml_declaration {
+            out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+            if self.indent.is_some() {
+                out.push('\n');
+            }
+        }
+        let uses_xlink = self.namespaces && subtree_uses_prefix(arena, key, "xlink:");
+        self.write_node(arena, key, 0, None, uses_xlink, &mut out);
+        out
+    }
+
+    fn write_node(
+        &self,
+        arena: &Arena,
+        key: NodeKey,
+        depth: usize,
+        parent_ns: Option<&'static str>,
+        declare_xlink: bool,
+        out: &mut String,
+    ) {
+        let Some(node) = arena.nodes.get(key) else {
+            return; // Node was removed, skip rendering
+        };
+        if node.tag.is_empty() {
+            return; // Enter placeholders are not part of the document
+        }
+        let (name, ns) = if self.namespaces {
+            resolve_tag(&node.tag, parent_ns)
+        } else {
+            (node.tag.as_str(), parent_ns)
+        };
+
+        let mut attrs: Vec<(&str, &str)> = node
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        attrs.sort_by(|a, b| attr_rank(a.0).cmp(&attr_rank(b.0)).then(a.0.cmp(b.0)));
+
+        let pad = self.indent.map(|n| " ".repeat(n * depth));
+        if let Some(pad) = &pad {
+            out.push_str(pad);
+        }
+        out.push('<');
+        out.push_str(name);
+        if self.namespaces {
+            if let Some(ns) = ns
+                && ns != parent_ns.unwrap_or("")
+                && !node.attributes.contains_key("xmlns")
+            {
+                let _ = write!(out, " xmlns=\"{}\"", ns);
+            }
+            if declare_xlink && !node.attributes.contains_key("xmlns:xlink") {
+                let _ = write!(out, " xmlns:xlink=\"{}\"", XLINK_NS);
+            }
+        }
+        for (k, v) in attrs {
+            let _ = write!(out, " {}=\"{}\"", k, escape_attr(v));
+        }
+
+        let children: Vec<NodeKey> = node
+            .children
+            .iter()
+            .copied()
+            .filter(|&c| arena.nodes.get(c).is_some_and(|n| !n.tag.is_empty()))
+            .collect();
+        if children.is_empty() && node.text.is_none() {
+            out.push_str("/>");
+            if self.indent.is_some() {
+                out.push('\n');
+            }
+            return;
+        }
+        out.push('>');
+        if let Some(ref text) = node.text {
+            if name == "style" {
+                out.push_str("<![CDATA[");
+                out.push_str(&text.replace("]]>", "]]]]><![CDATA[>"));
+                out.push_str("]]>");
+            } else {
+                out.push_str(&escape_text(text));
+            }
+        }
+        // Mixed content is written inline so whitespace never leaks into text
+        let inline = node.text.is_some();
+        let child_serializer = if inline {
+            self.clone().minified()
+        } else {
+            self.clone()
+        };
+        if !children.is_empty() && !inline && self.indent.is_some() {
+            out.push('\n');
+        }
+        for child in children.iter().copied() {
+            child_serializer.write_node(arena, child, depth + 1, ns, false, out);
+        }
+        if !children.is_empty()
+            && !inline
+            && let Some(pad) = &pad
+        {
+            out.push_str(pad);
+        }
+        let _ = write!(out, "</{}>", name);
+        if self.indent.is_some() {
+            out.push('\n');
+        }
+    }
+}
+
+/// Split a `prefix:local` tag, returning the local name and its namespace
+fn resolve_tag<'a>(
+    tag: &'a str,
+    parent_ns: Option<&'static str>,
+) -> (&'a str, Option<&'static str>) {
+    if let Some((prefix, local)) = tag.split_once(':')
+        && let Some(ns) = namespace(prefix)
+    {
+        return (local, Some(ns));
+    }
+    match tag {
+        "svg" => (tag, Some(SVG_NS)),
+        _ => (tag, parent_ns),
+    }
+}
+
+fn attr_rank(name: &str) -> u8 {
+    if name == "xmlns" {
+        0
+    } else if name.starts_with("xmlns:") {
+        1
+    } else {
+        2
+    }
+}
+
+fn subtree_uses_prefix(arena: &Arena, key: NodeKey, prefix: &str) -> bool {
+    let mut stack = vec![key];
+    while let Some(k) = stack.pop() {
+        if let Some(node) = arena.nodes.get(k) {
+            if node.attributes.keys().any(|a| a.starts_with(prefix)) {
+                return true;
+            }
+            stack.extend(&node.children);
+        }
+    }
+    false
+}
@@ -2,24 +2,134 @@ use crate::selection::arena::{Arena, NodeKey};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-#[derive(Debug, Clone, PartialEq)]
+/// A compound selector: an optional tag plus `#id`, `.class`, `[attr]` and
+/// pseudo-class conditions that must all hold for a single element
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Selector {
     pub tag: Option<String>,
     pub id: Option<String>,
     pub classes: Vec<String>,
     pub is_wildcard: bool,
+    pub attributes: Vec<AttrSelector>,
+    pub pseudos: Vec<PseudoClass>,
+}
+
+/// Attribute selector operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrOp {
+    /// `[name]`
+    Exists,
+    /// `[name=value]`
+    Equals,
+    /// `[name~=value]`, whitespace-separated word
+    Includes,
+    /// `[name|=value]`, exact or followed by `-`
+    DashMatch,
+    /// `[name^=value]`
+    Prefix,
+    /// `[name$=value]`
+    Suffix,
+    /// `[name*=value]`
+    Substring,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttrSelector {
+    pub name: String,
+    pub op: AttrOp,
+    pub value: String,
+}
+
+impl AttrSelector {
+    fn matches(&self, actual: Option<&str>) -> bool {
+        let Some(actual) = actual else {
+            return false;
+        };
+        let v = self.value.as_str();
+        match self.op {
+            AttrOp::Exists => true,
+            AttrOp::Equals => actual == v,
+            AttrOp::Includes => !v.is_empty() && actual.split_whitespace().any(|w| w == v),
+            AttrOp::DashMatch => actual == v || actual.starts_with(&format!("{}-", v)),
+            AttrOp::Prefix => !v.is_empty() && actual.starts_with(v),
+            AttrOp::Suffix => !v.is_empty() && actual.ends_with(v),
+            AttrOp::Substring => !v.is_empty() && actual.contains(v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PseudoClass {
+    FirstChild,
+    LastChild,
+    /// `:nth-child(an+b)`, stored as `(a, b)`
+    NthChild(i32, i32),
+    Not(SelectorList),
+}
+
+/// Relationship between two compounds of a [`ComplexSelector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+}
+
+/// Compounds joined by combinators, e.g. `.axis > .tick line`
+///
+/// `combinators[i]` joins `compounds[i]` and `compounds[i + 1]`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComplexSelector {
+    pub compounds: Vec<Selector>,
+    pub combinators: Vec<Combinator>,
+}
+
+/// Comma-separated selector list; an element matches if any selector does
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SelectorList {
+    pub selectors: Vec<ComplexSelector>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectorError {
+    /// The selector (or one item of a list) is empty
+    Empty,
+    /// A character that cannot appear at this position
+    UnexpectedChar { ch: char, pos: usize },
+    /// Input ended inside `[...]`, `(...)` or a quoted string
+    UnexpectedEnd,
+    /// A pseudo-class other than `:first-child`, `:last-child`, `:nth-child()` or `:not()`
+    UnsupportedPseudo(String),
+    /// An `:nth-child()` argument that is not `odd`, `even` or `an+b`
+    InvalidNth(String),
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorError::Empty => write!(f, "empty selector"),
+            SelectorError::UnexpectedChar { ch, pos } => {
+                write!(f, "unexpected '{}' at {}", ch, pos)
+            }
+            SelectorError::UnexpectedEnd => write!(f, "unexpected end of selector"),
+            SelectorError::UnsupportedPseudo(name) => {
+                write!(f, "unsupported pseudo-class :{}", name)
+            }
+            SelectorError::InvalidNth(arg) => write!(f, "invalid :nth-child argument '{}'", arg),
+        }
+    }
 }
 
+impl std::error::Error for SelectorError {}
+
 impl Selector {
     pub fn new() -> Self {
-        Selector {
-            tag: None,
-            id: None,
-            classes: Vec::new(),
-            is_wildcard: false,
-        }
+        Selector::default()
     }
 
+    /// Match on tag, id and classes only; attribute and pseudo-class
+    /// conditions need the tree, see [`Selector::matches_node`]
     pub fn matches(&self, node_tag: &str, node_id: Option<&str>, node_classes: &[String]) -> bool {
         // Wildcard matches everything
         if self.is_wildcard {
@@ -49,71 +159,411 @@ impl Selector {
 
         true
     }
+
+    /// Match every condition against the node at `key`
+    pub fn matches_node(&self, arena: &Arena, key: NodeKey) -> bool {
+        let Some(node) = arena.nodes.get(key) else {
+            return false;
+        };
+        if node.tag.is_empty() {
+            return false; // Enter placeholders are never matched
+        }
+        let id = node.attributes.get("id").map(|s| s.as_str());
+        let classes: Vec<String> = node.attributes.get("class").map_or_else(Vec::new, |cls| {
+            cls.split_whitespace().map(|s| s.to_string()).collect()
+        });
+        if !self.matches(&node.tag, id, &classes) {
+            return false;
+        }
+        if !self
+            .attributes
+            .iter()
+            .all(|a| a.matches(node.attributes.get(&a.name).map(|s| s.as_str())))
+        {
+            return false;
+        }
+        self.pseudos.iter().all(|p| match p {
+            PseudoClass::FirstChild => sibling_position(arena, key).is_some_and(|(n, _)| n == 1),
+            PseudoClass::LastChild => {
+                sibling_position(arena, key).is_some_and(|(_, after)| after == 0)
+            }
+            PseudoClass::NthChild(a, b) => {
+                sibling_position(arena, key).is_some_and(|(n, _)| nth_matches(*a, *b, n as i32))
+            }
+            PseudoClass::Not(list) => !list.matches(arena, key),
+        })
+    }
 }
 
-/// Parse a CSS selector string into a Selector struct
-/// Supports:
-/// - Tag selectors: "div", "rect", "g"
-/// - Class selectors: ".class", "div.class", "rect.axis.major"
-/// - ID selectors: "#id", "div#id", "rect#myid.class"
-/// - Wildcard: "*"
-/// - Compound selectors: "rect.axis.major#chart"
-pub fn parse_selector(selector: &str) -> Selector {
-    let mut result = Selector::new();
+impl ComplexSelector {
+    /// Match right to left; ancestors may lie outside the searched subtree,
+    /// as with `querySelectorAll`
+    pub fn matches(&self, arena: &Arena, key: NodeKey) -> bool {
+        !self.compounds.is_empty() && self.matches_at(arena, key, self.compounds.len() - 1)
+    }
+
+    fn matches_at(&self, arena: &Arena, key: NodeKey, i: usize) -> bool {
+        if !self.compounds[i].matches_node(arena, key) {
+            return false;
+        }
+        if i == 0 {
+            return true;
+        }
+        let mut parent = arena.nodes.get(key).and_then(|n| n.parent);
+        match self.combinators[i - 1] {
+            Combinator::Child => parent.is_some_and(|p| self.matches_at(arena, p, i - 1)),
+            Combinator::Descendant => {
+                while let Some(p) = parent {
+                    if self.matches_at(arena, p, i - 1) {
+                        return true;
+                    }
+                    parent = arena.nodes.get(p).and_then(|n| n.parent);
+                }
+                false
+            }
+        }
+    }
+}
 
-    // Handle wildcard
-    if selector.trim() == "*" {
-        result.is_wildcard = true;
-        return result;
+impl SelectorList {
+    pub fn matches(&self, arena: &Arena, key: NodeKey) -> bool {
+        self.selectors.iter().any(|s| s.matches(arena, key))
     }
+}
 
-    let mut remaining = selector;
+/// 1-based position among element siblings, and the number of element
+/// siblings after it; `None` for the root
+fn sibling_position(arena: &Arena, key: NodeKey) -> Option<(usize, usize)> {
+    let parent = arena.nodes.get(key)?.parent?;
+    let siblings: Vec<NodeKey> = arena
+        .nodes
+        .get(parent)?
+        .children
+        .iter()
+        .copied()
+        .filter(|&c| arena.nodes.get(c).is_some_and(|n| !n.tag.is_empty()))
+        .collect();
+    let index = siblings.iter().position(|&c| c == key)?;
+    Some((index + 1, siblings.len() - index - 1))
+}
 
-    // Extract ID if present
-    if let Some(id_pos) = remaining.find('#') {
-        let (before_id, after_id) = remaining.split_at(id_pos);
-        remaining = before_id;
+fn nth_matches(a: i32, b: i32, n: i32) -> bool {
+    if a == 0 {
+        return n == b;
+    }
+    let diff = n - b;
+    diff % a == 0 && diff / a >= 0
+}
 
-        // Parse ID - everything after # until next . or end
-        let id_part = &after_id[1..]; // skip the #
-        if let Some(dot_pos) = id_part.find('.') {
-            let (id, classes_part) = id_part.split_at(dot_pos);
-            result.id = Some(id.to_string());
-            // Add classes after ID
-            for class in classes_part[1..].split('.') {
-                if !class.is_empty() {
-                    result.classes.push(class.to_string());
-                }
+/// Parse a selector list such as `g > text, .axis .tick line, [data-id="3"]`
+///
+/// Supports tag, `*`, `#id`, `.class`, attribute selectors (`[a]`, `[a=v]`,
+/// `~=`, `|=`, `^=`, `$=`, `*=`), `:first-child`, `:last-child`,
+/// `:nth-child(an+b | odd | even)`, `:not(list)`, the descendant and child
+/// combinators, and comma-separated lists.
+pub fn parse_selector_list(selector: &str) -> Result<SelectorList, SelectorError> {
+    let mut parser = SelectorParser {
+        chars: selector.char_indices().collect(),
+        i: 0,
+    };
+    let list = parser.list()?;
+    match parser.peek() {
+        None => Ok(list),
+        Some(ch) => Err(SelectorError::UnexpectedChar {
+            ch,
+            pos: parser.pos(),
+        }),
+    }
+}
+
+struct SelectorParser {
+    chars: Vec<(usize, char)>,
+    i: usize,
+}
+
+impl SelectorParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.i).map(|&(_, c)| c)
+    }
+
+    fn pos(&self) -> usize {
+        self.chars.get(self.i).map_or(usize::MAX, |&(p, _)| p)
+    }
+
+    fn unexpected(&self) -> SelectorError {
+        match self.peek() {
+            Some(ch) => SelectorError::UnexpectedChar {
+                ch,
+                pos: self.pos(),
+            },
+            None => SelectorError::UnexpectedEnd,
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.i;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.i += 1;
+        }
+        self.i > start
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let mut out = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                out.push(c);
+                self.i += 1;
+            } else if c == '\\' {
+                // CSS escape of the next character, e.g. `.a\:b`
+                self.i += 1;
+                out.push(self.peek()?);
+                self.i += 1;
+            } else {
+                break;
             }
+        }
+        (!out.is_empty()).then_some(out)
+    }
+
+    /// Parse a comma-separated list, stopping at `)` or the end of input
+    fn list(&mut self) -> Result<SelectorList, SelectorError> {
+        let mut selectors = vec![self.complex()?];
+        while self.peek() == Some(',') {
+            self.i += 1;
+            selectors.push(self.complex()?);
+        }
+        Ok(SelectorList { selectors })
+    }
+
+    fn complex(&mut self) -> Result<ComplexSelector, SelectorError> {
+        self.skip_whitespace();
+        let mut complex = ComplexSelector::default();
+        complex.compounds.push(self.compound()?);
+        loop {
+            let spaced = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => {
+                    self.i += 1;
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                None | Some(',') | Some(')') => break,
+                Some(_) if spaced => Combinator::Descendant,
+                Some(_) => return Err(self.unexpected()),
+            };
+            complex.combinators.push(combinator);
+            complex.compounds.push(self.compound()?);
+        }
+        Ok(complex)
+    }
+
+    fn compound(&mut self) -> Result<Selector, SelectorError> {
+        let mut sel = Selector::new();
+        let start = self.i;
+        if self.peek() == Some('*') {
+            self.i += 1;
         } else {
-            result.id = Some(id_part.to_string());
+            sel.tag = self.ident();
         }
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.i += 1;
+                    sel.id = Some(self.ident().ok_or_else(|| self.unexpected())?);
+                }
+                Some('.') => {
+                    self.i += 1;
+                    let class = self.ident().ok_or_else(|| self.unexpected())?;
+                    sel.classes.push(class);
+                }
+                Some('[') => {
+                    self.i += 1;
+                    let attr = self.attribute()?;
+                    sel.attributes.push(attr);
+                }
+                Some(':') => {
+                    self.i += 1;
+                    let pseudo = self.pseudo()?;
+                    sel.pseudos.push(pseudo);
+                }
+                _ => break,
+            }
+        }
+        if self.i == start {
+            return Err(match self.peek() {
+                None | Some(',') | Some(')') => SelectorError::Empty,
+                Some(_) => self.unexpected(),
+            });
+        }
+        sel.is_wildcard = sel.tag.is_none()
+            && sel.id.is_none()
+            && sel.classes.is_empty()
+            && sel.attributes.is_empty()
+            && sel.pseudos.is_empty();
+        Ok(sel)
     }
 
-    // Parse tag and classes from remaining part
-    let parts: Vec<&str> = remaining.split('.').collect();
+    fn attribute(&mut self) -> Result<AttrSelector, SelectorError> {
+        self.skip_whitespace();
+        let name = self.ident().ok_or_else(|| self.unexpected())?;
+        self.skip_whitespace();
+        let op = match self.peek() {
+            Some(']') => {
+                self.i += 1;
+                return Ok(AttrSelector {
+                    name,
+                    op: AttrOp::Exists,
+                    value: String::new(),
+                });
+            }
+            Some('=') => AttrOp::Equals,
+            Some('~') => AttrOp::Includes,
+            Some('|') => AttrOp::DashMatch,
+            Some('^') => AttrOp::Prefix,
+            Some('$') => AttrOp::Suffix,
+            Some('*') => AttrOp::Substring,
+            _ => return Err(self.unexpected()),
+        };
+        self.i += 1;
+        if op != AttrOp::Equals {
+            if self.peek() != Some('=') {
+                return Err(self.unexpected());
+            }
+            self.i += 1;
+        }
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(q @ ('"' | '\'')) => {
+                self.i += 1;
+                let mut value = String::new();
+                loop {
+                    match self.peek() {
+                        None => return Err(SelectorError::UnexpectedEnd),
+                        Some(c) if c == q => break,
+                        Some('\\') => {
+                            self.i += 1;
+                            value.push(self.peek().ok_or(SelectorError::UnexpectedEnd)?);
+                        }
+                        Some(c) => value.push(c),
+                    }
+                    self.i += 1;
+                }
+                self.i += 1;
+                value
+            }
+            _ => self.ident().ok_or_else(|| self.unexpected())?,
+        };
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            return Err(self.unexpected());
+        }
+        self.i += 1;
+        Ok(AttrSelector { name, op, value })
+    }
 
-    if !parts.is_empty() {
-        let first_part = parts[0].trim();
-        if !first_part.is_empty() {
-            result.tag = Some(first_part.to_string());
+    fn pseudo(&mut self) -> Result<PseudoClass, SelectorError> {
+        let name = self.ident().ok_or_else(|| self.unexpected())?;
+        match name.to_ascii_lowercase().as_str() {
+            "first-child" => Ok(PseudoClass::FirstChild),
+            "last-child" => Ok(PseudoClass::LastChild),
+            "nth-child" => {
+                let arg = self.parenthesized()?;
+                let (a, b) = parse_nth(&arg).ok_or(SelectorError::InvalidNth(arg))?;
+                Ok(PseudoClass::NthChild(a, b))
+            }
+            "not" => {
+                if self.peek() != Some('(') {
+                    return Err(self.unexpected());
+                }
+                self.i += 1;
+                let list = self.list()?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    return Err(self.unexpected());
+                }
+                self.i += 1;
+                Ok(PseudoClass::Not(list))
+            }
+            _ => Err(SelectorError::UnsupportedPseudo(name)),
         }
+    }
 
-        // Collect all classes
-        for part in &parts[1..] {
-            if !part.is_empty() {
-                result.classes.push(part.to_string());
+    fn parenthesized(&mut self) -> Result<String, SelectorError> {
+        if self.peek() != Some('(') {
+            return Err(self.unexpected());
+        }
+        self.i += 1;
+        let mut arg = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(SelectorError::UnexpectedEnd),
+                Some(')') => break,
+                Some(c) => arg.push(c),
             }
+            self.i += 1;
         }
+        self.i += 1;
+        Ok(arg)
     }
+}
+
+/// Parse an `:nth-child` argument into `(a, b)` for `an+b`
+fn parse_nth(arg: &str) -> Option<(i32, i32)> {
+    let arg: String = arg.chars().filter(|c| !c.is_whitespace()).collect();
+    let arg = arg.to_ascii_lowercase();
+    match arg.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => {}
+    }
+    let Some((a, b)) = arg.split_once('n') else {
+        return arg.parse().ok().map(|b| (0, b));
+    };
+    let a = match a {
+        "" | "+" => 1,
+        "-" => -1,
+        _ => a.parse().ok()?,
+    };
+    let b = match b {
+        "" => 0,
+        _ if b.starts_with('+') || b.starts_with('-') => b.parse().ok()?,
+        _ => return None,
+    };
+    Some((a, b))
+}
+
+/// Parse a single compound selector such as `rect#chart.axis.major` or `*`
+///
+/// Input that is not a single compound selector, including combinators and
+/// comma-separated lists, is an error; use [`parse_selector_list`] for those.
+pub fn parse_selector(selector: &str) -> Result<Selector, SelectorError> {
+    let mut parser = SelectorParser {
+        chars: selector.trim().char_indices().collect(),
+        i: 0,
+    };
+    let sel = parser.compound()?;
+    match parser.peek() {
+        None => Ok(sel),
+        Some(ch) => Err(SelectorError::UnexpectedChar {
+            ch,
+            pos: parser.pos(),
+        }),
+    }
+}
 
-    result
+/// Whether the node at `key` matches a selector string; invalid selectors
+/// match nothing
+pub fn matches_selector(arena: &Arena, key: NodeKey, selector: &str) -> bool {
+    parse_selector_list(selector).is_ok_and(|list| list.matches(arena, key))
 }
 
 /// Legacy compatibility function - returns (tag, classes) tuple
-pub fn parse_selector_legacy(selector: &str) -> (Option<String>, Vec<String>) {
-    let parsed = parse_selector(selector);
-    (parsed.tag, parsed.classes)
+pub fn parse_selector_legacy(
+    selector: &str,
+) -> Result<(Option<String>, Vec<String>), SelectorError> {
+    let parsed = parse_selector(selector)?;
+    Ok((parsed.tag, parsed.classes))
 }
 
 pub fn remove_node_recursively(arena: &mut Arena, key: NodeKey) {
@@ -162,9 +612,8 @@ pub fn find_matching_descendants_legacy(
 ) {
     let selector = Selector {
         tag: tag.clone(),
-        id: None,
         classes: classes.clone(),
-        is_wildcard: false,
+        ..Selector::new()
     };
     find_matching_descendants(arena, keys, &selector, found);
 }
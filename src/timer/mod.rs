@@ -28,6 +28,76 @@ pub fn now() -> Instant {
     Instant::now()
 }
 
+/// A source of elapsed time in milliseconds, used to drive transitions and simulations.
+pub trait Clock {
+    fn now(&self) -> f64;
+}
+
+/// Deterministic clock that only moves when stepped by hand.
+///
+/// Clones share the same time, so one handle can drive every transition created from it.
+///
+/// # Example
+/// ```rust
+/// use rust_d3::timer::{Clock, ManualClock};
+/// let clock = ManualClock::new();
+/// let handle = clock.clone();
+/// clock.advance(250.0);
+/// assert_eq!(handle.now(), 250.0);
+/// clock.set(1000.0);
+/// assert_eq!(handle.now(), 1000.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    elapsed: Arc<Mutex<f64>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock::default()
+    }
+    /// Move the clock forward by `ms` milliseconds
+    pub fn advance(&self, ms: f64) {
+        *self.elapsed.lock().unwrap() += ms;
+    }
+    /// Jump to an absolute time in milliseconds
+    pub fn set(&self, ms: f64) {
+        *self.elapsed.lock().unwrap() = ms;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+/// Wall clock measuring milliseconds since it was created
+#[derive(Clone)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> f64 {
+        self.origin.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
 pub fn flush() {
     let timers: Vec<Arc<Timer>> = GLOBAL_TIMERS.lock().unwrap().values().cloned().collect();
     for timer in timers {
@@ -0,0 +1,100 @@
+//! Decoding topology objects into GeoJSON features
+
+use super::{TopoGeometry, TopoGeometryValue, Topology, arc_index};
+use crate::geojson::{Feature, GeoJson, Geometry, GeometryValue, Position};
+
+/// Decode `object` into GeoJSON: a geometry collection becomes a
+/// [`FeatureCollection`](crate::geojson::FeatureCollection) with one feature per geometry, anything else a
+/// single [`Feature`]
+pub fn feature(topology: &Topology, object: &TopoGeometry) -> GeoJson {
+    let decoder = Decoder::new(topology);
+    match &object.value {
+        TopoGeometryValue::GeometryCollection(geometries) => {
+            GeoJson::FeatureCollection(geometries.iter().map(|g| decoder.feature(g)).collect())
+        }
+        _ => GeoJson::Feature(decoder.feature(object)),
+    }
+}
+
+/// A topology with its arcs decoded to absolute positions
+pub(super) struct Decoder<'a> {
+    topology: &'a Topology,
+    arcs: Vec<Vec<Position>>,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(topology: &'a Topology) -> Self {
+        Self {
+            topology,
+            arcs: topology.decode_arcs(),
+        }
+    }
+
+    fn feature(&self, object: &TopoGeometry) -> Feature {
+        Feature {
+            id: object.id.clone(),
+            geometry: self.geometry(object),
+            // Like topojson-client, features always have properties
+            properties: Some(object.properties.clone().unwrap_or_default()),
+            bbox: object.bbox.clone(),
+            ..Feature::default()
+        }
+    }
+
+    fn geometry(&self, object: &TopoGeometry) -> Option<Geometry> {
+        let value = match &object.value {
+            TopoGeometryValue::Null => return None,
+            TopoGeometryValue::Point(p) => GeometryValue::Point(self.topology.transform_point(p)),
+            TopoGeometryValue::MultiPoint(points) => GeometryValue::MultiPoint(
+                points
+                    .iter()
+                    .map(|p| self.topology.transform_point(p))
+                    .collect(),
+            ),
+            TopoGeometryValue::LineString(arcs) => GeometryValue::LineString(self.line(arcs)),
+            TopoGeometryValue::MultiLineString(lines) => {
+                GeometryValue::MultiLineString(lines.iter().map(|l| self.line(l)).collect())
+            }
+            TopoGeometryValue::Polygon(rings) => GeometryValue::Polygon(self.polygon(rings)),
+            TopoGeometryValue::MultiPolygon(polygons) => {
+                GeometryValue::MultiPolygon(polygons.iter().map(|p| self.polygon(p)).collect())
+            }
+            TopoGeometryValue::GeometryCollection(geometries) => GeometryValue::GeometryCollection(
+                geometries.iter().filter_map(|g| self.geometry(g)).collect(),
+            ),
+        };
+        Some(Geometry::new(value))
+    }
+
+    /// Concatenate arcs, dropping the shared point where consecutive arcs meet
+    pub fn line(&self, arcs: &[i64]) -> Vec<Position> {
+        let mut points: Vec<Position> = Vec::new();
+        for &i in arcs {
+            points.pop();
+            let arc = &self.arcs[arc_index(i)];
+            let start = points.len();
+            points.extend(arc.iter().cloned());
+            if i < 0 {
+                points[start..].reverse();
+            }
+        }
+        // Degenerate per the specification, but keep the line valid
+        if points.len() == 1 {
+            points.push(points[0].clone());
+        }
+        points
+    }
+
+    pub fn ring(&self, arcs: &[i64]) -> Vec<Position> {
+        let mut points = self.line(arcs);
+        // An arc of only two points makes a ring too short to be closed
+        while !points.is_empty() && points.len() < 4 {
+            points.push(points[0].clone());
+        }
+        points
+    }
+
+    pub fn polygon(&self, rings: &[Vec<i64>]) -> Vec<Vec<Position>> {
+        rings.iter().map(|r| self.ring(r)).collect()
+    }
+}
@@ -0,0 +1,106 @@
+//! Merging polygons that share arcs
+
+use super::feature::Decoder;
+use super::stitch::stitch;
+use super::{TopoGeometry, TopoGeometryValue, Topology, arc_index};
+use crate::geojson::{Geometry, GeometryValue, Position};
+use std::collections::HashMap;
+
+/// The union of the polygons of `objects`, as a MultiPolygon
+///
+/// Polygons connected through shared arcs become one polygon whose rings are
+/// the arcs used only once; of several rings, the one with the largest area
+/// is the exterior. Useful for, e.g., drawing a region from its counties.
+pub fn merge(topology: &Topology, objects: &[&TopoGeometry]) -> Geometry {
+    let mut polygons: Vec<&[Vec<i64>]> = Vec::new();
+    fn extract<'a>(o: &'a TopoGeometry, polygons: &mut Vec<&'a [Vec<i64>]>) {
+        match &o.value {
+            TopoGeometryValue::GeometryCollection(geometries) => {
+                for g in geometries {
+                    extract(g, polygons);
+                }
+            }
+            TopoGeometryValue::Polygon(rings) => polygons.push(rings),
+            TopoGeometryValue::MultiPolygon(rings) => {
+                polygons.extend(rings.iter().map(Vec::as_slice))
+            }
+            _ => {}
+        }
+    }
+    for object in objects {
+        extract(object, &mut polygons);
+    }
+
+    let mut polygons_by_arc: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (p, polygon) in polygons.iter().enumerate() {
+        for &arc in polygon.iter().flatten() {
+            polygons_by_arc.entry(arc_index(arc)).or_default().push(p);
+        }
+    }
+
+    // Group polygons connected through shared arcs
+    let mut visited = vec![false; polygons.len()];
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for p in 0..polygons.len() {
+        if visited[p] {
+            continue;
+        }
+        visited[p] = true;
+        let mut group = Vec::new();
+        let mut neighbors = vec![p];
+        while let Some(q) = neighbors.pop() {
+            group.push(q);
+            for &arc in polygons[q].iter().flatten() {
+                for &r in &polygons_by_arc[&arc_index(arc)] {
+                    if !visited[r] {
+                        visited[r] = true;
+                        neighbors.push(r);
+                    }
+                }
+            }
+        }
+        groups.push(group);
+    }
+
+    let decoder = Decoder::new(topology);
+    let merged = groups
+        .iter()
+        .map(|group| {
+            // The exterior arcs, used by a single polygon
+            let arcs = group
+                .iter()
+                .flat_map(|&p| polygons[p].iter().flatten())
+                .filter(|&&arc| polygons_by_arc[&arc_index(arc)].len() < 2)
+                .copied()
+                .collect();
+            let mut rings = stitch(topology, arcs);
+            if rings.len() > 1 {
+                let mut k = ring_area(&decoder.ring(&rings[0]));
+                for i in 1..rings.len() {
+                    let ki = ring_area(&decoder.ring(&rings[i]));
+                    if ki > k {
+                        rings.swap(0, i);
+                        k = ki;
+                    }
+                }
+            }
+            rings
+        })
+        .filter(|rings| !rings.is_empty())
+        .map(|rings| decoder.polygon(&rings))
+        .collect();
+    Geometry::new(GeometryValue::MultiPolygon(merged))
+}
+
+/// Twice the absolute planar area of `ring`
+fn ring_area(ring: &[Position]) -> f64 {
+    let Some(mut b) = ring.last() else {
+        return 0.0;
+    };
+    let mut area = 0.0;
+    for a in ring {
+        area += b[0] * a[1] - b[1] * a[0];
+        b = a;
+    }
+    area.abs()
+}
@@ -0,0 +1,85 @@
+//! Meshes: arcs stitched into lines, each arc drawn once
+
+use super::feature::Decoder;
+use super::stitch::stitch;
+use super::{TopoGeometry, TopoGeometryValue, Topology, arc_index};
+use crate::geojson::{Geometry, GeometryValue};
+use std::collections::BTreeMap;
+
+/// A MultiLineString of the arcs of `object`, or of every arc of the
+/// topology for `None`
+///
+/// Arcs shared by several geometries appear once, so stroking the mesh of
+/// `countries` draws every border without doubling it.
+pub fn mesh(topology: &Topology, object: Option<&TopoGeometry>) -> Geometry {
+    let arcs = match object {
+        Some(object) => extract_arcs(object, None),
+        None => (0..topology.arcs.len() as i64).collect(),
+    };
+    mesh_geometry(topology, arcs)
+}
+
+/// Like [`mesh`], keeping only arcs for which `filter(a, b)` holds, where
+/// `a` and `b` are the first and last geometry using the arc
+///
+/// Exterior arcs are used by a single geometry, so `a` and `b` are the same
+/// object: `|a, b| !std::ptr::eq(a, b)` keeps interior borders only, and
+/// `|a, b| std::ptr::eq(a, b)` the exterior outline.
+pub fn mesh_with<F>(topology: &Topology, object: &TopoGeometry, filter: F) -> Geometry
+where
+    F: Fn(&TopoGeometry, &TopoGeometry) -> bool,
+{
+    let arcs = extract_arcs(object, Some(&filter));
+    mesh_geometry(topology, arcs)
+}
+
+fn mesh_geometry(topology: &Topology, arcs: Vec<i64>) -> Geometry {
+    let decoder = Decoder::new(topology);
+    let lines = stitch(topology, arcs)
+        .iter()
+        .map(|line| decoder.line(line))
+        .collect();
+    Geometry::new(GeometryValue::MultiLineString(lines))
+}
+
+/// The (possibly reversed) index and geometry of each use of an arc
+type UsesByArc<'a> = BTreeMap<usize, Vec<(i64, &'a TopoGeometry)>>;
+
+type ArcFilter<'f> = dyn Fn(&TopoGeometry, &TopoGeometry) -> bool + 'f;
+
+/// Each arc used by `object`, once, in arc order
+fn extract_arcs<'a>(
+    object: &'a TopoGeometry,
+    filter: Option<&ArcFilter>,
+) -> Vec<i64> {
+    let mut geometries_by_arc: UsesByArc<'a> = BTreeMap::new();
+    fn extract<'a>(o: &'a TopoGeometry, geometries_by_arc: &mut UsesByArc<'a>) {
+        let add = |i: i64| {
+            geometries_by_arc
+                .entry(arc_index(i))
+                .or_default()
+                .push((i, o));
+        };
+        match &o.value {
+            TopoGeometryValue::GeometryCollection(geometries) => {
+                for g in geometries {
+                    extract(g, geometries_by_arc);
+                }
+            }
+            TopoGeometryValue::LineString(arcs) => arcs.iter().copied().for_each(add),
+            TopoGeometryValue::MultiLineString(arcs) | TopoGeometryValue::Polygon(arcs) => {
+                arcs.iter().flatten().copied().for_each(add)
+            }
+            TopoGeometryValue::MultiPolygon(arcs) => {
+                arcs.iter().flatten().flatten().copied().for_each(add)
+            }
+            _ => {}
+        }
+    }
+    extract(object, &mut geometries_by_arc);
+    geometries_by_arc
+        .into_values()
+        .filter(|uses| filter.is_none_or(|filter| filter(uses[0].1, uses[uses.len() - 1].1)))
+        .map(|uses| uses[0].0)
+        .collect()
+}
@@ -0,0 +1,285 @@
+//! TopoJSON decoding (port of topojson-client)
+//!
+//! A [`Topology`] stores shared, optionally quantized and delta-encoded arcs;
+//! its geometry objects reference arcs by index (`~i` for arc `i` reversed).
+//! [`feature`] decodes objects into the crate's [`geojson`](crate::geojson)
+//! types, [`mesh`] and [`mesh_with`] stitch arcs into lines so shared borders
+//! are drawn once, and [`merge`] unions polygons that share arcs.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::topojson::{Topology, feature, mesh_with};
+//!
+//! let topology: Topology = r#"{
+//!     "type": "Topology",
+//!     "transform": {"scale": [1, 1], "translate": [0, 0]},
+//!     "objects": {"squares": {"type": "GeometryCollection", "geometries": [
+//!         {"type": "Polygon", "arcs": [[0, 1]], "id": "left"},
+//!         {"type": "Polygon", "arcs": [[-1, 2]], "id": "right"}
+//!     ]}},
+//!     "arcs": [
+//!         [[1, 0], [0, 1]],
+//!         [[1, 1], [-1, 0], [0, -1], [1, 0]],
+//!         [[1, 0], [1, 0], [0, 1], [-1, 0]]
+//!     ]
+//! }"#
+//! .parse()
+//! .unwrap();
+//!
+//! let squares = topology.object("squares").unwrap();
+//! assert_eq!(feature(&topology, squares).into_features().len(), 2);
+//!
+//! // The border between the squares, and nothing else
+//! let borders = mesh_with(&topology, squares, |a, b| !std::ptr::eq(a, b));
+//! println!("{}", rust_d3::geojson::GeoJson::from(borders));
+//! ```
+
+mod feature;
+mod merge;
+mod mesh;
+mod stitch;
+
+pub use feature::feature;
+pub use merge::merge;
+pub use mesh::{mesh, mesh_with};
+
+use crate::geojson::{Bbox, FeatureId, JsonObject, Position};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Quantization transform: `position = quantized * scale + translate`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Transform {
+    pub scale: [f64; 2],
+    pub translate: [f64; 2],
+}
+
+/// A TopoJSON topology
+///
+/// Deserializing checks that every arc is non-empty, every position has at
+/// least two numbers and every arc index refers to an arc of the topology.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "RawTopology")]
+pub struct Topology {
+    #[serde(default)]
+    pub bbox: Option<Bbox>,
+    /// Present when the topology is quantized; arcs are then delta-encoded
+    #[serde(default)]
+    pub transform: Option<Transform>,
+    pub objects: BTreeMap<String, TopoGeometry>,
+    pub arcs: Vec<Vec<Position>>,
+}
+
+#[derive(Deserialize)]
+struct RawTopology {
+    #[serde(default)]
+    bbox: Option<Bbox>,
+    #[serde(default)]
+    transform: Option<Transform>,
+    objects: BTreeMap<String, TopoGeometry>,
+    arcs: Vec<Vec<Position>>,
+}
+
+impl TryFrom<RawTopology> for Topology {
+    type Error = String;
+
+    fn try_from(raw: RawTopology) -> Result<Self, Self::Error> {
+        for (i, arc) in raw.arcs.iter().enumerate() {
+            if arc.is_empty() {
+                return Err(format!("arc {} is empty", i));
+            }
+            check_positions(arc)?;
+        }
+        for object in raw.objects.values() {
+            check_geometry(object, raw.arcs.len())?;
+        }
+        Ok(Topology {
+            bbox: raw.bbox,
+            transform: raw.transform,
+            objects: raw.objects,
+            arcs: raw.arcs,
+        })
+    }
+}
+
+fn check_positions(positions: &[Position]) -> Result<(), String> {
+    match positions.iter().any(|p| p.len() < 2) {
+        true => Err("a position must be an array of at least two numbers".to_string()),
+        false => Ok(()),
+    }
+}
+
+fn check_arcs<'a>(arcs: impl IntoIterator<Item = &'a i64>, arc_count: usize) -> Result<(), String> {
+    match arcs.into_iter().find(|&&i| arc_index(i) >= arc_count) {
+        Some(i) => Err(format!("arc index {} is out of range", i)),
+        None => Ok(()),
+    }
+}
+
+fn check_geometry(object: &TopoGeometry, arc_count: usize) -> Result<(), String> {
+    match &object.value {
+        TopoGeometryValue::Null => Ok(()),
+        TopoGeometryValue::Point(p) => check_positions(std::slice::from_ref(p)),
+        TopoGeometryValue::MultiPoint(points) => check_positions(points),
+        TopoGeometryValue::LineString(arcs) => check_arcs(arcs, arc_count),
+        TopoGeometryValue::MultiLineString(arcs) | TopoGeometryValue::Polygon(arcs) => {
+            check_arcs(arcs.iter().flatten(), arc_count)
+        }
+        TopoGeometryValue::MultiPolygon(arcs) => {
+            check_arcs(arcs.iter().flatten().flatten(), arc_count)
+        }
+        TopoGeometryValue::GeometryCollection(geometries) => geometries
+            .iter()
+            .try_for_each(|g| check_geometry(g, arc_count)),
+    }
+}
+
+impl Topology {
+    /// The named object, e.g. `"countries"`
+    pub fn object(&self, name: &str) -> Option<&TopoGeometry> {
+        self.objects.get(name)
+    }
+
+    /// Absolute, untransformed coordinates of every arc
+    pub(crate) fn decode_arcs(&self) -> Vec<Vec<Position>> {
+        self.arcs
+            .iter()
+            .map(|arc| {
+                let mut x0 = 0.0;
+                let mut y0 = 0.0;
+                arc.iter()
+                    .map(|p| match self.transform {
+                        Some(_) => {
+                            x0 += p[0];
+                            y0 += p[1];
+                            self.transform_point(&with_xy(p, x0, y0))
+                        }
+                        None => p.clone(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// A (non-delta-encoded) position with the transform applied
+    pub(crate) fn transform_point(&self, p: &Position) -> Position {
+        match self.transform {
+            Some(Transform { scale, translate }) => with_xy(
+                p,
+                p[0] * scale[0] + translate[0],
+                p[1] * scale[1] + translate[1],
+            ),
+            None => p.clone(),
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// A geometry object of a topology, with its feature members
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopoGeometry {
+    pub value: TopoGeometryValue,
+    pub id: Option<FeatureId>,
+    pub properties: Option<JsonObject>,
+    pub bbox: Option<Bbox>,
+}
+
+/// Arc indexes or positions, by type; a negative index `~i` is arc `i`
+/// reversed
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopoGeometryValue {
+    /// `"type": null`, decoded as a feature without geometry
+    Null,
+    Point(Position),
+    MultiPoint(Vec<Position>),
+    LineString(Vec<i64>),
+    MultiLineString(Vec<Vec<i64>>),
+    Polygon(Vec<Vec<i64>>),
+    MultiPolygon(Vec<Vec<Vec<i64>>>),
+    GeometryCollection(Vec<TopoGeometry>),
+}
+
+#[derive(Deserialize)]
+struct RawGeometry {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    id: Option<Value>,
+    properties: Option<JsonObject>,
+    bbox: Option<Bbox>,
+    arcs: Option<Value>,
+    coordinates: Option<Value>,
+    geometries: Option<Vec<TopoGeometry>>,
+}
+
+impl<'de> Deserialize<'de> for TopoGeometry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawGeometry::deserialize(deserializer)?;
+        fn member<T: serde::de::DeserializeOwned, E: serde::de::Error>(
+            value: Option<Value>,
+            name: &str,
+        ) -> Result<T, E> {
+            let value = value.ok_or_else(|| E::custom(format!("missing member '{}'", name)))?;
+            serde_json::from_value(value).map_err(E::custom)
+        }
+        let value = match raw.kind.as_deref() {
+            None => TopoGeometryValue::Null,
+            Some("Point") => TopoGeometryValue::Point(member(raw.coordinates, "coordinates")?),
+            Some("MultiPoint") => {
+                TopoGeometryValue::MultiPoint(member(raw.coordinates, "coordinates")?)
+            }
+            Some("LineString") => TopoGeometryValue::LineString(member(raw.arcs, "arcs")?),
+            Some("MultiLineString") => {
+                TopoGeometryValue::MultiLineString(member(raw.arcs, "arcs")?)
+            }
+            Some("Polygon") => TopoGeometryValue::Polygon(member(raw.arcs, "arcs")?),
+            Some("MultiPolygon") => TopoGeometryValue::MultiPolygon(member(raw.arcs, "arcs")?),
+            Some("GeometryCollection") => TopoGeometryValue::GeometryCollection(
+                raw.geometries
+                    .ok_or_else(|| D::Error::custom("missing member 'geometries'"))?,
+            ),
+            Some(kind) => {
+                return Err(D::Error::custom(format!(
+                    "unknown TopoJSON type '{}'",
+                    kind
+                )));
+            }
+        };
+        let id = match raw.id {
+            None | Some(Value::Null) => None,
+            Some(Value::String(s)) => Some(FeatureId::String(s)),
+            Some(Value::Number(n)) => Some(FeatureId::Number(n)),
+            Some(_) => return Err(D::Error::custom("member 'id' must be a string or number")),
+        };
+        Ok(Self {
+            value,
+            id,
+            properties: raw.properties,
+            bbox: raw.bbox,
+        })
+    }
+}
+
+/// Arc `i`, or arc `~i` for negative `i`
+fn arc_index(i: i64) -> usize {
+    (if i < 0 { !i } else { i }) as usize
+}
+
+fn with_xy(p: &Position, x: f64, y: f64) -> Position {
+    let mut q = p.clone();
+    q[0] = x;
+    q[1] = y;
+    q
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,122 @@
+//! Joining arcs that meet end to start into longer lines
+
+use super::{Topology, arc_index};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Quantized (or raw) end point, as a hashable key
+type Key = [u64; 2];
+
+struct Fragment {
+    arcs: Vec<i64>,
+    start: Key,
+    end: Key,
+}
+
+/// Group `arcs` into lines of connected arcs, in the order topojson-client
+/// returns them: stitched fragments first, then unconnected arcs
+pub(super) fn stitch(topology: &Topology, mut arcs: Vec<i64>) -> Vec<Vec<i64>> {
+    // Stitch empty arcs first, since they may be subsumed by other arcs
+    let mut empty_index = 0;
+    for j in 0..arcs.len() {
+        let arc = topology.arcs[arc_index(arcs[j])].as_slice();
+        if matches!(arc, [_, p] if p[0] == 0.0 && p[1] == 0.0) {
+            arcs.swap(empty_index, j);
+            empty_index += 1;
+        }
+    }
+
+    // Fragments by id, in creation order, and by their end points
+    let mut fragments: BTreeMap<usize, Fragment> = BTreeMap::new();
+    let mut by_start: HashMap<Key, usize> = HashMap::new();
+    let mut by_end: HashMap<Key, usize> = HashMap::new();
+    for (id, &i) in arcs.iter().enumerate() {
+        let [start, end] = ends(topology, i);
+        if let Some(f) = by_end.remove(&start) {
+            let fragment = fragments.get_mut(&f).unwrap();
+            fragment.arcs.push(i);
+            fragment.end = end;
+            if let Some(g) = by_start.remove(&end) {
+                let f_start = fragment.start;
+                if g != f {
+                    let tail = fragments.remove(&g).unwrap();
+                    let fragment = fragments.get_mut(&f).unwrap();
+                    fragment.arcs.extend(tail.arcs);
+                    fragment.end = tail.end;
+                }
+                let fragment_end = fragments[&f].end;
+                by_start.insert(f_start, f);
+                by_end.insert(fragment_end, f);
+            } else {
+                by_start.insert(fragment.start, f);
+                by_end.insert(end, f);
+            }
+        } else if let Some(f) = by_start.remove(&end) {
+            let fragment = fragments.get_mut(&f).unwrap();
+            fragment.arcs.insert(0, i);
+            fragment.start = start;
+            if let Some(g) = by_end.remove(&start) {
+                let f_end = fragment.end;
+                let mut merged = f;
+                if g != f {
+                    let tail = fragments.remove(&f).unwrap();
+                    let fragment = fragments.get_mut(&g).unwrap();
+                    fragment.arcs.extend(tail.arcs);
+                    fragment.end = tail.end;
+                    merged = g;
+                }
+                let merged_start = fragments[&merged].start;
+                by_start.insert(merged_start, merged);
+                by_end.insert(f_end, merged);
+            } else {
+                by_start.insert(start, f);
+                by_end.insert(fragment.end, f);
+            }
+        } else {
+            fragments.insert(
+                id,
+                Fragment {
+                    arcs: vec![i],
+                    start,
+                    end,
+                },
+            );
+            by_start.insert(start, id);
+            by_end.insert(end, id);
+        }
+    }
+
+    let stitched: HashSet<usize> = fragments
+        .values()
+        .flat_map(|f| f.arcs.iter().map(|&i| arc_index(i)))
+        .collect();
+    let mut lines: Vec<Vec<i64>> = fragments.into_values().map(|f| f.arcs).collect();
+    lines.extend(
+        arcs.iter()
+            .filter(|&&i| !stitched.contains(&arc_index(i)))
+            .map(|&i| vec![i]),
+    );
+    lines
+}
+
+/// First and last point of arc `i`, in quantized coordinates when the
+/// topology is quantized
+fn ends(topology: &Topology, i: i64) -> [Key; 2] {
+    let arc = &topology.arcs[arc_index(i)];
+    let p0 = [arc[0][0], arc[0][1]];
+    let p1 = match topology.transform {
+        Some(_) => arc
+            .iter()
+            .fold([0.0, 0.0], |[x, y], d| [x + d[0], y + d[1]]),
+        None => [arc[arc.len() - 1][0], arc[arc.len() - 1][1]],
+    };
+    if i < 0 {
+        [key(p1), key(p0)]
+    } else {
+        [key(p0), key(p1)]
+    }
+}
+
+fn key([x, y]: [f64; 2]) -> Key {
+    // Adding zero folds -0 into 0
+    [(x + 0.0).to_bits(), (y + 0.0).to_bits()]
+}
@@ -0,0 +1,203 @@
+//! Tests for topojson (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::geojson::{FeatureId, GeoJson, Geometry, GeometryValue};
+    use crate::topojson::{Topology, feature, merge, mesh, mesh_with};
+
+    fn squares() -> Topology {
+        include_str!("../../tests/fixtures/topojson/squares.json")
+            .parse()
+            .unwrap()
+    }
+
+    fn lines() -> Topology {
+        include_str!("../../tests/fixtures/topojson/lines.json")
+            .parse()
+            .unwrap()
+    }
+
+    fn positions(points: &[[f64; 2]]) -> Vec<Vec<f64>> {
+        points.iter().map(|p| p.to_vec()).collect()
+    }
+
+    fn multi_line_string(geometry: Geometry) -> Vec<Vec<Vec<f64>>> {
+        match geometry.value {
+            GeometryValue::MultiLineString(lines) => lines,
+            value => panic!("expected a MultiLineString, got {}", value.type_name()),
+        }
+    }
+
+    #[test]
+    fn test_parse_topology() {
+        let topology = squares();
+        assert_eq!(topology.bbox, Some(vec![10.0, 20.0, 12.0, 21.0]));
+        assert_eq!(topology.transform.unwrap().scale, [0.5, 0.5]);
+        assert_eq!(topology.arcs.len(), 3);
+        assert!(topology.object("squares").is_some());
+        assert!(topology.object("missing").is_none());
+
+        let unknown = r#"{"type": "Topology", "objects": {"o": {"type": "Circle"}}, "arcs": []}"#;
+        assert!(unknown.parse::<Topology>().is_err());
+        let missing = r#"{"type": "Topology", "objects": {"o": {"type": "Polygon"}}, "arcs": []}"#;
+        assert!(missing.parse::<Topology>().is_err());
+    }
+
+    #[test]
+    fn test_parse_malformed_topology() {
+        let malformed = [
+            include_str!("../../tests/fixtures/topojson/arc_index_out_of_range.json"),
+            include_str!("../../tests/fixtures/topojson/empty_arc.json"),
+            include_str!("../../tests/fixtures/topojson/short_position.json"),
+        ];
+        for json in malformed {
+            assert!(json.parse::<Topology>().is_err());
+        }
+
+        let short_point = r#"{"type": "Topology", "objects": {"o": {"type": "Point", "coordinates": [1]}}, "arcs": []}"#;
+        assert!(short_point.parse::<Topology>().is_err());
+        let err = include_str!("../../tests/fixtures/topojson/arc_index_out_of_range.json")
+            .parse::<Topology>()
+            .unwrap_err();
+        assert!(err.to_string().contains("arc index -3 is out of range"));
+    }
+
+    #[test]
+    fn test_feature_quantized_polygons() {
+        let topology = squares();
+        let GeoJson::FeatureCollection(collection) =
+            feature(&topology, topology.object("squares").unwrap())
+        else {
+            panic!("expected a FeatureCollection");
+        };
+        assert_eq!(collection.features.len(), 2);
+        let a = &collection.features[0];
+        assert_eq!(a.id, Some(FeatureId::from("a")));
+        assert_eq!(a.property("name").unwrap(), "A");
+        assert_eq!(
+            a.geometry.as_ref().unwrap().value,
+            GeometryValue::Polygon(vec![positions(&[
+                [11.0, 20.0],
+                [11.0, 21.0],
+                [10.0, 21.0],
+                [10.0, 20.0],
+                [11.0, 20.0]
+            ])])
+        );
+        // The shared arc is reversed for the second square
+        assert_eq!(
+            collection.features[1].geometry.as_ref().unwrap().value,
+            GeometryValue::Polygon(vec![positions(&[
+                [11.0, 21.0],
+                [11.0, 20.0],
+                [12.0, 20.0],
+                [12.0, 21.0],
+                [11.0, 21.0]
+            ])])
+        );
+
+        let GeoJson::Feature(capital) = feature(&topology, topology.object("capital").unwrap())
+        else {
+            panic!("expected a Feature");
+        };
+        assert_eq!(capital.id, Some(FeatureId::from(1u64)));
+        assert_eq!(
+            capital.geometry.unwrap().value,
+            GeometryValue::Point(vec![10.5, 20.5])
+        );
+    }
+
+    #[test]
+    fn test_feature_unquantized_lines() {
+        let topology = lines();
+        let features = feature(&topology, topology.object("collection").unwrap()).into_features();
+        assert_eq!(features.len(), 4);
+        assert_eq!(
+            features[0].geometry.as_ref().unwrap().value,
+            GeometryValue::LineString(positions(&[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [2.0, 1.0]]))
+        );
+        assert_eq!(
+            features[1].geometry.as_ref().unwrap().value,
+            GeometryValue::LineString(positions(&[[2.0, 1.0], [1.0, 1.0], [1.0, 0.0]]))
+        );
+        assert_eq!(
+            features[2].geometry.as_ref().unwrap().value,
+            GeometryValue::MultiPoint(positions(&[[0.5, 0.5], [1.5, 1.5]]))
+        );
+        // Null geometries keep their id and properties
+        assert!(features[3].geometry.is_none());
+        assert_eq!(features[3].id, Some(FeatureId::from("unlocated")));
+        assert_eq!(features[3].property("note").unwrap(), "no geometry");
+        // Features always have properties
+        assert!(features[2].properties.as_ref().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_mesh() {
+        let topology = squares();
+        let all = multi_line_string(mesh(&topology, None));
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].len(), 8);
+
+        let squares = topology.object("squares").unwrap();
+        let interior = multi_line_string(mesh_with(&topology, squares, |a, b| !std::ptr::eq(a, b)));
+        assert_eq!(interior, vec![positions(&[[11.0, 20.0], [11.0, 21.0]])]);
+
+        let exterior = multi_line_string(mesh_with(&topology, squares, |a, b| std::ptr::eq(a, b)));
+        assert_eq!(
+            exterior,
+            vec![positions(&[
+                [11.0, 21.0],
+                [10.0, 21.0],
+                [10.0, 20.0],
+                [11.0, 20.0],
+                [12.0, 20.0],
+                [12.0, 21.0],
+                [11.0, 21.0]
+            ])]
+        );
+
+        // Arcs used twice appear once
+        let topology = lines();
+        let lines = multi_line_string(mesh(&topology, topology.object("collection")));
+        assert_eq!(
+            lines,
+            vec![positions(&[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [2.0, 1.0]])]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let topology = squares();
+        let crate::topojson::TopoGeometryValue::GeometryCollection(geometries) =
+            &topology.object("squares").unwrap().value
+        else {
+            panic!("expected a GeometryCollection");
+        };
+        let both: Vec<_> = geometries.iter().collect();
+        assert_eq!(
+            merge(&topology, &both).value,
+            GeometryValue::MultiPolygon(vec![vec![positions(&[
+                [11.0, 21.0],
+                [10.0, 21.0],
+                [10.0, 20.0],
+                [11.0, 20.0],
+                [12.0, 20.0],
+                [12.0, 21.0],
+                [11.0, 21.0]
+            ])]])
+        );
+
+        let a = merge(&topology, &both[..1]);
+        let GeometryValue::MultiPolygon(polygons) = a.value else {
+            panic!("expected a MultiPolygon");
+        };
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0][0].len(), 5);
+
+        assert_eq!(
+            merge(&topology, &[]).value,
+            GeometryValue::MultiPolygon(vec![])
+        );
+    }
+}
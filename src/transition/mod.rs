@@ -0,0 +1,436 @@
+//! d3-transition (Rust port)
+//!
+//! Animated interpolation of attributes, styles and text on a [`Selection`].
+//! Transitions are driven by a [`Clock`] from `crate::timer`, by default a
+//! [`SystemClock`] so [`Transition::tick`] follows wall time; with a
+//! [`ManualClock`](crate::timer::ManualClock) any frame of an animation can be rendered deterministically.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::selection::Selection;
+//! use rust_d3::timer::ManualClock;
+//!
+//! let mut svg = Selection::create("svg");
+//! let mut rect = svg.append("rect");
+//! rect.attr("width", "0");
+//!
+//! let clock = ManualClock::new();
+//! let mut t = rect.transition();
+//! t.clock(&clock).duration(1000.0).ease(rust_d3::ease::linear).attr("width", "100");
+//!
+//! clock.set(250.0);
+//! t.tick();
+//! assert_eq!(rect.get_attr("width"), Some("25".to_string()));
+//! ```
+
+use crate::interpolate::Interpolate;
+use crate::selection::{Arena, Node, NodeKey, Selection};
+use crate::timer::{Clock, SystemClock};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Interpolator from eased time `t` to an attribute, style or text value
+pub type Interpolator = Box<dyn Fn(f64) -> String>;
+
+type TweenFactory = Box<dyn FnMut(&Node, usize, Option<String>) -> Interpolator>;
+type EventHandler = Box<dyn FnMut(&Node, usize)>;
+
+/// Lifecycle of a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionState {
+    /// Waiting for a preceding transition in the chain to end
+    Pending,
+    /// Waiting for its delay to elapse
+    Scheduled,
+    Running,
+    Ended,
+}
+
+enum TweenTarget {
+    Attr(String),
+    Style(String),
+    Text,
+}
+
+struct Tween {
+    target: TweenTarget,
+    factory: TweenFactory,
+    interpolators: Vec<(NodeKey, Interpolator)>,
+}
+
+struct TransitionInner {
+    arena: Rc<RefCell<Arena>>,
+    keys: Vec<NodeKey>,
+    clock: Rc<dyn Clock>,
+    origin: f64,
+    delay: f64,
+    duration: f64,
+    ease: Rc<dyn Fn(f32) -> f32>,
+    tweens: Vec<Tween>,
+    handlers: HashMap<String, Vec<EventHandler>>,
+    state: TransitionState,
+    remove_on_end: bool,
+    followers: Vec<Transition>,
+}
+
+/// A scheduled animation over the nodes of a selection (D3 transition)
+///
+/// `Transition` is a cheap handle: clones refer to the same transition.
+#[derive(Clone)]
+pub struct Transition {
+    inner: Rc<RefCell<TransitionInner>>,
+}
+
+impl Transition {
+    /// Create a transition over a selection, starting now on a fresh [`SystemClock`]
+    ///
+    /// Use [`Transition::clock`] to drive it from another clock, such as a
+    /// [`ManualClock`](crate::timer::ManualClock) for deterministic frames.
+    pub fn new(selection: &Selection) -> Self {
+        let clock: Rc<dyn Clock> = Rc::new(SystemClock::new());
+        let origin = clock.now();
+        Transition {
+            inner: Rc::new(RefCell::new(TransitionInner {
+                arena: selection.arena(),
+                keys: selection.iter().copied().collect(),
+                clock,
+                origin,
+                delay: 0.0,
+                duration: 250.0,
+                ease: Rc::new(crate::ease::cubic_inout),
+                tweens: Vec::new(),
+                handlers: HashMap::new(),
+                state: TransitionState::Scheduled,
+                remove_on_end: false,
+                followers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Drive this transition from `clock`; the schedule restarts at the clock's current time
+    pub fn clock<C: Clock + Clone + 'static>(&mut self, clock: &C) -> &mut Self {
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.origin = clock.now();
+            inner.clock = Rc::new(clock.clone());
+        }
+        self
+    }
+    /// Delay in milliseconds before the transition starts
+    pub fn delay(&mut self, ms: f64) -> &mut Self {
+        self.inner.borrow_mut().delay = ms.max(0.0);
+        self
+    }
+    /// Duration in milliseconds (default 250)
+    pub fn duration(&mut self, ms: f64) -> &mut Self {
+        self.inner.borrow_mut().duration = ms.max(0.0);
+        self
+    }
+    /// Easing function from `crate::ease` (default `cubic_inout`)
+    pub fn ease<F: Fn(f32) -> f32 + 'static>(&mut self, ease: F) -> &mut Self {
+        self.inner.borrow_mut().ease = Rc::new(ease);
+        self
+    }
+    pub fn get_delay(&self) -> f64 {
+        self.inner.borrow().delay
+    }
+    pub fn get_duration(&self) -> f64 {
+        self.inner.borrow().duration
+    }
+    pub fn state(&self) -> TransitionState {
+        self.inner.borrow().state
+    }
+
+    /// Transition an attribute to a constant end value
+    pub fn attr(&mut self, name: &str, value: &str) -> &mut Self {
+        let value = value.to_string();
+        self.attr_tween(name, move |_, _, from| interpolate_value(from, value.clone()))
+    }
+    /// Transition an attribute to a per-node end value
+    pub fn attr_fn<F>(&mut self, name: &str, mut f: F) -> &mut Self
+    where
+        F: FnMut(&Node, usize) -> String + 'static,
+    {
+        self.attr_tween(name, move |node, i, from| interpolate_value(from, f(node, i)))
+    }
+    /// Transition an attribute with a custom interpolator factory (D3 attrTween)
+    ///
+    /// The factory runs once per node when the transition starts and receives the current value.
+    pub fn attr_tween<F>(&mut self, name: &str, factory: F) -> &mut Self
+    where
+        F: FnMut(&Node, usize, Option<String>) -> Interpolator + 'static,
+    {
+        self.push_tween(TweenTarget::Attr(name.to_string()), Box::new(factory))
+    }
+    /// Transition a style property to a constant end value
+    pub fn style(&mut self, name: &str, value: &str) -> &mut Self {
+        let value = value.to_string();
+        self.style_tween(name, move |_, _, from| interpolate_value(from, value.clone()))
+    }
+    /// Transition a style property to a per-node end value
+    pub fn style_fn<F>(&mut self, name: &str, mut f: F) -> &mut Self
+    where
+        F: FnMut(&Node, usize) -> String + 'static,
+    {
+        self.style_tween(name, move |node, i, from| interpolate_value(from, f(node, i)))
+    }
+    /// Transition a style property with a custom interpolator factory (D3 styleTween)
+    pub fn style_tween<F>(&mut self, name: &str, factory: F) -> &mut Self
+    where
+        F: FnMut(&Node, usize, Option<String>) -> Interpolator + 'static,
+    {
+        self.push_tween(TweenTarget::Style(name.to_string()), Box::new(factory))
+    }
+    /// Transition text content; numbers embedded in the text are interpolated
+    pub fn text(&mut self, value: &str) -> &mut Self {
+        let value = value.to_string();
+        self.text_tween(move |_, _, from| interpolate_value(from, value.clone()))
+    }
+    /// Transition text content with a custom interpolator factory (D3 textTween)
+    pub fn text_tween<F>(&mut self, factory: F) -> &mut Self
+    where
+        F: FnMut(&Node, usize, Option<String>) -> Interpolator + 'static,
+    {
+        self.push_tween(TweenTarget::Text, Box::new(factory))
+    }
+    fn push_tween(&mut self, target: TweenTarget, factory: TweenFactory) -> &mut Self {
+        self.inner.borrow_mut().tweens.push(Tween {
+            target,
+            factory,
+            interpolators: Vec::new(),
+        });
+        self
+    }
+
+    /// Register a handler for "start", "end" or "interrupt", called once per node
+    pub fn on<F>(&mut self, event: &str, handler: F) -> &mut Self
+    where
+        F: FnMut(&Node, usize) + 'static,
+    {
+        self.inner
+            .borrow_mut()
+            .handlers
+            .entry(event.to_string())
+            .or_default()
+            .push(Box::new(handler));
+        self
+    }
+    /// Remove the selected nodes when the transition ends
+    pub fn remove(&mut self) -> &mut Self {
+        self.inner.borrow_mut().remove_on_end = true;
+        self
+    }
+
+    /// Schedule a follow-up transition on the same nodes, starting when this one ends
+    ///
+    /// The follow-up inherits this transition's clock, duration and easing.
+    pub fn transition(&mut self) -> Transition {
+        let mut inner = self.inner.borrow_mut();
+        let follower = Transition {
+            inner: Rc::new(RefCell::new(TransitionInner {
+                arena: Rc::clone(&inner.arena),
+                keys: inner.keys.clone(),
+                clock: Rc::clone(&inner.clock),
+                origin: inner.origin,
+                delay: 0.0,
+                duration: inner.duration,
+                ease: Rc::clone(&inner.ease),
+                tweens: Vec::new(),
+                handlers: HashMap::new(),
+                state: TransitionState::Pending,
+                remove_on_end: false,
+                followers: Vec::new(),
+            })),
+        };
+        inner.followers.push(follower.clone());
+        follower
+    }
+
+    /// Cancel the transition if it has not ended, firing "interrupt" handlers
+    pub fn interrupt(&mut self) -> &mut Self {
+        let interrupted = {
+            let mut inner = self.inner.borrow_mut();
+            let active = inner.state != TransitionState::Ended;
+            inner.state = TransitionState::Ended;
+            inner.followers.clear();
+            active
+        };
+        if interrupted {
+            self.fire("interrupt");
+        }
+        self
+    }
+
+    /// The selection this transition animates
+    pub fn selection(&self) -> Selection {
+        let inner = self.inner.borrow();
+        Selection::new(Rc::clone(&inner.arena), inner.keys.clone())
+    }
+
+    /// Advance to the clock's current time and apply the frame
+    pub fn tick(&mut self) -> TransitionState {
+        let now = self.inner.borrow().clock.now();
+        self.tick_at(now)
+    }
+
+    /// Apply the frame at absolute time `now` (milliseconds on this transition's clock)
+    pub fn tick_at(&mut self, now: f64) -> TransitionState {
+        let state = self.inner.borrow().state;
+        match state {
+            TransitionState::Pending => {}
+            TransitionState::Ended => self.tick_followers(now),
+            TransitionState::Scheduled | TransitionState::Running => {
+                let (origin, delay, duration) = {
+                    let inner = self.inner.borrow();
+                    (inner.origin, inner.delay, inner.duration)
+                };
+                let elapsed = now - origin - delay;
+                if elapsed >= 0.0 {
+                    if state == TransitionState::Scheduled {
+                        self.start();
+                    }
+                    let t = if duration > 0.0 {
+                        (elapsed / duration).min(1.0)
+                    } else {
+                        1.0
+                    };
+                    self.apply(t);
+                    if t >= 1.0 {
+                        self.end(origin + delay + duration);
+                        self.tick_followers(now);
+                    }
+                }
+            }
+        }
+        self.inner.borrow().state
+    }
+
+    /// Jump the transition (and any chained transitions) to their final frame
+    pub fn finish(&mut self) {
+        self.tick_at(f64::INFINITY);
+    }
+
+    fn tick_followers(&mut self, now: f64) {
+        let followers = self.inner.borrow().followers.clone();
+        for mut follower in followers {
+            follower.tick_at(now);
+        }
+    }
+
+    fn start(&mut self) {
+        {
+            let mut guard = self.inner.borrow_mut();
+            let inner = &mut *guard;
+            inner.state = TransitionState::Running;
+            let arena = inner.arena.borrow();
+            for tween in inner.tweens.iter_mut() {
+                tween.interpolators = inner
+                    .keys
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, &key)| {
+                        let node = arena.nodes.get(key)?;
+                        let from = current_value(node, &tween.target);
+                        Some((key, (tween.factory)(node, i, from)))
+                    })
+                    .collect();
+            }
+        }
+        self.fire("start");
+    }
+
+    fn apply(&mut self, t: f64) {
+        let inner = self.inner.borrow();
+        let eased = if t >= 1.0 {
+            1.0
+        } else {
+            (inner.ease)(t as f32) as f64
+        };
+        for tween in &inner.tweens {
+            for (key, interpolate) in &tween.interpolators {
+                let key = *key;
+                if !inner.arena.borrow().nodes.contains_key(key) {
+                    continue;
+                }
+                let value = interpolate(eased);
+                match &tween.target {
+                    TweenTarget::Attr(name) => {
+                        Selection::new(Rc::clone(&inner.arena), vec![key]).attr(name, &value);
+                    }
+                    TweenTarget::Style(name) => {
+                        Selection::new(Rc::clone(&inner.arena), vec![key]).style(name, &value);
+                    }
+                    TweenTarget::Text => {
+                        inner.arena.borrow_mut().nodes[key].text = Some(value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn end(&mut self, end_time: f64) {
+        let (followers, remove) = {
+            let mut inner = self.inner.borrow_mut();
+            inner.state = TransitionState::Ended;
+            (inner.followers.clone(), inner.remove_on_end)
+        };
+        self.fire("end");
+        if remove {
+            self.selection().remove();
+        }
+        for follower in followers {
+            let mut fi = follower.inner.borrow_mut();
+            fi.origin = end_time;
+            fi.state = TransitionState::Scheduled;
+        }
+    }
+
+    fn fire(&mut self, event: &str) {
+        let (arena, keys, mut handlers) = {
+            let mut inner = self.inner.borrow_mut();
+            match inner.handlers.remove(event) {
+                Some(handlers) => (Rc::clone(&inner.arena), inner.keys.clone(), handlers),
+                None => return,
+            }
+        };
+        for (i, key) in keys.into_iter().enumerate() {
+            // Clone so handlers may freely touch the arena
+            let node = arena.borrow().nodes.get(key).cloned();
+            if let Some(node) = node {
+                for handler in handlers.iter_mut() {
+                    handler(&node, i);
+                }
+            }
+        }
+        self.inner
+            .borrow_mut()
+            .handlers
+            .insert(event.to_string(), handlers);
+    }
+}
+
+fn current_value(node: &Node, target: &TweenTarget) -> Option<String> {
+    match target {
+        TweenTarget::Attr(name) => node.attributes.get(name).cloned(),
+        TweenTarget::Style(name) => node.attributes.get("style").and_then(|style| {
+            style.split(';').find_map(|decl| {
+                let (k, v) = decl.split_once(':')?;
+                (k.trim() == name).then(|| v.trim().to_string())
+            })
+        }),
+        TweenTarget::Text => node.text.clone(),
+    }
+}
+
+/// Pick an interpolator from the start and end values, like `d3.interpolate`
+///
+/// Numbers interpolate numerically, `#rrggbb` colours in RGB, and anything else
+/// interpolates the numbers embedded in the string. Without a start value the
+/// end value is applied immediately.
+pub fn interpolate_value(from: Option<String>, to: String) -> Interpolator {
+    match from {
+        Some(from) => Box::new(move |t| String::interpolate(&from, &to, t)),
+        None => Box::new(move |_| to.clone()),
+    }
+}
@@ -0,0 +1,590 @@
+//! d3-zoom: panning and zooming (Rust port)
+//!
+//! [`ZoomTransform`] is the pure transform model: apply and invert points,
+//! and rescale continuous scales to the zoomed view. [`Zoom`] is the
+//! behaviour: a state machine fed abstract pointer, wheel and double-click
+//! inputs (from egui, another toolkit or a test) that applies scale and
+//! translate extents and reports start, zoom and end events. Time is passed
+//! in explicitly, in milliseconds, so gestures and transitions can be driven
+//! headless.
+//!
+//! # Usage Example
+//! ```rust
+//! use rust_d3::scale::ScaleLinear;
+//! use rust_d3::zoom::{WheelDeltaMode, Zoom, ZoomEventType, ZoomInput, ZoomTransform};
+//!
+//! let mut zoom = Zoom::new([[0.0, 0.0], [600.0, 400.0]]).scale_extent([1.0, 8.0]);
+//!
+//! // Drag: pan by (50, 20)
+//! zoom.handle(ZoomInput::PointerDown { id: 0, point: [100.0, 100.0] }, 0.0);
+//! zoom.handle(ZoomInput::PointerMove { id: 0, point: [150.0, 120.0] }, 16.0);
+//! let events = zoom.handle(ZoomInput::PointerUp { id: 0 }, 32.0);
+//! assert_eq!(events[0].event_type, ZoomEventType::End);
+//! assert_eq!(zoom.get_transform(), ZoomTransform::new(1.0, 50.0, 20.0));
+//!
+//! // Wheel: zoom in about the pointer, ending after 150ms without wheel input
+//! zoom.handle(
+//!     ZoomInput::Wheel { point: [300.0, 200.0], delta_y: -500.0, mode: WheelDeltaMode::Pixel, ctrl: false },
+//!     100.0,
+//! );
+//! assert!((zoom.get_transform().k - 2.0).abs() < 1e-9);
+//! zoom.tick(300.0);
+//! assert!(!zoom.is_active());
+//!
+//! // Draw with the zoomed scale
+//! let x = ScaleLinear::new([0.0, 100.0], [0.0, 600.0]);
+//! let zx = zoom.get_transform().rescale_x(&x);
+//! let t = zoom.get_transform();
+//! assert!((zx.scale(50.0) - t.apply_x(x.scale(50.0))).abs() < 1e-9);
+//! ```
+
+mod transform;
+
+pub use transform::{Rescale, ZoomTransform};
+
+use crate::ease::cubic_inout;
+use crate::interpolate::ZoomInterpolator;
+
+/// A rectangle `[[x0, y0], [x1, y1]]`
+pub type Extent = [[f64; 2]; 2];
+
+/// Constrains a transform, given the viewport extent and the translate extent
+pub type Constrain = fn(ZoomTransform, Extent, Extent) -> ZoomTransform;
+
+/// Converts a wheel input (`delta_y`, mode, ctrl) to a change in log₂ scale
+pub type WheelDelta = fn(f64, WheelDeltaMode, bool) -> f64;
+
+/// Milliseconds without wheel input after which a wheel gesture ends
+const WHEEL_DELAY: f64 = 150.0;
+
+/// Units of a wheel delta, as in DOM wheel events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WheelDeltaMode {
+    Pixel,
+    Line,
+    Page,
+}
+
+/// An input event, in the coordinates of the zoomed viewport
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZoomInput {
+    /// A mouse button or touch went down; up to two pointers pan and pinch
+    PointerDown {
+        id: u64,
+        point: [f64; 2],
+    },
+    PointerMove {
+        id: u64,
+        point: [f64; 2],
+    },
+    PointerUp {
+        id: u64,
+    },
+    /// `delta_y` follows DOM wheel events: positive scrolls down, zooming out
+    Wheel {
+        point: [f64; 2],
+        delta_y: f64,
+        mode: WheelDeltaMode,
+        ctrl: bool,
+    },
+    /// Zooms in by two about `point`, or out with shift
+    DoubleClick {
+        point: [f64; 2],
+        shift: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomEventType {
+    Start,
+    Zoom,
+    End,
+}
+
+/// Reported by [`Zoom`] as the transform changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoomEvent {
+    pub event_type: ZoomEventType,
+    pub transform: ZoomTransform,
+}
+
+// A pointer of the current gesture: where it is, and the untransformed
+// point it holds under itself
+#[derive(Debug, Clone, Copy)]
+struct Pointer {
+    id: u64,
+    point: [f64; 2],
+    anchor: [f64; 2],
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WheelGesture {
+    point: [f64; 2],
+    anchor: [f64; 2],
+    idle_at: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ZoomTransition {
+    start: f64,
+    point: [f64; 2],
+    width: f64,
+    interpolator: ZoomInterpolator,
+    end: ZoomTransform,
+}
+
+// Which gesture produced a transform, so the others can re-anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Pointer,
+    Wheel,
+    Other,
+}
+
+/// The zoom behaviour: the current transform, its constraints and the state
+/// of in-progress gestures
+///
+/// Every input returns the events it caused. Wheel gestures end, and
+/// transitions advance, on [`tick`](Self::tick).
+#[derive(Debug, Clone)]
+pub struct Zoom {
+    transform: ZoomTransform,
+    extent: Extent,
+    scale_extent: [f64; 2],
+    translate_extent: Extent,
+    constrain: Constrain,
+    wheel_delta: WheelDelta,
+    duration: f64,
+    active: usize,
+    pointers: Vec<Pointer>,
+    wheel: Option<WheelGesture>,
+    transition: Option<ZoomTransition>,
+    events: Vec<ZoomEvent>,
+}
+
+impl Zoom {
+    /// A zoom behaviour for a viewport of `extent`, at the identity transform
+    pub fn new(extent: Extent) -> Self {
+        Self {
+            transform: ZoomTransform::IDENTITY,
+            extent,
+            scale_extent: [0.0, f64::INFINITY],
+            translate_extent: [
+                [f64::NEG_INFINITY, f64::NEG_INFINITY],
+                [f64::INFINITY, f64::INFINITY],
+            ],
+            constrain: default_constrain,
+            wheel_delta: default_wheel_delta,
+            duration: 250.0,
+            active: 0,
+            pointers: Vec::new(),
+            wheel: None,
+            transition: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// The viewport; the default point to zoom about is its centre
+    pub fn extent(mut self, extent: Extent) -> Self {
+        self.extent = extent;
+        self
+    }
+
+    /// The allowed scale factors, `[0, ∞]` by default
+    pub fn scale_extent(mut self, scale_extent: [f64; 2]) -> Self {
+        self.scale_extent = scale_extent;
+        self
+    }
+
+    /// The world extent the viewport may not pan beyond, infinite by default
+    pub fn translate_extent(mut self, translate_extent: Extent) -> Self {
+        self.translate_extent = translate_extent;
+        self
+    }
+
+    pub fn constrain(mut self, constrain: Constrain) -> Self {
+        self.constrain = constrain;
+        self
+    }
+
+    pub fn wheel_delta(mut self, wheel_delta: WheelDelta) -> Self {
+        self.wheel_delta = wheel_delta;
+        self
+    }
+
+    /// Duration of double-click zoom transitions in milliseconds; zero zooms
+    /// immediately
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn get_transform(&self) -> ZoomTransform {
+        self.transform
+    }
+
+    pub fn get_extent(&self) -> Extent {
+        self.extent
+    }
+
+    pub fn get_scale_extent(&self) -> [f64; 2] {
+        self.scale_extent
+    }
+
+    pub fn get_translate_extent(&self) -> Extent {
+        self.translate_extent
+    }
+
+    pub fn get_duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Whether a gesture or transition is in progress
+    pub fn is_active(&self) -> bool {
+        self.active > 0
+    }
+
+    /// Whether [`tick`](Self::tick) has pending work: a wheel gesture to end
+    /// or a transition to advance
+    pub fn needs_tick(&self) -> bool {
+        self.wheel.is_some() || self.transition.is_some()
+    }
+
+    /// Feed an input event at time `now` (milliseconds)
+    pub fn handle(&mut self, input: ZoomInput, now: f64) -> Vec<ZoomEvent> {
+        match input {
+            ZoomInput::PointerDown { id, point } => self.pointer_down(id, point),
+            ZoomInput::PointerMove { id, point } => self.pointer_move(id, point),
+            ZoomInput::PointerUp { id } => self.pointer_up(id),
+            ZoomInput::Wheel {
+                point,
+                delta_y,
+                mode,
+                ctrl,
+            } => self.wheeled(point, (self.wheel_delta)(delta_y, mode, ctrl), now),
+            ZoomInput::DoubleClick { point, shift } => {
+                let t0 = self.transform;
+                let k = t0.k * if shift { 0.5 } else { 2.0 };
+                let t1 = self.constrained(translate(self.scale(t0, k), point, t0.invert(point)));
+                if self.duration > 0.0 {
+                    self.start_transition(t1, Some(point), now);
+                } else {
+                    self.interrupt();
+                    self.apply(t1);
+                }
+            }
+        }
+        std::mem::take(&mut self.events)
+    }
+
+    /// Advance time to `now`: end idle wheel gestures and step transitions
+    pub fn tick(&mut self, now: f64) -> Vec<ZoomEvent> {
+        if let Some(wheel) = self.wheel
+            && now >= wheel.idle_at
+        {
+            self.wheel = None;
+            self.end();
+        }
+        if let Some(transition) = self.transition {
+            let t = ((now - transition.start) / self.duration).clamp(0.0, 1.0);
+            let transform = if t >= 1.0 {
+                transition.end
+            } else {
+                let [x, y, w] = transition.interpolator.at(cubic_inout(t as f32) as f64);
+                let k = transition.width / w;
+                let [px, py] = transition.point;
+                ZoomTransform::new(k, px - x * k, py - y * k)
+            };
+            self.emit(Key::Other, transform);
+            if t >= 1.0 {
+                self.transition = None;
+                self.end();
+            }
+        }
+        std::mem::take(&mut self.events)
+    }
+
+    /// Set the transform immediately, unconstrained
+    pub fn set_transform(&mut self, transform: ZoomTransform) -> Vec<ZoomEvent> {
+        self.interrupt();
+        self.apply(transform);
+        std::mem::take(&mut self.events)
+    }
+
+    /// Transition smoothly to `transform` over the duration, keeping `point`
+    /// (by default the centre of the extent) fixed where possible
+    pub fn transition_to(
+        &mut self,
+        transform: ZoomTransform,
+        point: Option<[f64; 2]>,
+        now: f64,
+    ) -> Vec<ZoomEvent> {
+        if self.duration > 0.0 {
+            self.start_transition(transform, point, now);
+        } else {
+            self.interrupt();
+            self.apply(transform);
+        }
+        std::mem::take(&mut self.events)
+    }
+
+    /// Multiply the scale by `k` about `point`, by default the centre of the
+    /// extent
+    pub fn scale_by(&mut self, k: f64, point: Option<[f64; 2]>) -> Vec<ZoomEvent> {
+        self.scale_to(self.transform.k * k, point)
+    }
+
+    /// Set the scale to `k` about `point`, by default the centre of the extent
+    pub fn scale_to(&mut self, k: f64, point: Option<[f64; 2]>) -> Vec<ZoomEvent> {
+        let t0 = self.transform;
+        let p0 = point.unwrap_or_else(|| centroid(self.extent));
+        let t1 = self.constrained(translate(self.scale(t0, k), p0, t0.invert(p0)));
+        self.set_constrained(t1)
+    }
+
+    /// Translate by `(x, y)` in untransformed units
+    pub fn translate_by(&mut self, x: f64, y: f64) -> Vec<ZoomEvent> {
+        let t1 = self.constrained(self.transform.translate(x, y));
+        self.set_constrained(t1)
+    }
+
+    /// Translate so the untransformed point `(x, y)` is displayed at `point`,
+    /// by default the centre of the extent
+    pub fn translate_to(&mut self, x: f64, y: f64, point: Option<[f64; 2]>) -> Vec<ZoomEvent> {
+        let [px, py] = point.unwrap_or_else(|| centroid(self.extent));
+        let t1 = ZoomTransform::IDENTITY
+            .translate(px, py)
+            .scale_by(self.transform.k)
+            .translate(-x, -y);
+        let t1 = self.constrained(t1);
+        self.set_constrained(t1)
+    }
+
+    fn set_constrained(&mut self, transform: ZoomTransform) -> Vec<ZoomEvent> {
+        self.interrupt();
+        self.apply(transform);
+        std::mem::take(&mut self.events)
+    }
+
+    fn pointer_down(&mut self, id: u64, point: [f64; 2]) {
+        if self.pointers.len() >= 2 || self.pointers.iter().any(|p| p.id == id) {
+            return;
+        }
+        self.interrupt();
+        self.pointers.push(Pointer {
+            id,
+            point,
+            anchor: self.transform.invert(point),
+        });
+        if self.pointers.len() == 1 {
+            self.start();
+        }
+    }
+
+    fn pointer_move(&mut self, id: u64, point: [f64; 2]) {
+        let Some(pointer) = self.pointers.iter_mut().find(|p| p.id == id) else {
+            return;
+        };
+        pointer.point = point;
+        let mut t = self.transform;
+        let (p, l) = match self.pointers[..] {
+            [a, b] => {
+                // Pinch: the scale that keeps both anchors under their pointers
+                let dp = distance2(a.point, b.point);
+                let dl = distance2(a.anchor, b.anchor);
+                t = self.scale(t, (dp / dl).sqrt());
+                (midpoint(a.point, b.point), midpoint(a.anchor, b.anchor))
+            }
+            [a] => (a.point, a.anchor),
+            _ => return,
+        };
+        let t = self.constrained(translate(t, p, l));
+        self.emit(Key::Pointer, t);
+    }
+
+    fn pointer_up(&mut self, id: u64) {
+        let Some(i) = self.pointers.iter().position(|p| p.id == id) else {
+            return;
+        };
+        self.pointers.remove(i);
+        match self.pointers.first_mut() {
+            Some(rest) => rest.anchor = self.transform.invert(rest.point),
+            None => self.end(),
+        }
+    }
+
+    fn wheeled(&mut self, point: [f64; 2], delta: f64, now: f64) {
+        let t = self.transform;
+        let k = self.clamp_scale(t.k * 2f64.powf(delta));
+        let wheel = match self.wheel {
+            Some(mut wheel) => {
+                if wheel.point != point {
+                    wheel.point = point;
+                    wheel.anchor = t.invert(point);
+                }
+                wheel
+            }
+            None if t.k == k => return,
+            None => {
+                self.interrupt();
+                self.start();
+                WheelGesture {
+                    point,
+                    anchor: t.invert(point),
+                    idle_at: now,
+                }
+            }
+        };
+        self.wheel = Some(WheelGesture {
+            idle_at: now + WHEEL_DELAY,
+            ..wheel
+        });
+        let t = self.constrained(translate(self.scale(t, k), wheel.point, wheel.anchor));
+        self.emit(Key::Wheel, t);
+    }
+
+    fn start_transition(&mut self, end: ZoomTransform, point: Option<[f64; 2]>, now: f64) {
+        self.interrupt();
+        let e = self.extent;
+        let point = point.unwrap_or_else(|| centroid(e));
+        let width = (e[1][0] - e[0][0]).max(e[1][1] - e[0][1]);
+        let a = self.transform;
+        let [ax, ay] = a.invert(point);
+        let [bx, by] = end.invert(point);
+        self.transition = Some(ZoomTransition {
+            start: now,
+            point,
+            width,
+            interpolator: ZoomInterpolator::new(
+                [ax, ay, width / a.k],
+                [bx, by, width / end.k],
+                std::f64::consts::SQRT_2,
+            ),
+            end,
+        });
+        self.start();
+    }
+
+    // Cancel a running transition
+    fn interrupt(&mut self) {
+        if self.transition.take().is_some() {
+            self.end();
+        }
+    }
+
+    // A complete programmatic change: start, zoom, end
+    fn apply(&mut self, transform: ZoomTransform) {
+        self.start();
+        self.emit(Key::Other, transform);
+        self.end();
+    }
+
+    fn start(&mut self) {
+        self.active += 1;
+        if self.active == 1 {
+            self.push(ZoomEventType::Start);
+        }
+    }
+
+    fn end(&mut self) {
+        self.active = self.active.saturating_sub(1);
+        if self.active == 0 {
+            self.push(ZoomEventType::End);
+        }
+    }
+
+    fn emit(&mut self, key: Key, transform: ZoomTransform) {
+        // Other gestures keep their points anchored under the new transform
+        if key != Key::Wheel
+            && let Some(wheel) = &mut self.wheel
+        {
+            wheel.anchor = transform.invert(wheel.point);
+        }
+        if key != Key::Pointer {
+            for pointer in &mut self.pointers {
+                pointer.anchor = transform.invert(pointer.point);
+            }
+        }
+        self.transform = transform;
+        self.push(ZoomEventType::Zoom);
+    }
+
+    fn push(&mut self, event_type: ZoomEventType) {
+        self.events.push(ZoomEvent {
+            event_type,
+            transform: self.transform,
+        });
+    }
+
+    fn clamp_scale(&self, k: f64) -> f64 {
+        k.min(self.scale_extent[1]).max(self.scale_extent[0])
+    }
+
+    // `transform` with its scale set to `k`, within the scale extent
+    fn scale(&self, transform: ZoomTransform, k: f64) -> ZoomTransform {
+        let k = self.clamp_scale(k);
+        ZoomTransform { k, ..transform }
+    }
+
+    fn constrained(&self, transform: ZoomTransform) -> ZoomTransform {
+        (self.constrain)(transform, self.extent, self.translate_extent)
+    }
+}
+
+/// Keeps the viewport inside the translate extent, centring it where the
+/// extent is smaller than the viewport
+pub fn default_constrain(
+    transform: ZoomTransform,
+    extent: Extent,
+    translate_extent: Extent,
+) -> ZoomTransform {
+    let fit = |d0: f64, d1: f64| {
+        if d1 > d0 {
+            (d0 + d1) / 2.0
+        } else if d0 < 0.0 {
+            d0
+        } else {
+            d1.max(0.0)
+        }
+    };
+    let dx0 = transform.invert_x(extent[0][0]) - translate_extent[0][0];
+    let dx1 = transform.invert_x(extent[1][0]) - translate_extent[1][0];
+    let dy0 = transform.invert_y(extent[0][1]) - translate_extent[0][1];
+    let dy1 = transform.invert_y(extent[1][1]) - translate_extent[1][1];
+    transform.translate(fit(dx0, dx1), fit(dy0, dy1))
+}
+
+/// D3's wheel delta: a 500-pixel scroll doubles or halves the scale, and
+/// ctrl (trackpad pinch) zooms ten times faster
+pub fn default_wheel_delta(delta_y: f64, mode: WheelDeltaMode, ctrl: bool) -> f64 {
+    let unit = match mode {
+        WheelDeltaMode::Pixel => 0.002,
+        WheelDeltaMode::Line => 0.05,
+        WheelDeltaMode::Page => 1.0,
+    };
+    -delta_y * unit * if ctrl { 10.0 } else { 1.0 }
+}
+
+// The transform that displays the untransformed point `anchor` at `point`
+fn translate(transform: ZoomTransform, point: [f64; 2], anchor: [f64; 2]) -> ZoomTransform {
+    ZoomTransform {
+        x: point[0] - anchor[0] * transform.k,
+        y: point[1] - anchor[1] * transform.k,
+        ..transform
+    }
+}
+
+fn centroid(extent: Extent) -> [f64; 2] {
+    midpoint(extent[0], extent[1])
+}
+
+fn midpoint(a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0]
+}
+
+fn distance2(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)
+}
+
+#[cfg(test)]
+mod tests;
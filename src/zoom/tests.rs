@@ -0,0 +1,283 @@
+//! Tests for d3-zoom (Rust)
+
+#[cfg(test)]
+mod tests {
+    use crate::scale::{ScaleLinear, ScaleTime};
+    use crate::zoom::*;
+    use chrono::NaiveDate;
+
+    fn types(events: &[ZoomEvent]) -> Vec<ZoomEventType> {
+        events.iter().map(|e| e.event_type).collect()
+    }
+
+    fn close(a: ZoomTransform, b: ZoomTransform) -> bool {
+        (a.k - b.k).abs() < 1e-9 && (a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_transform() {
+        let t = ZoomTransform::IDENTITY.translate(10.0, 20.0).scale_by(2.0);
+        assert_eq!(t, ZoomTransform::new(2.0, 10.0, 20.0));
+        assert_eq!(t.translate(5.0, 0.0), ZoomTransform::new(2.0, 20.0, 20.0));
+        assert_eq!(t.apply([1.0, 2.0]), [12.0, 24.0]);
+        assert_eq!(t.invert([12.0, 24.0]), [1.0, 2.0]);
+        assert_eq!(t.apply_x(3.0), 16.0);
+        assert_eq!(t.invert_y(20.0), 0.0);
+        assert_eq!(t.to_string(), "translate(10,20) scale(2)");
+        assert_eq!(ZoomTransform::default(), ZoomTransform::IDENTITY);
+    }
+
+    #[test]
+    fn test_rescale() {
+        let t = ZoomTransform::new(2.0, -100.0, 0.0);
+        let x = ScaleLinear::new([0.0, 10.0], [0.0, 100.0]);
+        let zx = t.rescale_x(&x);
+        assert_eq!(zx.domain(), [5.0, 10.0]);
+        // The rescaled scale draws where the transformed original would
+        assert_eq!(zx.scale(7.0), t.apply_x(x.scale(7.0)));
+        let zy = ZoomTransform::new(4.0, 0.0, -200.0).rescale_y(&x);
+        assert_eq!(zy.domain(), [5.0, 7.5]);
+
+        let day = |d| {
+            NaiveDate::from_ymd_opt(2024, 1, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let time = ScaleTime::new([day(1), day(11)], [0.0, 100.0]);
+        assert_eq!(t.rescale_x(&time).domain(), [day(6), day(11)]);
+    }
+
+    #[test]
+    fn test_drag_pans() {
+        let mut zoom = Zoom::new([[0.0, 0.0], [100.0, 100.0]]);
+        let events = zoom.handle(
+            ZoomInput::PointerDown {
+                id: 1,
+                point: [10.0, 10.0],
+            },
+            0.0,
+        );
+        assert_eq!(types(&events), [ZoomEventType::Start]);
+        assert!(zoom.is_active());
+        let events = zoom.handle(
+            ZoomInput::PointerMove {
+                id: 1,
+                point: [30.0, 5.0],
+            },
+            10.0,
+        );
+        assert_eq!(types(&events), [ZoomEventType::Zoom]);
+        assert_eq!(events[0].transform, ZoomTransform::new(1.0, 20.0, -5.0));
+        // Moves of other pointers are ignored
+        assert!(
+            zoom.handle(
+                ZoomInput::PointerMove {
+                    id: 2,
+                    point: [0.0, 0.0]
+                },
+                20.0
+            )
+            .is_empty()
+        );
+        let events = zoom.handle(ZoomInput::PointerUp { id: 1 }, 30.0);
+        assert_eq!(types(&events), [ZoomEventType::End]);
+        assert!(!zoom.is_active());
+    }
+
+    #[test]
+    fn test_pinch() {
+        let mut zoom = Zoom::new([[0.0, 0.0], [100.0, 100.0]]);
+        zoom.handle(
+            ZoomInput::PointerDown {
+                id: 1,
+                point: [40.0, 50.0],
+            },
+            0.0,
+        );
+        let events = zoom.handle(
+            ZoomInput::PointerDown {
+                id: 2,
+                point: [60.0, 50.0],
+            },
+            0.0,
+        );
+        assert!(events.is_empty());
+        // Spread the fingers to twice the distance about the same midpoint
+        zoom.handle(
+            ZoomInput::PointerMove {
+                id: 1,
+                point: [30.0, 50.0],
+            },
+            10.0,
+        );
+        zoom.handle(
+            ZoomInput::PointerMove {
+                id: 2,
+                point: [70.0, 50.0],
+            },
+            10.0,
+        );
+        let t = zoom.get_transform();
+        assert!(close(t, ZoomTransform::new(2.0, -50.0, -50.0)));
+        assert_eq!(t.invert([50.0, 50.0]), [50.0, 50.0]);
+        // Lifting one finger continues as a pan from where the other is
+        zoom.handle(ZoomInput::PointerUp { id: 1 }, 20.0);
+        assert!(zoom.is_active());
+        zoom.handle(
+            ZoomInput::PointerMove {
+                id: 2,
+                point: [80.0, 50.0],
+            },
+            30.0,
+        );
+        assert!(close(
+            zoom.get_transform(),
+            ZoomTransform::new(2.0, -40.0, -50.0)
+        ));
+        let events = zoom.handle(ZoomInput::PointerUp { id: 2 }, 40.0);
+        assert_eq!(types(&events), [ZoomEventType::End]);
+    }
+
+    #[test]
+    fn test_wheel() {
+        let mut zoom = Zoom::new([[0.0, 0.0], [100.0, 100.0]]).scale_extent([0.5, 4.0]);
+        let wheel = |delta_y| ZoomInput::Wheel {
+            point: [20.0, 20.0],
+            delta_y,
+            mode: WheelDeltaMode::Line,
+            ctrl: false,
+        };
+        let events = zoom.handle(wheel(-20.0), 0.0);
+        assert_eq!(types(&events), [ZoomEventType::Start, ZoomEventType::Zoom]);
+        assert!(close(
+            zoom.get_transform(),
+            ZoomTransform::new(2.0, -20.0, -20.0)
+        ));
+        // Clamped to the scale extent, about the same point
+        zoom.handle(wheel(-100.0), 100.0);
+        assert_eq!(zoom.get_transform().k, 4.0);
+        assert_eq!(zoom.get_transform().invert([20.0, 20.0]), [20.0, 20.0]);
+        assert!(zoom.needs_tick());
+        assert!(zoom.tick(200.0).is_empty());
+        assert_eq!(types(&zoom.tick(250.0)), [ZoomEventType::End]);
+        assert!(!zoom.needs_tick());
+        // Wheeling against the scale extent does nothing
+        assert!(zoom.handle(wheel(-1.0), 300.0).is_empty());
+
+        assert_eq!(
+            default_wheel_delta(-500.0, WheelDeltaMode::Pixel, false),
+            1.0
+        );
+        assert_eq!(default_wheel_delta(1.0, WheelDeltaMode::Page, true), -10.0);
+    }
+
+    #[test]
+    fn test_translate_extent() {
+        let mut zoom = Zoom::new([[0.0, 0.0], [100.0, 100.0]])
+            .scale_extent([1.0, 8.0])
+            .translate_extent([[0.0, 0.0], [100.0, 100.0]]);
+        zoom.translate_by(50.0, 0.0);
+        assert_eq!(zoom.get_transform(), ZoomTransform::IDENTITY);
+        zoom.scale_by(2.0, Some([0.0, 0.0]));
+        assert_eq!(zoom.get_transform(), ZoomTransform::new(2.0, 0.0, 0.0));
+        zoom.translate_by(-80.0, -10.0);
+        assert_eq!(zoom.get_transform(), ZoomTransform::new(2.0, -100.0, -20.0));
+        // Zooming out below the extent is clamped, then re-centred
+        zoom.scale_to(0.5, None);
+        assert_eq!(zoom.get_transform(), ZoomTransform::IDENTITY);
+
+        // Dragging cannot pan past the edge
+        zoom.scale_to(2.0, None);
+        zoom.handle(
+            ZoomInput::PointerDown {
+                id: 0,
+                point: [50.0, 50.0],
+            },
+            0.0,
+        );
+        zoom.handle(
+            ZoomInput::PointerMove {
+                id: 0,
+                point: [500.0, 50.0],
+            },
+            0.0,
+        );
+        assert_eq!(zoom.get_transform().x, 0.0);
+
+        let t = default_constrain(
+            ZoomTransform::new(0.5, 0.0, 0.0),
+            [[0.0, 0.0], [100.0, 100.0]],
+            [[0.0, 0.0], [100.0, 100.0]],
+        );
+        assert_eq!(t, ZoomTransform::new(0.5, 25.0, 25.0));
+    }
+
+    #[test]
+    fn test_programmatic() {
+        let mut zoom = Zoom::new([[0.0, 0.0], [200.0, 100.0]]);
+        let events = zoom.translate_to(0.0, 0.0, None);
+        assert_eq!(
+            types(&events),
+            [
+                ZoomEventType::Start,
+                ZoomEventType::Zoom,
+                ZoomEventType::End
+            ]
+        );
+        assert_eq!(zoom.get_transform(), ZoomTransform::new(1.0, 100.0, 50.0));
+        zoom.scale_by(3.0, None);
+        assert_eq!(zoom.get_transform(), ZoomTransform::new(3.0, 100.0, 50.0));
+        let t = ZoomTransform::new(0.25, 1.0, 2.0);
+        zoom.set_transform(t);
+        assert_eq!(zoom.get_transform(), t);
+        assert_eq!(zoom.get_extent(), [[0.0, 0.0], [200.0, 100.0]]);
+        assert_eq!(zoom.get_duration(), 250.0);
+    }
+
+    #[test]
+    fn test_double_click_transition() {
+        let mut zoom = Zoom::new([[0.0, 0.0], [100.0, 100.0]]);
+        let events = zoom.handle(
+            ZoomInput::DoubleClick {
+                point: [25.0, 25.0],
+                shift: false,
+            },
+            1000.0,
+        );
+        assert_eq!(types(&events), [ZoomEventType::Start]);
+        let mid = zoom.tick(1125.0);
+        assert_eq!(types(&mid), [ZoomEventType::Zoom]);
+        assert!(mid[0].transform.k > 1.0 && mid[0].transform.k < 2.0);
+        // The double-clicked point stays put throughout
+        let p = mid[0].transform.invert([25.0, 25.0]);
+        assert!((p[0] - 25.0).abs() < 1e-9 && (p[1] - 25.0).abs() < 1e-9);
+        let events = zoom.tick(1250.0);
+        assert_eq!(types(&events), [ZoomEventType::Zoom, ZoomEventType::End]);
+        assert_eq!(zoom.get_transform(), ZoomTransform::new(2.0, -25.0, -25.0));
+
+        // A pointer interrupts a transition, ending it before its own gesture
+        zoom.transition_to(ZoomTransform::IDENTITY, None, 2000.0);
+        zoom.tick(2100.0);
+        let events = zoom.handle(
+            ZoomInput::PointerDown {
+                id: 0,
+                point: [0.0, 0.0],
+            },
+            2100.0,
+        );
+        assert_eq!(types(&events), [ZoomEventType::End, ZoomEventType::Start]);
+        assert!(!zoom.needs_tick());
+
+        let mut instant = Zoom::new([[0.0, 0.0], [100.0, 100.0]]).duration(0.0);
+        let events = instant.handle(
+            ZoomInput::DoubleClick {
+                point: [0.0, 0.0],
+                shift: true,
+            },
+            0.0,
+        );
+        assert_eq!(events.len(), 3);
+        assert_eq!(instant.get_transform(), ZoomTransform::new(0.5, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,125 @@
+//! d3-zoom: the zoom transform
+
+use crate::scale::{ScaleLinear, ScaleLog, ScalePow, ScaleSymlog, ScaleTime};
+use std::fmt;
+
+/// A uniform scale `k` followed by a translation `(x, y)`: a point `p` is
+/// displayed at `p * k + (x, y)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoomTransform {
+    pub k: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Default for ZoomTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ZoomTransform {
+    /// k = 1, x = y = 0
+    pub const IDENTITY: ZoomTransform = ZoomTransform {
+        k: 1.0,
+        x: 0.0,
+        y: 0.0,
+    };
+
+    pub fn new(k: f64, x: f64, y: f64) -> Self {
+        Self { k, x, y }
+    }
+
+    /// This transform with its scale multiplied by `k`
+    pub fn scale_by(&self, k: f64) -> Self {
+        if k == 1.0 {
+            *self
+        } else {
+            Self::new(self.k * k, self.x, self.y)
+        }
+    }
+
+    /// This transform translated by `(x, y)` in its own (scaled) units
+    pub fn translate(&self, x: f64, y: f64) -> Self {
+        if x == 0.0 && y == 0.0 {
+            *self
+        } else {
+            Self::new(self.k, self.x + self.k * x, self.y + self.k * y)
+        }
+    }
+
+    pub fn apply(&self, point: [f64; 2]) -> [f64; 2] {
+        [self.apply_x(point[0]), self.apply_y(point[1])]
+    }
+
+    pub fn apply_x(&self, x: f64) -> f64 {
+        x * self.k + self.x
+    }
+
+    pub fn apply_y(&self, y: f64) -> f64 {
+        y * self.k + self.y
+    }
+
+    /// The point that [`apply`](Self::apply) maps to `point`
+    pub fn invert(&self, point: [f64; 2]) -> [f64; 2] {
+        [self.invert_x(point[0]), self.invert_y(point[1])]
+    }
+
+    pub fn invert_x(&self, x: f64) -> f64 {
+        (x - self.x) / self.k
+    }
+
+    pub fn invert_y(&self, y: f64) -> f64 {
+        (y - self.y) / self.k
+    }
+
+    /// A copy of `x` whose domain is transformed horizontally, so that
+    /// drawing with it shows the zoomed view of `x`
+    pub fn rescale_x<S: Rescale>(&self, x: &S) -> S {
+        x.rescale(&|r| self.invert_x(r))
+    }
+
+    /// Like [`rescale_x`](Self::rescale_x), vertically
+    pub fn rescale_y<S: Rescale>(&self, y: &S) -> S {
+        y.rescale(&|r| self.invert_y(r))
+    }
+}
+
+/// `translate(x,y) scale(k)`, for an SVG `transform` attribute
+impl fmt::Display for ZoomTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "translate({},{}) scale({})", self.x, self.y, self.k)
+    }
+}
+
+/// An invertible scale whose domain a [`ZoomTransform`] can rescale
+pub trait Rescale: Sized {
+    /// A copy whose domain maps from the range positions `invert(r)` of the
+    /// current range stops `r`
+    fn rescale(&self, invert: &dyn Fn(f64) -> f64) -> Self;
+}
+
+macro_rules! rescale_numeric {
+    ($scale:ident) => {
+        impl Rescale for $scale<f64> {
+            fn rescale(&self, invert: &dyn Fn(f64) -> f64) -> Self {
+                let mut scale = self.clone();
+                scale.domain = self.range.iter().map(|&r| self.invert(invert(r))).collect();
+                scale
+            }
+        }
+    };
+}
+
+rescale_numeric!(ScaleLinear);
+rescale_numeric!(ScaleLog);
+rescale_numeric!(ScalePow);
+rescale_numeric!(ScaleSymlog);
+
+impl Rescale for ScaleTime<f64> {
+    fn rescale(&self, invert: &dyn Fn(f64) -> f64) -> Self {
+        let mut scale = self.clone();
+        scale.domain = self.range.iter().map(|&r| self.invert(invert(r))).collect();
+        scale
+    }
+}
@@ -971,3 +971,34 @@ fn test_axis_positioning_accuracy() {
         assert!((hundred_tick.position - 950.0).abs() < 1e-10);
     }
 }
+
+#[test]
+fn test_axis_renders_any_scale() {
+    use rust_d3::scale::ScalePow;
+    use rust_d3::selection::Selection;
+
+    // Scales without a dedicated renderer go through the shared AxisScale path
+    let pow = ScalePow::new([0.0, 10.0], [0.0, 100.0], 2.0);
+    let axis = Axis::new(pow, AxisOrientation::Bottom).tick_count(2);
+    let ticks = axis.ticks();
+    assert_eq!(ticks.iter().map(|t| t.value).collect::<Vec<_>>(), vec![0.0, 5.0, 10.0]);
+    assert_eq!(ticks[1].label, "5");
+    assert!((ticks[1].position - 25.0).abs() < 1e-9);
+
+    let mut g = Selection::create("g");
+    axis.render(&mut g);
+    let svg = g.render();
+    assert!(svg.contains(r#"class="domain""#));
+    assert!(svg.contains(">5</text>"));
+
+    // Band ticks sit at the centre of each band and get the same domain line
+    let band = ScaleBand::new(vec!["a", "b"], [0.0, 100.0], 0.0, 0.0, 0.5);
+    let axis = Axis::new(band, AxisOrientation::Left);
+    let positions: Vec<f64> = axis.ticks().iter().map(|t| t.position).collect();
+    assert_eq!(positions, vec![25.0, 75.0]);
+    let mut g = Selection::create("g");
+    axis.render(&mut g);
+    let svg = g.render();
+    assert!(svg.contains(r#"class="domain""#));
+    assert!(svg.contains(">b</text>"));
+}
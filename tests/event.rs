@@ -0,0 +1,224 @@
+use rust_d3::dispatch::Event;
+use rust_d3::selection::{EventPhase, NodeEvent, Selection};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+// svg > g > rect, returning the three single-node selections
+fn tree() -> (Selection, Selection, Selection) {
+    let mut svg = Selection::create("svg");
+    let mut g = svg.append("g");
+    let rect = g.append("rect");
+    (svg, g, rect)
+}
+
+fn logger(
+    log: &Log,
+    label: &'static str,
+) -> impl FnMut(&mut Selection, &NodeEvent) + Clone + 'static {
+    let log = Rc::clone(log);
+    move |this, event| {
+        let phase = match event.phase {
+            EventPhase::Capturing => "capture",
+            EventPhase::AtTarget => "target",
+            EventPhase::Bubbling => "bubble",
+        };
+        let tag = this.node().unwrap().tag;
+        log.borrow_mut().push(format!("{label} {tag} {phase}"));
+    }
+}
+
+fn event(event_type: &'static str) -> Event<'static> {
+    Event {
+        event_type: Cow::Borrowed(event_type),
+        data: None,
+        timestamp: std::time::Instant::now(),
+        source: None,
+        propagation_stopped: Arc::new(AtomicBool::new(false)),
+        default_prevented: Arc::new(AtomicBool::new(false)),
+    }
+}
+
+#[test]
+fn test_capture_target_bubble_order() {
+    let (mut svg, mut g, mut rect) = tree();
+    let log = Log::default();
+    svg.on("click", logger(&log, "a"));
+    svg.on_capture("click.cap", logger(&log, "b"));
+    g.on("click", logger(&log, "c"));
+    g.on_capture("click.cap", logger(&log, "d"));
+    rect.on("click", logger(&log, "e"));
+    rect.on_capture("click.cap", logger(&log, "f"));
+    // Other types are not invoked
+    rect.on("mouseover", logger(&log, "x"));
+
+    rect.dispatch("click", None);
+    assert_eq!(
+        *log.borrow(),
+        [
+            "b svg capture",
+            "d g capture",
+            "f rect target",
+            "e rect target",
+            "c g bubble",
+            "a svg bubble",
+        ]
+    );
+}
+
+#[test]
+fn test_stop_propagation() {
+    let (mut svg, mut g, mut rect) = tree();
+    let log = Log::default();
+    svg.on("click", logger(&log, "svg"));
+    g.on("click.first", logger(&log, "g1"));
+    g.on("click.stop", |_, event| event.stop_propagation());
+    g.on("click.last", logger(&log, "g2"));
+    rect.on("click", logger(&log, "rect"));
+
+    rect.dispatch("click", None);
+    // Every listener on the stopping node still runs
+    assert_eq!(
+        *log.borrow(),
+        ["rect rect target", "g1 g bubble", "g2 g bubble"]
+    );
+
+    // Stopping in the capture phase keeps the event from the target
+    log.borrow_mut().clear();
+    svg.on_capture("click.guard", |_, event| event.stop_propagation());
+    rect.dispatch("click", None);
+    assert!(log.borrow().is_empty());
+}
+
+#[test]
+fn test_prevent_default_and_detail() {
+    let (mut svg, _g, mut rect) = tree();
+    let seen = Rc::new(RefCell::new(None));
+    let record = Rc::clone(&seen);
+    svg.on("select", move |_, event| {
+        *record.borrow_mut() = event.detail::<u32>().copied();
+        assert!(event.detail::<String>().is_none());
+        assert_eq!(event.target, *rect_key_of(event));
+        event.prevent_default();
+    });
+
+    let mut e = event("select");
+    e.data = Some(Arc::new(42u32));
+    assert!(!rect.dispatch_event(&e));
+    assert_eq!(*seen.borrow(), Some(42));
+
+    assert!(svg.off("select").dispatch_event(&event("select")));
+    // Dispatching to the root has no capture or bubble phase
+    assert!(rect.dispatch_event(&event("select")));
+}
+
+// The target is the node the event was dispatched to, whichever node's
+// listener is running
+fn rect_key_of<'a>(event: &'a NodeEvent) -> &'a rust_d3::selection::NodeKey {
+    assert_ne!(event.target, event.current_target);
+    &event.target
+}
+
+#[test]
+fn test_namespaces_and_removal() {
+    let (mut svg, _g, mut rect) = tree();
+    let log = Log::default();
+    svg.on("click.a mouseover.a", logger(&log, "a"));
+    svg.on("click.b", logger(&log, "b"));
+    svg.on("click", logger(&log, "plain"));
+    // Re-registering a typename replaces it in place
+    svg.on("click.a", logger(&log, "a2"));
+    rect.dispatch("click", None);
+    assert_eq!(
+        *log.borrow(),
+        ["a2 svg bubble", "b svg bubble", "plain svg bubble"]
+    );
+
+    log.borrow_mut().clear();
+    // `type` removes only the unnamed listener, `.name` all with that name
+    svg.off("click").off(".a");
+    rect.dispatch("click", None).dispatch("mouseover", None);
+    assert_eq!(*log.borrow(), ["b svg bubble"]);
+    svg.off("click.b");
+    assert!(svg.node().is_some());
+    rect.dispatch("click", None);
+    assert_eq!(log.borrow().len(), 1);
+}
+
+#[test]
+fn test_listeners_modify_nodes() {
+    let mut svg = Selection::create("svg");
+    let mut circles = svg.append("g");
+    for cx in ["10", "20"] {
+        circles.append("circle").attr("cx", cx);
+    }
+    let mut circles = circles.select_all(Some("circle"));
+    circles.on("click", |this, _| {
+        this.attr("fill", "red");
+    });
+    let mut second = svg.select_by("circle:nth-child(2)");
+    second.dispatch("click", None);
+    assert_eq!(second.get_attr("fill").as_deref(), Some("red"));
+    assert_eq!(svg.select_by("circle").get_attr("fill"), None);
+}
+
+#[test]
+fn test_listeners_use_the_document() {
+    let (mut svg, mut g, mut rect) = tree();
+    // Listeners may select, modify and dispatch on the same document
+    g.on("click", |this, _| {
+        this.select_by("rect")
+            .attr("fill", "red")
+            .dispatch("paint", None);
+    });
+    let log = Log::default();
+    svg.on("paint", logger(&log, "svg"));
+    rect.dispatch("click", None);
+    assert_eq!(rect.get_attr("fill").as_deref(), Some("red"));
+    assert_eq!(*log.borrow(), ["svg svg bubble"]);
+
+    // A listener dispatching its own event type is not re-entered
+    let count = Rc::new(RefCell::new(0));
+    let calls = Rc::clone(&count);
+    rect.on("ping", move |this, _| {
+        *calls.borrow_mut() += 1;
+        this.dispatch("ping", None);
+    });
+    rect.dispatch("ping", None);
+    assert_eq!(*count.borrow(), 1);
+}
+
+#[test]
+fn test_listener_changes_during_dispatch() {
+    let (mut svg, mut g, mut rect) = tree();
+    let log = Log::default();
+    // A listener removing itself runs once
+    rect.on("click", {
+        let log = Rc::clone(&log);
+        move |this, _| {
+            log.borrow_mut().push("once".into());
+            this.off("click");
+        }
+    });
+    // A listener removing an ancestor's listener stops it from running
+    g.on("click", |this, _| {
+        this.parent().off("click.late");
+    });
+    svg.on("click.late", logger(&log, "late"));
+    rect.dispatch("click", None).dispatch("click", None);
+    assert_eq!(*log.borrow(), ["once"]);
+
+    // A replacement registered during dispatch sticks
+    log.borrow_mut().clear();
+    let replacement = logger(&log, "new");
+    svg.on("click.swap", move |this, _| {
+        this.on("click.swap", replacement.clone());
+    });
+    rect.dispatch("click", None).dispatch("click", None);
+    assert_eq!(*log.borrow(), ["new svg bubble"]);
+    assert!(g.node().is_some());
+}
@@ -0,0 +1,107 @@
+use rust_d3::selection::{ParseError, Selection, Serializer};
+
+#[test]
+fn test_fill_svg_template() {
+    let template = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- report card -->
+<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+  <text id="title" x="10" y="20">Placeholder</text>
+  <g class="bars"></g>
+</svg>"#;
+    let mut doc = Selection::parse_svg(template).unwrap();
+    doc.select_by("#title").text("Q3 Revenue");
+    let mut bars = doc.select_by(".bars");
+    for h in [30, 50] {
+        bars.append("rect").attr("height", &h.to_string());
+    }
+    assert_eq!(
+        doc.render(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" height=\"100\" width=\"200\">\
+         <text id=\"title\" x=\"10\" y=\"20\">Q3 Revenue</text>\
+         <g class=\"bars\"><rect height=\"30\"/><rect height=\"50\"/></g></svg>"
+    );
+}
+
+#[test]
+fn test_round_trip_rendered_output() {
+    let mut svg = Selection::create("svg");
+    svg.attr("viewBox", "0 0 10 10");
+    svg.append("text")
+        .attr("data-label", "say \"hi\" & <bye>")
+        .text("1 < 2");
+    svg.append("style").text(".a > .b { fill: red }");
+    svg.append("path").attr("d", "M0,0L10,10");
+    let rendered = svg.render();
+    let parsed = Selection::parse_svg(&rendered).unwrap();
+    assert_eq!(parsed.render(), rendered);
+    let pretty = Serializer::new().pretty(2);
+    assert_eq!(
+        Selection::parse_svg(&svg.render_with(&pretty))
+            .unwrap()
+            .render(),
+        rendered
+    );
+}
+
+#[test]
+fn test_entities_and_cdata_are_decoded() {
+    let doc = Selection::parse_svg(
+        "<svg><text>é&lt;&#65;&#x42;&amp;&unknown;</text><desc><![CDATA[a < b]]></desc></svg>",
+    )
+    .unwrap();
+    let nodes = doc.clone_shallow().children().nodes();
+    assert_eq!(nodes[0].text.as_deref(), Some("é<AB&&unknown;"));
+    assert_eq!(nodes[1].text.as_deref(), Some("a < b"));
+}
+
+#[test]
+fn test_html_fragment_is_tolerant() {
+    let doc = Selection::parse_html(
+        "<div class=note><p>One<br>two<input disabled></div><span>after</span>",
+    )
+    .unwrap();
+    assert_eq!(doc.len(), 2);
+    assert_eq!(
+        doc.render(),
+        "<div class=\"note\"><p>Onetwo<br/><input disabled=\"\"/></p></div>"
+    );
+}
+
+#[test]
+fn test_xml_errors() {
+    assert_eq!(
+        Selection::parse_svg("<svg><g></svg>").err(),
+        Some(ParseError::UnmatchedClose {
+            tag: "svg".to_string(),
+            pos: 8
+        })
+    );
+    assert_eq!(
+        Selection::parse_svg("<svg><g/>").err(),
+        Some(ParseError::UnclosedElement {
+            tag: "svg".to_string()
+        })
+    );
+    assert_eq!(
+        Selection::parse_svg("<!-- nothing -->").err(),
+        Some(ParseError::NoRootElement)
+    );
+    assert!(Selection::parse_svg("<svg width=10/>").is_err());
+}
+
+#[test]
+fn test_html_and_append_markup_build_children() {
+    let mut svg = Selection::create("svg");
+    svg.append("text").text("old");
+    svg.html("<g><circle r=\"2\"/></g>");
+    assert_eq!(svg.render(), "<svg><g><circle r=\"2\"/></g></svg>");
+
+    let mut g = svg.select_by("g");
+    let added = g.append_markup("<rect/><line/>").unwrap();
+    assert_eq!(added.len(), 2);
+    assert_eq!(
+        svg.render(),
+        "<svg><g><circle r=\"2\"/><rect/><line/></g></svg>"
+    );
+    assert!(g.append_markup("<rect>").is_err());
+}
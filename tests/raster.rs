@@ -0,0 +1,97 @@
+use rust_d3::selection::{RasterError, RasterOptions, Selection};
+
+fn two_tone_chart() -> Selection {
+    let mut svg = Selection::create("svg");
+    svg.attr("width", "100").attr("height", "50");
+    svg.append("rect")
+        .attr("width", "50")
+        .attr("height", "50")
+        .attr("fill", "#ff0000");
+    svg.append("rect")
+        .attr("x", "50")
+        .attr("width", "50")
+        .attr("height", "50")
+        .attr("fill", "#0000ff");
+    svg
+}
+
+fn rgba(pixmap: &tiny_skia::Pixmap, x: u32, y: u32) -> (u8, u8, u8, u8) {
+    let p = pixmap.pixel(x, y).unwrap();
+    (p.red(), p.green(), p.blue(), p.alpha())
+}
+
+#[test]
+fn test_render_pixmap_at_scale() {
+    let pixmap = two_tone_chart().render_pixmap(100, 50, 2.0).unwrap();
+    assert_eq!((pixmap.width(), pixmap.height()), (200, 100));
+    assert_eq!(rgba(&pixmap, 10, 50), (255, 0, 0, 255));
+    assert_eq!(rgba(&pixmap, 190, 50), (0, 0, 255, 255));
+}
+
+#[test]
+fn test_thumbnail_fits_and_centers() {
+    // 100x50 drawing into a 40x40 box: scaled by 0.4, letterboxed vertically
+    let options = RasterOptions::new().background("#ffffff");
+    let pixmap = two_tone_chart()
+        .render_pixmap_with(40, 40, 1.0, &options)
+        .unwrap();
+    assert_eq!(rgba(&pixmap, 20, 2), (255, 255, 255, 255));
+    assert_eq!(rgba(&pixmap, 5, 20), (255, 0, 0, 255));
+    assert_eq!(rgba(&pixmap, 35, 20), (0, 0, 255, 255));
+}
+
+#[test]
+fn test_transparent_by_default() {
+    let mut svg = Selection::create("svg");
+    svg.attr("width", "10").attr("height", "10");
+    let pixmap = svg.render_pixmap(10, 10, 1.0).unwrap();
+    assert_eq!(rgba(&pixmap, 5, 5), (0, 0, 0, 0));
+}
+
+#[test]
+fn test_svg_without_size_uses_requested_size() {
+    let mut svg = Selection::create("svg");
+    svg.append("rect")
+        .attr("width", "20")
+        .attr("height", "20")
+        .attr("fill", "#00ff00");
+    let pixmap = svg.render_pixmap(20, 20, 1.0).unwrap();
+    assert_eq!(rgba(&pixmap, 19, 19), (0, 255, 0, 255));
+}
+
+#[test]
+fn test_render_png_signature() {
+    let png = two_tone_chart().render_png(100, 50, 1.0).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+}
+
+#[test]
+fn test_errors() {
+    let chart = two_tone_chart();
+    assert!(matches!(
+        chart.render_png(0, 50, 1.0),
+        Err(RasterError::InvalidSize { width: 0, .. })
+    ));
+    assert!(matches!(
+        chart.render_pixmap(10, 10, f32::NAN),
+        Err(RasterError::InvalidSize { .. })
+    ));
+    let options = RasterOptions::new().background("not-a-colour");
+    assert!(matches!(
+        chart.render_pixmap_with(10, 10, 1.0, &options),
+        Err(RasterError::InvalidColor(_))
+    ));
+    assert!(matches!(
+        Selection::create("g").render_png(10, 10, 1.0),
+        Err(RasterError::Svg(_))
+    ));
+    let mut svg = Selection::create("svg");
+    assert!(matches!(
+        svg.select_all(Some("rect")).render_png(10, 10, 1.0),
+        Err(RasterError::EmptySelection)
+    ));
+    assert!(matches!(
+        RasterOptions::new().font_file("does/not/exist.ttf"),
+        Err(RasterError::Font(_))
+    ));
+}
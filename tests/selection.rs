@@ -1,4 +1,4 @@
-use rust_d3::selection::{Arena, Node, Selection};
+use rust_d3::selection::{Arena, DataJoin, Node, Selection};
 use slotmap::SlotMap;
 use std::cell::RefCell;
 use std::collections::HashSet;
@@ -349,7 +349,7 @@ fn test_clone_event_handlers_not_cloned() {
     let mut rect = svg.append("rect");
 
     // Add event handler to original
-    rect.on("click", |_node| { /* handler */ });
+    rect.on("click", |_node, _event| { /* handler */ });
 
     // Verify the event handler was added
     {
@@ -664,6 +664,133 @@ fn test_data_join_keyed_order_preservation() {
     assert_eq!(update_nodes[1].data.as_ref().unwrap(), "A");
 }
 
+#[test]
+fn test_data_join_key_fn_runs_once_per_datum() {
+    let mut root = Selection::create("div");
+    let calls = std::cell::Cell::new(0);
+    let key_fn = |d: &&str, _: usize| {
+        calls.set(calls.get() + 1);
+        d.to_string()
+    };
+    let mut join = root
+        .select_all(Some("p"))
+        .data_with_key(&["A", "B", "C"], key_fn);
+    join.enter.join("p");
+    assert_eq!(calls.get(), 3);
+
+    // Existing nodes keep the key they were bound with, so only each datum
+    // is keyed, entering or not
+    calls.set(0);
+    let mut join = root
+        .select_all(Some("p"))
+        .data_with_key(&["X", "A", "Y", "Z", "C"], key_fn);
+    assert_eq!(calls.get(), 5);
+    // Entering nodes go before the next matched node
+    join.enter.append("p");
+    join.exit.remove();
+    let order: Vec<String> = root
+        .select_all(Some("p"))
+        .nodes()
+        .iter()
+        .map(|n| n.data.clone().unwrap())
+        .collect();
+    assert_eq!(order, ["X", "A", "Y", "Z", "C"]);
+}
+
+fn join_counts(join: &DataJoin) -> (usize, usize, usize) {
+    (join.update.len(), join.enter.len(), join.exit.len())
+}
+
+#[test]
+fn test_data_join_rebinds_nodes_bound_by_index() {
+    #[derive(Clone)]
+    struct Row {
+        id: u32,
+        value: &'static str,
+    }
+    let rows = [Row { id: 1, value: "a" }, Row { id: 2, value: "b" }];
+
+    // Nodes bound by `data` are keyed by their string data
+    let mut root = Selection::create("ul");
+    root.select_all(Some("li")).data(&["1", "2"]).join("li");
+    let join = root
+        .select_all(Some("li"))
+        .data_with_key(&["2", "3"], |d, _| d.to_string());
+    assert_eq!(join_counts(&join), (1, 1, 1));
+
+    // Typed nodes are keyed by applying the key function to their datum
+    let mut root = Selection::create("ul");
+    root.select_all(Some("li")).data_typed(&rows).join("li");
+    let moved = [Row { id: 2, value: "c" }, Row { id: 1, value: "d" }];
+    let join = root
+        .select_all(Some("li"))
+        .data_typed_with_key(&moved, |r: &Row, _| r.id);
+    assert_eq!(join_counts(&join), (2, 0, 0));
+    let values: Vec<_> = join
+        .update
+        .nodes()
+        .iter()
+        .map(|n| n.datum::<Row>().unwrap().value)
+        .collect();
+    assert_eq!(values, ["c", "d"]);
+}
+
+#[test]
+fn test_data_join_enters_under_each_group_parent() {
+    let mut root = Selection::create("div");
+    for _ in 0..2 {
+        root.append("section").append("p");
+    }
+    let mut sections = root.select_all(Some("section"));
+
+    let mut join = sections.select_all(Some("p")).data(&["a", "b"]);
+    assert_eq!(join_counts(&join), (2, 2, 0));
+    let merged = join.join("p");
+    assert_eq!(merged.len(), 4);
+    for &key in sections.iter() {
+        let mut section = Selection::new(root.arena(), vec![key]);
+        assert_eq!(section.select_all(Some("p")).len(), 2);
+    }
+
+    let mut join = sections
+        .select_all(Some("p"))
+        .data_with_key(&["b", "c"], |d, _| d.to_string());
+    assert_eq!(join_counts(&join), (2, 2, 2));
+    join.join("p");
+    for &key in sections.iter() {
+        let mut section = Selection::new(root.arena(), vec![key]);
+        let data: Vec<_> = section
+            .select_all(Some("p"))
+            .nodes()
+            .iter()
+            .map(|n| n.data.clone().unwrap())
+            .collect();
+        assert_eq!(data, ["b", "c"]);
+    }
+}
+
+#[test]
+fn test_data_join_borrowed_data() {
+    // Data borrowed from a local string, not `'static`
+    let text = String::from("a b c");
+    let words: Vec<&str> = text.split(' ').collect();
+    let mut root = Selection::create("div");
+    root.select_all(Some("p")).data(&words).join("p");
+    let mut join = root
+        .select_all(Some("p"))
+        .data_with_key(&words[1..], |d, _| d.to_string());
+    assert_eq!(join.update.len(), 2);
+    assert_eq!(join.exit.len(), 1);
+    join.exit.remove();
+    let data: Vec<String> = root
+        .select_all(Some("p"))
+        .nodes()
+        .iter()
+        .map(|n| n.data.clone().unwrap())
+        .collect();
+    assert_eq!(data, ["b", "c"]);
+}
+
 #[test]
 fn test_data_join_join() {
     let arena = Rc::new(RefCell::new(Arena {
@@ -894,3 +1021,210 @@ fn test_order_method() {
         .collect();
     assert_eq!(reordered_ids, vec!["rect3", "rect2", "rect1"]);
 }
+
+#[test]
+fn test_keyed_join_reuses_nodes_for_records() {
+    #[derive(Clone)]
+    struct Row {
+        id: u32,
+        value: f64,
+    }
+    impl std::fmt::Display for Row {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}:{}", self.id, self.value)
+        }
+    }
+    let mut svg = Selection::create("svg");
+    let rows = vec![Row { id: 1, value: 1.0 }, Row { id: 2, value: 2.0 }];
+    let mut join = svg.select_all(Some("rect")).data_with_key(&rows, |r, _| r.id);
+    let first = join.join("rect");
+    let first_keys: Vec<_> = first.iter().cloned().collect();
+
+    // Values change but ids stay: every node is reused
+    let rows = vec![Row { id: 2, value: 5.0 }, Row { id: 1, value: 7.0 }];
+    let join = svg.select_all(Some("rect")).data_with_key(&rows, |r, _| r.id);
+    assert_eq!(join.enter.len(), 0);
+    assert_eq!(join.exit.len(), 0);
+    let update_keys: Vec<_> = join.update.iter().cloned().collect();
+    assert_eq!(update_keys, vec![first_keys[1], first_keys[0]]);
+    let data: Vec<_> = join.update.map(|n| n.data.clone().unwrap());
+    assert_eq!(data, vec!["2:5", "1:7"]);
+}
+
+#[test]
+fn test_keyed_join_duplicate_keys() {
+    let mut root = Selection::create("div");
+    let mut join = root
+        .select_all(Some("p"))
+        .data_with_key(&["a", "b", "c"], |d, _| d.to_string());
+    join.join("p");
+    // Give a fourth node the duplicate key "a"
+    let mut join = root
+        .select_all(Some("p"))
+        .data_with_key(&["a", "b", "c", "a"], |d, _| d.to_string());
+    assert_eq!(join.update.len(), 3);
+    assert_eq!(join.enter.len(), 1); // duplicate datum enters
+    join.join("p");
+
+    let join = root
+        .select_all(Some("p"))
+        .data_with_key(&["a", "b", "c"], |d, _| d.to_string());
+    // The later node with key "a" exits
+    assert_eq!(join.update.len(), 3);
+    assert_eq!(join.exit.len(), 1);
+    let all: Vec<_> = root.select_all(Some("p")).iter().cloned().collect();
+    assert_eq!(join.exit.iter().cloned().collect::<Vec<_>>(), vec![all[3]]);
+}
+
+#[test]
+fn test_keyed_join_exit_keeps_node_order() {
+    let mut root = Selection::create("div");
+    let mut join = root
+        .select_all(Some("p"))
+        .data_with_key(&["a", "b", "c", "d", "e"], |d, _| d.to_string());
+    join.join("p");
+    let join = root
+        .select_all(Some("p"))
+        .data_with_key(&["c"], |d, _| d.to_string());
+    let exit: Vec<_> = join.exit.map(|n| n.data.clone().unwrap());
+    assert_eq!(exit, vec!["a", "b", "d", "e"]);
+}
+
+#[test]
+fn test_join_inserts_enter_in_data_order() {
+    let mut root = Selection::create("ul");
+    root.append("h1");
+    let mut join = root
+        .select_all(Some("li"))
+        .data_with_key(&["a", "c"], |d, _| d.to_string());
+    join.join("li");
+
+    let mut join = root
+        .select_all(Some("li"))
+        .data_with_key(&["a", "b", "c", "d"], |d, _| d.to_string());
+    let merged = join.join("li");
+    let order: Vec<_> = merged.map(|n| n.data.clone().unwrap());
+    assert_eq!(order, vec!["a", "b", "c", "d"]);
+    // Document order follows data order and leaves other children in place
+    assert_eq!(
+        root.render(),
+        "<ul><h1/><li/><li/><li/><li/></ul>"
+    );
+    let doc: Vec<_> = root
+        .children()
+        .map(|n| n.data.clone().unwrap_or_default());
+    assert_eq!(doc, vec!["", "a", "b", "c", "d"]);
+}
+
+#[test]
+fn test_join_reorders_update_nodes() {
+    let mut root = Selection::create("ul");
+    let mut join = root
+        .select_all(Some("li"))
+        .data_with_key(&["a", "b", "c"], |d, _| d.to_string());
+    join.join("li");
+    let mut join = root
+        .select_all(Some("li"))
+        .data_with_key(&["c", "x", "a"], |d, _| d.to_string());
+    join.join("li");
+    let doc: Vec<_> = root.children().map(|n| n.data.clone().unwrap());
+    assert_eq!(doc, vec!["c", "x", "a"]);
+}
+
+#[test]
+fn test_data_join_new_matches_children() {
+    let list = Selection::create("ul");
+    let mut join = DataJoin::new(&list, &[1, 2, 3], |d, _| *d);
+    assert_eq!(join.enter().len(), 3);
+    join.join("li");
+
+    let join = DataJoin::new(&list, &[3, 4], |d, _| *d);
+    assert_eq!(join.update().len(), 1);
+    assert_eq!(join.enter().len(), 1);
+    assert_eq!(join.exit().len(), 2);
+    assert_eq!(join.update().node().unwrap().data.as_deref(), Some("3"));
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Sale {
+    id: u32,
+    region: &'static str,
+    amount: f64,
+}
+
+fn sales() -> Vec<Sale> {
+    vec![
+        Sale { id: 1, region: "north", amount: 30.0 },
+        Sale { id: 2, region: "south", amount: 10.0 },
+        Sale { id: 3, region: "east", amount: 20.0 },
+    ]
+}
+
+#[test]
+fn test_typed_data_accessors() {
+    let mut svg = Selection::create("svg");
+    let mut join = svg.select_all(Some("rect")).data_typed(&sales());
+    let mut bars = join.join("rect");
+    bars.attr_typed("height", |s: &Sale, _| s.amount.to_string())
+        .style_typed("fill", |s: &Sale, _| {
+            if s.region == "north" { "red".into() } else { "blue".into() }
+        })
+        .attr_fn("title", |node, _, _| node.datum::<Sale>().unwrap().region.to_string());
+    let nodes = bars.nodes();
+    assert_eq!(nodes[0].attributes["height"], "30");
+    assert_eq!(nodes[0].attributes["style"], "fill:red");
+    assert_eq!(nodes[2].attributes["title"], "east");
+    // Typed-only binding leaves the string view empty
+    assert!(nodes[0].data.is_none());
+}
+
+#[test]
+fn test_typed_each_filter_sort() {
+    let mut svg = Selection::create("svg");
+    let mut join = svg.select_all(Some("rect")).data_typed(&sales());
+    let mut bars = join.join("rect");
+
+    let big = bars.filter_typed(|s: &Sale| s.amount >= 20.0);
+    assert_eq!(big.len(), 2);
+
+    bars.sort_by_typed(|a: &Sale, b: &Sale| a.amount.partial_cmp(&b.amount).unwrap());
+    let ids: Vec<u32> = bars.map(|n| n.datum::<Sale>().unwrap().id);
+    assert_eq!(ids, vec![2, 3, 1]);
+
+    let mut seen = Vec::new();
+    bars.each_typed(|node, s: &Sale| {
+        node.text = Some(s.region.to_string());
+        seen.push(s.id);
+    });
+    assert_eq!(seen, vec![2, 3, 1]);
+    assert_eq!(bars.node().unwrap().text.as_deref(), Some("south"));
+}
+
+#[test]
+fn test_typed_keyed_join_uses_key_fn_on_node_data() {
+    let mut svg = Selection::create("svg");
+    let mut join = svg
+        .select_all(Some("rect"))
+        .data_typed_with_key(&sales(), |s, _| s.id);
+    join.join("rect");
+
+    let mut updated = sales();
+    updated[0].amount = 99.0;
+    updated.remove(1);
+    let join = svg
+        .select_all(Some("rect"))
+        .data_typed_with_key(&updated, |s, _| s.id);
+    assert_eq!(join.update.len(), 2);
+    assert_eq!(join.exit.len(), 1);
+    assert_eq!(join.exit.node().unwrap().datum::<Sale>().unwrap().id, 2);
+    assert_eq!(join.update.node().unwrap().datum::<Sale>().unwrap().amount, 99.0);
+}
+
+#[test]
+fn test_datum_is_inherited_by_appended_children() {
+    let mut svg = Selection::create("svg");
+    let mut g = svg.append("g");
+    g.datum_typed(sales()[0].clone());
+    let label = g.append("text");
+    assert_eq!(label.node().unwrap().datum::<Sale>(), Some(&sales()[0]));
+}
@@ -19,7 +19,7 @@ fn test_selector_parsing(selector: &str, id: Option<&str>, classes: &[&str], exp
 
     // Test tag selector
     let selector = "svg";
-    let parsed_selector = rust_d3::selection::utils::parse_selector(selector);
+    let parsed_selector = rust_d3::selection::utils::parse_selector(selector).unwrap();
     assert!(parsed_selector.matches(
         &node.tag,
         node.attributes.get("id").map(|s| s.as_str()),
@@ -28,7 +28,7 @@ fn test_selector_parsing(selector: &str, id: Option<&str>, classes: &[&str], exp
 
     // Test id selector
     let id_selector = "#chart";
-    let parsed_selector = rust_d3::selection::utils::parse_selector(id_selector);
+    let parsed_selector = rust_d3::selection::utils::parse_selector(id_selector).unwrap();
     assert!(parsed_selector.matches(
         &node.tag,
         node.attributes.get("id").map(|s| s.as_str()),
@@ -37,7 +37,7 @@ fn test_selector_parsing(selector: &str, id: Option<&str>, classes: &[&str], exp
 
     // Test class selector
     let class_selector = ".axis";
-    let parsed_selector = rust_d3::selection::utils::parse_selector(class_selector);
+    let parsed_selector = rust_d3::selection::utils::parse_selector(class_selector).unwrap();
     assert!(parsed_selector.matches(
         &node.tag,
         node.attributes.get("id").map(|s| s.as_str()),
@@ -46,7 +46,7 @@ fn test_selector_parsing(selector: &str, id: Option<&str>, classes: &[&str], exp
 
     // Test compound selector
     let compound_selector = "svg.axis.major";
-    let parsed_selector = rust_d3::selection::utils::parse_selector(compound_selector);
+    let parsed_selector = rust_d3::selection::utils::parse_selector(compound_selector).unwrap();
     assert!(parsed_selector.matches(
         &node.tag,
         node.attributes.get("id").map(|s| s.as_str()),
@@ -55,7 +55,7 @@ fn test_selector_parsing(selector: &str, id: Option<&str>, classes: &[&str], exp
 
     // Test wildcard selector
     let wildcard_selector = "*";
-    let parsed_selector = rust_d3::selection::utils::parse_selector(wildcard_selector);
+    let parsed_selector = rust_d3::selection::utils::parse_selector(wildcard_selector).unwrap();
     assert!(parsed_selector.matches(
         &node.tag,
         node.attributes.get("id").map(|s| s.as_str()),
@@ -135,29 +135,27 @@ fn test_d3_selector_examples(selector: &str, expected_len: usize) {
 fn test_selector_edge_cases() {
     use rust_d3::selection::utils::parse_selector;
 
-    // Test empty selector
-    let empty_selector = parse_selector("");
-    assert!(empty_selector.tag.is_none());
-    assert!(empty_selector.id.is_none());
-    assert!(empty_selector.classes.is_empty());
-    assert!(!empty_selector.is_wildcard);
+    // Empty selectors, combinators and lists are not compound selectors
+    assert!(parse_selector("").is_err());
+    assert!(parse_selector("div > p").is_err());
+    assert!(parse_selector("rect, circle").is_err());
 
     // Test class-only selector
-    let class_only = parse_selector(".test");
+    let class_only = parse_selector(".test").unwrap();
     assert!(class_only.tag.is_none());
     assert!(class_only.id.is_none());
     assert_eq!(class_only.classes, vec!["test".to_string()]);
     assert!(!class_only.is_wildcard);
 
     // Test id-only selector
-    let id_only = parse_selector("#myid");
+    let id_only = parse_selector("#myid").unwrap();
     assert!(id_only.tag.is_none());
     assert_eq!(id_only.id, Some("myid".to_string()));
     assert!(id_only.classes.is_empty());
     assert!(!id_only.is_wildcard);
 
     // Test complex compound selector
-    let complex = parse_selector("rect#myid.class1.class2");
+    let complex = parse_selector("rect#myid.class1.class2").unwrap();
     assert_eq!(complex.tag, Some("rect".to_string()));
     assert_eq!(complex.id, Some("myid".to_string()));
     assert_eq!(
@@ -167,9 +165,134 @@ fn test_selector_edge_cases() {
     assert!(!complex.is_wildcard);
 
     // Test wildcard
-    let wildcard = parse_selector("*");
+    let wildcard = parse_selector("*").unwrap();
     assert!(wildcard.tag.is_none());
     assert!(wildcard.id.is_none());
     assert!(wildcard.classes.is_empty());
     assert!(wildcard.is_wildcard);
 }
+
+#[test]
+fn test_invalid_selector_matches_nothing() {
+    use rust_d3::selection::utils::{find_matching_descendants, parse_selector};
+
+    let arena = Rc::new(RefCell::new(Arena {
+        nodes: slotmap::SlotMap::with_key(),
+    }));
+    let mut svg = Selection::root(Rc::clone(&arena), "svg");
+    svg.append("g").append("rect");
+    assert_eq!(svg.select_all(Some("div >")).len(), 0);
+    assert_eq!(svg.select_all(Some("#")).len(), 0);
+
+    // A failed parse cannot be mistaken for `*`
+    let mut found = Vec::new();
+    if let Ok(selector) = parse_selector("g >") {
+        let keys: Vec<_> = svg.iter().copied().collect();
+        find_matching_descendants(Rc::clone(&arena), &keys, &selector, &mut found);
+    }
+    assert!(found.is_empty());
+}
+
+/// <svg>
+///   <g class="axis x">
+///     <g class="tick" data-id="1"><line/><text>a</text></g>
+///     <g class="tick" data-id="2"><line/><text>b</text></g>
+///     <g class="tick minor" data-id="3"><line/><text>c</text></g>
+///   </g>
+///   <text class="title">t</text>
+/// </svg>
+fn axis_fixture() -> Selection {
+    let mut svg = Selection::create("svg");
+    let mut axis = svg.append("g");
+    axis.attr("class", "axis x");
+    for (i, label) in ["a", "b", "c"].iter().enumerate() {
+        let mut tick = axis.append("g");
+        tick.attr("class", if i == 2 { "tick minor" } else { "tick" })
+            .attr("data-id", &(i + 1).to_string());
+        tick.append("line");
+        tick.append("text").text(label);
+    }
+    svg.append("text").attr("class", "title").text("t");
+    svg
+}
+
+#[test_case(".tick line", 3 ; "descendant")]
+#[test_case(".axis text", 3 ; "descendant skipping levels")]
+#[test_case("svg > text", 1 ; "child")]
+#[test_case("g > text", 3 ; "child of any g")]
+#[test_case(".axis > .tick > line", 3 ; "child chain")]
+#[test_case("[data-id]", 3 ; "attribute exists")]
+#[test_case("[data-id=\"3\"]", 1 ; "attribute equals quoted")]
+#[test_case("g[data-id='2'] text", 1 ; "attribute with descendant")]
+#[test_case("[class~=minor]", 1 ; "attribute includes word")]
+#[test_case("[class^=ti]", 4 ; "attribute prefix")]
+#[test_case(".tick:first-child", 1 ; "first child")]
+#[test_case(".tick:last-child text", 1 ; "last child")]
+#[test_case(".tick:nth-child(2)", 1 ; "nth child index")]
+#[test_case(".tick:nth-child(odd)", 2 ; "nth child odd")]
+#[test_case(".tick:nth-child(-n+2)", 2 ; "nth child formula")]
+#[test_case(".tick:not(.minor)", 2 ; "not")]
+#[test_case("text:not(.tick text)", 1 ; "not with complex selector")]
+#[test_case("line, .title", 4 ; "selector list")]
+#[test_case("g > > line", 0 ; "invalid selector matches nothing")]
+fn test_complex_selectors(selector: &str, expected_len: usize) {
+    let mut svg = axis_fixture();
+    assert_eq!(svg.select_all(Some(selector)).len(), expected_len);
+}
+
+#[test]
+fn test_selectors_across_selection_methods() {
+    let mut svg = axis_fixture();
+    let mut axis = svg.select("g.axis");
+    assert_eq!(axis.len(), 1);
+
+    // find_all only considers direct children
+    assert_eq!(axis.find_all(".tick:not(.minor)").len(), 2);
+    assert_eq!(axis.find_all("line").len(), 0);
+
+    // select returns the first match in document order
+    let first = svg.select(".tick text").node().unwrap();
+    assert_eq!(first.text.as_deref(), Some("a"));
+
+    // select_by includes the selected nodes themselves
+    let all = svg.select_by("svg, [data-id='3'] > text");
+    let tags: Vec<String> = all.nodes().iter().map(|n| n.tag.clone()).collect();
+    assert_eq!(tags, vec!["svg", "text"]);
+
+    let found = svg.find(".minor text").unwrap();
+    assert_eq!(found.text.as_deref(), Some("c"));
+    assert!(svg.find("circle, [data-id='9']").is_none());
+}
+
+#[test]
+fn test_selector_list_parsing() {
+    use rust_d3::selection::{Combinator, PseudoClass, SelectorError, parse_selector_list};
+
+    let list = parse_selector_list("g.axis > .tick line, text").unwrap();
+    assert_eq!(list.selectors.len(), 2);
+    assert_eq!(
+        list.selectors[0].combinators,
+        vec![Combinator::Child, Combinator::Descendant]
+    );
+    assert_eq!(list.selectors[0].compounds[0].tag, Some("g".to_string()));
+
+    let nth = parse_selector_list("li:nth-child(2n+1)").unwrap();
+    assert_eq!(
+        nth.selectors[0].compounds[0].pseudos,
+        vec![PseudoClass::NthChild(2, 1)]
+    );
+
+    assert_eq!(parse_selector_list("a,"), Err(SelectorError::Empty));
+    assert_eq!(
+        parse_selector_list(":hover"),
+        Err(SelectorError::UnsupportedPseudo("hover".to_string()))
+    );
+    assert_eq!(
+        parse_selector_list("li:nth-child(x)"),
+        Err(SelectorError::InvalidNth("x".to_string()))
+    );
+    assert_eq!(
+        parse_selector_list("[data-id=\"3\""),
+        Err(SelectorError::UnexpectedEnd)
+    );
+}
@@ -0,0 +1,113 @@
+use rust_d3::selection::{Selection, Serializer};
+
+#[test]
+fn test_attribute_and_text_escaping() {
+    let mut svg = Selection::create("svg");
+    svg.append("text")
+        .attr("data-label", "say \"hi\" & <bye>")
+        .text("1 < 2 && 3 > 2");
+    assert_eq!(
+        svg.render(),
+        "<svg><text data-label=\"say &quot;hi&quot; &amp; &lt;bye&gt;\">1 &lt; 2 &amp;&amp; 3 &gt; 2</text></svg>"
+    );
+}
+
+#[test]
+fn test_attribute_order_is_stable() {
+    let build = |order: &[(&str, &str)]| {
+        let mut svg = Selection::create("svg");
+        let mut rect = svg.append("rect");
+        for (k, v) in order {
+            rect.attr(k, v);
+        }
+        svg.render()
+    };
+    let a = build(&[("y", "2"), ("x", "1"), ("width", "3"), ("height", "4")]);
+    let b = build(&[("height", "4"), ("width", "3"), ("x", "1"), ("y", "2")]);
+    assert_eq!(a, b);
+    assert_eq!(a, "<svg><rect height=\"4\" width=\"3\" x=\"1\" y=\"2\"/></svg>");
+}
+
+#[test]
+fn test_style_and_class_keep_declaration_order() {
+    for _ in 0..10 {
+        let mut svg = Selection::create("svg");
+        let mut rect = svg.append("rect");
+        rect.style("fill", "red")
+            .style("stroke", "blue")
+            .style("opacity", "0.5")
+            .classed("bar", true)
+            .classed("highlight", true)
+            .classed("active", true);
+        rect.style("fill", "green");
+        assert_eq!(
+            svg.render(),
+            "<svg><rect class=\"bar highlight active\" style=\"fill:green;stroke:blue;opacity:0.5\"/></svg>"
+        );
+    }
+}
+
+#[test]
+fn test_xml_declaration_and_namespaces() {
+    let mut svg = Selection::create("svg");
+    svg.append("use").attr("xlink:href", "#marker");
+    let out = svg.render_with(&Serializer::new().xml_declaration(true).namespaces(true));
+    assert_eq!(
+        out,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\"><use xlink:href=\"#marker\"/></svg>"
+    );
+}
+
+#[test]
+fn test_existing_xmlns_is_not_duplicated() {
+    let mut svg = Selection::create("svg");
+    svg.attr("xmlns", "http://www.w3.org/2000/svg").attr("width", "10");
+    let out = svg.render_with(&Serializer::new().namespaces(true));
+    assert_eq!(out, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\"/>");
+}
+
+#[test]
+fn test_prefixed_tags_resolve_namespaces() {
+    let mut svg = Selection::create("svg");
+    let mut fo = svg.append("svg:foreignObject");
+    fo.append("xhtml:div").text("hello");
+    let out = svg.render_with(&Serializer::new().namespaces(true));
+    assert_eq!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"><foreignObject><div xmlns=\"http://www.w3.org/1999/xhtml\">hello</div></foreignObject></svg>"
+    );
+    // Without namespace handling tags are written as stored
+    assert!(svg.render().contains("<svg:foreignObject>"));
+}
+
+#[test]
+fn test_pretty_print() {
+    let mut svg = Selection::create("svg");
+    let mut g = svg.append("g");
+    g.append("rect").attr("x", "1");
+    g.append("text").text("label");
+    svg.append("circle");
+    let out = svg.render_with(&Serializer::new().pretty(2));
+    assert_eq!(
+        out,
+        "<svg>\n  <g>\n    <rect x=\"1\"/>\n    <text>label</text>\n  </g>\n  <circle/>\n</svg>\n"
+    );
+}
+
+#[test]
+fn test_style_block_uses_cdata() {
+    let mut svg = Selection::create("svg");
+    svg.append("style").text(".a > .b { fill: red } ]]> tail");
+    assert_eq!(
+        svg.render(),
+        "<svg><style><![CDATA[.a > .b { fill: red } ]]]]><![CDATA[> tail]]></style></svg>"
+    );
+}
+
+#[test]
+fn test_enter_placeholders_are_not_rendered() {
+    let mut svg = Selection::create("svg");
+    let join = svg.select_all(Some("rect")).data(&[1, 2]);
+    assert_eq!(join.enter.len(), 2);
+    assert_eq!(svg.render(), "<svg/>");
+}
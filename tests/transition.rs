@@ -0,0 +1,188 @@
+use rust_d3::ease;
+use rust_d3::selection::Selection;
+use rust_d3::timer::ManualClock;
+use rust_d3::transition::TransitionState;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn test_attr_interpolates_on_manual_clock() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("x", "0");
+    let clock = ManualClock::new();
+    let mut t = rect.transition();
+    t.clock(&clock).duration(100.0).ease(ease::linear).attr("x", "200");
+
+    assert_eq!(t.tick(), TransitionState::Running);
+    assert_eq!(rect.get_attr("x"), Some("0".to_string()));
+    clock.set(50.0);
+    t.tick();
+    assert_eq!(rect.get_attr("x"), Some("100".to_string()));
+    clock.set(100.0);
+    assert_eq!(t.tick(), TransitionState::Ended);
+    assert_eq!(rect.get_attr("x"), Some("200".to_string()));
+}
+
+#[test]
+fn test_default_clock_follows_wall_time() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("x", "0");
+    let mut t = rect.transition();
+    t.duration(10.0).attr("x", "200");
+
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    assert_eq!(t.tick(), TransitionState::Ended);
+    assert_eq!(rect.get_attr("x"), Some("200".to_string()));
+}
+
+#[test]
+fn test_delay_holds_start_value() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("width", "10");
+    let clock = ManualClock::new();
+    let mut t = rect.transition();
+    t.clock(&clock)
+        .delay(100.0)
+        .duration(100.0)
+        .ease(ease::linear)
+        .attr("width", "20");
+    clock.set(50.0);
+    assert_eq!(t.tick(), TransitionState::Scheduled);
+    assert_eq!(rect.get_attr("width"), Some("10".to_string()));
+    clock.set(150.0);
+    t.tick();
+    assert_eq!(rect.get_attr("width"), Some("15".to_string()));
+}
+
+#[test]
+fn test_easing_is_applied() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("y", "0");
+    let clock = ManualClock::new();
+    let mut t = rect.transition();
+    t.clock(&clock).duration(100.0).ease(ease::quad_in).attr("y", "100");
+    clock.set(50.0);
+    t.tick();
+    assert_eq!(rect.get_attr("y"), Some("25".to_string()));
+}
+
+#[test]
+fn test_style_color_and_text_tweens() {
+    let mut svg = Selection::create("svg");
+    let mut label = svg.append("text");
+    label.style("fill", "#000000").text("0 items");
+    let clock = ManualClock::new();
+    let mut t = label.transition();
+    t.clock(&clock)
+        .duration(100.0)
+        .ease(ease::linear)
+        .style("fill", "#ffffff")
+        .text("40 items");
+    clock.set(50.0);
+    t.tick();
+    let node = label.node().unwrap();
+    assert_eq!(node.attributes["style"], "fill:#808080");
+    assert_eq!(node.text.as_deref(), Some("20 items"));
+    clock.set(100.0);
+    t.tick();
+    assert_eq!(label.node().unwrap().text.as_deref(), Some("40 items"));
+}
+
+#[test]
+fn test_attr_fn_uses_bound_data() {
+    let mut svg = Selection::create("svg");
+    let mut g = svg.append("g");
+    for _ in 0..3 {
+        g.append("rect").attr("height", "0");
+    }
+    let mut rects = g.select_all(Some("rect"));
+    rects.data(&[10, 20, 30]);
+    let mut t = rects.transition();
+    t.attr_fn("height", |node, _| node.data.clone().unwrap());
+    t.finish();
+    let heights = rects.map(|n| n.attributes["height"].clone());
+    assert_eq!(heights, vec!["10", "20", "30"]);
+}
+
+#[test]
+fn test_end_event_and_remove() {
+    let mut svg = Selection::create("svg");
+    let mut circles = svg.append("circle");
+    circles.attr("r", "5");
+    let ended = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&ended);
+    let mut t = circles.transition();
+    t.attr("r", "0")
+        .on("end", move |node, i| log.borrow_mut().push((node.tag.clone(), i)))
+        .remove();
+    t.finish();
+    assert_eq!(*ended.borrow(), vec![("circle".to_string(), 0)]);
+    assert!(svg.node().unwrap().children.is_empty());
+}
+
+#[test]
+fn test_chained_transition_starts_after_first() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("x", "0");
+    let clock = ManualClock::new();
+    let mut first = rect.transition();
+    first.clock(&clock).duration(100.0).ease(ease::linear).attr("x", "100");
+    let mut second = first.transition();
+    second.attr("x", "0");
+    assert_eq!(second.state(), TransitionState::Pending);
+
+    clock.set(150.0);
+    first.tick();
+    assert_eq!(first.state(), TransitionState::Ended);
+    assert_eq!(second.state(), TransitionState::Running);
+    assert_eq!(rect.get_attr("x"), Some("50".to_string()));
+
+    clock.set(200.0);
+    first.tick();
+    assert_eq!(second.state(), TransitionState::Ended);
+    assert_eq!(rect.get_attr("x"), Some("0".to_string()));
+}
+
+#[test]
+fn test_interrupt_stops_transition() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("x", "0");
+    let clock = ManualClock::new();
+    let interrupted = Rc::new(RefCell::new(0));
+    let count = Rc::clone(&interrupted);
+    let mut t = rect.transition();
+    t.clock(&clock)
+        .duration(100.0)
+        .ease(ease::linear)
+        .attr("x", "100")
+        .on("interrupt", move |_, _| *count.borrow_mut() += 1);
+    clock.set(50.0);
+    t.tick();
+    t.interrupt();
+    clock.set(100.0);
+    t.tick();
+    assert_eq!(rect.get_attr("x"), Some("50".to_string()));
+    assert_eq!(*interrupted.borrow(), 1);
+}
+
+#[test]
+fn test_render_intermediate_frame() {
+    let mut svg = Selection::create("svg");
+    let mut rect = svg.append("rect");
+    rect.attr("transform", "translate(0,0)");
+    let clock = ManualClock::new();
+    let mut t = rect.transition();
+    t.clock(&clock)
+        .duration(1000.0)
+        .ease(ease::linear)
+        .attr("transform", "translate(100,40)");
+    clock.set(250.0);
+    t.tick();
+    assert_eq!(svg.render(), "<svg><rect transform=\"translate(25,10)\"/></svg>");
+}